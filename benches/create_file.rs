@@ -0,0 +1,42 @@
+//! Benchmarks the creation of a 10000-byte file through the `Filesystem` facade, which allocates a chain
+//! of several hundred clusters (run with `cargo bench`).
+use rodos::Filesystem;
+use std::time::{Duration, Instant};
+
+/// The size in bytes of the created file
+const FILE_SIZE: usize = 10000;
+
+/// The number of times the file is created (and deleted afterwards)
+const RUNS: u32 = 50;
+
+fn main() {
+    let storage_file_path =
+        std::env::temp_dir().join(format!("rodos-bench-{}.bin", std::process::id()));
+    let _ = std::fs::remove_file(&storage_file_path);
+
+    let mut filesystem = Filesystem::open(&storage_file_path.to_string_lossy())
+        .expect("Unable to open the benchmark disk");
+    let content = "A".repeat(FILE_SIZE);
+
+    let mut total_elapsed = Duration::ZERO;
+    for _ in 0..RUNS {
+        let start = Instant::now();
+        filesystem
+            .write_file("bench", "txt", &content)
+            .expect("Unable to create the file");
+        total_elapsed += start.elapsed();
+
+        filesystem
+            .delete("bench", "txt")
+            .expect("Unable to delete the file");
+    }
+
+    println!(
+        "create of a {} B file: {:?} per run ({} runs)",
+        FILE_SIZE,
+        total_elapsed / RUNS,
+        RUNS
+    );
+
+    let _ = std::fs::remove_file(&storage_file_path);
+}
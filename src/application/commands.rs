@@ -1,9 +1,23 @@
+pub(crate) mod badblocks;
 pub(crate) mod cd;
 pub(crate) mod cp;
 pub(crate) mod create;
 pub(crate) mod defrag;
 pub(crate) mod del;
+pub(crate) mod echo;
 pub(crate) mod fmt;
+pub(crate) mod import;
+pub(crate) mod label;
+pub(crate) mod ln;
 pub(crate) mod mkdir;
+pub(crate) mod mount;
+pub(crate) mod redo;
 pub(crate) mod rename;
+pub(crate) mod resize;
+pub(crate) mod rmdir;
 pub(crate) mod setattr;
+pub(crate) mod trash;
+pub(crate) mod truncate;
+pub(crate) mod umount;
+pub(crate) mod undo;
+pub(crate) mod writecluster;
@@ -0,0 +1,52 @@
+use crate::application::Void;
+use crate::core::Arm;
+use crate::domain::i_disk_manager::IDiskManager;
+use mediator::{Request, RequestHandler};
+
+/// BadBlocksRequest is a request to mark a cluster as bad
+/// # Fields
+/// * `cluster_index` - the index of the cluster to mark as bad
+pub(crate) struct BadBlocksRequest {
+    pub(crate) cluster_index: usize,
+}
+
+impl BadBlocksRequest {
+    pub(crate) fn new(cluster_index: usize) -> Self {
+        Self { cluster_index }
+    }
+}
+
+impl Request<Void> for BadBlocksRequest {}
+
+/// BadBlocksHandler is a handler for BadBlocksRequest holding a reference to the disk manager
+pub(crate) struct BadBlocksHandler {
+    disk_manager: Arm<dyn IDiskManager>,
+}
+
+impl BadBlocksHandler {
+    pub(crate) fn new(disk_manager: Arm<dyn IDiskManager>) -> Self {
+        Self { disk_manager }
+    }
+}
+
+impl RequestHandler<BadBlocksRequest, Void> for BadBlocksHandler {
+    fn handle(&mut self, request: BadBlocksRequest) -> Void {
+        log::info!("Marking cluster {} as bad...", request.cluster_index);
+
+        match self.disk_manager.lock() {
+            Ok(mut disk_manager) => {
+                disk_manager.pull_sync();
+
+                match disk_manager.mark_bad_cluster(&request) {
+                    Ok(()) => {
+                        log::info!("Cluster {} marked as bad", request.cluster_index);
+                        disk_manager.push_sync();
+                        Ok(())
+                    }
+                    Err(e) => Err(e),
+                }
+            }
+            Err(_) => Err(Box::try_from("Unable to lock disk manager!").unwrap()),
+        }
+    }
+}
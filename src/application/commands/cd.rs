@@ -6,7 +6,7 @@ use mediator::{Request, RequestHandler};
 
 /// Request to change the working directory
 /// # Fields
-/// * `directory_name` - The name of the directory to change to
+/// * `directory_name` - The name of the directory to change to (`-` for the previous working directory)
 pub(crate) struct ChangeDirectoryRequest {
     pub(crate) directory_name: String,
 }
@@ -38,8 +38,27 @@ impl RequestHandler<ChangeDirectoryRequest, Void> for ChangeDirectoryHandler {
             Ok(mut disk_manager) => {
                 disk_manager.pull_sync();
 
+                // `cd -` goes back to the working directory before the last `cd`
+                let request = match request.directory_name.as_str() {
+                    "-" => match disk_manager.get_previous_directory() {
+                        Some(previous_directory) => ChangeDirectoryRequest::new(previous_directory),
+                        None => {
+                            return Err(
+                                Box::try_from("No previous directory to go back to").unwrap()
+                            )
+                        }
+                    },
+                    _ => request,
+                };
+
+                let working_directory_path = disk_manager.get_working_directory_full_path();
                 match disk_manager.change_working_directory(&request) {
                     Ok(_) => {
+                        disk_manager.set_previous_directory(working_directory_path);
+
+                        // record the new working directory in the boot sector
+                        disk_manager.push_sync();
+
                         log::info!(
                             "Changed directory successfully to {}",
                             disk_manager.get_working_directory_full_path()
@@ -1,7 +1,22 @@
+use crate::application::commands::cd::ChangeDirectoryRequest;
+use crate::application::commands::create::CreateRequest;
+use crate::application::commands::del::DeleteRequest;
+use crate::application::queries::stat::StatRequest;
 use crate::application::Void;
+use crate::core::checksum::Checksum;
+use crate::core::config::Config;
+use crate::core::content_type::ContentType;
+use crate::core::mount_table::MountTable;
 use crate::core::Arm;
+use crate::domain::file_entry::{FileEntry, FileEntryAttributesFlags};
 use crate::domain::i_disk_manager::IDiskManager;
+use crate::{error, info};
+use chrono::Utc;
+use color_print::cprintln;
 use mediator::{Request, RequestHandler};
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::sync::Arc;
 
 /// CopyRequest is a request to copy a file from one location to another
 /// # Fields
@@ -9,11 +24,21 @@ use mediator::{Request, RequestHandler};
 /// * `src_extension` - The extension of the file to copy
 /// * `dest_name` - The name of the destination file
 /// * `dest_extension` - The extension of the destination file
+/// * `dest_directory` - The (relative or absolute) path of the directory to copy into, the working directory if none
+/// * `force` - Whether to overwrite the destination if it already exists
+/// * `src_label` - The label of the mounted disk holding the file to copy, the main disk if none
+/// * `dest_label` - The label of the mounted disk to copy into, the main disk if none
+/// * `verify` - Whether to compare the copy against its source afterwards, deleting it on a mismatch
 pub(crate) struct CopyRequest {
     pub(crate) src_name: String,
     pub(crate) src_extension: String,
     pub(crate) dest_name: String,
     pub(crate) dest_extension: String,
+    pub(crate) dest_directory: Option<String>,
+    pub(crate) force: bool,
+    pub(crate) src_label: Option<String>,
+    pub(crate) dest_label: Option<String>,
+    pub(crate) verify: bool,
 }
 
 impl CopyRequest {
@@ -22,26 +47,408 @@ impl CopyRequest {
         src_extension: String,
         dest_name: String,
         dest_extension: String,
+        dest_directory: Option<String>,
+        force: bool,
     ) -> Self {
         Self {
             src_name,
             src_extension,
             dest_name,
             dest_extension,
+            dest_directory,
+            force,
+            src_label: None,
+            dest_label: None,
+            verify: false,
         }
     }
+
+    /// Sets the labels of the mounted disks of the source and of the destination.
+    pub(crate) fn with_labels(self, src_label: Option<String>, dest_label: Option<String>) -> Self {
+        Self {
+            src_label,
+            dest_label,
+            ..self
+        }
+    }
+
+    /// Sets whether to verify the copy against its source.
+    pub(crate) fn with_verification(self, verify: bool) -> Self {
+        Self { verify, ..self }
+    }
 }
 
 impl Request<Void> for CopyRequest {}
 
-/// CopyHandler is a handler for CopyRequest holding a reference to the disk manager
+/// CopiedEntry describes a file or directory of a copied tree, as compared by the verification of the copy
+/// # Fields
+/// * `is_file` - whether the entry is a file
+/// * `size` - the size of the file (0 for a directory)
+/// * `checksum` - the CRC-32 of the content of the file (0 for a directory)
+#[derive(Debug, Clone, PartialEq)]
+struct CopiedEntry {
+    is_file: bool,
+    size: u32,
+    checksum: u32,
+}
+
+/// CopyHandler is a handler for CopyRequest holding a reference to the config (for the temp buffer file),
+/// to the disk manager and to the table of the mounted disks (for the copies from or to a mounted disk)
 pub(crate) struct CopyHandler {
+    config: Arm<Config>,
     disk_manager: Arm<dyn IDiskManager>,
+    mount_table: Arm<MountTable>,
 }
 
 impl CopyHandler {
-    pub(crate) fn new(disk_manager: Arm<dyn IDiskManager>) -> Self {
-        Self { disk_manager }
+    pub(crate) fn new(
+        config: Arm<Config>,
+        disk_manager: Arm<dyn IDiskManager>,
+        mount_table: Arm<MountTable>,
+    ) -> Self {
+        Self {
+            config,
+            disk_manager,
+            mount_table,
+        }
+    }
+
+    /// Returns the disk manager of the disk mounted under the given label, or the one of the main disk if none.
+    fn get_disk_manager(
+        &self,
+        label: Option<&str>,
+    ) -> Result<Arm<dyn IDiskManager>, Box<dyn Error>> {
+        let label = match label {
+            Some(label) => label,
+            None => return Ok(self.disk_manager.clone()),
+        };
+
+        match self.mount_table.lock() {
+            Ok(mount_table) => match mount_table.get(label) {
+                Some(disk_manager) => Ok(disk_manager),
+                None => Err(Box::try_from(format!("Label {} is not mounted", label)).unwrap()),
+            },
+            Err(_) => Err(Box::try_from("Unable to lock mount table!").unwrap()),
+        }
+    }
+
+    /// Copies a file from a disk to another one: its bytes are read from the source disk and written through the
+    /// destination disk into a new file, as the disks may have different FAT types and cluster sizes.
+    fn copy_across_disks(
+        &self,
+        src_disk_manager: &Arm<dyn IDiskManager>,
+        dest_disk_manager: &Arm<dyn IDiskManager>,
+        request: &CopyRequest,
+    ) -> Void {
+        if request.src_extension.is_empty() {
+            return Err(Box::try_from("Only files can be copied across disks").unwrap());
+        }
+
+        let (src_file_entry, content) = match src_disk_manager.lock() {
+            Ok(mut src_disk_manager) => {
+                src_disk_manager.pull_sync();
+
+                let src_file_entry = src_disk_manager.get_file_entry(&StatRequest::new(
+                    request.src_name.clone(),
+                    request.src_extension.clone(),
+                ))?;
+                let content = src_disk_manager.read_entry_bytes(&src_file_entry);
+
+                (src_file_entry, content)
+            }
+            Err(_) => return Err(Box::try_from("Unable to lock disk manager!").unwrap()),
+        };
+
+        match dest_disk_manager.lock() {
+            Ok(mut dest_disk_manager) => {
+                dest_disk_manager.pull_sync();
+
+                // copy from within the dest directory, then go back to the working directory
+                let working_directory_path = dest_disk_manager.get_working_directory_full_path();
+                if let Some(dest_directory) = &request.dest_directory {
+                    dest_disk_manager.change_working_directory(&ChangeDirectoryRequest::new(
+                        dest_directory.clone(),
+                    ))?;
+                    // reload the disk to link the root table of the dest directory
+                    dest_disk_manager.pull_sync();
+                }
+
+                let result =
+                    self.write_copy(&mut *dest_disk_manager, &src_file_entry, content, request);
+                if result.is_ok() {
+                    dest_disk_manager.push_sync();
+                }
+
+                if request.dest_directory.is_some() {
+                    dest_disk_manager.change_working_directory(&ChangeDirectoryRequest::new(
+                        working_directory_path,
+                    ))?;
+                }
+
+                result
+            }
+            Err(_) => Err(Box::try_from("Unable to lock disk manager!").unwrap()),
+        }
+    }
+
+    /// Writes the given content of the src file into the dest file of the working directory of the given disk,
+    /// overwriting it if forced.
+    fn write_copy(
+        &self,
+        disk_manager: &mut dyn IDiskManager,
+        src_file_entry: &FileEntry,
+        content: Vec<u8>,
+        request: &CopyRequest,
+    ) -> Void {
+        if let Ok(dest_file_entry) = disk_manager.get_file_entry(&StatRequest::new(
+            request.dest_name.clone(),
+            request.dest_extension.clone(),
+        )) {
+            if !request.force {
+                return Err(Box::try_from(format!(
+                    "File {}.{} already exists",
+                    request.dest_name, request.dest_extension
+                ))
+                .unwrap());
+            }
+
+            if dest_file_entry.is_read_only() {
+                return Err(Box::try_from(format!(
+                    "File {}.{} is read only",
+                    request.dest_name, request.dest_extension
+                ))
+                .unwrap());
+            }
+
+            log::info!(
+                "Overwriting {}.{} with {}.{}",
+                request.dest_name,
+                request.dest_extension,
+                request.src_name,
+                request.src_extension
+            );
+            disk_manager.delete_file(&DeleteRequest::new(
+                request.dest_name.clone(),
+                request.dest_extension.clone(),
+                true,
+                false,
+                false,
+            ))?;
+        }
+
+        // write the content to the temp buffer file to be read back when filling the new file
+        let temp_file_path = self.config.lock().unwrap().temp_file_path.clone();
+        std::fs::write(temp_file_path, &content)?;
+
+        disk_manager.create_file(&CreateRequest::new(
+            request.dest_name.clone(),
+            request.dest_extension.clone(),
+            content.len() as u32,
+            src_file_entry.attributes | FileEntryAttributesFlags::Archive as u8,
+            Utc::now(),
+            ContentType::Temp,
+        ))
+    }
+}
+
+impl CopyHandler {
+    /// Collects the entries of the tree rooted at the given file entry, keyed by their path relative to it
+    /// (`.` for the file entry itself).
+    fn collect_copied_entries(
+        disk_manager: &dyn IDiskManager,
+        file_entry: &FileEntry,
+        path: String,
+        copied_entries: &mut BTreeMap<String, CopiedEntry>,
+    ) {
+        let copied_entry = match file_entry.is_file() {
+            true => CopiedEntry {
+                is_file: true,
+                size: file_entry.size,
+                checksum: Checksum::crc32(&disk_manager.read_entry_bytes(file_entry)),
+            },
+            false => CopiedEntry {
+                is_file: false,
+                size: 0,
+                checksum: 0,
+            },
+        };
+        copied_entries.insert(path.clone(), copied_entry);
+
+        for child_entry in file_entry
+            .children_entries
+            .as_deref()
+            .unwrap_or_default()
+            .iter()
+            .filter(|child_entry| {
+                !child_entry.name.is_empty() && child_entry.name != "." && child_entry.name != ".."
+            })
+        {
+            Self::collect_copied_entries(
+                disk_manager,
+                child_entry,
+                format!("{}/{}", path, child_entry.get_full_name()),
+                copied_entries,
+            );
+        }
+    }
+
+    /// Collects the entries of the tree rooted at the given file or directory of the working directory
+    /// (or of the given directory) of a disk.
+    fn collect_tree(
+        disk_manager: &Arm<dyn IDiskManager>,
+        name: &str,
+        extension: &str,
+        directory: Option<&String>,
+    ) -> Result<BTreeMap<String, CopiedEntry>, Box<dyn Error>> {
+        match disk_manager.lock() {
+            Ok(mut disk_manager) => {
+                disk_manager.pull_sync();
+
+                // look the entry up from within its directory, then go back to the working directory
+                let working_directory_path = disk_manager.get_working_directory_full_path();
+                if let Some(directory) = directory {
+                    disk_manager.change_working_directory(&ChangeDirectoryRequest::new(
+                        directory.clone(),
+                    ))?;
+                    disk_manager.pull_sync();
+                }
+
+                let file_entry = disk_manager
+                    .get_file_entry(&StatRequest::new(name.to_string(), extension.to_string()));
+
+                if directory.is_some() {
+                    disk_manager.change_working_directory(&ChangeDirectoryRequest::new(
+                        working_directory_path,
+                    ))?;
+                }
+
+                let mut copied_entries = BTreeMap::new();
+                Self::collect_copied_entries(
+                    &*disk_manager,
+                    &file_entry?,
+                    ".".to_string(),
+                    &mut copied_entries,
+                );
+                Ok(copied_entries)
+            }
+            Err(_) => Err(Box::try_from("Unable to lock disk manager!").unwrap()),
+        }
+    }
+
+    /// Compares the source and the copied trees, returning the description of every discrepancy.
+    fn compare_trees(
+        src_entries: &BTreeMap<String, CopiedEntry>,
+        dest_entries: &BTreeMap<String, CopiedEntry>,
+    ) -> Vec<String> {
+        let mut discrepancies = Vec::new();
+
+        for (path, src_entry) in src_entries {
+            match dest_entries.get(path) {
+                None => discrepancies.push(format!("{} is missing from the copy", path)),
+                Some(dest_entry) if dest_entry.is_file != src_entry.is_file => {
+                    discrepancies.push(format!("{} is not of the same kind as its source", path))
+                }
+                Some(dest_entry) if dest_entry.size != src_entry.size => {
+                    discrepancies.push(format!(
+                        "{} has {} B instead of {} B",
+                        path, dest_entry.size, src_entry.size
+                    ))
+                }
+                Some(dest_entry) if dest_entry.checksum != src_entry.checksum => discrepancies
+                    .push(format!(
+                        "{} has the checksum {:08x} instead of {:08x}",
+                        path, dest_entry.checksum, src_entry.checksum
+                    )),
+                Some(_) => {}
+            }
+        }
+
+        discrepancies.extend(
+            dest_entries
+                .keys()
+                .filter(|path| !src_entries.contains_key(*path))
+                .map(|path| format!("{} is not in the source", path)),
+        );
+
+        discrepancies
+    }
+
+    /// Compares the copy against its source (the sizes and the checksums of the files, recursively for a directory)
+    /// and deletes the copy if they don't match.
+    fn verify_copy(
+        src_disk_manager: &Arm<dyn IDiskManager>,
+        dest_disk_manager: &Arm<dyn IDiskManager>,
+        request: &CopyRequest,
+    ) -> Void {
+        log::info!("Verifying the copy...");
+
+        let src_entries = Self::collect_tree(
+            src_disk_manager,
+            &request.src_name,
+            &request.src_extension,
+            None,
+        )?;
+        let dest_entries = Self::collect_tree(
+            dest_disk_manager,
+            &request.dest_name,
+            &request.dest_extension,
+            request.dest_directory.as_ref(),
+        )?;
+
+        let discrepancies = Self::compare_trees(&src_entries, &dest_entries);
+        if discrepancies.is_empty() {
+            let entries_count = src_entries.len();
+            info!(
+                "Copy verified: {} entries match their source",
+                entries_count
+            );
+            log::info!("Verified the {} copied entries", entries_count);
+            return Ok(());
+        }
+
+        discrepancies.iter().for_each(|discrepancy| {
+            error!("Error: {}", discrepancy);
+            log::error!("Copy mismatch: {}", discrepancy);
+        });
+
+        // roll the copy back
+        match dest_disk_manager.lock() {
+            Ok(mut dest_disk_manager) => {
+                dest_disk_manager.pull_sync();
+
+                let working_directory_path = dest_disk_manager.get_working_directory_full_path();
+                if let Some(dest_directory) = &request.dest_directory {
+                    dest_disk_manager.change_working_directory(&ChangeDirectoryRequest::new(
+                        dest_directory.clone(),
+                    ))?;
+                    dest_disk_manager.pull_sync();
+                }
+
+                let result = dest_disk_manager.delete_file(&DeleteRequest::new(
+                    request.dest_name.clone(),
+                    request.dest_extension.clone(),
+                    true,
+                    false,
+                    false,
+                ));
+                dest_disk_manager.push_sync();
+
+                if request.dest_directory.is_some() {
+                    dest_disk_manager.change_working_directory(&ChangeDirectoryRequest::new(
+                        working_directory_path,
+                    ))?;
+                }
+
+                result?;
+            }
+            Err(_) => return Err(Box::try_from("Unable to lock disk manager!").unwrap()),
+        }
+
+        Err(Box::try_from(format!(
+            "The copy doesn't match its source ({} discrepancies), so it has been deleted",
+            discrepancies.len()
+        ))
+        .unwrap())
     }
 }
 
@@ -49,7 +456,25 @@ impl RequestHandler<CopyRequest, Void> for CopyHandler {
     fn handle(&mut self, request: CopyRequest) -> Void {
         log::info!("Copying file...");
 
-        match self.disk_manager.lock() {
+        let src_disk_manager = self.get_disk_manager(request.src_label.as_deref())?;
+        let dest_disk_manager = self.get_disk_manager(request.dest_label.as_deref())?;
+        if !Arc::ptr_eq(&src_disk_manager, &dest_disk_manager) {
+            self.copy_across_disks(&src_disk_manager, &dest_disk_manager, &request)?;
+            if request.verify {
+                Self::verify_copy(&src_disk_manager, &dest_disk_manager, &request)?;
+            }
+
+            log::info!(
+                "Copied file successfully from {}.{} to {}.{} across disks",
+                request.src_name,
+                request.src_extension,
+                request.dest_name,
+                request.dest_extension
+            );
+            return Ok(());
+        }
+
+        let result = match src_disk_manager.lock() {
             Ok(mut disk_manager) => match disk_manager.copy_file(&request) {
                 Ok(()) => {
                     log::info!(
@@ -65,6 +490,131 @@ impl RequestHandler<CopyRequest, Void> for CopyHandler {
                 Err(e) => Err(e),
             },
             Err(_) => Err(Box::try_from("Unable to lock disk manager!").unwrap()),
+        };
+
+        if result.is_ok() && request.verify {
+            return Self::verify_copy(&src_disk_manager, &dest_disk_manager, &request);
         }
+
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::test_disk::TestDisk;
+    use std::sync::Mutex;
+
+    /// A main disk with 16-byte clusters next to a disk with 32-byte clusters mounted under the `big` label.
+    struct TwoDisks {
+        main_disk: TestDisk,
+        big_disk: TestDisk,
+        mount_table: Arm<MountTable>,
+    }
+
+    impl TwoDisks {
+        fn new() -> Self {
+            let main_disk = TestDisk::new();
+            let big_disk = TestDisk::with_config(|config| config.disk.cluster_size = Some(32));
+            let mut mount_table = MountTable::new(
+                main_disk.config.lock().unwrap().storage_file_path.clone(),
+                crate::open_mounted_disk,
+            );
+            let big_storage_file_path = big_disk.config.lock().unwrap().storage_file_path.clone();
+            mount_table
+                .mount(&big_storage_file_path, "big", false)
+                .unwrap();
+
+            Self {
+                main_disk,
+                big_disk,
+                mount_table: Arc::new(Mutex::new(mount_table)),
+            }
+        }
+
+        /// Runs the request as `cp` does from the main disk.
+        fn copy(&self, request: CopyRequest) -> Void {
+            let mut copy_handler = CopyHandler::new(
+                self.main_disk.config.clone(),
+                Arc::new(Mutex::new(self.main_disk.reopen())),
+                self.mount_table.clone(),
+            );
+
+            copy_handler.handle(request)
+        }
+    }
+
+    fn copy_request(src_name: &str, dest_name: &str) -> CopyRequest {
+        CopyRequest::new(
+            src_name.to_string(),
+            "txt".to_string(),
+            dest_name.to_string(),
+            "txt".to_string(),
+            None,
+            false,
+        )
+    }
+
+    /// Returns the size of a file of the working directory of the given disk along with its number of clusters.
+    fn file_allocation(disk: &mut TestDisk, name: &str) -> (u32, usize) {
+        disk.pull_sync();
+        let file_entry = disk
+            .get_file_entry(&StatRequest::new(name.to_string(), "txt".to_string()))
+            .unwrap();
+
+        (
+            file_entry.size,
+            disk.cluster_chain(file_entry.first_cluster as usize).len(),
+        )
+    }
+
+    #[test]
+    fn a_file_is_copied_back_from_a_mounted_disk() {
+        let mut disks = TwoDisks::new();
+        disks.big_disk.create("a", "txt", 100).unwrap();
+
+        disks
+            .copy(copy_request("a", "a").with_labels(Some("big".to_string()), None))
+            .unwrap();
+
+        assert_eq!(
+            disks.main_disk.read("a", "txt"),
+            disks.big_disk.read("a", "txt")
+        );
+        assert_eq!(file_allocation(&mut disks.main_disk, "a"), (100, 7));
+        assert_eq!(file_allocation(&mut disks.big_disk, "a"), (100, 4));
+    }
+
+    #[test]
+    fn a_directory_is_not_copied_across_disks() {
+        let mut disks = TwoDisks::new();
+        disks.main_disk.mkdir("dir").unwrap();
+
+        let result = disks.copy(
+            CopyRequest::new(
+                "dir".to_string(),
+                String::new(),
+                "dir".to_string(),
+                String::new(),
+                None,
+                false,
+            )
+            .with_labels(None, Some("big".to_string())),
+        );
+
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Only files can be copied across disks"
+        );
+    }
+
+    #[test]
+    fn copying_from_an_unmounted_label_is_rejected() {
+        let disks = TwoDisks::new();
+
+        let result = disks.copy(copy_request("a", "a").with_labels(Some("usb".to_string()), None));
+
+        assert_eq!(result.unwrap_err().to_string(), "Label usb is not mounted");
     }
 }
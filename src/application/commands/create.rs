@@ -1,10 +1,17 @@
 use crate::application::Void;
+use crate::core::config::Config;
 use crate::core::content_type::ContentType;
 use crate::core::Arm;
 use crate::domain::i_disk_manager::IDiskManager;
+use crate::info;
 use chrono::{DateTime, Utc};
 use color_print::cprintln;
 use mediator::{Request, RequestHandler};
+use std::error::Error;
+use std::io::BufRead;
+
+/// The line marking the end of the content typed for a file created from stdin
+const STDIN_SENTINEL: &str = ".";
 
 /// CreateRequest is a request to create a file
 /// # Fields
@@ -46,17 +53,67 @@ impl CreateRequest {
 impl Request<Void> for CreateRequest {}
 
 pub(crate) struct CreateHandler {
+    config: Arm<Config>,
     disk_manager: Arm<dyn IDiskManager>,
 }
 
 impl CreateHandler {
-    pub(crate) fn new(disk_manager: Arm<dyn IDiskManager>) -> Self {
-        Self { disk_manager }
+    pub(crate) fn new(config: Arm<Config>, disk_manager: Arm<dyn IDiskManager>) -> Self {
+        Self {
+            config,
+            disk_manager,
+        }
+    }
+
+    /// Reads lines from stdin until the sentinel line is met and saves them into the given stdin file.
+    /// Returns the number of bytes written.
+    fn read_stdin_to_file(stdin_file_path: &str) -> Result<u32, Box<dyn Error>> {
+        info!(
+            "Enter the file content, then a single `{}` line to finish:",
+            STDIN_SENTINEL
+        );
+
+        Self::read_lines_to_file(std::io::stdin().lock(), stdin_file_path)
+    }
+
+    /// Reads lines from the given input until the sentinel line is met and saves them into the given stdin file.
+    /// Returns the number of bytes written.
+    fn read_lines_to_file(
+        mut input: impl BufRead,
+        stdin_file_path: &str,
+    ) -> Result<u32, Box<dyn Error>> {
+        let mut lines: Vec<String> = Vec::new();
+        loop {
+            let mut line = String::new();
+            let read_bytes = input.read_line(&mut line)?;
+
+            // stop at the sentinel line or at the end of the input
+            let line = line.trim_end_matches(['\r', '\n']);
+            if read_bytes == 0 || line == STDIN_SENTINEL {
+                break;
+            }
+
+            lines.push(line.to_string());
+        }
+
+        let content = lines.join("\n");
+        if content.len() > 10000 {
+            return Err(Box::try_from("Content must be 10000 bytes or less!").unwrap());
+        }
+
+        std::fs::write(stdin_file_path, content.as_bytes())?;
+        Ok(content.len() as u32)
     }
 }
 
 impl RequestHandler<CreateRequest, Void> for CreateHandler {
-    fn handle(&mut self, request: CreateRequest) -> Void {
+    fn handle(&mut self, mut request: CreateRequest) -> Void {
+        // the size of a file created from stdin is given by the actual content, not by the requested size
+        if request.content_type == ContentType::Stdin {
+            let stdin_file_path = self.config.lock().unwrap().stdin_file_path.clone();
+            request.size = Self::read_stdin_to_file(&stdin_file_path)?;
+        }
+
         log::info!(
             "Creating file {}.{} with dimension {} and content type {}",
             request.name,
@@ -89,3 +146,54 @@ impl RequestHandler<CreateRequest, Void> for CreateHandler {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::file_entry::FileEntryAttributes;
+    use crate::infrastructure::test_disk::TestDisk;
+
+    #[test]
+    fn a_file_created_from_stdin_holds_the_typed_lines() {
+        let mut disk = TestDisk::new();
+        let stdin_file_path = disk.config.lock().unwrap().stdin_file_path.clone();
+
+        let size = CreateHandler::read_lines_to_file(
+            "first line\r\nsecond line\n.\nafter the sentinel\n".as_bytes(),
+            &stdin_file_path,
+        )
+        .unwrap();
+        assert_eq!(size, 22);
+
+        // the requested size is replaced by the size of the typed content, as the handler does
+        let mut request = CreateRequest::new(
+            "typed".to_string(),
+            "txt".to_string(),
+            100,
+            FileEntryAttributes::combine(&[
+                FileEntryAttributes::File,
+                FileEntryAttributes::ReadWrite,
+                FileEntryAttributes::Visible,
+            ]),
+            Utc::now(),
+            ContentType::Stdin,
+        );
+        request.size = size;
+        disk.run(|disk_manager| disk_manager.create_file(&request))
+            .unwrap();
+
+        assert_eq!(disk.read("typed", "txt"), b"first line\nsecond line");
+    }
+
+    #[test]
+    fn reading_stdin_stops_at_the_end_of_the_input() {
+        let disk = TestDisk::new();
+        let stdin_file_path = disk.config.lock().unwrap().stdin_file_path.clone();
+
+        let size =
+            CreateHandler::read_lines_to_file("no sentinel".as_bytes(), &stdin_file_path).unwrap();
+
+        assert_eq!(size, 11);
+        assert_eq!(std::fs::read(&stdin_file_path).unwrap(), b"no sentinel");
+    }
+}
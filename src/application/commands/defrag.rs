@@ -1,6 +1,7 @@
 use crate::application::Void;
 use crate::core::Arm;
 use crate::domain::i_disk_manager::IDiskManager;
+use color_print::cprintln;
 use mediator::{Request, RequestHandler};
 
 /// DefragmentRequest is a request to defragment the disk
@@ -31,11 +32,29 @@ impl RequestHandler<DefragmentRequest, Void> for DefragmentHandler {
 
         match self.disk_manager.lock() {
             Ok(mut disk_manager) => {
-                disk_manager.pull_sync();
+                // fragmentation_report pulls the disk before walking the allocation chains
+                let fragmentation_before = disk_manager
+                    .fragmentation_report()
+                    .get_fragmentation_percentage();
 
                 match disk_manager.defragment_disk() {
                     Ok(()) => {
-                        log::info!("Disk has been defragmented successfully");
+                        disk_manager.push_sync();
+
+                        let fragmentation_after = disk_manager
+                            .fragmentation_report()
+                            .get_fragmentation_percentage();
+                        cprintln!(
+                            "<c!>Fragmentation</>: <w!>{:.1}%</> -> <w!>{:.1}%</>",
+                            fragmentation_before,
+                            fragmentation_after
+                        );
+
+                        log::info!(
+                            "Disk has been defragmented successfully (fragmentation: {:.1}% -> {:.1}%)",
+                            fragmentation_before,
+                            fragmentation_after
+                        );
                         Ok(())
                     }
                     Err(e) => Err(e),
@@ -1,66 +1,329 @@
+use crate::application::commands::cd::ChangeDirectoryRequest;
+use crate::application::queries::ls::ListRequest;
 use crate::application::Void;
+use crate::core::config::Config;
+use crate::core::confirmation_prompt::IConfirmationPrompt;
+use crate::core::filter_type::FilterType;
+use crate::core::glob_pattern::GlobPattern;
 use crate::core::Arm;
+use crate::domain::file_entry::FileEntry;
 use crate::domain::i_disk_manager::IDiskManager;
 use color_print::cprintln;
 use mediator::{Request, RequestHandler};
+use std::error::Error;
 
 /// DeleteRequest is a request to delete a file or directory
 /// # Fields
 /// * `file_name` - the name of the file or directory to delete
 /// * `file_extension` - the extension of the file to delete
+/// * `skip_confirmation` - whether to delete without asking for confirmation
+/// * `dry_run` - whether to only list the file entries which would be deleted
+/// * `recursive` - whether to delete the matching file entries of the subdirectories as well
+///
+/// The name and the extension may contain the `*` and `?` wildcards, in which case
+/// every matching (visible) file or directory from the working directory is deleted.
 pub(crate) struct DeleteRequest {
     pub(crate) file_name: String,
     pub(crate) file_extension: String,
+    pub(crate) skip_confirmation: bool,
+    pub(crate) dry_run: bool,
+    pub(crate) recursive: bool,
 }
 
 impl DeleteRequest {
-    pub(crate) fn new(file_name: String, file_extension: String) -> Self {
+    pub(crate) fn new(
+        file_name: String,
+        file_extension: String,
+        skip_confirmation: bool,
+        dry_run: bool,
+        recursive: bool,
+    ) -> Self {
         Self {
             file_name,
             file_extension,
+            skip_confirmation,
+            dry_run,
+            recursive,
+        }
+    }
+
+    /// Returns the full name of the file entry to delete (`name.extension` for files and `name` for directories).
+    pub(crate) fn get_full_name(&self) -> String {
+        match self.file_extension.is_empty() {
+            true => self.file_name.clone(),
+            false => format!("{}.{}", self.file_name, self.file_extension),
         }
     }
 }
 
 impl Request<Void> for DeleteRequest {}
 
-/// DeleteHandler is a handler for DeleteRequest holding a reference to the disk manager
+/// A file entry matching the pattern of a deletion, along with the path of its directory
+/// relative to the working directory (empty for the working directory itself).
+struct MatchingFileEntry {
+    directory_path: String,
+    file_entry: FileEntry,
+}
+
+impl MatchingFileEntry {
+    /// Returns the path of the file entry relative to the working directory.
+    fn get_path(&self) -> String {
+        match self.directory_path.is_empty() {
+            true => self.file_entry.get_full_name(),
+            false => format!(
+                "{}/{}",
+                self.directory_path,
+                self.file_entry.get_full_name()
+            ),
+        }
+    }
+}
+
+/// DeleteHandler is a handler for DeleteRequest holding a reference to the config, to the disk manager
+/// and to the source of the answers to the confirmation questions
 pub(crate) struct DeleteHandler {
+    config: Arm<Config>,
     disk_manager: Arm<dyn IDiskManager>,
+    confirmation_prompt: Box<dyn IConfirmationPrompt>,
 }
 
 impl DeleteHandler {
-    pub(crate) fn new(disk_manager: Arm<dyn IDiskManager>) -> Self {
-        Self { disk_manager }
+    pub(crate) fn new(
+        config: Arm<Config>,
+        disk_manager: Arm<dyn IDiskManager>,
+        confirmation_prompt: Box<dyn IConfirmationPrompt>,
+    ) -> Self {
+        Self {
+            config,
+            disk_manager,
+            confirmation_prompt,
+        }
     }
-}
 
-impl RequestHandler<DeleteRequest, Void> for DeleteHandler {
-    fn handle(&mut self, request: DeleteRequest) -> Void {
-        match request.file_extension.as_str() {
-            "" => {
-                log::info!("Deleting directory {}...", request.file_name);
-                cprintln!("Deleting directory <b!>{}</>...", request.file_name,);
+    /// Asks the given question unless the confirmation of destructive commands is disabled
+    /// (`confirm_destructive` from the config) or skipped by the request.
+    fn is_confirmed(
+        confirmation_prompt: &mut dyn IConfirmationPrompt,
+        confirm_destructive: bool,
+        request: &DeleteRequest,
+        question: &str,
+    ) -> Result<bool, Box<dyn Error>> {
+        if !confirm_destructive || request.skip_confirmation {
+            return Ok(true);
+        }
+
+        confirmation_prompt.confirm(question)
+    }
+
+    /// Collects the visible file entries of the given directory table matching the pattern and, when recursive,
+    /// the ones of its subdirectories (depth-first, below the given directory path).
+    ///
+    /// A matching directory is deleted along with its content, so the entries it holds are not collected.
+    fn collect_matching_file_entries(
+        file_entries: &[FileEntry],
+        pattern: &GlobPattern,
+        recursive: bool,
+        directory_path: &str,
+    ) -> Vec<MatchingFileEntry> {
+        let mut matching_file_entries = Vec::new();
+
+        for file_entry in file_entries.iter().filter(|file_entry| {
+            !file_entry.name.is_empty()
+                && file_entry.name != "."
+                && file_entry.name != ".."
+                && !file_entry.is_hidden()
+        }) {
+            if pattern.is_match(&file_entry.get_full_name()) {
+                matching_file_entries.push(MatchingFileEntry {
+                    directory_path: directory_path.to_string(),
+                    file_entry: file_entry.clone(),
+                });
+            } else if recursive && !file_entry.is_file() {
+                let subdirectory_path = match directory_path.is_empty() {
+                    true => file_entry.name.clone(),
+                    false => format!("{}/{}", directory_path, file_entry.name),
+                };
+                matching_file_entries.extend(Self::collect_matching_file_entries(
+                    file_entry.children_entries.as_deref().unwrap_or_default(),
+                    pattern,
+                    recursive,
+                    &subdirectory_path,
+                ));
             }
-            _ => {
-                log::info!(
-                    "Deleting file {}.{}...",
-                    request.file_name,
-                    request.file_extension
-                );
-                cprintln!(
-                    "Deleting file <b!>{}.{}</>...",
-                    request.file_name,
-                    request.file_extension
+        }
+
+        matching_file_entries
+    }
+
+    /// Deletes every visible file or directory matching the pattern of the request, from the working directory
+    /// and, if recursive, from its subdirectories, after the user confirms the deletion
+    /// (on a dry run, the matching file entries are only listed).
+    fn delete_matching_file_entries(&mut self, request: DeleteRequest) -> Void {
+        let pattern = request.get_full_name();
+        match self.disk_manager.lock() {
+            Ok(mut disk_manager) => {
+                disk_manager.pull_sync();
+
+                let list_request = ListRequest::new(vec![FilterType::AllAndHidden], None);
+                let matching_file_entries = Self::collect_matching_file_entries(
+                    &disk_manager.list_files(&list_request)?,
+                    &GlobPattern::new(&pattern),
+                    request.recursive,
+                    "",
                 );
+
+                if matching_file_entries.is_empty() {
+                    return Err(
+                        Box::try_from(format!("No file or directory matches {}", pattern)).unwrap(),
+                    );
+                }
+
+                match request.dry_run {
+                    true => cprintln!(
+                        "<b!>{}</> file(s) or directories matching <b!>{}</> would be deleted:",
+                        matching_file_entries.len(),
+                        pattern
+                    ),
+                    false => cprintln!(
+                        "<b!>{}</> file(s) or directories match <b!>{}</>:",
+                        matching_file_entries.len(),
+                        pattern
+                    ),
+                }
+                matching_file_entries
+                    .iter()
+                    .for_each(|matching_file_entry| println!("{}", matching_file_entry.get_path()));
+
+                if request.dry_run {
+                    log::info!(
+                        "Previewed the deletion of the file entries matching {}",
+                        pattern
+                    );
+                    return Ok(());
+                }
+
+                if !Self::is_confirmed(
+                    self.confirmation_prompt.as_mut(),
+                    self.config.lock().unwrap().confirm_destructive,
+                    &request,
+                    "Delete all of them?",
+                )? {
+                    return Err(Box::try_from("Deletion cancelled").unwrap());
+                }
+
+                let use_trash = self.config.lock().unwrap().use_trash;
+                let working_directory_path = disk_manager.get_working_directory_full_path();
+                for matching_file_entry in matching_file_entries {
+                    log::info!("Deleting {}...", matching_file_entry.get_path());
+
+                    // go to the directory of the file entry and back to the working directory afterwards
+                    if !matching_file_entry.directory_path.is_empty() {
+                        disk_manager.pull_sync();
+                        disk_manager.change_working_directory(&ChangeDirectoryRequest::new(
+                            matching_file_entry.directory_path.clone(),
+                        ))?;
+                    }
+
+                    disk_manager.pull_sync();
+                    let delete_request = DeleteRequest::new(
+                        matching_file_entry.file_entry.name.clone(),
+                        matching_file_entry.file_entry.extension.clone(),
+                        true,
+                        false,
+                        false,
+                    );
+                    let result = match use_trash {
+                        true => disk_manager.move_to_trash(&delete_request),
+                        false => disk_manager.delete_file(&delete_request),
+                    };
+                    disk_manager.push_sync();
+
+                    if !matching_file_entry.directory_path.is_empty() {
+                        disk_manager.pull_sync();
+                        disk_manager.change_working_directory(&ChangeDirectoryRequest::new(
+                            working_directory_path.clone(),
+                        ))?;
+                    }
+
+                    result?;
+                }
+
+                log::info!("Deleted file entries matching {} successfully", pattern);
+                Ok(())
             }
+            Err(_) => Err(Box::try_from("Unable to lock disk manager!").unwrap()),
+        }
+    }
+}
+
+impl RequestHandler<DeleteRequest, Void> for DeleteHandler {
+    fn handle(&mut self, request: DeleteRequest) -> Void {
+        if GlobPattern::is_glob(&request.get_full_name()) || request.dry_run || request.recursive {
+            return self.delete_matching_file_entries(request);
         }
 
         match self.disk_manager.lock() {
             Ok(mut disk_manager) => {
                 disk_manager.pull_sync();
 
-                match disk_manager.delete_file(&request) {
+                // ask for confirmation only if there is something to delete
+                // (otherwise let the disk manager report the missing file entry)
+                let list_request = ListRequest::new(
+                    vec![
+                        FilterType::Glob(request.get_full_name()),
+                        FilterType::AllAndHidden,
+                    ],
+                    None,
+                );
+                if !disk_manager.list_files(&list_request)?.is_empty() {
+                    let question = match request.file_extension.as_str() {
+                        "" => format!(
+                            "Delete directory {} and all its content?",
+                            request.file_name
+                        ),
+                        _ => format!(
+                            "Delete file {}.{}?",
+                            request.file_name, request.file_extension
+                        ),
+                    };
+
+                    if !Self::is_confirmed(
+                        self.confirmation_prompt.as_mut(),
+                        self.config.lock().unwrap().confirm_destructive,
+                        &request,
+                        &question,
+                    )? {
+                        return Err(Box::try_from("Deletion cancelled").unwrap());
+                    }
+                }
+
+                match request.file_extension.as_str() {
+                    "" => {
+                        log::info!("Deleting directory {}...", request.file_name);
+                        cprintln!("Deleting directory <b!>{}</>...", request.file_name,);
+                    }
+                    _ => {
+                        log::info!(
+                            "Deleting file {}.{}...",
+                            request.file_name,
+                            request.file_extension
+                        );
+                        cprintln!(
+                            "Deleting file <b!>{}.{}</>...",
+                            request.file_name,
+                            request.file_extension
+                        );
+                    }
+                }
+
+                // with the trash enabled, the file entry is moved to the trash instead of being freed
+                // (the config is released first, as the disk manager locks it as well)
+                let use_trash = self.config.lock().unwrap().use_trash;
+                let result = match use_trash {
+                    true => disk_manager.move_to_trash(&request),
+                    false => disk_manager.delete_file(&request),
+                };
+                match result {
                     Ok(_) => {
                         log::info!("Deleted file entry successfully");
                         disk_manager.push_sync();
@@ -73,3 +336,172 @@ impl RequestHandler<DeleteRequest, Void> for DeleteHandler {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::confirmation_prompt::ScriptedConfirmationPrompt;
+    use crate::infrastructure::test_disk::TestDisk;
+    use std::sync::{Arc, Mutex};
+
+    /// Runs the request against the disk, with the confirmation of destructive commands enabled
+    /// and the given answer to its questions, returning its result along with the questions asked.
+    fn run_delete(disk: &TestDisk, request: DeleteRequest, answer: bool) -> (Void, Vec<String>) {
+        disk.config.lock().unwrap().confirm_destructive = true;
+        let confirmation_prompt = ScriptedConfirmationPrompt::new(answer);
+        let questions = confirmation_prompt.questions.clone();
+        let mut delete_handler = DeleteHandler::new(
+            disk.config.clone(),
+            Arc::new(Mutex::new(disk.reopen())),
+            Box::new(confirmation_prompt),
+        );
+
+        let result = delete_handler.handle(request);
+        let questions = questions.lock().unwrap().clone();
+
+        (result, questions)
+    }
+
+    /// Runs `del <name>.<extension>` (see `run_delete`).
+    fn del(disk: &TestDisk, name: &str, extension: &str, answer: bool) -> (Void, Vec<String>) {
+        let request =
+            DeleteRequest::new(name.to_string(), extension.to_string(), false, false, false);
+
+        run_delete(disk, request, answer)
+    }
+
+    /// Returns the full names of the entries of the root directory.
+    fn list_names(disk: &mut TestDisk) -> Vec<String> {
+        disk.pull_sync();
+        disk.list_files(&ListRequest::new(vec![], None))
+            .unwrap()
+            .iter()
+            .map(FileEntry::get_full_name)
+            .collect()
+    }
+
+    fn create_log_files() -> TestDisk {
+        let mut disk = TestDisk::new();
+        disk.create("tmp1", "log", 10).unwrap();
+        disk.create("tmp2", "log", 10).unwrap();
+        disk.create("app", "log", 10).unwrap();
+        disk.create("tmp3", "txt", 10).unwrap();
+
+        disk
+    }
+
+    #[test]
+    fn del_with_a_pattern_matching_nothing_fails() {
+        let mut disk = create_log_files();
+
+        let (result, questions) = del(&disk, "old*", "log", true);
+
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "No file or directory matches old*.log"
+        );
+        assert!(questions.is_empty());
+        assert_eq!(list_names(&mut disk).len(), 4);
+    }
+
+    #[test]
+    fn del_with_a_pattern_matching_one_file_deletes_it() {
+        let mut disk = create_log_files();
+
+        let (result, _) = del(&disk, "a?p", "log", true);
+
+        result.unwrap();
+        assert_eq!(
+            list_names(&mut disk),
+            vec!["tmp1.log", "tmp2.log", "tmp3.txt"]
+        );
+    }
+
+    #[test]
+    fn del_with_a_pattern_matching_many_files_deletes_them_once_confirmed() {
+        let mut disk = create_log_files();
+
+        let (result, questions) = del(&disk, "tmp*", "log", false);
+        assert_eq!(result.unwrap_err().to_string(), "Deletion cancelled");
+        assert_eq!(questions, vec!["Delete all of them?"]);
+        assert_eq!(list_names(&mut disk).len(), 4);
+
+        let (result, _) = del(&disk, "tmp*", "log", true);
+        result.unwrap();
+        assert_eq!(list_names(&mut disk), vec!["app.log", "tmp3.txt"]);
+    }
+
+    #[test]
+    fn del_with_a_literal_name_deletes_only_that_file() {
+        let mut disk = create_log_files();
+
+        let (result, questions) = del(&disk, "tmp1", "log", true);
+
+        result.unwrap();
+        assert_eq!(questions, vec!["Delete file tmp1.log?"]);
+        assert_eq!(
+            list_names(&mut disk),
+            vec!["tmp2.log", "app.log", "tmp3.txt"]
+        );
+    }
+
+    #[test]
+    fn del_cancelled_keeps_the_file() {
+        let mut disk = create_log_files();
+
+        let (result, questions) = del(&disk, "app", "log", false);
+
+        assert_eq!(result.unwrap_err().to_string(), "Deletion cancelled");
+        assert_eq!(questions, vec!["Delete file app.log?"]);
+        assert_eq!(list_names(&mut disk).len(), 4);
+    }
+
+    #[test]
+    fn del_of_a_directory_asks_about_its_content() {
+        let mut disk = TestDisk::new();
+        disk.mkdir("docs").unwrap();
+
+        let (result, questions) = del(&disk, "docs", "", false);
+
+        assert!(result.is_err());
+        assert_eq!(
+            questions,
+            vec!["Delete directory docs and all its content?"]
+        );
+        assert_eq!(list_names(&mut disk), vec!["docs"]);
+    }
+
+    #[test]
+    fn del_with_yes_asks_nothing() {
+        let mut disk = create_log_files();
+        let request = DeleteRequest::new("app".to_string(), "log".to_string(), true, false, false);
+
+        let (result, questions) = run_delete(&disk, request, false);
+
+        result.unwrap();
+        assert!(questions.is_empty());
+        assert_eq!(list_names(&mut disk).len(), 3);
+    }
+
+    #[test]
+    fn del_without_confirm_destructive_asks_nothing() {
+        let mut disk = create_log_files();
+        let mut delete_handler = DeleteHandler::new(
+            disk.config.clone(),
+            Arc::new(Mutex::new(disk.reopen())),
+            Box::new(ScriptedConfirmationPrompt::new(false)),
+        );
+
+        delete_handler
+            .handle(DeleteRequest::new(
+                "tmp*".to_string(),
+                "log".to_string(),
+                false,
+                false,
+                false,
+            ))
+            .unwrap();
+
+        assert_eq!(list_names(&mut disk), vec!["app.log", "tmp3.txt"]);
+    }
+}
@@ -0,0 +1,80 @@
+use crate::application::Void;
+use crate::core::Arm;
+use crate::domain::i_disk_manager::IDiskManager;
+use color_print::cprintln;
+use mediator::{Request, RequestHandler};
+
+/// EchoRequest is a request to write or append a text into a file
+/// # Fields
+/// * `name` - the name of the file
+/// * `extension` - the extension of the file
+/// * `content` - the text to be written into the file
+/// * `append` - whether to append the text to the end of the file instead of overwriting its content
+pub(crate) struct EchoRequest {
+    pub(crate) name: String,
+    pub(crate) extension: String,
+    pub(crate) content: String,
+    pub(crate) append: bool,
+}
+
+impl EchoRequest {
+    pub(crate) fn new(name: String, extension: String, content: String, append: bool) -> Self {
+        Self {
+            name,
+            extension,
+            content,
+            append,
+        }
+    }
+}
+
+impl Request<Void> for EchoRequest {}
+
+/// EchoHandler is a handler for EchoRequest holding a reference to the disk manager
+pub(crate) struct EchoHandler {
+    disk_manager: Arm<dyn IDiskManager>,
+}
+
+impl EchoHandler {
+    pub(crate) fn new(disk_manager: Arm<dyn IDiskManager>) -> Self {
+        Self { disk_manager }
+    }
+}
+
+impl RequestHandler<EchoRequest, Void> for EchoHandler {
+    fn handle(&mut self, request: EchoRequest) -> Void {
+        log::info!(
+            "Writing {} bytes into file {}.{}",
+            request.content.len(),
+            request.name,
+            request.extension
+        );
+        cprintln!(
+            "Writing <y!>{}</> bytes into file <b!>{}.{}</>...",
+            request.content.len(),
+            request.name,
+            request.extension
+        );
+
+        match self.disk_manager.lock() {
+            Ok(mut disk_manager) => {
+                disk_manager.pull_sync();
+
+                let result = match request.append {
+                    true => disk_manager.append_to_file(&request),
+                    false => disk_manager.write_file_content(&request),
+                };
+
+                match result {
+                    Ok(_) => {
+                        log::info!("Written file content successfully");
+                        disk_manager.push_sync();
+                        Ok(())
+                    }
+                    Err(e) => Err(e),
+                }
+            }
+            Err(_) => Err(Box::try_from("Unable to lock disk manager!").unwrap()),
+        }
+    }
+}
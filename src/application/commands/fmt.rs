@@ -1,47 +1,317 @@
 use crate::application::Void;
+use crate::core::config::Config;
+use crate::core::confirmation_prompt::IConfirmationPrompt;
 use crate::core::Arm;
+use crate::domain::boot_sector::BootSector;
 use crate::domain::i_disk_manager::IDiskManager;
+use crate::warn;
+use color_print::cprintln;
 use mediator::{Request, RequestHandler};
 
 /// FormatRequest is a request to format the disk
 /// # Fields
 /// * `fat_type` - the FAT type to format the disk with
+/// * `cluster_size` - the cluster size to format the disk with (the current one if not given)
+/// * `skip_confirmation` - whether to format without asking for confirmation
+/// * `dry_run` - whether to only print the geometry of the formatted disk, leaving the disk untouched
 pub(crate) struct FormatRequest {
     pub(crate) fat_type: u16,
+    pub(crate) cluster_size: Option<u16>,
+    pub(crate) skip_confirmation: bool,
+    pub(crate) dry_run: bool,
 }
 
 impl FormatRequest {
-    pub(crate) fn new(fat_type: u16) -> Self {
-        Self { fat_type }
+    pub(crate) fn new(
+        fat_type: u16,
+        cluster_size: Option<u16>,
+        skip_confirmation: bool,
+        dry_run: bool,
+    ) -> Self {
+        Self {
+            fat_type,
+            cluster_size,
+            skip_confirmation,
+            dry_run,
+        }
     }
 }
 
 impl Request<Void> for FormatRequest {}
 
-/// FormatHandler is a handler for FormatRequest holding a reference to the disk manager
+/// FormatHandler is a handler for FormatRequest holding a reference to the config, to the disk manager
+/// and to the source of the answers to the confirmation questions
 pub(crate) struct FormatHandler {
+    config: Arm<Config>,
     disk_manager: Arm<dyn IDiskManager>,
+    confirmation_prompt: Box<dyn IConfirmationPrompt>,
+}
+
+impl FormatHandler {
+    pub(crate) fn new(
+        config: Arm<Config>,
+        disk_manager: Arm<dyn IDiskManager>,
+        confirmation_prompt: Box<dyn IConfirmationPrompt>,
+    ) -> Self {
+        Self {
+            config,
+            disk_manager,
+            confirmation_prompt,
+        }
+    }
 }
 
 impl FormatHandler {
-    pub(crate) fn new(disk_manager: Arm<dyn IDiskManager>) -> Self {
-        Self { disk_manager }
+    /// Prints the geometry of a disk formatted with the given boot sector.
+    fn print_geometry(boot_sector: &BootSector) {
+        let cluster_size = boot_sector.cluster_size as u32;
+        let data_clusters = (boot_sector.cluster_count as u32)
+            .saturating_sub(boot_sector.get_reserved_clusters_count() as u32);
+
+        cprintln!("<c!>FAT</>: <w!>FAT{}</>", boot_sector.fat_cell_bits);
+        cprintln!("<c!>Cluster size</>: <w!>{} B</>", cluster_size);
+        cprintln!("<c!>Cluster count</>: <w!>{}</>", boot_sector.cluster_count);
+        cprintln!(
+            "<c!>Boot sector</>: <w!>{} cluster(s)</>",
+            boot_sector.clusters_per_boot_sector
+        );
+        cprintln!(
+            "<c!>FAT region</>: <w!>{} cluster(s)</>",
+            boot_sector.get_fat_clusters_count()
+        );
+        cprintln!(
+            "<c!>Root region</>: <w!>{} cluster(s)</> ({} entries)",
+            boot_sector.get_root_clusters_count(),
+            boot_sector.root_entry_count
+        );
+        cprintln!("<c!>Data region</>: <w!>{} cluster(s)</>", data_clusters);
+        cprintln!(
+            "<c!>Usable space</>: <w!>{} B</>",
+            data_clusters * cluster_size
+        );
+        cprintln!(
+            "<c!>Total space</>: <w!>{} B</>",
+            boot_sector.get_disk_size()
+        );
     }
 }
 
 impl RequestHandler<FormatRequest, Void> for FormatHandler {
     fn handle(&mut self, req: FormatRequest) -> Void {
+        let confirm_destructive = self.config.lock().unwrap().confirm_destructive;
+        if confirm_destructive && !req.skip_confirmation && !req.dry_run {
+            let question = format!(
+                "Format the disk with FAT{}? All the data will be lost.",
+                req.fat_type
+            );
+
+            if !self.confirmation_prompt.confirm(&question)? {
+                return Err(Box::try_from("Format cancelled").unwrap());
+            }
+        }
+
         log::info!("Formatting disk with FAT type {}", req.fat_type);
 
         match self.disk_manager.lock() {
-            Ok(mut disk_manager) => match disk_manager.format_disk(&req) {
-                Ok(()) => {
-                    log::info!("Disk has been formatted successfully");
-                    Ok(())
+            Ok(mut disk_manager) => {
+                let boot_sector = disk_manager.get_boot_sector();
+                let old_disk_size = boot_sector.get_disk_size();
+                let new_boot_sector = boot_sector.with_format(
+                    req.fat_type,
+                    req.cluster_size.unwrap_or(boot_sector.cluster_size),
+                );
+                let new_disk_size = new_boot_sector.get_disk_size();
+
+                match disk_manager.format_disk(&req) {
+                    Ok(()) if req.dry_run => {
+                        Self::print_geometry(&new_boot_sector);
+                        if new_disk_size < old_disk_size {
+                            let fat_type = req.fat_type;
+                            warn!(
+                                "Warning: FAT{} cannot address the whole disk, which would be shrunk from {} B to {} B",
+                                fat_type,
+                                old_disk_size,
+                                new_disk_size
+                            );
+                        }
+
+                        log::info!("Previewed the format of the disk: {:?}", new_boot_sector);
+                        Ok(())
+                    }
+                    Ok(()) => {
+                        log::info!("Disk has been formatted successfully");
+
+                        if new_disk_size < old_disk_size {
+                            let fat_type = req.fat_type;
+                            warn!(
+                                "Warning: FAT{} cannot address the whole disk, which was shrunk from {} B to {} B",
+                                fat_type,
+                                old_disk_size,
+                                new_disk_size
+                            );
+                        }
+
+                        Ok(())
+                    }
+                    Err(e) => Err(e),
                 }
-                Err(e) => Err(e),
-            },
+            }
             Err(_e) => Err(Box::try_from("Unable to lock disk manager!").unwrap()),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::confirmation_prompt::ScriptedConfirmationPrompt;
+    use crate::infrastructure::test_disk::TestDisk;
+    use std::sync::{Arc, Mutex};
+
+    /// Runs `fmt 16` against a disk holding a file, with the confirmation of destructive commands enabled
+    /// and the given answer to its questions, returning its result, the questions asked and whether the file is left.
+    fn format(skip_confirmation: bool, answer: bool) -> (Void, Vec<String>, bool) {
+        let mut disk = TestDisk::with_config(|config| config.confirm_destructive = true);
+        disk.write("a", "txt", "content").unwrap();
+        let confirmation_prompt = ScriptedConfirmationPrompt::new(answer);
+        let questions = confirmation_prompt.questions.clone();
+        let mut format_handler = FormatHandler::new(
+            disk.config.clone(),
+            Arc::new(Mutex::new(disk.reopen())),
+            Box::new(confirmation_prompt),
+        );
+
+        let result = format_handler.handle(FormatRequest::new(16, None, skip_confirmation, false));
+        let questions = questions.lock().unwrap().clone();
+        let file_left = !disk.reopen().walk().is_empty();
+
+        (result, questions, file_left)
+    }
+
+    #[test]
+    fn fmt_confirmed_wipes_the_disk() {
+        let (result, questions, file_left) = format(false, true);
+
+        result.unwrap();
+        assert_eq!(
+            questions,
+            vec!["Format the disk with FAT16? All the data will be lost."]
+        );
+        assert!(!file_left);
+    }
+
+    #[test]
+    fn fmt_cancelled_leaves_the_disk_untouched() {
+        let (result, questions, file_left) = format(false, false);
+
+        assert_eq!(result.unwrap_err().to_string(), "Format cancelled");
+        assert_eq!(questions.len(), 1);
+        assert!(file_left);
+    }
+
+    #[test]
+    fn fmt_with_yes_asks_nothing() {
+        let (result, questions, file_left) = format(true, false);
+
+        result.unwrap();
+        assert!(questions.is_empty());
+        assert!(!file_left);
+    }
+
+    /// Runs `fmt -y <fat_type> [<cluster_size>]` against the disk and reloads it.
+    fn format_with(disk: &mut TestDisk, fat_type: u16, cluster_size: Option<u16>) {
+        let mut format_handler = FormatHandler::new(
+            disk.config.clone(),
+            Arc::new(Mutex::new(disk.reopen())),
+            Box::new(ScriptedConfirmationPrompt::new(true)),
+        );
+        format_handler
+            .handle(FormatRequest::new(fat_type, cluster_size, true, false))
+            .unwrap();
+        disk.disk_manager = disk.reopen();
+    }
+
+    #[test]
+    fn a_fat12_disk_keeps_its_files_across_sessions() {
+        let mut disk = TestDisk::new();
+        format_with(&mut disk, 12, None);
+        assert_eq!(disk.get_boot_sector().fat_cell_bits, 12);
+        assert_eq!(disk.get_boot_sector().cluster_count, 4087);
+
+        // the big file spans most of the 12-bit cluster range
+        disk.write("a", "txt", "first file").unwrap();
+        disk.mkdir("dir").unwrap();
+        disk.cd("dir").unwrap();
+        disk.create("big", "bin", 50_000).unwrap();
+        disk.cd("/").unwrap();
+        disk.append("a", "txt", ", appended after the big file")
+            .unwrap();
+
+        let mut disk_manager = disk.reopen();
+        assert_eq!(
+            disk_manager.get_fat_slice(0, 4087),
+            disk.get_fat_slice(0, 4087)
+        );
+        assert!(disk_manager.check_disk().is_empty());
+        disk.disk_manager = disk_manager;
+        assert_eq!(
+            disk.read("a", "txt"),
+            b"first file, appended after the big file"
+        );
+        disk.cd("dir").unwrap();
+        let big_content = disk.read("big", "bin");
+        assert_eq!(big_content.len(), 50_000);
+        assert!(big_content.starts_with(b"ABCDEFGHIJKLMNOPQRSTUVWXYZABC"));
+    }
+
+    #[test]
+    fn fmt_sets_the_fat_type_and_the_cluster_size_independently() {
+        // (fat type, cluster size, expected cluster size, expected cluster count) for the 128 KiB default disk
+        let combinations = [
+            (16, None, 16, 8192),
+            (32, None, 16, 8192),
+            (16, Some(32), 32, 4096),
+            (32, Some(32), 32, 4096),
+            (12, Some(32), 32, 4087),
+            (12, None, 16, 4087),
+        ];
+
+        for (fat_type, cluster_size, expected_cluster_size, expected_cluster_count) in combinations
+        {
+            let mut disk = TestDisk::new();
+
+            format_with(&mut disk, fat_type, cluster_size);
+
+            let boot_sector = disk.get_boot_sector();
+            assert_eq!(
+                (
+                    boot_sector.fat_cell_bits,
+                    boot_sector.cluster_size,
+                    boot_sector.cluster_count
+                ),
+                (fat_type, expected_cluster_size, expected_cluster_count),
+                "fmt {} {:?}",
+                fat_type,
+                cluster_size
+            );
+            assert_eq!(
+                std::fs::metadata(&disk.config.lock().unwrap().storage_file_path)
+                    .unwrap()
+                    .len(),
+                boot_sector.get_disk_size() as u64
+            );
+        }
+    }
+
+    #[test]
+    fn fmt_keeps_the_cluster_size_chosen_by_the_previous_format() {
+        let mut disk = TestDisk::new();
+        format_with(&mut disk, 16, Some(32));
+
+        format_with(&mut disk, 32, None);
+
+        assert_eq!(disk.get_boot_sector().fat_cell_bits, 32);
+        assert_eq!(disk.get_boot_sector().cluster_size, 32);
+        assert_eq!(disk.get_boot_sector().get_disk_size(), 8192 * 16);
+    }
+}
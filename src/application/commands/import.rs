@@ -0,0 +1,358 @@
+use crate::application::commands::cd::ChangeDirectoryRequest;
+use crate::application::commands::create::CreateRequest;
+use crate::application::commands::mkdir::MakeDirectoryRequest;
+use crate::application::Void;
+use crate::core::config::Config;
+use crate::core::content_type::ContentType;
+use crate::core::Arm;
+use crate::domain::file_entry::{FileEntry, FileEntryAttributes};
+use crate::domain::i_disk_manager::IDiskManager;
+use crate::warn;
+use chrono::{DateTime, Utc};
+use color_print::cprintln;
+use mediator::{Request, RequestHandler};
+use std::path::Path;
+
+/// ImportRequest is a request to copy a file or directory (recursively) from the host filesystem
+/// into the working directory
+/// # Fields
+/// * `host_path` - The path of the file or directory on the host
+/// * `name` - The name of the imported file or directory (derived from the host name if missing)
+/// * `extension` - The extension of the imported file (derived from the host name if missing)
+pub(crate) struct ImportRequest {
+    pub(crate) host_path: String,
+    pub(crate) name: Option<String>,
+    pub(crate) extension: Option<String>,
+}
+
+impl ImportRequest {
+    pub(crate) fn new(host_path: String, name: Option<String>, extension: Option<String>) -> Self {
+        Self {
+            host_path,
+            name,
+            extension,
+        }
+    }
+}
+
+impl Request<Void> for ImportRequest {}
+
+/// ImportHandler is a handler for ImportRequest holding a reference to the config (for the temp buffer file)
+/// and to the disk manager
+pub(crate) struct ImportHandler {
+    config: Arm<Config>,
+    disk_manager: Arm<dyn IDiskManager>,
+}
+
+impl ImportHandler {
+    pub(crate) fn new(config: Arm<Config>, disk_manager: Arm<dyn IDiskManager>) -> Self {
+        Self {
+            config,
+            disk_manager,
+        }
+    }
+
+    /// Turns a host name part into a valid RoDOS one: the characters other than ASCII letters, digits
+    /// and `_` are replaced with `_` and the result is truncated to `max_length`, with a warning.
+    fn sanitize_name_part(host_name: &str, name_part: &str, max_length: usize) -> String {
+        let mut sanitized: String = name_part
+            .chars()
+            .map(|c| match c.is_ascii_alphanumeric() || c == '_' {
+                true => c,
+                false => '_',
+            })
+            .collect();
+        sanitized.truncate(max_length);
+
+        if sanitized != name_part {
+            let message = format!("Warning: {} is imported as {}", name_part, sanitized);
+            warn!("{} (from {})", message, host_name);
+            log::warn!("{} (from {})", message, host_name);
+        }
+
+        sanitized
+    }
+
+    /// Splits a host file name into a valid RoDOS name and extension (at the last `.`).
+    fn split_host_file_name(host_name: &str) -> Option<(String, String)> {
+        let (name, extension) = host_name.rsplit_once('.')?;
+        if name.is_empty() || extension.is_empty() {
+            return None;
+        }
+
+        Some((
+            Self::sanitize_name_part(host_name, name, FileEntry::MAX_NAME_LENGTH),
+            Self::sanitize_name_part(host_name, extension, FileEntry::MAX_EXTENSION_LENGTH),
+        ))
+    }
+
+    /// Returns the modification time of the host file or directory, or now if it is not available.
+    fn get_host_modification_datetime(host_path: &Path) -> DateTime<Utc> {
+        match std::fs::metadata(host_path).and_then(|metadata| metadata.modified()) {
+            Ok(modification_time) => DateTime::<Utc>::from(modification_time),
+            Err(_) => Utc::now(),
+        }
+    }
+
+    /// Creates a file in the working directory filled with the bytes of the host file.
+    fn import_file(
+        &self,
+        disk_manager: &mut dyn IDiskManager,
+        host_path: &Path,
+        name: String,
+        extension: String,
+    ) -> Void {
+        let content = std::fs::read(host_path)?;
+        let size = u32::try_from(content.len())
+            .map_err(|_| format!("Host file {} is too large", host_path.display()))?;
+
+        // write the content to the temp buffer file to be read back when filling the new file
+        let temp_file_path = self.config.lock().unwrap().temp_file_path.clone();
+        std::fs::write(temp_file_path, content)?;
+
+        cprintln!(
+            "Importing <b!>{}</> as <b!>{}.{}</> (<y!>{} B</>)...",
+            host_path.display(),
+            name,
+            extension,
+            size
+        );
+        disk_manager.pull_sync();
+        disk_manager.create_file(&CreateRequest::new(
+            name,
+            extension,
+            size,
+            FileEntryAttributes::combine(&[
+                FileEntryAttributes::File,
+                FileEntryAttributes::ReadWrite,
+                FileEntryAttributes::Visible,
+                FileEntryAttributes::Archive,
+            ]),
+            Self::get_host_modification_datetime(host_path),
+            ContentType::Temp,
+        ))?;
+        disk_manager.push_sync();
+
+        Ok(())
+    }
+
+    /// Creates a directory in the working directory and imports every child of the host directory into it.
+    fn import_directory(
+        &self,
+        disk_manager: &mut dyn IDiskManager,
+        host_path: &Path,
+        name: String,
+    ) -> Void {
+        cprintln!(
+            "Importing <b!>{}</> as <b!>{}</>...",
+            host_path.display(),
+            name
+        );
+        disk_manager.pull_sync();
+        disk_manager.make_directory(&MakeDirectoryRequest::new(
+            name.clone(),
+            FileEntryAttributes::combine(&[
+                FileEntryAttributes::Directory,
+                FileEntryAttributes::ReadWrite,
+                FileEntryAttributes::Visible,
+            ]),
+            Self::get_host_modification_datetime(host_path),
+        ))?;
+        disk_manager.push_sync();
+
+        disk_manager.pull_sync();
+        disk_manager.change_working_directory(&ChangeDirectoryRequest::new(name))?;
+
+        let result = self.import_children(disk_manager, host_path);
+
+        // always get back to the parent directory, even if the import of a child failed
+        disk_manager.pull_sync();
+        disk_manager.change_working_directory(&ChangeDirectoryRequest::new("..".to_string()))?;
+
+        result
+    }
+
+    /// Imports every child of the host directory into the working directory (in name order).
+    fn import_children(&self, disk_manager: &mut dyn IDiskManager, host_path: &Path) -> Void {
+        let mut children = std::fs::read_dir(host_path)?
+            .map(|child| child.map(|child| child.path()))
+            .collect::<Result<Vec<_>, _>>()?;
+        children.sort();
+
+        for child_path in children {
+            let child_name = child_path
+                .file_name()
+                .map(|child_name| child_name.to_string_lossy().to_string())
+                .unwrap_or_default();
+
+            if child_path.is_dir() {
+                let name =
+                    Self::sanitize_name_part(&child_name, &child_name, FileEntry::MAX_NAME_LENGTH);
+                self.import_directory(disk_manager, &child_path, name)?;
+                continue;
+            }
+
+            match Self::split_host_file_name(&child_name) {
+                Some((name, extension)) => {
+                    self.import_file(disk_manager, &child_path, name, extension)?
+                }
+                None => {
+                    warn!("Warning: Skipping {} as it has no extension", child_name);
+                    log::warn!("Skipping {} as it has no extension", child_name);
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl RequestHandler<ImportRequest, Void> for ImportHandler {
+    fn handle(&mut self, request: ImportRequest) -> Void {
+        log::info!("Importing {}", request.host_path);
+
+        let host_path = Path::new(&request.host_path);
+        if !host_path.exists() {
+            return Err(
+                Box::try_from(format!("Host path {} does not exist", request.host_path)).unwrap(),
+            );
+        }
+
+        let host_name = host_path
+            .file_name()
+            .map(|host_name| host_name.to_string_lossy().to_string())
+            .unwrap_or_default();
+
+        match self.disk_manager.lock() {
+            Ok(mut disk_manager) => {
+                disk_manager.pull_sync();
+
+                match (host_path.is_dir(), request.name, request.extension) {
+                    (true, _, Some(_)) => Err(Box::try_from(
+                        "A directory cannot be imported with an extension",
+                    )
+                    .unwrap()),
+                    (true, Some(name), None) => {
+                        self.import_directory(&mut *disk_manager, host_path, name)
+                    }
+                    (true, None, None) => {
+                        let name = Self::sanitize_name_part(
+                            &host_name,
+                            &host_name,
+                            FileEntry::MAX_NAME_LENGTH,
+                        );
+                        self.import_directory(&mut *disk_manager, host_path, name)
+                    }
+                    (false, Some(name), Some(extension)) => {
+                        self.import_file(&mut *disk_manager, host_path, name, extension)
+                    }
+                    (false, Some(_), None) => {
+                        Err(Box::try_from("A file must be imported with an extension").unwrap())
+                    }
+                    (false, None, _) => match Self::split_host_file_name(&host_name) {
+                        Some((name, extension)) => {
+                            self.import_file(&mut *disk_manager, host_path, name, extension)
+                        }
+                        None => Err(Box::try_from(format!(
+                            "Host file {} has no extension, please give it a name",
+                            host_name
+                        ))
+                        .unwrap()),
+                    },
+                }
+            }
+            Err(_) => Err(Box::try_from("Unable to lock disk manager!").unwrap()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::queries::ls::ListRequest;
+    use crate::infrastructure::test_disk::TestDisk;
+    use std::path::PathBuf;
+    use std::sync::{Arc, Mutex};
+
+    /// Returns a path in the folder of the test disk, removed along with it.
+    fn host_path(disk: &TestDisk, file_name: &str) -> PathBuf {
+        PathBuf::from(&disk.config.lock().unwrap().disk_dir_path).join(file_name)
+    }
+
+    fn import(
+        disk: &TestDisk,
+        host_path: &Path,
+        name: Option<&str>,
+        extension: Option<&str>,
+    ) -> Void {
+        let mut import_handler =
+            ImportHandler::new(disk.config.clone(), Arc::new(Mutex::new(disk.reopen())));
+
+        import_handler.handle(ImportRequest::new(
+            host_path.to_string_lossy().into_owned(),
+            name.map(str::to_string),
+            extension.map(str::to_string),
+        ))
+    }
+
+    fn list_names(disk: &mut TestDisk) -> Vec<String> {
+        disk.pull_sync();
+        disk.list_files(&ListRequest::new(vec![], None))
+            .unwrap()
+            .iter()
+            .map(|file_entry| file_entry.get_full_name())
+            .collect()
+    }
+
+    #[test]
+    fn import_recreates_a_host_directory_with_the_bytes_of_its_files() {
+        let mut disk = TestDisk::new();
+        let imported_path = host_path(&disk, "project");
+        std::fs::create_dir_all(imported_path.join("src")).unwrap();
+        std::fs::write(imported_path.join("readme.md"), "# Project").unwrap();
+        std::fs::write(imported_path.join("src").join("main.rs"), "fn main() {}").unwrap();
+        std::fs::write(imported_path.join("src").join("lib.rs"), [0u8, 1, 2, 255]).unwrap();
+
+        import(&disk, &imported_path, None, None).unwrap();
+
+        assert_eq!(list_names(&mut disk), vec!["project"]);
+        disk.cd("project").unwrap();
+        assert_eq!(list_names(&mut disk), vec!["readme.md", "src"]);
+        assert_eq!(disk.read("readme", "md"), b"# Project");
+        disk.cd("src").unwrap();
+        assert_eq!(list_names(&mut disk), vec!["lib.rs", "main.rs"]);
+        assert_eq!(disk.read("main", "rs"), b"fn main() {}");
+        assert_eq!(disk.read("lib", "rs"), [0u8, 1, 2, 255]);
+    }
+
+    #[test]
+    fn import_truncates_the_host_names_too_long_for_the_disk() {
+        let mut disk = TestDisk::new();
+        let imported_path = host_path(
+            &disk,
+            "a-name-longer-than-the-32-characters-allowed.markdown",
+        );
+        std::fs::write(&imported_path, "content").unwrap();
+
+        import(&disk, &imported_path, None, None).unwrap();
+
+        assert_eq!(
+            list_names(&mut disk),
+            vec!["a_name_longer_than_the_32_charac.mar"]
+        );
+        assert_eq!(
+            disk.read("a_name_longer_than_the_32_charac", "mar"),
+            b"content"
+        );
+    }
+
+    #[test]
+    fn import_fails_without_enough_free_space() {
+        let mut disk = TestDisk::new();
+        let imported_path = host_path(&disk, "huge.bin");
+        std::fs::write(&imported_path, vec![0u8; 200_000]).unwrap();
+
+        assert!(import(&disk, &imported_path, None, None).is_err());
+        assert!(list_names(&mut disk).is_empty());
+    }
+}
@@ -0,0 +1,64 @@
+use crate::application::Void;
+use crate::core::Arm;
+use crate::domain::i_disk_manager::IDiskManager;
+use crate::{info, success};
+use color_print::cprintln;
+use mediator::{Request, RequestHandler};
+
+/// LabelRequest is a request to show or set the volume label of the disk
+/// # Fields
+/// * `new_label` - the label to set, or `None` to show the current one
+pub(crate) struct LabelRequest {
+    pub(crate) new_label: Option<String>,
+}
+
+impl LabelRequest {
+    pub(crate) fn new(new_label: Option<String>) -> Self {
+        Self { new_label }
+    }
+}
+
+impl Request<Void> for LabelRequest {}
+
+/// LabelHandler is a handler for LabelRequest holding a reference to the disk manager
+pub(crate) struct LabelHandler {
+    disk_manager: Arm<dyn IDiskManager>,
+}
+
+impl LabelHandler {
+    pub(crate) fn new(disk_manager: Arm<dyn IDiskManager>) -> Self {
+        Self { disk_manager }
+    }
+}
+
+impl RequestHandler<LabelRequest, Void> for LabelHandler {
+    fn handle(&mut self, request: LabelRequest) -> Void {
+        match self.disk_manager.lock() {
+            Ok(mut disk_manager) => {
+                disk_manager.pull_sync();
+
+                if request.new_label.is_none() {
+                    log::info!("Showing the volume label...");
+
+                    match disk_manager.get_boot_sector().label.as_str() {
+                        "" => {
+                            info!("The disk has no volume label");
+                        }
+                        label => println!("Volume label: {}", label),
+                    }
+                    return Ok(());
+                }
+
+                log::info!("Setting the volume label...");
+                disk_manager.set_volume_label(&request)?;
+                disk_manager.push_sync();
+
+                let label = disk_manager.get_boot_sector().label.clone();
+                success!("Volume label set to {}!", label);
+                log::info!("Volume label set to {}", label);
+                Ok(())
+            }
+            Err(_) => Err(Box::try_from("Unable to lock disk manager!").unwrap()),
+        }
+    }
+}
@@ -0,0 +1,74 @@
+use crate::application::Void;
+use crate::core::Arm;
+use crate::domain::i_disk_manager::IDiskManager;
+use mediator::{Request, RequestHandler};
+
+/// LinkRequest is a request to create a hard link to a file, i.e. a second file entry sharing its allocation chain
+/// # Fields
+/// * `src_name` - The name of the linked file
+/// * `src_extension` - The extension of the linked file
+/// * `link_name` - The name of the new link
+/// * `link_extension` - The extension of the new link
+pub(crate) struct LinkRequest {
+    pub(crate) src_name: String,
+    pub(crate) src_extension: String,
+    pub(crate) link_name: String,
+    pub(crate) link_extension: String,
+}
+
+impl LinkRequest {
+    pub(crate) fn new(
+        src_name: String,
+        src_extension: String,
+        link_name: String,
+        link_extension: String,
+    ) -> Self {
+        Self {
+            src_name,
+            src_extension,
+            link_name,
+            link_extension,
+        }
+    }
+}
+
+impl Request<Void> for LinkRequest {}
+
+/// LinkHandler is a handler for LinkRequest holding a reference to the disk manager
+pub(crate) struct LinkHandler {
+    disk_manager: Arm<dyn IDiskManager>,
+}
+
+impl LinkHandler {
+    pub(crate) fn new(disk_manager: Arm<dyn IDiskManager>) -> Self {
+        Self { disk_manager }
+    }
+}
+
+impl RequestHandler<LinkRequest, Void> for LinkHandler {
+    fn handle(&mut self, request: LinkRequest) -> Void {
+        log::info!("Linking file...");
+
+        match self.disk_manager.lock() {
+            Ok(mut disk_manager) => {
+                disk_manager.pull_sync();
+
+                match disk_manager.link_file(&request) {
+                    Ok(()) => {
+                        log::info!(
+                            "Linked file {}.{} as {}.{} successfully",
+                            request.src_name,
+                            request.src_extension,
+                            request.link_name,
+                            request.link_extension
+                        );
+                        disk_manager.push_sync();
+                        Ok(())
+                    }
+                    Err(e) => Err(e),
+                }
+            }
+            Err(_) => Err(Box::try_from("Unable to lock disk manager!").unwrap()),
+        }
+    }
+}
@@ -58,7 +58,7 @@ impl RequestHandler<MakeDirectoryRequest, Void> for MakeDirectoryHandler {
                     }
                     Err(err) => {
                         log::error!("Unable to make directory {}: {}", req.name, err);
-                        Err(Box::try_from("Unable to make directory").unwrap())
+                        Err(Box::try_from(format!("Unable to make directory: {}", err)).unwrap())
                     }
                 }
             }
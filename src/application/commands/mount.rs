@@ -0,0 +1,58 @@
+use crate::application::Void;
+use crate::core::mount_table::MountTable;
+use crate::core::Arm;
+use mediator::{Request, RequestHandler};
+
+/// MountRequest is a request to mount another disk image under a label
+/// # Fields
+/// * `image_path` - the path of the storage file of the disk on the host
+/// * `label` - the label addressing the disk in the commands (as a `label:` prefix of their arguments)
+/// * `read_only` - whether to mount the disk read-only
+pub(crate) struct MountRequest {
+    pub(crate) image_path: String,
+    pub(crate) label: String,
+    pub(crate) read_only: bool,
+}
+
+impl MountRequest {
+    pub(crate) fn new(image_path: String, label: String, read_only: bool) -> Self {
+        Self {
+            image_path,
+            label,
+            read_only,
+        }
+    }
+}
+
+impl Request<Void> for MountRequest {}
+
+/// MountHandler is a handler for MountRequest holding a reference to the table of the mounted disks
+pub(crate) struct MountHandler {
+    mount_table: Arm<MountTable>,
+}
+
+impl MountHandler {
+    pub(crate) fn new(mount_table: Arm<MountTable>) -> Self {
+        Self { mount_table }
+    }
+}
+
+impl RequestHandler<MountRequest, Void> for MountHandler {
+    fn handle(&mut self, request: MountRequest) -> Void {
+        log::info!("Mounting {} as {}...", request.image_path, request.label);
+
+        match self.mount_table.lock() {
+            Ok(mut mount_table) => {
+                mount_table.mount(&request.image_path, &request.label, request.read_only)?;
+
+                log::info!(
+                    "Mounted {} as {} successfully",
+                    request.image_path,
+                    request.label
+                );
+                Ok(())
+            }
+            Err(_) => Err(Box::try_from("Unable to lock mount table!").unwrap()),
+        }
+    }
+}
@@ -0,0 +1,56 @@
+use crate::application::Void;
+use crate::core::Arm;
+use crate::domain::i_disk_manager::IDiskManager;
+use crate::{info, success};
+use color_print::cprintln;
+use mediator::{Request, RequestHandler};
+
+/// RedoRequest is a request to redo the last command undone by `undo`
+pub(crate) struct RedoRequest;
+
+impl RedoRequest {
+    pub(crate) fn new() -> Self {
+        Self {}
+    }
+}
+
+impl Request<Void> for RedoRequest {}
+
+/// RedoHandler is a handler for RedoRequest holding a reference to the disk manager
+pub(crate) struct RedoHandler {
+    disk_manager: Arm<dyn IDiskManager>,
+}
+
+impl RedoHandler {
+    pub(crate) fn new(disk_manager: Arm<dyn IDiskManager>) -> Self {
+        Self { disk_manager }
+    }
+}
+
+impl RequestHandler<RedoRequest, Void> for RedoHandler {
+    fn handle(&mut self, _req: RedoRequest) -> Void {
+        log::info!("Redoing the last undone command...");
+
+        match self.disk_manager.lock() {
+            Ok(mut disk_manager) => {
+                disk_manager.pull_sync();
+
+                match disk_manager.reapply_snapshot()? {
+                    true => {
+                        disk_manager.push_sync();
+
+                        success!("Last undone command redone successfully!");
+                        log::info!("Last undone command has been redone successfully");
+                    }
+                    false => {
+                        info!("Nothing to redo");
+                        log::info!("Nothing to redo");
+                    }
+                }
+
+                Ok(())
+            }
+            Err(_) => Err(Box::try_from("Unable to lock disk manager!").unwrap()),
+        }
+    }
+}
@@ -0,0 +1,64 @@
+use crate::application::Void;
+use crate::core::Arm;
+use crate::domain::i_disk_manager::IDiskManager;
+use color_print::cprintln;
+use mediator::{Request, RequestHandler};
+
+/// ResizeRequest is a request to grow or shrink the disk
+/// # Fields
+/// * `cluster_count` - the new total number of clusters of the disk
+pub(crate) struct ResizeRequest {
+    pub(crate) cluster_count: u16,
+}
+
+impl ResizeRequest {
+    pub(crate) fn new(cluster_count: u16) -> Self {
+        Self { cluster_count }
+    }
+}
+
+impl Request<Void> for ResizeRequest {}
+
+/// ResizeHandler is a handler for ResizeRequest holding a reference to the disk manager
+pub(crate) struct ResizeHandler {
+    disk_manager: Arm<dyn IDiskManager>,
+}
+
+impl ResizeHandler {
+    pub(crate) fn new(disk_manager: Arm<dyn IDiskManager>) -> Self {
+        Self { disk_manager }
+    }
+}
+
+impl RequestHandler<ResizeRequest, Void> for ResizeHandler {
+    fn handle(&mut self, req: ResizeRequest) -> Void {
+        log::info!("Resizing disk to {} clusters...", req.cluster_count);
+
+        match self.disk_manager.lock() {
+            Ok(mut disk_manager) => {
+                disk_manager.pull_sync();
+                let old_disk_size = disk_manager.get_boot_sector().get_disk_size();
+
+                match disk_manager.resize_disk(&req) {
+                    Ok(()) => {
+                        let new_disk_size = disk_manager.get_boot_sector().get_disk_size();
+                        cprintln!(
+                            "<c!>Disk size</>: <w!>{} B</> -> <w!>{} B</>",
+                            old_disk_size,
+                            new_disk_size
+                        );
+
+                        log::info!(
+                            "Disk has been resized successfully ({} B -> {} B)",
+                            old_disk_size,
+                            new_disk_size
+                        );
+                        Ok(())
+                    }
+                    Err(e) => Err(e),
+                }
+            }
+            Err(_e) => Err(Box::try_from("Unable to lock disk manager!").unwrap()),
+        }
+    }
+}
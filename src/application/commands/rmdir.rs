@@ -0,0 +1,138 @@
+use crate::application::Void;
+use crate::core::Arm;
+use crate::domain::i_disk_manager::IDiskManager;
+use color_print::cprintln;
+use mediator::{Request, RequestHandler};
+
+/// RemoveDirectoryRequest is a request to remove an empty directory
+/// # Fields
+/// * `name` - the name of the directory to remove
+pub(crate) struct RemoveDirectoryRequest {
+    pub(crate) name: String,
+}
+
+impl RemoveDirectoryRequest {
+    pub(crate) fn new(name: String) -> Self {
+        Self { name }
+    }
+}
+
+impl Request<Void> for RemoveDirectoryRequest {}
+
+/// RemoveDirectoryHandler is a handler that handles RemoveDirectoryRequests holding a reference to a disk manager
+pub(crate) struct RemoveDirectoryHandler {
+    disk_manager: Arm<dyn IDiskManager>,
+}
+
+impl RemoveDirectoryHandler {
+    pub(crate) fn new(disk_manager: Arm<dyn IDiskManager>) -> Self {
+        Self { disk_manager }
+    }
+}
+
+impl RequestHandler<RemoveDirectoryRequest, Void> for RemoveDirectoryHandler {
+    fn handle(&mut self, request: RemoveDirectoryRequest) -> Void {
+        match self.disk_manager.lock() {
+            Ok(mut disk_manager) => {
+                disk_manager.pull_sync();
+
+                log::info!("Removing directory {}...", request.name);
+                cprintln!("Removing directory <b!>{}</>...", request.name);
+
+                match disk_manager.remove_empty_directory(&request) {
+                    Ok(_) => {
+                        log::info!("Removed directory {} successfully", request.name);
+                        disk_manager.push_sync();
+                        Ok(())
+                    }
+                    Err(e) => Err(e),
+                }
+            }
+            Err(_) => Err(Box::try_from("Unable to lock disk manager!").unwrap()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::queries::ls::ListRequest;
+    use crate::core::filter_type::FilterType;
+    use crate::infrastructure::test_disk::TestDisk;
+    use std::sync::{Arc, Mutex};
+
+    fn rmdir(disk: &TestDisk, name: &str) -> Void {
+        let mut remove_directory_handler =
+            RemoveDirectoryHandler::new(Arc::new(Mutex::new(disk.reopen())));
+
+        remove_directory_handler.handle(RemoveDirectoryRequest::new(name.to_string()))
+    }
+
+    fn list_names(disk: &TestDisk) -> Vec<String> {
+        disk.reopen()
+            .list_files(&ListRequest::new(vec![FilterType::AllAndHidden], None))
+            .unwrap()
+            .iter()
+            .map(|file_entry| file_entry.get_full_name())
+            .filter(|full_name| !full_name.is_empty() && full_name != "." && full_name != "..")
+            .collect()
+    }
+
+    #[test]
+    fn rmdir_removes_an_empty_directory() {
+        let mut disk = TestDisk::new();
+        disk.mkdir("empty").unwrap();
+        disk.mkdir("other").unwrap();
+
+        rmdir(&disk, "empty").unwrap();
+
+        assert_eq!(list_names(&disk), vec!["other"]);
+    }
+
+    #[test]
+    fn rmdir_fails_on_a_directory_with_children() {
+        let mut disk = TestDisk::new();
+        disk.mkdir("files").unwrap();
+        disk.mkdir("dirs").unwrap();
+        disk.cd("files").unwrap();
+        disk.write("a", "txt", "a").unwrap();
+        disk.cd("/dirs").unwrap();
+        disk.mkdir("sub").unwrap();
+        disk.cd("/").unwrap();
+
+        assert_eq!(
+            rmdir(&disk, "files").unwrap_err().to_string(),
+            "Directory files is not empty"
+        );
+        assert_eq!(
+            rmdir(&disk, "dirs").unwrap_err().to_string(),
+            "Directory dirs is not empty"
+        );
+        assert_eq!(list_names(&disk), vec!["files", "dirs"]);
+        disk.cd("files").unwrap();
+        assert_eq!(disk.read("a", "txt"), b"a");
+    }
+
+    #[test]
+    fn rmdir_fails_on_a_missing_directory() {
+        let disk = TestDisk::new();
+
+        assert_eq!(
+            rmdir(&disk, "missing").unwrap_err().to_string(),
+            "Directory missing does not exist"
+        );
+    }
+
+    #[test]
+    fn del_still_removes_a_directory_with_children() {
+        let mut disk = TestDisk::new();
+        disk.mkdir("files").unwrap();
+        disk.cd("files").unwrap();
+        disk.write("a", "txt", "a").unwrap();
+        disk.cd("/").unwrap();
+
+        disk.delete("files", "").unwrap();
+
+        assert!(list_names(&disk).is_empty());
+    }
+}
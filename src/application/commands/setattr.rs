@@ -1,7 +1,12 @@
+use crate::application::queries::ls::ListRequest;
 use crate::application::Void;
+use crate::core::filter_type::FilterType;
+use crate::core::glob_pattern::GlobPattern;
 use crate::core::Arm;
 use crate::domain::file_entry::FileEntryAttributes;
 use crate::domain::i_disk_manager::IDiskManager;
+use crate::error;
+use color_print::cprintln;
 use mediator::{Request, RequestHandler};
 
 /// SetAttributesRequest is a request to set attributes for a file
@@ -9,6 +14,9 @@ use mediator::{Request, RequestHandler};
 /// * `name` - the name of the file
 /// * `extension` - the extension of the file (empty if directory)
 /// * `attributes` - the attributes to set
+///
+/// The name and the extension may contain the `*` and `?` wildcards, in which case the attributes
+/// of every matching file or directory (hidden ones included) from the working directory are set.
 pub(crate) struct SetAttributesRequest {
     pub(crate) name: String,
     pub(crate) extension: String,
@@ -27,6 +35,14 @@ impl SetAttributesRequest {
             attributes,
         }
     }
+
+    /// Returns the full name of the file entry to update (`name.extension` for files and `name` for directories).
+    pub(crate) fn get_full_name(&self) -> String {
+        match self.extension.is_empty() {
+            true => self.name.clone(),
+            false => format!("{}.{}", self.name, self.extension),
+        }
+    }
 }
 
 impl Request<Void> for SetAttributesRequest {}
@@ -40,10 +56,80 @@ impl SetAttributesHandler {
     pub(crate) fn new(disk_manager: Arm<dyn IDiskManager>) -> Self {
         Self { disk_manager }
     }
+
+    /// Sets the attributes of every file or directory from the working directory matching the wildcard pattern
+    /// of the request, reporting the outcome for each of them (a failure doesn't stop the others from being updated).
+    fn set_matching_attributes(&mut self, request: SetAttributesRequest) -> Void {
+        let pattern = request.get_full_name();
+        match self.disk_manager.lock() {
+            Ok(mut disk_manager) => {
+                disk_manager.pull_sync();
+
+                let list_request = ListRequest::new(
+                    vec![FilterType::Glob(pattern.clone()), FilterType::AllAndHidden],
+                    None,
+                );
+                let file_entries: Vec<_> = disk_manager
+                    .list_files(&list_request)?
+                    .into_iter()
+                    .filter(|file_entry| file_entry.name != "." && file_entry.name != "..")
+                    .collect();
+
+                if file_entries.is_empty() {
+                    return Err(
+                        Box::try_from(format!("No file or directory matches {}", pattern)).unwrap(),
+                    );
+                }
+
+                let mut failures_count = 0;
+                for file_entry in file_entries.iter() {
+                    let full_name = file_entry.get_full_name();
+
+                    disk_manager.pull_sync();
+                    match disk_manager.set_attributes(&SetAttributesRequest::new(
+                        file_entry.name.clone(),
+                        file_entry.extension.clone(),
+                        request.attributes.clone(),
+                    )) {
+                        Ok(()) => {
+                            disk_manager.push_sync();
+                            cprintln!("Attributes of <b!>{}</> set", full_name);
+                        }
+                        Err(err) => {
+                            failures_count += 1;
+                            error!("Error: {}: {}", full_name, err);
+                            log::error!("Unable to set the attributes of {}: {}", full_name, err);
+                        }
+                    }
+                }
+
+                if failures_count > 0 {
+                    return Err(Box::try_from(format!(
+                        "The attributes of {} out of {} entries matching {} could not be set",
+                        failures_count,
+                        file_entries.len(),
+                        pattern
+                    ))
+                    .unwrap());
+                }
+
+                log::info!(
+                    "Set attributes of entries matching {} successfully",
+                    pattern
+                );
+                Ok(())
+            }
+            Err(_) => Err(Box::try_from("Unable to lock disk manager").unwrap()),
+        }
+    }
 }
 
 impl RequestHandler<SetAttributesRequest, Void> for SetAttributesHandler {
     fn handle(&mut self, request: SetAttributesRequest) -> Void {
+        if GlobPattern::is_glob(&request.get_full_name()) {
+            return self.set_matching_attributes(request);
+        }
+
         log::info!(
             "Setting attributes {:?} for file {}",
             request.attributes,
@@ -0,0 +1,97 @@
+use crate::application::Void;
+use crate::core::Arm;
+use crate::domain::i_disk_manager::IDiskManager;
+use crate::{info, success};
+use color_print::cprintln;
+use mediator::{Request, RequestHandler};
+
+/// TrashAction is what a `trash` command does with the file entries moved to the trash by `del`
+/// - `List`: list the trashed file entries
+/// - `Empty`: permanently delete the trashed file entries, reclaiming their clusters
+/// - `Restore`: move the trashed file entry with the given name back to the working directory
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum TrashAction {
+    List,
+    Empty,
+    Restore { name: String, extension: String },
+}
+
+/// TrashRequest is a request to list, empty or restore from the trash
+/// # Fields
+/// * `action` - what to do with the trashed file entries
+pub(crate) struct TrashRequest {
+    pub(crate) action: TrashAction,
+}
+
+impl TrashRequest {
+    pub(crate) fn new(action: TrashAction) -> Self {
+        Self { action }
+    }
+}
+
+impl Request<Void> for TrashRequest {}
+
+/// TrashHandler is a handler for TrashRequest holding a reference to the disk manager
+pub(crate) struct TrashHandler {
+    disk_manager: Arm<dyn IDiskManager>,
+}
+
+impl TrashHandler {
+    pub(crate) fn new(disk_manager: Arm<dyn IDiskManager>) -> Self {
+        Self { disk_manager }
+    }
+}
+
+impl RequestHandler<TrashRequest, Void> for TrashHandler {
+    fn handle(&mut self, request: TrashRequest) -> Void {
+        match self.disk_manager.lock() {
+            Ok(mut disk_manager) => {
+                disk_manager.pull_sync();
+
+                match request.action {
+                    TrashAction::List => {
+                        log::info!("Listing the trash...");
+
+                        let trashed_file_entries = disk_manager.list_trash();
+                        if trashed_file_entries.is_empty() {
+                            info!("The trash is empty");
+                            return Ok(());
+                        }
+
+                        trashed_file_entries
+                            .iter()
+                            .for_each(|file_entry| println!("{}", file_entry));
+                        Ok(())
+                    }
+                    TrashAction::Empty => {
+                        log::info!("Emptying the trash...");
+
+                        let free_space_before = disk_manager.get_free_space();
+                        disk_manager.empty_trash()?;
+                        disk_manager.push_sync();
+
+                        let reclaimed_space = disk_manager.get_free_space() - free_space_before;
+                        success!("Trash emptied, {} B reclaimed!", reclaimed_space);
+                        log::info!("Emptied the trash, reclaiming {} B", reclaimed_space);
+                        Ok(())
+                    }
+                    TrashAction::Restore { name, extension } => {
+                        let full_name = match extension.is_empty() {
+                            true => name.clone(),
+                            false => format!("{}.{}", name, extension),
+                        };
+                        log::info!("Restoring {} from the trash...", full_name);
+
+                        disk_manager.restore_from_trash(&name, &extension)?;
+                        disk_manager.push_sync();
+
+                        success!("{} restored from the trash!", full_name);
+                        log::info!("Restored {} from the trash", full_name);
+                        Ok(())
+                    }
+                }
+            }
+            Err(_) => Err(Box::try_from("Unable to lock disk manager!").unwrap()),
+        }
+    }
+}
@@ -0,0 +1,72 @@
+use crate::application::Void;
+use crate::core::Arm;
+use crate::domain::i_disk_manager::IDiskManager;
+use color_print::cprintln;
+use mediator::{Request, RequestHandler};
+
+/// TruncateRequest is a request to shrink or grow a file to a given size
+/// # Fields
+/// * `name` - the name of the file
+/// * `extension` - the extension of the file
+/// * `size` - the new size of the file
+pub(crate) struct TruncateRequest {
+    pub(crate) name: String,
+    pub(crate) extension: String,
+    pub(crate) size: u32,
+}
+
+impl TruncateRequest {
+    pub(crate) fn new(name: String, extension: String, size: u32) -> Self {
+        Self {
+            name,
+            extension,
+            size,
+        }
+    }
+}
+
+impl Request<Void> for TruncateRequest {}
+
+/// TruncateHandler is a handler for TruncateRequest holding a reference to the disk manager
+pub(crate) struct TruncateHandler {
+    disk_manager: Arm<dyn IDiskManager>,
+}
+
+impl TruncateHandler {
+    pub(crate) fn new(disk_manager: Arm<dyn IDiskManager>) -> Self {
+        Self { disk_manager }
+    }
+}
+
+impl RequestHandler<TruncateRequest, Void> for TruncateHandler {
+    fn handle(&mut self, request: TruncateRequest) -> Void {
+        log::info!(
+            "Truncating file {}.{} to {} bytes",
+            request.name,
+            request.extension,
+            request.size
+        );
+        cprintln!(
+            "Truncating file <b!>{}.{}</> to <y!>{}</> bytes...",
+            request.name,
+            request.extension,
+            request.size
+        );
+
+        match self.disk_manager.lock() {
+            Ok(mut disk_manager) => {
+                disk_manager.pull_sync();
+
+                match disk_manager.truncate_file(&request) {
+                    Ok(_) => {
+                        log::info!("Truncated file successfully");
+                        disk_manager.push_sync();
+                        Ok(())
+                    }
+                    Err(e) => Err(e),
+                }
+            }
+            Err(_) => Err(Box::try_from("Unable to lock disk manager!").unwrap()),
+        }
+    }
+}
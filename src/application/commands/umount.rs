@@ -0,0 +1,46 @@
+use crate::application::Void;
+use crate::core::mount_table::MountTable;
+use crate::core::Arm;
+use mediator::{Request, RequestHandler};
+
+/// UnmountRequest is a request to unmount a disk mounted under a label
+/// # Fields
+/// * `label` - the label the disk is mounted under
+pub(crate) struct UnmountRequest {
+    pub(crate) label: String,
+}
+
+impl UnmountRequest {
+    pub(crate) fn new(label: String) -> Self {
+        Self { label }
+    }
+}
+
+impl Request<Void> for UnmountRequest {}
+
+/// UnmountHandler is a handler for UnmountRequest holding a reference to the table of the mounted disks
+pub(crate) struct UnmountHandler {
+    mount_table: Arm<MountTable>,
+}
+
+impl UnmountHandler {
+    pub(crate) fn new(mount_table: Arm<MountTable>) -> Self {
+        Self { mount_table }
+    }
+}
+
+impl RequestHandler<UnmountRequest, Void> for UnmountHandler {
+    fn handle(&mut self, request: UnmountRequest) -> Void {
+        log::info!("Unmounting {}...", request.label);
+
+        match self.mount_table.lock() {
+            Ok(mut mount_table) => {
+                mount_table.unmount(&request.label)?;
+
+                log::info!("Unmounted {} successfully", request.label);
+                Ok(())
+            }
+            Err(_) => Err(Box::try_from("Unable to lock mount table!").unwrap()),
+        }
+    }
+}
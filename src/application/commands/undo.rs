@@ -0,0 +1,56 @@
+use crate::application::Void;
+use crate::core::Arm;
+use crate::domain::i_disk_manager::IDiskManager;
+use crate::{info, success};
+use color_print::cprintln;
+use mediator::{Request, RequestHandler};
+
+/// UndoRequest is a request to undo the last command modifying the disk
+pub(crate) struct UndoRequest;
+
+impl UndoRequest {
+    pub(crate) fn new() -> Self {
+        Self {}
+    }
+}
+
+impl Request<Void> for UndoRequest {}
+
+/// UndoHandler is a handler for UndoRequest holding a reference to the disk manager
+pub(crate) struct UndoHandler {
+    disk_manager: Arm<dyn IDiskManager>,
+}
+
+impl UndoHandler {
+    pub(crate) fn new(disk_manager: Arm<dyn IDiskManager>) -> Self {
+        Self { disk_manager }
+    }
+}
+
+impl RequestHandler<UndoRequest, Void> for UndoHandler {
+    fn handle(&mut self, _req: UndoRequest) -> Void {
+        log::info!("Undoing the last command...");
+
+        match self.disk_manager.lock() {
+            Ok(mut disk_manager) => {
+                disk_manager.pull_sync();
+
+                match disk_manager.restore_snapshot()? {
+                    true => {
+                        disk_manager.push_sync();
+
+                        success!("Last command undone successfully!");
+                        log::info!("Last command has been undone successfully");
+                    }
+                    false => {
+                        info!("Nothing to undo");
+                        log::info!("Nothing to undo");
+                    }
+                }
+
+                Ok(())
+            }
+            Err(_) => Err(Box::try_from("Unable to lock disk manager!").unwrap()),
+        }
+    }
+}
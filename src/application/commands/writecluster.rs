@@ -0,0 +1,61 @@
+use crate::application::Void;
+use crate::core::Arm;
+use crate::domain::i_disk_manager::IDiskManager;
+use mediator::{Request, RequestHandler};
+
+/// WriteClusterRequest is a request to overwrite the raw bytes of a cluster (a debugging tool: the FAT is not updated)
+/// # Fields
+/// * `cluster_index` - the index of the cluster to overwrite
+/// * `data` - the bytes written at the start of the cluster (the rest of it is zero-filled)
+pub(crate) struct WriteClusterRequest {
+    pub(crate) cluster_index: usize,
+    pub(crate) data: Vec<u8>,
+}
+
+impl WriteClusterRequest {
+    pub(crate) fn new(cluster_index: usize, data: Vec<u8>) -> Self {
+        Self {
+            cluster_index,
+            data,
+        }
+    }
+}
+
+impl Request<Void> for WriteClusterRequest {}
+
+/// WriteClusterHandler is a handler for WriteClusterRequest holding a reference to the disk manager
+pub(crate) struct WriteClusterHandler {
+    disk_manager: Arm<dyn IDiskManager>,
+}
+
+impl WriteClusterHandler {
+    pub(crate) fn new(disk_manager: Arm<dyn IDiskManager>) -> Self {
+        Self { disk_manager }
+    }
+}
+
+impl RequestHandler<WriteClusterRequest, Void> for WriteClusterHandler {
+    fn handle(&mut self, request: WriteClusterRequest) -> Void {
+        log::info!("Writing cluster {}...", request.cluster_index);
+
+        match self.disk_manager.lock() {
+            Ok(mut disk_manager) => {
+                disk_manager.pull_sync();
+
+                match disk_manager.write_cluster(&request) {
+                    Ok(()) => {
+                        log::info!(
+                            "Wrote {} B to cluster {}",
+                            request.data.len(),
+                            request.cluster_index
+                        );
+                        disk_manager.push_sync();
+                        Ok(())
+                    }
+                    Err(e) => Err(e),
+                }
+            }
+            Err(_) => Err(Box::try_from("Unable to lock disk manager!").unwrap()),
+        }
+    }
+}
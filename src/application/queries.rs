@@ -1,5 +1,22 @@
+pub(crate) mod bootinfo;
 pub(crate) mod cat;
+pub(crate) mod chkdsk;
+pub(crate) mod cksum;
+pub(crate) mod clear;
+pub(crate) mod df;
+pub(crate) mod diff;
+pub(crate) mod dumpfat;
+pub(crate) mod export;
+pub(crate) mod fraginfo;
+pub(crate) mod grep;
+pub(crate) mod head;
 pub(crate) mod help;
+pub(crate) mod hexdump;
 pub(crate) mod ls;
 pub(crate) mod neofetch;
 pub(crate) mod pwd;
+pub(crate) mod readcluster;
+pub(crate) mod stat;
+pub(crate) mod tail;
+pub(crate) mod version;
+pub(crate) mod wc;
@@ -0,0 +1,67 @@
+use crate::application::Void;
+use crate::core::Arm;
+use crate::domain::i_disk_manager::IDiskManager;
+use mediator::{Request, RequestHandler};
+
+/// BootInfoRequest is a request to dump the fields of the boot sector
+pub(crate) struct BootInfoRequest;
+
+impl BootInfoRequest {
+    pub(crate) fn new() -> Self {
+        Self {}
+    }
+}
+
+impl Request<Void> for BootInfoRequest {}
+
+/// BootInfoHandler is a handler for BootInfoRequest holding a reference to the disk manager
+pub(crate) struct BootInfoHandler {
+    disk_manager: Arm<dyn IDiskManager>,
+}
+
+impl BootInfoHandler {
+    pub(crate) fn new(disk_manager: Arm<dyn IDiskManager>) -> Self {
+        Self { disk_manager }
+    }
+}
+
+impl RequestHandler<BootInfoRequest, Void> for BootInfoHandler {
+    fn handle(&mut self, _req: BootInfoRequest) -> Void {
+        log::info!("Showing boot sector info...");
+
+        match self.disk_manager.lock() {
+            Ok(disk_manager) => {
+                let boot_sector = disk_manager.get_boot_sector();
+
+                // print the raw fields and the derived values as `key=value` lines
+                println!("cluster_size={}", boot_sector.cluster_size);
+                println!("cluster_count={}", boot_sector.cluster_count);
+                println!("root_entry_cell_size={}", boot_sector.root_entry_cell_size);
+                println!("root_entry_count={}", boot_sector.root_entry_count);
+                println!("root_entries_used={}", disk_manager.root_capacity().0);
+                println!("fat_cell_bits={}", boot_sector.fat_cell_bits);
+                println!(
+                    "clusters_per_boot_sector={}",
+                    boot_sector.clusters_per_boot_sector
+                );
+                println!("signature={:#06x}", boot_sector.signature);
+                println!(
+                    "working_directory_cluster={}",
+                    boot_sector.working_directory_cluster
+                );
+                println!("label={}", boot_sector.label);
+                println!("disk_size={}", boot_sector.get_disk_size());
+                println!("fat_clusters={}", boot_sector.get_fat_clusters_count());
+                println!("root_clusters={}", boot_sector.get_root_clusters_count());
+                println!(
+                    "reserved_clusters={}",
+                    boot_sector.get_reserved_clusters_count()
+                );
+
+                log::info!("Boot sector: {:?}", boot_sector);
+                Ok(())
+            }
+            Err(_) => Err(Box::try_from("Unable to lock disk manager!").unwrap()),
+        }
+    }
+}
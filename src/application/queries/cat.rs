@@ -1,6 +1,10 @@
+use crate::application::queries::ls::ListRequest;
 use crate::application::Void;
+use crate::core::filter_type::FilterType;
+use crate::core::glob_pattern::GlobPattern;
 use crate::core::Arm;
 use crate::domain::i_disk_manager::IDiskManager;
+use crate::error;
 use color_print::cprintln;
 use mediator::{Request, RequestHandler};
 
@@ -8,9 +12,16 @@ use mediator::{Request, RequestHandler};
 /// # Fields
 /// * `file_name` - The name of the file to show
 /// * `file_extension` - The extension of the file to show
+/// * `other_files` - The names and the extensions of the files shown after it, in order
+/// * `plain` - Whether to print the bare contents, one after the other, without the header of each file
+///
+/// The name and the extension may contain the `*` and `?` wildcards, in which case
+/// the content of every matching (visible) file from the working directory is shown.
 pub(crate) struct CatRequest {
     pub(crate) file_name: String,
     pub(crate) file_extension: String,
+    pub(crate) other_files: Vec<(String, String)>,
+    pub(crate) plain: bool,
 }
 
 impl CatRequest {
@@ -18,6 +29,17 @@ impl CatRequest {
         Self {
             file_name,
             file_extension,
+            other_files: Vec::new(),
+            plain: false,
+        }
+    }
+
+    /// Sets the files concatenated after the first one and whether to leave out the headers of the files.
+    pub(crate) fn with_other_files(self, other_files: Vec<(String, String)>, plain: bool) -> Self {
+        Self {
+            other_files,
+            plain,
+            ..self
         }
     }
 }
@@ -33,40 +55,156 @@ impl CatHandler {
     pub(crate) fn new(disk_manager: Arm<dyn IDiskManager>) -> Self {
         Self { disk_manager }
     }
-}
 
-impl RequestHandler<CatRequest, Void> for CatHandler {
-    fn handle(&mut self, request: CatRequest) -> Void {
+    /// Prints the content of a file, under a header naming it unless plain.
+    fn print_content(full_name: &str, content: &str, plain: bool) {
+        match plain {
+            true => print!("{}", content),
+            false => cprintln!("File <b!>{}</> content is:\n<g!>{}</>", full_name, content),
+        }
+    }
+
+    /// Ends the bare contents printed one after the other with a new line, so that the prompt starts on its own line.
+    fn end_plain_output(plain: bool) {
+        if plain {
+            println!();
+        }
+    }
+
+    /// Shows the content of a file from the working directory or, if its name holds wildcards,
+    /// of every visible file from the working directory matching them.
+    fn show_file(
+        disk_manager: &mut dyn IDiskManager,
+        file_name: &str,
+        file_extension: &str,
+        plain: bool,
+    ) -> Void {
+        let pattern = format!("{}.{}", file_name, file_extension);
+
+        if !GlobPattern::is_glob(file_name) && !GlobPattern::is_glob(file_extension) {
+            let cat_request = CatRequest::new(file_name.to_string(), file_extension.to_string());
+            let content = disk_manager.get_file_content(&cat_request)?;
+            Self::print_content(&pattern, &content, plain);
+
+            log::info!("Content for file {} has been shown successfully", pattern);
+            return Ok(());
+        }
+
+        let list_request = ListRequest::new(
+            vec![
+                FilterType::Glob(pattern.clone()),
+                FilterType::Files,
+                FilterType::All,
+            ],
+            None,
+        );
+        let file_entries = disk_manager.list_files(&list_request)?;
+
+        if file_entries.is_empty() {
+            return Err(Box::try_from(format!("No file matches {}", pattern)).unwrap());
+        }
+
+        for file_entry in file_entries {
+            let cat_request =
+                CatRequest::new(file_entry.name.clone(), file_entry.extension.clone());
+            let content = disk_manager.get_file_content(&cat_request)?;
+            Self::print_content(&file_entry.get_full_name(), &content, plain);
+        }
+
         log::info!(
-            "Showing file {}.{}",
-            request.file_name,
-            request.file_extension
+            "Content for files matching {} has been shown successfully",
+            pattern
         );
+        Ok(())
+    }
+}
 
+impl RequestHandler<CatRequest, Void> for CatHandler {
+    fn handle(&mut self, request: CatRequest) -> Void {
         match self.disk_manager.lock() {
             Ok(mut disk_manager) => {
                 disk_manager.pull_sync();
 
-                match disk_manager.get_file_content(&request) {
-                    Ok(content) => {
-                        cprintln!(
-                            "File <b!>{}.{}</> content is:\n<g!>{}</>",
-                            request.file_name,
-                            request.file_extension,
-                            content
-                        );
+                // a single file fails the command as a whole
+                if request.other_files.is_empty() {
+                    let result = Self::show_file(
+                        &mut *disk_manager,
+                        &request.file_name,
+                        &request.file_extension,
+                        request.plain,
+                    );
+                    disk_manager.push_sync();
+                    Self::end_plain_output(request.plain);
+                    return result;
+                }
+
+                // the files are concatenated in order, a failure doesn't stop the next ones from being shown
+                let files: Vec<(String, String)> =
+                    std::iter::once((request.file_name.clone(), request.file_extension.clone()))
+                        .chain(request.other_files.iter().cloned())
+                        .collect();
 
-                        log::info!(
-                            "Content for file {}.{} has been shown successfully",
-                            request.file_name,
-                            request.file_extension,
+                let mut failures_count = 0;
+                for (file_name, file_extension) in files.iter() {
+                    if let Err(err) = Self::show_file(
+                        &mut *disk_manager,
+                        file_name,
+                        file_extension,
+                        request.plain,
+                    ) {
+                        failures_count += 1;
+                        error!("Error: {}.{}: {}", file_name, file_extension, err);
+                        log::error!(
+                            "Unable to show the content of {}.{}: {}",
+                            file_name,
+                            file_extension,
+                            err
                         );
-                        Ok(())
                     }
-                    Err(e) => Err(e),
                 }
+                disk_manager.push_sync();
+                Self::end_plain_output(request.plain);
+
+                if failures_count > 0 {
+                    return Err(Box::try_from(format!(
+                        "{} out of {} files could not be shown",
+                        failures_count,
+                        files.len()
+                    ))
+                    .unwrap());
+                }
+
+                Ok(())
             }
             Err(_e) => Err(Box::try_from("Unable to lock disk manager!").unwrap()),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::test_disk::TestDisk;
+
+    #[test]
+    fn cat_with_a_pattern_matching_nothing_fails() {
+        let mut disk = TestDisk::new();
+        disk.write("notes", "md", "notes").unwrap();
+
+        let result = CatHandler::show_file(&mut disk.disk_manager, "read*", "md", true);
+
+        assert_eq!(result.unwrap_err().to_string(), "No file matches read*.md");
+    }
+
+    #[test]
+    fn cat_with_a_pattern_matching_one_or_many_files_succeeds() {
+        let mut disk = TestDisk::new();
+        disk.write("readme", "md", "one").unwrap();
+        disk.write("reading", "md", "many").unwrap();
+        disk.pull_sync();
+
+        CatHandler::show_file(&mut disk.disk_manager, "read*", "md", true).unwrap();
+        CatHandler::show_file(&mut disk.disk_manager, "readm?", "md", true).unwrap();
+        CatHandler::show_file(&mut disk.disk_manager, "readme", "md", true).unwrap();
+    }
+}
@@ -0,0 +1,62 @@
+use crate::application::Void;
+use crate::core::Arm;
+use crate::domain::i_disk_manager::IDiskManager;
+use crate::{success, warn};
+use color_print::cprintln;
+use mediator::{Request, RequestHandler};
+
+/// CheckDiskRequest is a request to check the consistency of the disk
+pub(crate) struct CheckDiskRequest;
+
+impl CheckDiskRequest {
+    pub(crate) fn new() -> Self {
+        Self {}
+    }
+}
+
+impl Request<Void> for CheckDiskRequest {}
+
+/// CheckDiskHandler is a handler for CheckDiskRequest holding a reference to the disk manager
+pub(crate) struct CheckDiskHandler {
+    disk_manager: Arm<dyn IDiskManager>,
+}
+
+impl CheckDiskHandler {
+    pub(crate) fn new(disk_manager: Arm<dyn IDiskManager>) -> Self {
+        Self { disk_manager }
+    }
+}
+
+impl RequestHandler<CheckDiskRequest, Void> for CheckDiskHandler {
+    fn handle(&mut self, _req: CheckDiskRequest) -> Void {
+        log::info!("Checking disk consistency...");
+
+        match self.disk_manager.lock() {
+            Ok(mut disk_manager) => {
+                disk_manager.pull_sync();
+
+                let consistency_errors = disk_manager.check_disk();
+                match consistency_errors.is_empty() {
+                    true => {
+                        success!("No consistency errors found");
+                    }
+                    false => {
+                        let consistency_errors_count = consistency_errors.len();
+                        warn!("Found {} consistency error(s):", consistency_errors_count);
+                        consistency_errors.iter().for_each(|consistency_error| {
+                            cprintln!("<r!>-</> {}", consistency_error);
+                            log::warn!("Consistency error: {}", consistency_error);
+                        });
+                    }
+                }
+
+                log::info!(
+                    "Disk consistency checked, {} error(s) found",
+                    consistency_errors.len()
+                );
+                Ok(())
+            }
+            Err(_) => Err(Box::try_from("Unable to lock disk manager!").unwrap()),
+        }
+    }
+}
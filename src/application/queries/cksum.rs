@@ -0,0 +1,125 @@
+use crate::application::queries::cat::CatRequest;
+use crate::application::Void;
+use crate::core::checksum::Checksum;
+use crate::core::Arm;
+use crate::domain::i_disk_manager::IDiskManager;
+use color_print::cprintln;
+use mediator::{Request, RequestHandler};
+
+/// CksumRequest is a request to compute the checksum of a file
+/// # Fields
+/// * `file_name` - The name of the file
+/// * `file_extension` - The extension of the file
+pub(crate) struct CksumRequest {
+    pub(crate) file_name: String,
+    pub(crate) file_extension: String,
+}
+
+impl CksumRequest {
+    pub(crate) fn new(file_name: String, file_extension: String) -> Self {
+        Self {
+            file_name,
+            file_extension,
+        }
+    }
+}
+
+impl Request<Void> for CksumRequest {}
+
+/// CksumHandler is a handler for CksumRequest holding a reference to the disk manager
+pub(crate) struct CksumHandler {
+    disk_manager: Arm<dyn IDiskManager>,
+}
+
+impl CksumHandler {
+    pub(crate) fn new(disk_manager: Arm<dyn IDiskManager>) -> Self {
+        Self { disk_manager }
+    }
+}
+
+impl RequestHandler<CksumRequest, Void> for CksumHandler {
+    fn handle(&mut self, request: CksumRequest) -> Void {
+        log::info!(
+            "Computing the checksum of file {}.{}",
+            request.file_name,
+            request.file_extension
+        );
+
+        match self.disk_manager.lock() {
+            Ok(mut disk_manager) => {
+                disk_manager.pull_sync();
+
+                // the raw bytes are exactly the `size` bytes of the file
+                let content = disk_manager.read_file_bytes(&CatRequest::new(
+                    request.file_name.clone(),
+                    request.file_extension.clone(),
+                ))?;
+                disk_manager.push_sync();
+
+                let crc32 = Checksum::crc32(&content);
+                cprintln!(
+                    "<g!>{:08x}</> <y!>{} B</> <b!>{}.{}</>",
+                    crc32,
+                    content.len(),
+                    request.file_name,
+                    request.file_extension
+                );
+
+                log::info!(
+                    "Checksum of file {}.{} is {:08x} ({} B)",
+                    request.file_name,
+                    request.file_extension,
+                    crc32,
+                    content.len()
+                );
+                Ok(())
+            }
+            Err(_) => Err(Box::try_from("Unable to lock disk manager!").unwrap()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::commands::cp::CopyRequest;
+    use crate::infrastructure::test_disk::TestDisk;
+
+    fn crc32(disk: &mut TestDisk, name: &str, extension: &str) -> u32 {
+        Checksum::crc32(&disk.read(name, extension))
+    }
+
+    #[test]
+    fn a_file_and_its_copy_have_the_same_checksum() {
+        let mut disk = TestDisk::new();
+        disk.create("data", "txt", 100).unwrap();
+        disk.run(|disk_manager| {
+            disk_manager.copy_file(&CopyRequest::new(
+                "data".to_string(),
+                "txt".to_string(),
+                "copy".to_string(),
+                "txt".to_string(),
+                None,
+                false,
+            ))
+        })
+        .unwrap();
+
+        assert_eq!(
+            crc32(&mut disk, "data", "txt"),
+            crc32(&mut disk, "copy", "txt")
+        );
+        assert_eq!(disk.read("copy", "txt").len(), 100);
+    }
+
+    #[test]
+    fn a_modified_file_has_another_checksum() {
+        let mut disk = TestDisk::new();
+        disk.write("data", "txt", "some content").unwrap();
+        let original_crc32 = crc32(&mut disk, "data", "txt");
+
+        disk.write("data", "txt", "some contend").unwrap();
+
+        assert_ne!(crc32(&mut disk, "data", "txt"), original_crc32);
+    }
+}
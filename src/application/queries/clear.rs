@@ -0,0 +1,43 @@
+use crate::application::Void;
+use mediator::{Request, RequestHandler};
+use std::io::{self, IsTerminal, Write};
+
+/// The ANSI sequence erasing the whole screen and moving the cursor to its top-left corner
+const CLEAR_SCREEN_SEQUENCE: &str = "\x1b[2J\x1b[H";
+
+/// ClearRequest is a request for clearing the terminal.
+pub(crate) struct ClearRequest;
+
+impl ClearRequest {
+    pub(crate) fn new() -> Self {
+        Self {}
+    }
+}
+
+impl Request<Void> for ClearRequest {}
+
+/// ClearHandler is a handler for ClearRequest, the prompt being printed again by the REPL right after it.
+pub(crate) struct ClearHandler;
+
+impl ClearHandler {
+    pub(crate) fn new() -> Self {
+        Self {}
+    }
+}
+
+impl RequestHandler<ClearRequest, Void> for ClearHandler {
+    fn handle(&mut self, _req: ClearRequest) -> Void {
+        log::info!("Clearing the terminal...");
+
+        let mut stdout = io::stdout();
+
+        // the escape sequence would only pollute the output of piped or scripted runs
+        match stdout.is_terminal() {
+            true => write!(stdout, "{}", CLEAR_SCREEN_SEQUENCE)?,
+            false => writeln!(stdout)?,
+        }
+        stdout.flush()?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,128 @@
+use crate::application::Void;
+use crate::core::Arm;
+use crate::domain::i_disk_manager::IDiskManager;
+use color_print::cprintln;
+use mediator::{Request, RequestHandler};
+
+/// The number of characters of the percentage-used bar
+const USAGE_BAR_WIDTH: usize = 40;
+
+/// DfRequest is a request to show a summary of the disk capacity
+pub(crate) struct DfRequest;
+
+impl DfRequest {
+    pub(crate) fn new() -> Self {
+        Self {}
+    }
+}
+
+impl Request<Void> for DfRequest {}
+
+/// DfHandler is a handler for DfRequest holding a reference to the disk manager
+pub(crate) struct DfHandler {
+    disk_manager: Arm<dyn IDiskManager>,
+}
+
+impl DfHandler {
+    pub(crate) fn new(disk_manager: Arm<dyn IDiskManager>) -> Self {
+        Self { disk_manager }
+    }
+
+    /// Build a bar of `USAGE_BAR_WIDTH` characters where the used part is filled with `#`
+    fn build_usage_bar(used_space: u64, total_space: u64) -> String {
+        let used_width = match total_space {
+            0 => 0,
+            _ => (used_space * USAGE_BAR_WIDTH as u64 / total_space) as usize,
+        };
+
+        format!(
+            "[{}{}]",
+            "#".repeat(used_width),
+            ".".repeat(USAGE_BAR_WIDTH - used_width)
+        )
+    }
+}
+
+impl RequestHandler<DfRequest, Void> for DfHandler {
+    fn handle(&mut self, _req: DfRequest) -> Void {
+        log::info!("Showing disk capacity...");
+
+        match self.disk_manager.lock() {
+            Ok(mut disk_manager) => {
+                // get_free_space pulls the disk before counting the free clusters
+                let free_space = disk_manager.get_free_space();
+                let total_space = disk_manager.get_total_space();
+                // the bad clusters are neither free nor used
+                let bad_space = disk_manager.get_bad_space();
+                let used_space = total_space - free_space - bad_space;
+                let used_percentage = match total_space {
+                    0 => 0.0,
+                    _ => used_space as f64 * 100.0 / total_space as f64,
+                };
+
+                let fragmentation = disk_manager
+                    .fragmentation_report()
+                    .get_fragmentation_percentage();
+
+                let (used_root_entries_count, max_root_entries_count) =
+                    disk_manager.root_capacity();
+
+                let boot_sector = disk_manager.get_boot_sector();
+                cprintln!("<c!>FAT</>: <w!>FAT{}</>", boot_sector.fat_cell_bits);
+                cprintln!("<c!>Cluster size</>: <w!>{} B</>", boot_sector.cluster_size);
+                cprintln!("<c!>Total space</>: <w!>{} B</>", total_space);
+                cprintln!("<c!>Used space</>: <w!>{} B</>", used_space);
+                cprintln!("<c!>Free space</>: <w!>{} B</>", free_space);
+                cprintln!("<c!>Bad space</>: <w!>{} B</>", bad_space);
+                cprintln!(
+                    "<c!>Root entries</>: <w!>{}/{}</>",
+                    used_root_entries_count,
+                    max_root_entries_count
+                );
+                cprintln!("<c!>Fragmentation</>: <w!>{:.1}%</>", fragmentation);
+                cprintln!(
+                    "<w!>{}</> <y!>{:.1}% used</>",
+                    Self::build_usage_bar(used_space, total_space),
+                    used_percentage
+                );
+
+                log::info!(
+                    "Disk capacity: {} B used out of {} B",
+                    used_space,
+                    total_space
+                );
+                Ok(())
+            }
+            Err(_) => Err(Box::try_from("Unable to lock disk manager!").unwrap()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_usage_bar_is_filled_in_proportion_to_the_used_space() {
+        assert_eq!(
+            DfHandler::build_usage_bar(0, 100),
+            format!("[{}]", ".".repeat(USAGE_BAR_WIDTH))
+        );
+        assert_eq!(
+            DfHandler::build_usage_bar(25, 100),
+            format!("[{}{}]", "#".repeat(10), ".".repeat(30))
+        );
+        assert_eq!(
+            DfHandler::build_usage_bar(100, 100),
+            format!("[{}]", "#".repeat(USAGE_BAR_WIDTH))
+        );
+    }
+
+    #[test]
+    fn the_usage_bar_of_an_empty_disk_is_empty() {
+        assert_eq!(
+            DfHandler::build_usage_bar(0, 0),
+            format!("[{}]", ".".repeat(USAGE_BAR_WIDTH))
+        );
+    }
+}
@@ -0,0 +1,197 @@
+use crate::application::queries::cat::CatRequest;
+use crate::application::Void;
+use crate::core::Arm;
+use crate::domain::i_disk_manager::IDiskManager;
+use crate::{success, warn};
+use color_print::cprintln;
+use mediator::{Request, RequestHandler};
+
+/// DiffRequest is a request to compare two files byte by byte
+/// # Fields
+/// * `first_name` - The name of the first file
+/// * `first_extension` - The extension of the first file
+/// * `second_name` - The name of the second file
+/// * `second_extension` - The extension of the second file
+pub(crate) struct DiffRequest {
+    pub(crate) first_name: String,
+    pub(crate) first_extension: String,
+    pub(crate) second_name: String,
+    pub(crate) second_extension: String,
+}
+
+impl DiffRequest {
+    pub(crate) fn new(
+        first_name: String,
+        first_extension: String,
+        second_name: String,
+        second_extension: String,
+    ) -> Self {
+        Self {
+            first_name,
+            first_extension,
+            second_name,
+            second_extension,
+        }
+    }
+}
+
+impl Request<Void> for DiffRequest {}
+
+/// DiffHandler is a handler for DiffRequest holding a reference to the disk manager
+pub(crate) struct DiffHandler {
+    disk_manager: Arm<dyn IDiskManager>,
+}
+
+impl DiffHandler {
+    pub(crate) fn new(disk_manager: Arm<dyn IDiskManager>) -> Self {
+        Self { disk_manager }
+    }
+
+    /// Returns the first offset where the bytes differ or, if one content is a prefix of the other,
+    /// the offset right after the end of the shorter one (None if the contents are identical).
+    fn find_first_difference(first_content: &[u8], second_content: &[u8]) -> Option<usize> {
+        if first_content == second_content {
+            return None;
+        }
+
+        let first_difference_offset = first_content
+            .iter()
+            .zip(second_content.iter())
+            .position(|(first_byte, second_byte)| first_byte != second_byte)
+            .unwrap_or(first_content.len().min(second_content.len()));
+
+        Some(first_difference_offset)
+    }
+}
+
+impl RequestHandler<DiffRequest, Void> for DiffHandler {
+    fn handle(&mut self, request: DiffRequest) -> Void {
+        let first_full_name = format!("{}.{}", request.first_name, request.first_extension);
+        let second_full_name = format!("{}.{}", request.second_name, request.second_extension);
+        log::info!("Comparing {} and {}", first_full_name, second_full_name);
+
+        match self.disk_manager.lock() {
+            Ok(mut disk_manager) => {
+                disk_manager.pull_sync();
+
+                let first_content = disk_manager.read_file_bytes(&CatRequest::new(
+                    request.first_name.clone(),
+                    request.first_extension.clone(),
+                ))?;
+                let second_content = disk_manager.read_file_bytes(&CatRequest::new(
+                    request.second_name.clone(),
+                    request.second_extension.clone(),
+                ))?;
+                disk_manager.push_sync();
+
+                let first_difference_offset =
+                    match Self::find_first_difference(&first_content, &second_content) {
+                        Some(first_difference_offset) => first_difference_offset,
+                        None => {
+                            success!(
+                                "Files {} and {} are identical",
+                                first_full_name,
+                                second_full_name
+                            );
+                            log::info!(
+                                "{} and {} are identical",
+                                first_full_name,
+                                second_full_name
+                            );
+                            return Ok(());
+                        }
+                    };
+
+                if first_content.len() != second_content.len() {
+                    let first_size = first_content.len();
+                    let second_size = second_content.len();
+                    warn!(
+                        "Sizes differ: {} has {} B and {} has {} B",
+                        first_full_name, first_size, second_full_name, second_size
+                    );
+                }
+
+                match (
+                    first_content.get(first_difference_offset),
+                    second_content.get(first_difference_offset),
+                ) {
+                    (Some(first_byte), Some(second_byte)) => cprintln!(
+                        "First difference at offset <b!>{:#x}</> ({}): <y!>{:02x}</> in {} vs <y!>{:02x}</> in {}",
+                        first_difference_offset,
+                        first_difference_offset,
+                        first_byte,
+                        first_full_name,
+                        second_byte,
+                        second_full_name
+                    ),
+                    (Some(_), None) => cprintln!(
+                        "{} is a prefix of {}, which goes on from offset <b!>{:#x}</> ({})",
+                        second_full_name,
+                        first_full_name,
+                        first_difference_offset,
+                        first_difference_offset
+                    ),
+                    _ => cprintln!(
+                        "{} is a prefix of {}, which goes on from offset <b!>{:#x}</> ({})",
+                        first_full_name,
+                        second_full_name,
+                        first_difference_offset,
+                        first_difference_offset
+                    ),
+                }
+
+                log::info!(
+                    "{} and {} differ from offset {}",
+                    first_full_name,
+                    second_full_name,
+                    first_difference_offset
+                );
+                Ok(())
+            }
+            Err(_) => Err(Box::try_from("Unable to lock disk manager!").unwrap()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::test_disk::TestDisk;
+    use std::sync::{Arc, Mutex};
+
+    /// Writes both files and returns their first difference.
+    fn diff(first_content: &str, second_content: &str) -> Option<usize> {
+        let mut disk = TestDisk::new();
+        disk.write("first", "txt", first_content).unwrap();
+        disk.write("second", "txt", second_content).unwrap();
+
+        let mut diff_handler = DiffHandler::new(Arc::new(Mutex::new(disk.reopen())));
+        diff_handler
+            .handle(DiffRequest::new(
+                "first".to_string(),
+                "txt".to_string(),
+                "second".to_string(),
+                "txt".to_string(),
+            ))
+            .unwrap();
+
+        DiffHandler::find_first_difference(&disk.read("first", "txt"), &disk.read("second", "txt"))
+    }
+
+    #[test]
+    fn identical_files_have_no_difference() {
+        assert_eq!(diff("same content", "same content"), None);
+    }
+
+    #[test]
+    fn files_of_the_same_size_differ_at_their_first_different_byte() {
+        assert_eq!(diff("same content", "same cintent"), Some(6));
+    }
+
+    #[test]
+    fn files_of_different_sizes_differ_after_their_common_prefix() {
+        assert_eq!(diff("same content", "same"), Some(4));
+        assert_eq!(diff("same", "same content"), Some(4));
+        assert_eq!(diff("same content", "other"), Some(0));
+    }
+}
@@ -0,0 +1,84 @@
+use crate::application::Void;
+use crate::core::Arm;
+use crate::domain::fat::FatValue;
+use crate::domain::i_disk_manager::IDiskManager;
+use color_print::cprintln;
+use mediator::{Request, RequestHandler};
+
+/// The number of FAT cells shown when no count is given
+const DEFAULT_CELL_COUNT: usize = 32;
+
+/// DumpFatRequest is a request to show a range of FAT cells
+/// # Fields
+/// * `start` - The index of the first FAT cell (the first non-reserved cell if missing)
+/// * `count` - The number of FAT cells to show (`DEFAULT_CELL_COUNT` if missing)
+pub(crate) struct DumpFatRequest {
+    pub(crate) start: Option<usize>,
+    pub(crate) count: Option<usize>,
+}
+
+impl DumpFatRequest {
+    pub(crate) fn new(start: Option<usize>, count: Option<usize>) -> Self {
+        Self { start, count }
+    }
+}
+
+impl Request<Void> for DumpFatRequest {}
+
+/// DumpFatHandler is a handler for DumpFatRequest holding a reference to the disk manager
+pub(crate) struct DumpFatHandler {
+    disk_manager: Arm<dyn IDiskManager>,
+}
+
+impl DumpFatHandler {
+    pub(crate) fn new(disk_manager: Arm<dyn IDiskManager>) -> Self {
+        Self { disk_manager }
+    }
+}
+
+impl RequestHandler<DumpFatRequest, Void> for DumpFatHandler {
+    fn handle(&mut self, request: DumpFatRequest) -> Void {
+        log::info!("Dumping FAT cells...");
+
+        match self.disk_manager.lock() {
+            Ok(mut disk_manager) => {
+                disk_manager.pull_sync();
+
+                let boot_sector = disk_manager.get_boot_sector();
+                let cluster_count = boot_sector.cluster_count as usize;
+                let start = request
+                    .start
+                    .unwrap_or(boot_sector.get_reserved_clusters_count() as usize);
+                let count = request.count.unwrap_or(DEFAULT_CELL_COUNT);
+
+                if start >= cluster_count {
+                    return Err(Box::try_from(format!(
+                        "FAT cell {} is out of range (the FAT has {} cells)",
+                        start, cluster_count
+                    ))
+                    .unwrap());
+                }
+
+                let fat_slice = disk_manager.get_fat_slice(start, count);
+                fat_slice
+                    .iter()
+                    .for_each(|(index, fat_value)| match fat_value {
+                        FatValue::Free => cprintln!("<w!>{:>5}</>: <k!>{}</>", index, fat_value),
+                        FatValue::Reserved => {
+                            cprintln!("<w!>{:>5}</>: <b!>{}</>", index, fat_value)
+                        }
+                        FatValue::Bad => cprintln!("<w!>{:>5}</>: <r!>{}</>", index, fat_value),
+                        _ => cprintln!("<w!>{:>5}</>: <g!>{}</>", index, fat_value),
+                    });
+
+                log::info!(
+                    "Dumped {} FAT cell(s) starting from {}",
+                    fat_slice.len(),
+                    start
+                );
+                Ok(())
+            }
+            Err(_) => Err(Box::try_from("Unable to lock disk manager!").unwrap()),
+        }
+    }
+}
@@ -0,0 +1,222 @@
+use crate::application::commands::cd::ChangeDirectoryRequest;
+use crate::application::queries::cat::CatRequest;
+use crate::application::queries::ls::ListRequest;
+use crate::application::queries::stat::StatRequest;
+use crate::application::Void;
+use crate::core::filter_type::FilterType;
+use crate::core::Arm;
+use crate::domain::file_entry::FileEntry;
+use crate::domain::i_disk_manager::IDiskManager;
+use mediator::{Request, RequestHandler};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// ExportRequest is a request to copy a file or directory (recursively) to the host filesystem
+/// # Fields
+/// * `name` - The name of the file or directory to export
+/// * `extension` - The extension of the file to export (empty for directories)
+/// * `host_path` - The path on the host where the file or directory is written (must not exist)
+pub(crate) struct ExportRequest {
+    pub(crate) name: String,
+    pub(crate) extension: String,
+    pub(crate) host_path: String,
+}
+
+impl ExportRequest {
+    pub(crate) fn new(name: String, extension: String, host_path: String) -> Self {
+        Self {
+            name,
+            extension,
+            host_path,
+        }
+    }
+}
+
+impl Request<Void> for ExportRequest {}
+
+/// ExportHandler is a handler for ExportRequest holding a reference to the disk manager
+pub(crate) struct ExportHandler {
+    disk_manager: Arm<dyn IDiskManager>,
+}
+
+impl ExportHandler {
+    pub(crate) fn new(disk_manager: Arm<dyn IDiskManager>) -> Self {
+        Self { disk_manager }
+    }
+
+    /// Writes the given file entry of the working directory to the host path,
+    /// descending into its children (hidden ones included) if it is a directory.
+    fn export_file_entry(
+        disk_manager: &mut dyn IDiskManager,
+        file_entry: &FileEntry,
+        host_path: &Path,
+    ) -> Void {
+        // never overwrite anything on the host
+        if host_path.exists() {
+            return Err(
+                Box::try_from(format!("Host path {} already exists", host_path.display())).unwrap(),
+            );
+        }
+
+        // refresh the working directory, as getting back from a subdirectory leaves it unlinked
+        disk_manager.pull_sync();
+
+        if file_entry.is_file() {
+            let content = disk_manager.read_file_bytes(&CatRequest::new(
+                file_entry.name.clone(),
+                file_entry.extension.clone(),
+            ))?;
+            std::fs::write(host_path, content)?;
+        } else {
+            std::fs::create_dir(host_path)?;
+
+            disk_manager
+                .change_working_directory(&ChangeDirectoryRequest::new(file_entry.name.clone()))?;
+
+            let result = Self::export_children(disk_manager, host_path);
+
+            // always get back to the parent directory, even if the export of a child failed
+            disk_manager.pull_sync();
+            disk_manager
+                .change_working_directory(&ChangeDirectoryRequest::new("..".to_string()))?;
+
+            result?;
+        }
+
+        Self::preserve_modification_time(file_entry, host_path);
+        log::info!(
+            "Exported {} to {}",
+            file_entry.get_full_name(),
+            host_path.display()
+        );
+
+        Ok(())
+    }
+
+    /// Exports every child of the working directory into the given host directory.
+    fn export_children(disk_manager: &mut dyn IDiskManager, host_path: &Path) -> Void {
+        let children_request = ListRequest::new(vec![FilterType::AllAndHidden], None);
+        let children = disk_manager.list_files(&children_request)?;
+
+        for child in children
+            .iter()
+            .filter(|child| child.name != "." && child.name != "..")
+        {
+            Self::export_file_entry(disk_manager, child, &host_path.join(child.get_full_name()))?;
+        }
+
+        Ok(())
+    }
+
+    /// Sets the modification time of the host file or directory to the one of the file entry, if possible.
+    fn preserve_modification_time(file_entry: &FileEntry, host_path: &Path) {
+        let modification_time = SystemTime::from(file_entry.last_modification_datetime);
+        let result = std::fs::File::options()
+            .write(file_entry.is_file())
+            .read(!file_entry.is_file())
+            .open(host_path)
+            .and_then(|host_file| host_file.set_modified(modification_time));
+
+        if let Err(err) = result {
+            log::warn!(
+                "Unable to preserve the modification time of {}: {}",
+                host_path.display(),
+                err
+            );
+        }
+    }
+}
+
+impl RequestHandler<ExportRequest, Void> for ExportHandler {
+    fn handle(&mut self, request: ExportRequest) -> Void {
+        log::info!(
+            "Exporting {}.{} to {}",
+            request.name,
+            request.extension,
+            request.host_path
+        );
+
+        match self.disk_manager.lock() {
+            Ok(mut disk_manager) => {
+                disk_manager.pull_sync();
+
+                let file_entry = disk_manager.get_file_entry(&StatRequest::new(
+                    request.name.clone(),
+                    request.extension.clone(),
+                ))?;
+                let host_path = PathBuf::from(&request.host_path);
+
+                Self::export_file_entry(&mut *disk_manager, &file_entry, &host_path)?;
+
+                // persist the last access dates of the exported files
+                disk_manager.push_sync();
+                Ok(())
+            }
+            Err(_) => Err(Box::try_from("Unable to lock disk manager!").unwrap()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::test_disk::TestDisk;
+    use std::sync::{Arc, Mutex};
+
+    /// Returns a path in the folder of the test disk, removed along with it.
+    fn host_path(disk: &TestDisk, file_name: &str) -> PathBuf {
+        PathBuf::from(&disk.config.lock().unwrap().disk_dir_path).join(file_name)
+    }
+
+    fn export(disk: &TestDisk, name: &str, extension: &str, host_path: &Path) -> Void {
+        let mut export_handler = ExportHandler::new(Arc::new(Mutex::new(disk.reopen())));
+
+        export_handler.handle(ExportRequest::new(
+            name.to_string(),
+            extension.to_string(),
+            host_path.to_string_lossy().into_owned(),
+        ))
+    }
+
+    #[test]
+    fn export_writes_a_directory_tree_with_the_bytes_of_its_files() {
+        let mut disk = TestDisk::new();
+        disk.mkdir("docs").unwrap();
+        disk.cd("docs").unwrap();
+        disk.write("notes", "txt", "hello\nworld").unwrap();
+        disk.mkdir("sub").unwrap();
+        disk.cd("sub").unwrap();
+        disk.create("big", "bin", 100).unwrap();
+        let big_content = disk.read("big", "bin");
+        disk.cd("/").unwrap();
+        let exported_path = host_path(&disk, "docs");
+
+        export(&disk, "docs", "", &exported_path).unwrap();
+
+        assert_eq!(
+            std::fs::read(exported_path.join("notes.txt")).unwrap(),
+            b"hello\nworld"
+        );
+        assert_eq!(
+            std::fs::read(exported_path.join("sub").join("big.bin")).unwrap(),
+            big_content
+        );
+        assert_eq!(big_content.len(), 100);
+    }
+
+    #[test]
+    fn export_fails_rather_than_overwriting_a_host_file() {
+        let mut disk = TestDisk::new();
+        disk.write("notes", "txt", "new").unwrap();
+        let exported_path = host_path(&disk, "notes.txt");
+        std::fs::write(&exported_path, "old").unwrap();
+
+        let result = export(&disk, "notes", "txt", &exported_path);
+
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            format!("Host path {} already exists", exported_path.display())
+        );
+        assert_eq!(std::fs::read(&exported_path).unwrap(), b"old");
+    }
+}
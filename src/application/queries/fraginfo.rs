@@ -0,0 +1,84 @@
+use crate::application::Void;
+use crate::core::Arm;
+use crate::domain::i_disk_manager::IDiskManager;
+use crate::success;
+use color_print::cprintln;
+use mediator::{Request, RequestHandler};
+
+/// FragInfoRequest is a request to report the fragmentation of the disk
+pub(crate) struct FragInfoRequest;
+
+impl FragInfoRequest {
+    pub(crate) fn new() -> Self {
+        Self {}
+    }
+}
+
+impl Request<Void> for FragInfoRequest {}
+
+/// FragInfoHandler is a handler for FragInfoRequest holding a reference to the disk manager
+pub(crate) struct FragInfoHandler {
+    disk_manager: Arm<dyn IDiskManager>,
+}
+
+impl FragInfoHandler {
+    pub(crate) fn new(disk_manager: Arm<dyn IDiskManager>) -> Self {
+        Self { disk_manager }
+    }
+}
+
+impl RequestHandler<FragInfoRequest, Void> for FragInfoHandler {
+    fn handle(&mut self, _req: FragInfoRequest) -> Void {
+        log::info!("Showing disk fragmentation...");
+
+        match self.disk_manager.lock() {
+            Ok(mut disk_manager) => {
+                // fragmentation_report pulls the disk before walking the allocation chains
+                let report = disk_manager.fragmentation_report();
+
+                let fragmented_files: Vec<_> = report
+                    .files
+                    .iter()
+                    .filter(|file| file.is_fragmented())
+                    .collect();
+
+                match fragmented_files.is_empty() {
+                    true => {
+                        success!("No fragmented files found");
+                    }
+                    false => fragmented_files.iter().for_each(|file| {
+                        cprintln!(
+                            "<w!>{}</>: <y!>{}</> of <w!>{}</> transition(s) non-contiguous",
+                            file.entry_path,
+                            file.fragmented_transitions,
+                            file.get_transitions_count()
+                        );
+                    }),
+                }
+
+                cprintln!(
+                    "<c!>Fragmented files</>: <w!>{}</> of <w!>{}</>",
+                    fragmented_files.len(),
+                    report.files.len()
+                );
+                cprintln!(
+                    "<c!>Non-contiguous transitions</>: <w!>{}</> of <w!>{}</>",
+                    report.get_fragmented_transitions_count(),
+                    report.get_transitions_count()
+                );
+                cprintln!(
+                    "<c!>Fragmentation</>: <w!>{:.1}%</>",
+                    report.get_fragmentation_percentage()
+                );
+
+                log::info!(
+                    "Disk fragmentation: {:.1}% ({} fragmented file(s))",
+                    report.get_fragmentation_percentage(),
+                    fragmented_files.len()
+                );
+                Ok(())
+            }
+            Err(_) => Err(Box::try_from("Unable to lock disk manager!").unwrap()),
+        }
+    }
+}
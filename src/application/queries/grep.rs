@@ -0,0 +1,163 @@
+use crate::application::Void;
+use crate::core::glob_pattern::GlobPattern;
+use crate::core::Arm;
+use crate::domain::file_entry::FileEntry;
+use crate::domain::i_disk_manager::IDiskManager;
+use crate::warn;
+use color_print::cprintln;
+use mediator::{Request, RequestHandler};
+use regex::Regex;
+
+/// GrepRequest is a request to search the lines matching a regex inside files
+/// # Fields
+/// * `pattern` - the regex the lines are matched against
+/// * `file_pattern` - the full name of the file to search, which may contain the `*` and `?` wildcards
+///   (optional when searching recursively, in which case every file is searched)
+/// * `recursive` - whether to search the files of the subdirectories of the working directory as well
+/// * `ignore_case` - whether to match the lines regardless of their case
+pub(crate) struct GrepRequest {
+    pub(crate) pattern: String,
+    pub(crate) file_pattern: Option<String>,
+    pub(crate) recursive: bool,
+    pub(crate) ignore_case: bool,
+}
+
+impl GrepRequest {
+    pub(crate) fn new(
+        pattern: String,
+        file_pattern: Option<String>,
+        recursive: bool,
+        ignore_case: bool,
+    ) -> Self {
+        Self {
+            pattern,
+            file_pattern,
+            recursive,
+            ignore_case,
+        }
+    }
+}
+
+impl Request<Void> for GrepRequest {}
+
+/// GrepHandler is a handler for GrepRequest holding a reference to the disk manager
+pub(crate) struct GrepHandler {
+    disk_manager: Arm<dyn IDiskManager>,
+}
+
+impl GrepHandler {
+    pub(crate) fn new(disk_manager: Arm<dyn IDiskManager>) -> Self {
+        Self { disk_manager }
+    }
+
+    /// Returns the visible files to search along with their paths: the ones of the working directory
+    /// or, if recursive, the ones of its whole subtree (in the order of `IDiskManager::walk`).
+    fn collect_files(
+        disk_manager: &dyn IDiskManager,
+        request: &GrepRequest,
+    ) -> Vec<(String, FileEntry)> {
+        let working_directory_path = disk_manager.get_working_directory_full_path();
+        let directory_prefix = match working_directory_path.as_str() {
+            "/" => "/".to_string(),
+            path => format!("{}/", path),
+        };
+        let file_pattern = request.file_pattern.as_deref().map(GlobPattern::new);
+
+        disk_manager
+            .walk()
+            .into_iter()
+            .filter(|(entry_path, file_entry)| {
+                file_entry.is_file()
+                    && !file_entry.is_hidden()
+                    && entry_path.starts_with(&directory_prefix)
+                    && (request.recursive || !entry_path[directory_prefix.len()..].contains('/'))
+                    && file_pattern
+                        .as_ref()
+                        .is_none_or(|pattern| pattern.is_match(&file_entry.get_full_name()))
+            })
+            .collect()
+    }
+
+    /// Returns the matching lines of the given content along with their line numbers (starting from 1),
+    /// or `None` if the content is not text (i.e. it is not valid UTF-8 or it contains NUL bytes).
+    fn get_matching_lines<'a>(regex: &Regex, content: &'a [u8]) -> Option<Vec<(usize, &'a str)>> {
+        let text = std::str::from_utf8(content)
+            .ok()
+            .filter(|text| !text.contains('\0'))?;
+
+        Some(
+            text.lines()
+                .enumerate()
+                .filter(|(_, line)| regex.is_match(line))
+                .map(|(index, line)| (index + 1, line))
+                .collect(),
+        )
+    }
+}
+
+impl RequestHandler<GrepRequest, Void> for GrepHandler {
+    fn handle(&mut self, request: GrepRequest) -> Void {
+        log::info!("Searching for {} in files...", request.pattern);
+
+        let regex = match request.ignore_case {
+            true => Regex::new(format!("(?i){}", request.pattern).as_str()),
+            false => Regex::new(request.pattern.as_str()),
+        }
+        .map_err(|err| format!("Invalid pattern {}: {}", request.pattern, err))?;
+
+        match self.disk_manager.lock() {
+            Ok(mut disk_manager) => {
+                disk_manager.pull_sync();
+
+                let files = Self::collect_files(&*disk_manager, &request);
+                if files.is_empty() {
+                    return Err(Box::try_from(match &request.file_pattern {
+                        Some(file_pattern) => format!("No file matches {}", file_pattern),
+                        None => "No file to search".to_string(),
+                    })
+                    .unwrap());
+                }
+
+                // the matches are prefixed by the path of their file unless a single file is searched
+                let with_paths = request.recursive
+                    || request
+                        .file_pattern
+                        .as_deref()
+                        .is_none_or(GlobPattern::is_glob);
+                let mut matches_count = 0;
+
+                for (entry_path, file_entry) in files {
+                    let content = disk_manager.read_entry_bytes(&file_entry);
+
+                    let matching_lines = match Self::get_matching_lines(&regex, &content) {
+                        Some(matching_lines) => matching_lines,
+                        None => {
+                            warn!("Binary file {} skipped", entry_path);
+                            continue;
+                        }
+                    };
+
+                    for (line_number, line) in matching_lines {
+                        match with_paths {
+                            true => {
+                                cprintln!("<m!>{}</>:<g!>{}</>: {}", entry_path, line_number, line)
+                            }
+                            false => cprintln!("<g!>{}</>: {}", line_number, line),
+                        }
+                        matches_count += 1;
+                    }
+                }
+
+                cprintln!("<b!>{} matching line(s)</>", matches_count);
+
+                log::info!(
+                    "Found {} line(s) matching {}",
+                    matches_count,
+                    request.pattern
+                );
+                Ok(())
+            }
+            Err(_) => Err(Box::try_from("Unable to lock disk manager!").unwrap()),
+        }
+    }
+}
@@ -1,5 +1,5 @@
 use crate::application::Void;
-use crate::core::config::Config;
+use crate::core::config::{Command, Config};
 use crate::core::Arm;
 use crate::{info, success, warn};
 use color_print::cprintln;
@@ -8,13 +8,15 @@ use mediator::{Request, RequestHandler};
 /// HelpRequest is a request to show help instructions for a command or all commands
 /// # Fields
 /// * `command` - the command to show help instructions for
+/// * `all` - whether to show the full help instructions (description and usage) of every command
 pub(crate) struct HelpRequest {
     pub(crate) command: Option<String>,
+    pub(crate) all: bool,
 }
 
 impl HelpRequest {
-    pub(crate) fn new(command: Option<String>) -> Self {
-        Self { command }
+    pub(crate) fn new(command: Option<String>, all: bool) -> Self {
+        Self { command, all }
     }
 }
 
@@ -29,6 +31,18 @@ impl HelpHandler {
     pub(crate) fn new(config: Arm<Config>) -> Self {
         Self { config }
     }
+
+    /// Prints the full help instructions of a command: its name, description and usage.
+    fn show_command(command: &Command) {
+        let command_name = command.name.clone();
+        success!("{}:", command_name);
+
+        let command_description = command.description.clone();
+        info!("{}", command_description);
+
+        let command_usage = command.usage.clone();
+        warn!("{}", command_usage);
+    }
 }
 
 impl RequestHandler<HelpRequest, Void> for HelpHandler {
@@ -39,23 +53,31 @@ impl RequestHandler<HelpRequest, Void> for HelpHandler {
             Ok(config) => match request.command {
                 Some(command) => match config.commands.get(&command) {
                     Some(command) => {
-                        let command_name = command.name.clone();
-                        success!("{}:", command_name);
-
-                        let command_description = command.description.clone();
-                        info!("{}", command_description);
-
-                        let command_usage = command.usage.clone();
-                        warn!("{}", command_usage);
+                        Self::show_command(command);
 
                         log::info!("Help shown successfully!");
                         Ok(())
                     }
                     None => Err(Box::try_from("Command not found").unwrap()),
                 },
+                None if request.all => {
+                    config
+                        .commands
+                        .values()
+                        .enumerate()
+                        .for_each(|(index, command)| {
+                            if index > 0 {
+                                println!();
+                            }
+                            Self::show_command(command);
+                        });
+
+                    log::info!("Help shown successfully!");
+                    Ok(())
+                }
                 None => {
                     info!("Available commands:");
-                    config.commands.iter().for_each(|(_name, command)| {
+                    config.commands.values().for_each(|command| {
                         cprintln!("<g!>{}</> - {}", command.name, command.description)
                     });
 
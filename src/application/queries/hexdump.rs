@@ -0,0 +1,181 @@
+use crate::application::queries::cat::CatRequest;
+use crate::application::Void;
+use crate::core::Arm;
+use crate::domain::i_disk_manager::IDiskManager;
+use color_print::cprintln;
+use mediator::{Request, RequestHandler};
+
+/// The number of bytes shown on each row of the dump
+pub(crate) const BYTES_PER_ROW: usize = 16;
+
+/// HexdumpRequest is a request to show the raw bytes of a file
+/// # Fields
+/// * `file_name` - The name of the file to dump
+/// * `file_extension` - The extension of the file to dump
+/// * `offset` - The offset of the first dumped byte (0 if missing)
+/// * `length` - The number of dumped bytes (up to the end of the file if missing)
+pub(crate) struct HexdumpRequest {
+    pub(crate) file_name: String,
+    pub(crate) file_extension: String,
+    pub(crate) offset: Option<usize>,
+    pub(crate) length: Option<usize>,
+}
+
+impl HexdumpRequest {
+    pub(crate) fn new(
+        file_name: String,
+        file_extension: String,
+        offset: Option<usize>,
+        length: Option<usize>,
+    ) -> Self {
+        Self {
+            file_name,
+            file_extension,
+            offset,
+            length,
+        }
+    }
+}
+
+impl Request<Void> for HexdumpRequest {}
+
+/// HexdumpHandler is a handler for HexdumpRequest holding a reference to the disk manager
+pub(crate) struct HexdumpHandler {
+    disk_manager: Arm<dyn IDiskManager>,
+}
+
+impl HexdumpHandler {
+    pub(crate) fn new(disk_manager: Arm<dyn IDiskManager>) -> Self {
+        Self { disk_manager }
+    }
+
+    /// Formats a row of bytes as `offset  hex bytes  |ascii|`, where the hex bytes are split
+    /// in two groups of 8 and the non-printable bytes are shown as `.` in the ascii column.
+    pub(crate) fn format_row(offset: usize, row: &[u8]) -> (String, String, String) {
+        let hex = (0..BYTES_PER_ROW)
+            .map(|i| match row.get(i) {
+                Some(byte) => format!("{:02x}", byte),
+                None => "  ".to_string(),
+            })
+            .collect::<Vec<_>>()
+            .chunks(BYTES_PER_ROW / 2)
+            .map(|group| group.join(" "))
+            .collect::<Vec<_>>()
+            .join("  ");
+        let ascii = row
+            .iter()
+            .map(|&byte| match byte.is_ascii_graphic() || byte == b' ' {
+                true => byte as char,
+                false => '.',
+            })
+            .collect::<String>();
+
+        (format!("{:08x}", offset), hex, ascii)
+    }
+}
+
+impl RequestHandler<HexdumpRequest, Void> for HexdumpHandler {
+    fn handle(&mut self, request: HexdumpRequest) -> Void {
+        log::info!(
+            "Dumping file {}.{}",
+            request.file_name,
+            request.file_extension
+        );
+
+        match self.disk_manager.lock() {
+            Ok(mut disk_manager) => {
+                disk_manager.pull_sync();
+
+                let content = disk_manager.read_file_bytes(&CatRequest::new(
+                    request.file_name.clone(),
+                    request.file_extension.clone(),
+                ))?;
+                disk_manager.push_sync();
+
+                let offset = request.offset.unwrap_or(0);
+                if offset > content.len() {
+                    return Err(Box::try_from(format!(
+                        "Offset {} is out of range (the file has {} B)",
+                        offset,
+                        content.len()
+                    ))
+                    .unwrap());
+                }
+
+                let end = match request.length {
+                    Some(length) => content.len().min(offset.saturating_add(length)),
+                    None => content.len(),
+                };
+
+                content[offset..end]
+                    .chunks(BYTES_PER_ROW)
+                    .enumerate()
+                    .for_each(|(row_index, row)| {
+                        let (row_offset, hex, ascii) =
+                            Self::format_row(offset + row_index * BYTES_PER_ROW, row);
+                        cprintln!("<b!>{}</>  {}  <g!>|{}|</>", row_offset, hex, ascii);
+                    });
+                cprintln!("<b!>{:08x}</>", end);
+
+                log::info!(
+                    "Dumped bytes {}..{} of file {}.{}",
+                    offset,
+                    end,
+                    request.file_name,
+                    request.file_extension
+                );
+                Ok(())
+            }
+            Err(_) => Err(Box::try_from("Unable to lock disk manager!").unwrap()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::test_disk::TestDisk;
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn the_first_row_of_a_known_file_shows_its_offset_hex_and_ascii() {
+        let mut disk = TestDisk::new();
+        disk.write("hello", "txt", "Hello, World!\n\tbye").unwrap();
+        let content = disk.read("hello", "txt");
+
+        let (offset, hex, ascii) = HexdumpHandler::format_row(0, &content[..BYTES_PER_ROW]);
+
+        assert_eq!(offset, "00000000");
+        assert_eq!(hex, "48 65 6c 6c 6f 2c 20 57  6f 72 6c 64 21 0a 09 62");
+        assert_eq!(ascii, "Hello, World!..b");
+    }
+
+    #[test]
+    fn a_partial_row_keeps_the_hex_column_aligned() {
+        let (offset, hex, ascii) = HexdumpHandler::format_row(0x10, b"ye");
+
+        assert_eq!(offset, "00000010");
+        assert_eq!(hex.len(), BYTES_PER_ROW * 3);
+        assert!(hex.starts_with("79 65 "));
+        assert_eq!(ascii, "ye");
+    }
+
+    #[test]
+    fn an_offset_past_the_end_of_the_file_is_rejected() {
+        let mut disk = TestDisk::new();
+        disk.write("hello", "txt", "Hello").unwrap();
+        let mut hexdump_handler = HexdumpHandler::new(Arc::new(Mutex::new(disk.reopen())));
+
+        let result = hexdump_handler.handle(HexdumpRequest::new(
+            "hello".to_string(),
+            "txt".to_string(),
+            Some(6),
+            None,
+        ));
+
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Offset 6 is out of range (the file has 5 B)"
+        );
+    }
+}
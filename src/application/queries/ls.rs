@@ -1,10 +1,13 @@
+use crate::application::commands::cd::ChangeDirectoryRequest;
 use crate::application::Void;
 use crate::core::filter_type::FilterType;
 use crate::core::sort_type::SortType;
 use crate::core::Arm;
+use crate::domain::file_entry::FileEntry;
 use crate::domain::i_disk_manager::IDiskManager;
 use color_print::cprintln;
 use mediator::{Request, RequestHandler};
+use serde::Serialize;
 
 /// ListRequest is a request to list files in the current directory
 /// # Fields
@@ -23,6 +26,137 @@ impl ListRequest {
 
 impl Request<Void> for ListRequest {}
 
+/// JsonFileEntry is a file entry as printed by `ls --json`, along with the path of its directory
+#[derive(Serialize)]
+struct JsonFileEntry {
+    directory: String,
+    #[serde(flatten)]
+    file_entry: FileEntry,
+}
+
+/// ListFormat is the human-readable layout of the listed entries:
+/// - `Short`: the full names only
+/// - `Default`: one line per entry, as displayed by `FileEntry`
+/// - `Long`: a table under a header row with the attributes, the name, the extension, the last modification timestamp,
+///   the size, the first cluster, the number of clusters and the cluster ranges of the allocation chain
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ListFormat {
+    Short,
+    Default,
+    Long,
+}
+
+impl ListFormat {
+    /// The long format takes precedence over the short one when both are requested.
+    fn from_filters(filters: &[FilterType]) -> Self {
+        if filters.contains(&FilterType::InLongFormat) {
+            Self::Long
+        } else if filters.contains(&FilterType::InShortFormat) {
+            Self::Short
+        } else {
+            Self::Default
+        }
+    }
+}
+
+const LONG_FORMAT_HEADER: [&str; 8] = [
+    "ATTR", "NAME", "EXT", "MODIFIED", "SIZE", "FIRST", "CLUSTERS", "CHAIN",
+];
+const LONG_FORMAT_DATETIME: &str = "%Y-%m-%d %H:%M:%S UTC";
+
+/// LongFormatWidths holds the widths of the columns of the long format, i.e. the widths of their widest cells
+/// (header included): the attributes, the name, the extension and the timestamp are padded to the left,
+/// while the size, the first cluster and the clusters count are aligned to the right (the chain, being the last
+/// column, is left unpadded).
+struct LongFormatWidths {
+    columns: [usize; 8],
+}
+
+impl LongFormatWidths {
+    /// Measures the columns of the given entries, each paired with the clusters of its allocation chain.
+    fn new(rows: &[(&FileEntry, Vec<usize>)]) -> Self {
+        let mut columns = LONG_FORMAT_HEADER.map(str::len);
+
+        rows.iter()
+            .map(|(file_entry, chain_clusters)| Self::get_cells(file_entry, chain_clusters))
+            .for_each(|cells| {
+                columns
+                    .iter_mut()
+                    .zip(cells.iter())
+                    .for_each(|(width, cell)| *width = (*width).max(cell.chars().count()))
+            });
+
+        Self { columns }
+    }
+
+    /// Formats the clusters of an allocation chain as the ranges of consecutive clusters it goes through,
+    /// e.g. `5-6,9` for `5 -> 6 -> 9`, so that a fragmented chain stands out (`-` for an empty chain).
+    fn format_chain_ranges(chain_clusters: &[usize]) -> String {
+        if chain_clusters.is_empty() {
+            return "-".to_string();
+        }
+
+        let mut ranges: Vec<(usize, usize)> = Vec::new();
+        for &cluster_index in chain_clusters {
+            match ranges.last_mut() {
+                Some((_, range_end)) if *range_end + 1 == cluster_index => {
+                    *range_end = cluster_index
+                }
+                _ => ranges.push((cluster_index, cluster_index)),
+            }
+        }
+
+        ranges
+            .iter()
+            .map(|(range_start, range_end)| match range_start == range_end {
+                true => range_start.to_string(),
+                false => format!("{}-{}", range_start, range_end),
+            })
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+
+    fn get_cells(file_entry: &FileEntry, chain_clusters: &[usize]) -> [String; 8] {
+        [
+            file_entry.get_attributes_as_string(),
+            file_entry.name.clone(),
+            match file_entry.is_file() {
+                true => file_entry.extension.clone(),
+                false => String::new(),
+            },
+            file_entry
+                .last_modification_datetime
+                .format(LONG_FORMAT_DATETIME)
+                .to_string(),
+            file_entry.size.to_string(),
+            file_entry.first_cluster.to_string(),
+            chain_clusters.len().to_string(),
+            Self::format_chain_ranges(chain_clusters),
+        ]
+    }
+
+    fn format_cells(&self, cells: [&str; 8]) -> String {
+        let [attributes, name, extension, modified, size, first_cluster, clusters, chain] = cells;
+        let [w_attributes, w_name, w_extension, w_modified, w_size, w_first_cluster, w_clusters, _] =
+            self.columns;
+
+        format!(
+            "{:<w_attributes$}  {:<w_name$}  {:<w_extension$}  {:<w_modified$}  {:>w_size$}  {:>w_first_cluster$}  {:>w_clusters$}  {}",
+            attributes, name, extension, modified, size, first_cluster, clusters, chain
+        )
+    }
+
+    fn format_header(&self) -> String {
+        self.format_cells(LONG_FORMAT_HEADER)
+    }
+
+    /// Formats a file entry as a row of the long format.
+    fn format_row(&self, file_entry: &FileEntry, chain_clusters: &[usize]) -> String {
+        let cells = Self::get_cells(file_entry, chain_clusters);
+        self.format_cells(cells.each_ref().map(String::as_str))
+    }
+}
+
 /// ListHandler is a handler for ListRequest holding a reference to a disk manager
 pub(crate) struct ListHandler {
     disk_manager: Arm<dyn IDiskManager>,
@@ -32,6 +166,103 @@ impl ListHandler {
     pub(crate) fn new(disk_manager: Arm<dyn IDiskManager>) -> Self {
         Self { disk_manager }
     }
+
+    /// Prints the given entries as a table under a header row, each column being as wide as its widest cell.
+    fn print_long_format(disk_manager: &dyn IDiskManager, file_entries: &[FileEntry]) {
+        let rows: Vec<(&FileEntry, Vec<usize>)> = file_entries
+            .iter()
+            .map(|file_entry| {
+                (
+                    file_entry,
+                    disk_manager.cluster_chain(file_entry.first_cluster as usize),
+                )
+            })
+            .collect();
+        let widths = LongFormatWidths::new(&rows);
+
+        let header = widths.format_header();
+        cprintln!("<c!>{}</>", header);
+        rows.iter().for_each(|(file_entry, chain_clusters)| {
+            println!("{}", widths.format_row(file_entry, chain_clusters))
+        });
+    }
+
+    /// Lists the files of the working directory and, if the recursive filter is present,
+    /// the files of each of its subdirectories depth-first.
+    /// In JSON mode, the files are collected into `json_entries` instead of being printed.
+    fn list_directory_tree(
+        disk_manager: &mut dyn IDiskManager,
+        request: &ListRequest,
+        json_entries: &mut Vec<JsonFileEntry>,
+    ) -> Void {
+        disk_manager.pull_sync();
+        let file_entries = disk_manager.list_files(request)?;
+
+        if request.filters.contains(&FilterType::Json) {
+            let directory = disk_manager.get_working_directory_full_path();
+            json_entries.extend(file_entries.into_iter().map(|file_entry| JsonFileEntry {
+                directory: directory.clone(),
+                file_entry,
+            }));
+        } else {
+            cprintln!(
+                "<w!>Current dir `{}`</>: <b!>{} file(s)</>",
+                disk_manager.get_working_directory_full_path(),
+                file_entries.len()
+            );
+
+            match ListFormat::from_filters(&request.filters) {
+                ListFormat::Short => file_entries
+                    .iter()
+                    .for_each(|file_entry| println!("{}", file_entry.get_full_name())),
+                ListFormat::Default => file_entries.iter().for_each(|file_entry| {
+                    println!("{}", file_entry);
+                }),
+                ListFormat::Long => Self::print_long_format(disk_manager, &file_entries),
+            }
+        }
+
+        if !request.filters.contains(&FilterType::Recursive) {
+            return Ok(());
+        }
+
+        // get the subdirectories regardless of the other filters (hidden ones only if requested)
+        let visibility_filter = match request.filters.contains(&FilterType::AllAndHidden)
+            || request.filters.contains(&FilterType::Hidden)
+        {
+            true => FilterType::AllAndHidden,
+            false => FilterType::All,
+        };
+        let directories_request = ListRequest::new(
+            vec![FilterType::Directories, visibility_filter],
+            request.sort.clone(),
+        );
+        let directories = disk_manager.list_files(&directories_request)?;
+
+        for directory in directories
+            .iter()
+            .filter(|directory| directory.name != "." && directory.name != "..")
+        {
+            if !request.filters.contains(&FilterType::Json) {
+                println!();
+            }
+
+            disk_manager.pull_sync();
+            disk_manager
+                .change_working_directory(&ChangeDirectoryRequest::new(directory.name.clone()))?;
+
+            let result = Self::list_directory_tree(disk_manager, request, json_entries);
+
+            // always get back to the parent directory, even if the listing of the subdirectory failed
+            disk_manager.pull_sync();
+            disk_manager
+                .change_working_directory(&ChangeDirectoryRequest::new("..".to_string()))?;
+
+            result?;
+        }
+
+        Ok(())
+    }
 }
 
 impl RequestHandler<ListRequest, Void> for ListHandler {
@@ -40,48 +271,142 @@ impl RequestHandler<ListRequest, Void> for ListHandler {
 
         match self.disk_manager.lock() {
             Ok(mut disk_manager) => {
-                disk_manager.pull_sync();
-
-                match disk_manager.list_files(&request) {
-                    Ok(file_entries) => {
-                        cprintln!(
-                            "<w!>Current dir `{}`</>: <b!>{} file(s)</>",
-                            disk_manager.get_working_directory_full_path(),
-                            file_entries.len()
-                        );
-
-                        // if in short format, print only file names and extensions
-                        if request.filters.contains(&FilterType::InShortFormat) {
-                            file_entries
-                                .iter()
-                                .for_each(|file_entry| match file_entry.is_file() {
-                                    true => {
-                                        println!("{}.{}", file_entry.name, file_entry.extension)
-                                    }
-                                    false => println!("{}", file_entry.name),
-                                });
-                        } else {
-                            // otherwise, print all file entry info
-                            file_entries.iter().for_each(|file_entry| {
-                                println!("{}", file_entry);
-                            });
-                        }
-
-                        println!();
-                        cprintln!("<g!>Free space:</> {} B", disk_manager.get_free_space());
-                        cprintln!("<g!>Total space:</> {} B", disk_manager.get_total_space());
-
-                        log::info!(
-                            "Listed files successfully with filters: {:?} and sort: {:?}",
-                            request.filters,
-                            request.sort
-                        );
-                        Ok(())
-                    }
-                    Err(e) => Err(e),
+                let mut json_entries = Vec::new();
+                Self::list_directory_tree(&mut *disk_manager, &request, &mut json_entries)?;
+
+                // the free/total space footer is left out of the JSON output to keep it parseable
+                if request.filters.contains(&FilterType::Json) {
+                    println!("{}", serde_json::to_string_pretty(&json_entries)?);
+                } else {
+                    println!();
+                    cprintln!("<g!>Free space:</> {} B", disk_manager.get_free_space());
+                    cprintln!("<g!>Total space:</> {} B", disk_manager.get_total_space());
                 }
+
+                log::info!(
+                    "Listed files successfully with filters: {:?} and sort: {:?}",
+                    request.filters,
+                    request.sort
+                );
+                Ok(())
             }
             Err(_) => Err(Box::try_from("Unable to lock disk manager!").unwrap()),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::commands::setattr::SetAttributesRequest;
+    use crate::domain::file_entry::FileEntryAttributes;
+    use crate::infrastructure::test_disk::TestDisk;
+
+    /// Builds the tree `/a.txt`, `/docs/b.txt`, `/docs/sub/c.txt` and the hidden `/hid/d.txt`.
+    fn build_directory_tree() -> TestDisk {
+        let mut disk = TestDisk::new();
+        disk.write("a", "txt", "a").unwrap();
+        disk.mkdir("docs").unwrap();
+        disk.cd("docs").unwrap();
+        disk.write("b", "txt", "b").unwrap();
+        disk.mkdir("sub").unwrap();
+        disk.cd("sub").unwrap();
+        disk.write("c", "txt", "c").unwrap();
+        disk.cd("/").unwrap();
+        disk.mkdir("hid").unwrap();
+        disk.cd("hid").unwrap();
+        disk.write("d", "txt", "d").unwrap();
+        disk.cd("..").unwrap();
+        disk.run(|disk_manager| {
+            disk_manager.set_attributes(&SetAttributesRequest::new(
+                "hid".to_string(),
+                String::new(),
+                vec![FileEntryAttributes::Hidden],
+            ))
+        })
+        .unwrap();
+
+        disk
+    }
+
+    /// Lists the tree recursively, returning the directory and the full name of every listed entry.
+    fn list_recursively(
+        disk: &mut TestDisk,
+        mut filters: Vec<FilterType>,
+    ) -> Vec<(String, String)> {
+        filters.extend([FilterType::Recursive, FilterType::Json]);
+        let request = ListRequest::new(filters, Some(SortType::NameAsc));
+        let mut json_entries = Vec::new();
+
+        ListHandler::list_directory_tree(&mut disk.disk_manager, &request, &mut json_entries)
+            .unwrap();
+
+        json_entries
+            .into_iter()
+            .map(|json_entry| (json_entry.directory, json_entry.file_entry.get_full_name()))
+            .collect()
+    }
+
+    fn listed(entries: &[(&str, &str)]) -> Vec<(String, String)> {
+        entries
+            .iter()
+            .map(|(directory, name)| (directory.to_string(), name.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn ls_recursive_lists_the_subdirectories_depth_first() {
+        let mut disk = build_directory_tree();
+
+        assert_eq!(
+            list_recursively(&mut disk, vec![]),
+            listed(&[
+                ("/", "a.txt"),
+                ("/", "docs"),
+                ("/docs", "b.txt"),
+                ("/docs", "sub"),
+                ("/docs/sub", "c.txt"),
+            ])
+        );
+        disk.pull_sync();
+        assert_eq!(disk.get_working_directory_full_path(), "/");
+    }
+
+    #[test]
+    fn ls_recursive_descends_into_hidden_directories_only_with_h() {
+        let mut disk = build_directory_tree();
+
+        assert_eq!(
+            list_recursively(&mut disk, vec![FilterType::AllAndHidden, FilterType::Files]),
+            listed(&[
+                ("/", "a.txt"),
+                ("/docs", "b.txt"),
+                ("/docs/sub", "c.txt"),
+                ("/hid", "d.txt"),
+            ])
+        );
+    }
+
+    #[test]
+    fn ls_json_parses_back_into_the_listed_entries() {
+        let mut disk = build_directory_tree();
+        disk.pull_sync();
+        let request = ListRequest::new(vec![FilterType::Json], Some(SortType::NameAsc));
+        let mut json_entries = Vec::new();
+        ListHandler::list_directory_tree(&mut disk.disk_manager, &request, &mut json_entries)
+            .unwrap();
+
+        let json = serde_json::to_string_pretty(&json_entries).unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
+        let entries = parsed.as_array().unwrap();
+
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0]["directory"], "/");
+        assert_eq!(entries[0]["name"], "a");
+        assert_eq!(entries[0]["extension"], "txt");
+        assert_eq!(entries[0]["size"], 1);
+        assert_eq!(entries[0]["is_file"], true);
+        assert_eq!(entries[1]["name"], "docs");
+        assert_eq!(entries[1]["is_file"], false);
+    }
+}
@@ -38,6 +38,10 @@ impl RequestHandler<NeofetchRequest, Void> for NeofetchHandler {
         match (self.config.lock(), self.disk_manager.lock()) {
             (Ok(config), Ok(disk_manager)) => {
                 let boot_sector = disk_manager.get_boot_sector();
+                let label = match boot_sector.label.as_str() {
+                    "" => "none",
+                    label => label,
+                };
 
                 cprintln!("<bold>
                     <w!>WWWWWWWWWWWX</><c!>Okk0</><w!>XWWXXK</><c!>00000</><w!>KNWWWWWWWWWWW</>            <w!>{}</><b!>{}</><w!>{}</>
@@ -48,7 +52,7 @@ impl RequestHandler<NeofetchRequest, Void> for NeofetchHandler {
                     <w!>WXo</><b!>,,,:</><w!>0WWWWWWWWN</><c!>KOkkk</><w!>0NWKkdlcccloxOKNWW</>            <r>No Clusters</>: <w!>{}</>
                     <w!>Nd</><b!>,,,,</><w!>xWWWWWWWWWWWWWWWWNx:,'</><b!>,,,,,,',</><w!>ckNW</>            <r>Cluster Size</>: <w!>{} bytes</>
                     <w!>0:</><b!>',':</><w!>0</><c!>N</><w!>NWWWWWWWWWWWWWWk;'</><b!>,,,,</><w!>::</><b!>,,,,,,</><w!>oX</>            <r>Disk Size</>: <w!>{} bytes</>
-                    <w!>x</><b!>,,,'</>:<w!>0</><c!>KkX</><w!>WWWWWWWWWWWWWk</><b!>,,,,,,</><w!>dk:</><b!>',,,'</><w!>,x</>
+                    <w!>x</><b!>,,,'</>:<w!>0</><c!>KkX</><w!>WWWWWWWWWWWWWk</><b!>,,,,,,</><w!>dk:</><b!>',,,'</><w!>,x</>            <r>Label</>: <w!>{}</>
                     <w!>o</><b!>,,,'</><w!>;O</><c!>XddK</><w!>NWWWWWWWWW</><c!>WM</><w!>Xd</><b!>:,,;</><w!>lKKc'</><b!>,,,,</><w!>'o</>
                     <w!>d</><b!>,,,,,</><w!>l</><c!>K0oox0</><w!>KXNWNNN</><c!>XX0O</><w!>00OO</><c!>OK</><w!>WO;'</><b!>,,,,,</><w!>o</>
                     <w!>k;</><b!>',,,,</><w!>oK</><c!>0dlloddxxddoollodddk</><w!>K0c</><b!>,,,,,</><w!>',k</>
@@ -67,10 +71,11 @@ impl RequestHandler<NeofetchRequest, Void> for NeofetchHandler {
                     config.os,
                     config.version,
                     config.author,
-                    boot_sector.cluster_size,
+                    boot_sector.fat_cell_bits,
                     boot_sector.cluster_count,
                     boot_sector.cluster_size,
                     boot_sector.cluster_size as u32 * boot_sector.cluster_count as u32,
+                    label,
                 );
                 log::info!("Showing OS specifications... done");
 
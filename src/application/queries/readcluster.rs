@@ -0,0 +1,70 @@
+use crate::application::queries::hexdump::{HexdumpHandler, BYTES_PER_ROW};
+use crate::application::Void;
+use crate::core::Arm;
+use crate::domain::i_disk_manager::IDiskManager;
+use color_print::cprintln;
+use mediator::{Request, RequestHandler};
+
+/// ReadClusterRequest is a request to show the raw bytes of a cluster
+/// # Fields
+/// * `cluster_index` - the index of the cluster to dump
+pub(crate) struct ReadClusterRequest {
+    pub(crate) cluster_index: usize,
+}
+
+impl ReadClusterRequest {
+    pub(crate) fn new(cluster_index: usize) -> Self {
+        Self { cluster_index }
+    }
+}
+
+impl Request<Void> for ReadClusterRequest {}
+
+/// ReadClusterHandler is a handler for ReadClusterRequest holding a reference to the disk manager
+pub(crate) struct ReadClusterHandler {
+    disk_manager: Arm<dyn IDiskManager>,
+}
+
+impl ReadClusterHandler {
+    pub(crate) fn new(disk_manager: Arm<dyn IDiskManager>) -> Self {
+        Self { disk_manager }
+    }
+}
+
+impl RequestHandler<ReadClusterRequest, Void> for ReadClusterHandler {
+    fn handle(&mut self, request: ReadClusterRequest) -> Void {
+        log::info!("Dumping cluster {}...", request.cluster_index);
+
+        match self.disk_manager.lock() {
+            Ok(mut disk_manager) => {
+                disk_manager.pull_sync();
+
+                let cluster_data = disk_manager.read_cluster(request.cluster_index)?;
+                let fat_value = disk_manager
+                    .get_fat_slice(request.cluster_index, 1)
+                    .first()
+                    .map(|(_, fat_value)| fat_value.to_string())
+                    .unwrap_or_default();
+
+                // the offsets are relative to the start of the cluster, its FAT cell telling where the chain goes next
+                cprintln!(
+                    "<w!>Cluster {}</> (FAT: <g!>{}</>)",
+                    request.cluster_index,
+                    fat_value
+                );
+                cluster_data
+                    .chunks(BYTES_PER_ROW)
+                    .enumerate()
+                    .for_each(|(row_index, row)| {
+                        let (row_offset, hex, ascii) =
+                            HexdumpHandler::format_row(row_index * BYTES_PER_ROW, row);
+                        cprintln!("<b!>{}</>  {}  <g!>|{}|</>", row_offset, hex, ascii);
+                    });
+
+                log::info!("Dumped cluster {}", request.cluster_index);
+                Ok(())
+            }
+            Err(_) => Err(Box::try_from("Unable to lock disk manager!").unwrap()),
+        }
+    }
+}
@@ -0,0 +1,112 @@
+use crate::application::Void;
+use crate::core::Arm;
+use crate::domain::fat::FatValue;
+use crate::domain::i_disk_manager::IDiskManager;
+use color_print::cprintln;
+use mediator::{Request, RequestHandler};
+
+/// StatRequest is a request to show the metadata of a file or directory
+/// # Fields
+/// * `name` - The name of the file or directory
+/// * `extension` - The extension of the file (empty for directories)
+pub(crate) struct StatRequest {
+    pub(crate) name: String,
+    pub(crate) extension: String,
+}
+
+impl StatRequest {
+    pub(crate) fn new(name: String, extension: String) -> Self {
+        Self { name, extension }
+    }
+}
+
+impl Request<Void> for StatRequest {}
+
+/// StatHandler is a handler for StatRequest holding a reference to the disk manager
+pub(crate) struct StatHandler {
+    disk_manager: Arm<dyn IDiskManager>,
+}
+
+impl StatHandler {
+    pub(crate) fn new(disk_manager: Arm<dyn IDiskManager>) -> Self {
+        Self { disk_manager }
+    }
+
+    /// Formats the allocation chain of a file entry as its clusters followed by the FAT cell of the last one,
+    /// e.g. `5 -> 6 -> 9 -> EOC` (a chain looping back onto one of its clusters ends with that cluster,
+    /// a broken one with the unexpected FAT cell).
+    fn format_cluster_chain(disk_manager: &dyn IDiskManager, first_cluster: usize) -> String {
+        let chain_clusters = disk_manager.cluster_chain(first_cluster);
+        let last_cluster = match chain_clusters.last() {
+            Some(last_cluster) => *last_cluster,
+            None => return "none".to_string(),
+        };
+
+        let chain_end = match disk_manager.get_fat_slice(last_cluster, 1).first() {
+            Some((_, FatValue::EndOfChain)) => "EOC".to_string(),
+            Some((_, FatValue::Data(next_cluster_index))) => {
+                format!("{} (loop)", next_cluster_index)
+            }
+            Some((_, fat_value)) => fat_value.to_string(),
+            None => String::new(),
+        };
+
+        chain_clusters
+            .iter()
+            .map(|cluster_index| cluster_index.to_string())
+            .chain(std::iter::once(chain_end))
+            .collect::<Vec<_>>()
+            .join(" -> ")
+    }
+}
+
+impl RequestHandler<StatRequest, Void> for StatHandler {
+    fn handle(&mut self, request: StatRequest) -> Void {
+        log::info!("Showing metadata of {}.{}", request.name, request.extension);
+
+        match self.disk_manager.lock() {
+            Ok(mut disk_manager) => {
+                disk_manager.pull_sync();
+
+                match disk_manager.get_file_entry(&request) {
+                    Ok(file_entry) => {
+                        match file_entry.is_file() {
+                            true => cprintln!(
+                                "File <b!>{}.{}</>:",
+                                file_entry.name,
+                                file_entry.extension
+                            ),
+                            false => cprintln!("Directory <b!>{}</>:", file_entry.name),
+                        }
+                        cprintln!(
+                            "  Attributes: <g!>{}</>",
+                            file_entry.get_attributes_as_string()
+                        );
+                        cprintln!("  Size: <g!>{} B</>", file_entry.size);
+                        cprintln!("  First cluster: <g!>{}</>", file_entry.first_cluster);
+                        let cluster_chain = Self::format_cluster_chain(
+                            &*disk_manager,
+                            file_entry.first_cluster as usize,
+                        );
+                        cprintln!("  Clusters: <g!>{}</>", cluster_chain);
+                        cprintln!("  Created: <g!>{}</>", file_entry.creation_datetime);
+                        cprintln!(
+                            "  Modified: <g!>{}</>",
+                            file_entry.last_modification_datetime
+                        );
+                        cprintln!("  Accessed: <g!>{}</>", file_entry.last_access_date);
+
+                        log::info!(
+                            "Metadata of {}.{} has been shown successfully",
+                            request.name,
+                            request.extension
+                        );
+                        Ok(())
+                    }
+                    Err(e) => Err(e),
+                }
+            }
+            Err(_e) => Err(Box::try_from("Unable to lock disk manager!").unwrap()),
+        }
+    }
+}
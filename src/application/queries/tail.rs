@@ -0,0 +1,82 @@
+use crate::application::queries::cat::CatRequest;
+use crate::application::queries::head::DEFAULT_LINES_COUNT;
+use crate::application::Void;
+use crate::core::Arm;
+use crate::domain::i_disk_manager::IDiskManager;
+use color_print::cprintln;
+use mediator::{Request, RequestHandler};
+
+/// TailRequest is a request to show the last lines of a file
+/// # Fields
+/// * `file_name` - The name of the file
+/// * `file_extension` - The extension of the file
+/// * `lines_count` - The number of lines to show (`DEFAULT_LINES_COUNT` if not given)
+pub(crate) struct TailRequest {
+    pub(crate) file_name: String,
+    pub(crate) file_extension: String,
+    pub(crate) lines_count: Option<usize>,
+}
+
+impl TailRequest {
+    pub(crate) fn new(
+        file_name: String,
+        file_extension: String,
+        lines_count: Option<usize>,
+    ) -> Self {
+        Self {
+            file_name,
+            file_extension,
+            lines_count,
+        }
+    }
+}
+
+impl Request<Void> for TailRequest {}
+
+/// TailHandler is a handler for TailRequest holding a reference to the disk manager
+pub(crate) struct TailHandler {
+    disk_manager: Arm<dyn IDiskManager>,
+}
+
+impl TailHandler {
+    pub(crate) fn new(disk_manager: Arm<dyn IDiskManager>) -> Self {
+        Self { disk_manager }
+    }
+}
+
+impl RequestHandler<TailRequest, Void> for TailHandler {
+    fn handle(&mut self, request: TailRequest) -> Void {
+        let lines_count = request.lines_count.unwrap_or(DEFAULT_LINES_COUNT);
+        log::info!(
+            "Showing the last {} line(s) of file {}.{}",
+            lines_count,
+            request.file_name,
+            request.file_extension
+        );
+
+        match self.disk_manager.lock() {
+            Ok(mut disk_manager) => {
+                disk_manager.pull_sync();
+
+                let content = disk_manager.get_file_content(&CatRequest::new(
+                    request.file_name.clone(),
+                    request.file_extension.clone(),
+                ))?;
+                disk_manager.push_sync();
+
+                let lines: Vec<&str> = content.lines().collect();
+                lines[lines.len().saturating_sub(lines_count)..]
+                    .iter()
+                    .for_each(|line| cprintln!("<g!>{}</>", line));
+
+                log::info!(
+                    "The last lines of file {}.{} have been shown successfully",
+                    request.file_name,
+                    request.file_extension
+                );
+                Ok(())
+            }
+            Err(_) => Err(Box::try_from("Unable to lock disk manager!").unwrap()),
+        }
+    }
+}
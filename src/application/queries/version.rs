@@ -0,0 +1,79 @@
+use crate::application::Void;
+use crate::core::config::Config;
+use crate::core::Arm;
+use mediator::{Request, RequestHandler};
+
+/// VersionRequest is a request for printing the OS version on a single line.
+pub(crate) struct VersionRequest;
+
+impl VersionRequest {
+    pub(crate) fn new() -> Self {
+        Self {}
+    }
+}
+
+impl Request<Void> for VersionRequest {}
+
+/// VersionHandler is a handler for VersionRequest holding a reference to the config.
+pub(crate) struct VersionHandler {
+    config: Arm<Config>,
+}
+
+impl VersionHandler {
+    pub(crate) fn new(config: Arm<Config>) -> Self {
+        Self { config }
+    }
+
+    /// Formats the version line, e.g. `RoDOS 0.1.0 (rodos 0.1.0) by Sami Barbut-Dica`.
+    fn format_version(config: &Config) -> String {
+        format!(
+            "{} {} ({} {}) by {}",
+            config.os,
+            config.version,
+            env!("CARGO_PKG_NAME"),
+            env!("CARGO_PKG_VERSION"),
+            config.author
+        )
+    }
+}
+
+impl RequestHandler<VersionRequest, Void> for VersionHandler {
+    fn handle(&mut self, _req: VersionRequest) -> Void {
+        log::info!("Showing OS version...");
+
+        match self.config.lock() {
+            Ok(config) => {
+                // plain output so that the line stays easy to parse
+                println!("{}", Self::format_version(&config));
+                Ok(())
+            }
+            Err(_) => Err(Box::try_from("Unable to lock config!").unwrap()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_version_line_shows_the_config_and_the_package_version() {
+        let config = Config {
+            os: "TestOS".to_string(),
+            version: "9.8.7".to_string(),
+            author: "Someone Else".to_string(),
+            ..Config::default()
+        };
+
+        let version = VersionHandler::format_version(&config);
+
+        assert_eq!(
+            version,
+            format!(
+                "TestOS 9.8.7 (rodos {}) by Someone Else",
+                env!("CARGO_PKG_VERSION")
+            )
+        );
+        assert_eq!(version.lines().count(), 1);
+    }
+}
@@ -0,0 +1,138 @@
+use crate::application::queries::cat::CatRequest;
+use crate::application::queries::stat::StatRequest;
+use crate::application::Void;
+use crate::core::Arm;
+use crate::domain::i_disk_manager::IDiskManager;
+use color_print::cprintln;
+use mediator::{Request, RequestHandler};
+
+/// WcMetric is a metric of a file which can be shown alone by `wc`:
+/// - `Bytes`: the size of the file in bytes
+/// - `Lines`: the number of newline characters of the content
+/// - `Words`: the number of whitespace-separated words of the content
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum WcMetric {
+    Bytes,
+    Lines,
+    Words,
+}
+
+impl From<char> for WcMetric {
+    fn from(c: char) -> Self {
+        match c {
+            'l' => Self::Lines,
+            'w' => Self::Words,
+            _ => Self::Bytes,
+        }
+    }
+}
+
+/// WcRequest is a request to count the bytes, the lines and the words of a file
+/// # Fields
+/// * `file_name` - The name of the file
+/// * `file_extension` - The extension of the file
+/// * `metric` - The only metric to show, if any (otherwise all of them are shown)
+pub(crate) struct WcRequest {
+    pub(crate) file_name: String,
+    pub(crate) file_extension: String,
+    pub(crate) metric: Option<WcMetric>,
+}
+
+impl WcRequest {
+    pub(crate) fn new(file_name: String, file_extension: String, metric: Option<WcMetric>) -> Self {
+        Self {
+            file_name,
+            file_extension,
+            metric,
+        }
+    }
+}
+
+impl Request<Void> for WcRequest {}
+
+/// WcHandler is a handler for WcRequest holding a reference to the disk manager
+pub(crate) struct WcHandler {
+    disk_manager: Arm<dyn IDiskManager>,
+}
+
+impl WcHandler {
+    pub(crate) fn new(disk_manager: Arm<dyn IDiskManager>) -> Self {
+        Self { disk_manager }
+    }
+
+    /// Returns the number of lines and words of the given content,
+    /// or `None` if it is not text (i.e. it is not valid UTF-8 or it contains NUL bytes).
+    fn count_lines_and_words(content: &[u8]) -> Option<(usize, usize)> {
+        let text = std::str::from_utf8(content)
+            .ok()
+            .filter(|text| !text.contains('\0'))?;
+
+        Some((text.matches('\n').count(), text.split_whitespace().count()))
+    }
+}
+
+impl RequestHandler<WcRequest, Void> for WcHandler {
+    fn handle(&mut self, request: WcRequest) -> Void {
+        log::info!(
+            "Counting the bytes, lines and words of file {}.{}",
+            request.file_name,
+            request.file_extension
+        );
+
+        match self.disk_manager.lock() {
+            Ok(mut disk_manager) => {
+                disk_manager.pull_sync();
+
+                let file_entry = disk_manager.get_file_entry(&StatRequest::new(
+                    request.file_name.clone(),
+                    request.file_extension.clone(),
+                ))?;
+                let content = disk_manager.read_file_bytes(&CatRequest::new(
+                    request.file_name.clone(),
+                    request.file_extension.clone(),
+                ))?;
+                disk_manager.push_sync();
+
+                // the size of the entry is the number of bytes, regardless of how they decode
+                let bytes_count = file_entry.size;
+                let full_name = file_entry.get_full_name();
+
+                match (Self::count_lines_and_words(&content), request.metric) {
+                    (_, Some(WcMetric::Bytes)) => {
+                        cprintln!("<y!>{}</> <b!>{}</>", bytes_count, full_name)
+                    }
+                    (Some((lines_count, words_count)), None) => cprintln!(
+                        "<g!>{}</> <g!>{}</> <y!>{}</> <b!>{}</>",
+                        lines_count,
+                        words_count,
+                        bytes_count,
+                        full_name
+                    ),
+                    (Some((lines_count, _)), Some(WcMetric::Lines)) => {
+                        cprintln!("<g!>{}</> <b!>{}</>", lines_count, full_name)
+                    }
+                    (Some((_, words_count)), Some(WcMetric::Words)) => {
+                        cprintln!("<g!>{}</> <b!>{}</>", words_count, full_name)
+                    }
+                    // only the byte count is meaningful for binary content
+                    (None, None) => cprintln!(
+                        "<y!>{}</> <b!>{}</> (binary file, lines and words are not counted)",
+                        bytes_count,
+                        full_name
+                    ),
+                    (None, Some(_)) => {
+                        return Err(Box::try_from(format!(
+                            "File {} is binary, only its bytes can be counted",
+                            full_name
+                        ))
+                        .unwrap())
+                    }
+                }
+
+                log::info!("Counted the bytes, lines and words of file {}", full_name);
+                Ok(())
+            }
+            Err(_) => Err(Box::try_from("Unable to lock disk manager!").unwrap()),
+        }
+    }
+}
@@ -1,10 +1,19 @@
 use std::sync::{Arc, Mutex};
 
+pub(crate) mod audit_log;
+pub(crate) mod autosave;
+pub(crate) mod checksum;
 pub(crate) mod cli_macros;
 pub(crate) mod cli_parser;
+pub(crate) mod command_history;
+pub(crate) mod completion;
 pub(crate) mod config;
+pub(crate) mod confirmation_prompt;
 pub(crate) mod content_type;
 pub(crate) mod filter_type;
+pub(crate) mod glob_pattern;
+pub(crate) mod line_editor;
+pub(crate) mod mount_table;
 pub(crate) mod sort_type;
 
 /// A type alias for a `Arc<Mutex<T>>`.
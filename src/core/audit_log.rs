@@ -0,0 +1,61 @@
+use chrono::{SecondsFormat, Utc};
+use serde::Serialize;
+use std::io;
+use std::io::Write;
+
+/// AuditRecord describes an executed command, as recorded in the audit log.
+/// # Fields
+/// * `timestamp` - when the command was executed (RFC 3339, in UTC)
+/// * `command` - the command, as typed
+/// * `succeeded` - whether the command was handled successfully
+/// * `path` - the working directory of the disk the command addressed, as it was before the command ran
+///   (the directory the relative paths of the command are resolved from, even if the command changes it)
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct AuditRecord {
+    pub(crate) timestamp: String,
+    pub(crate) command: String,
+    pub(crate) succeeded: bool,
+    pub(crate) path: String,
+}
+
+impl AuditRecord {
+    pub(crate) fn new(command: &str, succeeded: bool, path: String) -> Self {
+        Self {
+            timestamp: Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true),
+            command: command.trim().to_string(),
+            succeeded,
+            path,
+        }
+    }
+}
+
+/// AuditLog appends the executed commands to a file, one JSON record per line, giving a replayable history
+/// of the session (unlike the application logs, the records are never rotated nor rewritten).
+/// # Fields
+/// * `file_path` - the file the records are appended to (an empty path disables the audit log)
+#[derive(Debug, Clone)]
+pub(crate) struct AuditLog {
+    file_path: String,
+}
+
+impl AuditLog {
+    pub(crate) fn new(file_path: String) -> Self {
+        Self { file_path }
+    }
+
+    /// Appends the given record to the audit log file, creating the file if it doesn't exist.
+    pub(crate) fn append(&self, record: &AuditRecord) -> io::Result<()> {
+        if self.file_path.is_empty() {
+            return Ok(());
+        }
+
+        let mut line = serde_json::to_string(record)?;
+        line.push('\n');
+
+        std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.file_path)?
+            .write_all(line.as_bytes())
+    }
+}
@@ -0,0 +1,181 @@
+use crate::core::Arm;
+use crate::domain::i_disk_manager::IDiskManager;
+use std::time::Duration;
+
+/// Returns the disk managers of the disks to autosave.
+pub(crate) type DiskManagersProvider = fn() -> Vec<Arm<dyn IDiskManager>>;
+
+/// Spawns the thread autosaving the disks: the pushes of a disk are written to its storage file once no command
+/// has pushed to it for the given delay, so that a quick sequence of commands writes its clusters only once.
+///
+/// The thread only holds the lock of a disk while checking and flushing it, so it never interleaves with a command.
+pub(crate) fn spawn(delay: Duration, get_disk_managers: DiskManagersProvider) {
+    // check often enough for a disk to be flushed shortly after the delay has elapsed
+    let period = (delay / 4).max(Duration::from_millis(10));
+
+    std::thread::spawn(move || loop {
+        std::thread::sleep(period);
+
+        for disk_manager in get_disk_managers() {
+            let mut disk_manager = match disk_manager.lock() {
+                Ok(disk_manager) => disk_manager,
+                Err(_) => continue,
+            };
+
+            if disk_manager
+                .get_last_unsaved_push()
+                .is_some_and(|last_unsaved_push| last_unsaved_push.elapsed() >= delay)
+            {
+                disk_manager.flush();
+                log::debug!("Autosaved the disk after {:?} of idleness", delay);
+            }
+        }
+    });
+}
+
+/// Writes the pushes of the disks not yet written by the autosave, e.g. before shutting down.
+pub(crate) fn flush_all(get_disk_managers: DiskManagersProvider) {
+    for disk_manager in get_disk_managers() {
+        if let Ok(mut disk_manager) = disk_manager.lock() {
+            disk_manager.flush();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::commands::create::CreateRequest;
+    use crate::core::content_type::ContentType;
+    use crate::domain::file_entry::FileEntryAttributes;
+    use crate::infrastructure::disk_manager::DiskManager;
+    use crate::infrastructure::test_disk::TestDisk;
+    use chrono::Utc;
+    use std::sync::{Arc, Mutex};
+    use std::time::Instant;
+
+    /// The number of files created by the tests, in a quick sequence of commands.
+    const FILES_COUNT: usize = 8;
+
+    /// The disks autosaved by the thread of `autosave_writes_a_quick_sequence_of_commands_once_idle`.
+    static AUTOSAVED_DISK_MANAGERS: Mutex<Vec<Arm<dyn IDiskManager>>> = Mutex::new(Vec::new());
+
+    /// The disks flushed by `flush_all_writes_the_pending_pushes`.
+    static FLUSHED_DISK_MANAGERS: Mutex<Vec<Arm<dyn IDiskManager>>> = Mutex::new(Vec::new());
+
+    /// Opens a shared disk manager on the storage file of the given test disk, with the autosave enabled.
+    fn open_autosaved(disk: &TestDisk) -> Arm<dyn IDiskManager> {
+        disk.config.lock().unwrap().autosave_delay_ms = 100;
+
+        Arc::new(Mutex::new(
+            DiskManager::open(disk.config.clone()).expect("Unable to open the test disk"),
+        ))
+    }
+
+    /// Creates the files `0.txt`, `1.txt`... as a quick sequence of commands does.
+    fn create_files(disk_manager: &Arm<dyn IDiskManager>) {
+        for index in 0..FILES_COUNT {
+            let mut disk_manager = disk_manager.lock().unwrap();
+            disk_manager.pull_sync();
+            disk_manager
+                .create_file(&CreateRequest::new(
+                    index.to_string(),
+                    "txt".to_string(),
+                    40,
+                    FileEntryAttributes::combine(&[
+                        FileEntryAttributes::File,
+                        FileEntryAttributes::ReadWrite,
+                        FileEntryAttributes::Visible,
+                    ]),
+                    Utc::now(),
+                    ContentType::Alpha,
+                ))
+                .unwrap();
+            disk_manager.push_sync();
+        }
+    }
+
+    /// Returns the names of the files persisted to the storage file of the given test disk.
+    fn persisted_names(disk: &TestDisk) -> Vec<String> {
+        disk.reopen()
+            .walk()
+            .into_iter()
+            .map(|(path, _)| path)
+            .collect()
+    }
+
+    fn expected_names() -> Vec<String> {
+        (0..FILES_COUNT)
+            .map(|index| format!("/{}.txt", index))
+            .collect()
+    }
+
+    #[test]
+    fn autosave_writes_a_quick_sequence_of_commands_once_idle() {
+        let mut synced_disk = TestDisk::new();
+        let (_, clusters_written_before) = synced_disk.get_cluster_io_counts();
+        for index in 0..FILES_COUNT {
+            synced_disk.create(&index.to_string(), "txt", 40).unwrap();
+        }
+        let synced_clusters_written =
+            synced_disk.get_cluster_io_counts().1 - clusters_written_before;
+        let disk = TestDisk::new();
+        let disk_manager = open_autosaved(&disk);
+        AUTOSAVED_DISK_MANAGERS
+            .lock()
+            .unwrap()
+            .push(disk_manager.clone());
+
+        create_files(&disk_manager);
+
+        // none of the commands has written the storage file yet
+        assert_eq!(disk_manager.lock().unwrap().get_cluster_io_counts().1, 0);
+        assert!(persisted_names(&disk).is_empty());
+
+        spawn(Duration::from_millis(100), || {
+            AUTOSAVED_DISK_MANAGERS.lock().unwrap().clone()
+        });
+        let start = Instant::now();
+        while disk_manager
+            .lock()
+            .unwrap()
+            .get_last_unsaved_push()
+            .is_some()
+        {
+            assert!(start.elapsed() < Duration::from_secs(10), "Not autosaved");
+            std::thread::sleep(Duration::from_millis(10));
+        }
+
+        // the clusters modified by several commands have been written once, by a single flush
+        let (_, clusters_written) = disk_manager.lock().unwrap().get_cluster_io_counts();
+        assert!(clusters_written > 0);
+        assert!(
+            clusters_written < synced_clusters_written,
+            "{} clusters written with the autosave, {} without it",
+            clusters_written,
+            synced_clusters_written
+        );
+        assert_eq!(persisted_names(&disk), expected_names());
+    }
+
+    #[test]
+    fn flush_all_writes_the_pending_pushes() {
+        let disk = TestDisk::new();
+        let disk_manager = open_autosaved(&disk);
+        FLUSHED_DISK_MANAGERS
+            .lock()
+            .unwrap()
+            .push(disk_manager.clone());
+        create_files(&disk_manager);
+        assert!(persisted_names(&disk).is_empty());
+
+        flush_all(|| FLUSHED_DISK_MANAGERS.lock().unwrap().clone());
+
+        assert!(disk_manager
+            .lock()
+            .unwrap()
+            .get_last_unsaved_push()
+            .is_none());
+        assert_eq!(persisted_names(&disk), expected_names());
+    }
+}
@@ -0,0 +1,34 @@
+/// The reversed polynomial of the IEEE 802.3 CRC-32 (the one used by zip, png, gzip etc.)
+const CRC32_POLYNOMIAL: u32 = 0xEDB8_8320;
+
+/// Checksum computes integrity checks over raw byte sequences.
+pub(crate) struct Checksum;
+
+impl Checksum {
+    /// Computes the IEEE CRC-32 of the given bytes (bitwise, without a lookup table).
+    pub(crate) fn crc32(bytes: &[u8]) -> u32 {
+        let crc = bytes.iter().fold(0xFFFF_FFFF_u32, |crc, &byte| {
+            (0..8).fold(crc ^ byte as u32, |crc, _| match crc & 1 {
+                1 => (crc >> 1) ^ CRC32_POLYNOMIAL,
+                _ => crc >> 1,
+            })
+        });
+
+        !crc
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn crc32_matches_the_ieee_check_values() {
+        assert_eq!(Checksum::crc32(b""), 0);
+        assert_eq!(Checksum::crc32(b"123456789"), 0xCBF4_3926);
+        assert_eq!(
+            Checksum::crc32(b"The quick brown fox jumps over the lazy dog"),
+            0x414F_A339
+        );
+    }
+}
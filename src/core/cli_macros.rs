@@ -2,17 +2,30 @@
 #[macro_export]
 macro_rules! prompt {
     ($($arg:tt)*) => {
+        let (path, free_space) = {
+            let mut disk_manager = DISK_ARC.lock().unwrap();
+            let path = CONFIG
+                .prompt
+                .render_path(&disk_manager.get_working_directory_full_path());
+            // the free space is only counted (which pulls the disk) if it is shown
+            let free_space = match CONFIG.prompt.show_free_space {
+                true => CONFIG
+                    .prompt
+                    .render_free_space(disk_manager.get_free_space()),
+                false => String::new(),
+            };
+
+            (path, free_space)
+        };
+
         cprint!(
-            "<w!>{}</><b!>{}</><w!>{}</><b!>{}</>{}<b!>{}</> ",
+            "<w!>{}</><b!>{}</><w!>{}</><b!>{}</>{}<k!>{}</><b!>{}</> ",
             CONFIG.prompt.host,
             CONFIG.prompt.separator,
             CONFIG.prompt.user,
             CONFIG.prompt.path_prefix,
-            DISK_ARC
-                .clone()
-                .lock()
-                .unwrap()
-                .get_working_directory_full_path(),
+            path,
+            free_space,
             CONFIG.prompt.terminator
         );
 
@@ -23,7 +36,6 @@ macro_rules! prompt {
                 warn!("Unable to flush stdout, please try again!");
 
                 log::warn!("Unable to flush stdout, please try again! Error: {}", err);
-                continue;
             }
         }
     };
@@ -66,6 +78,7 @@ macro_rules! info {
 }
 
 /// macro `handle!` for invoking the associated regex parser and mediator handler for a given command
+/// (evaluates to `true` if the command was parsed and handled successfully)
 #[macro_export]
 macro_rules! handle {
     ($mediator:tt, $parser_fn:tt, $input:expr) => {
@@ -74,11 +87,15 @@ macro_rules! handle {
                 if let Err(err) = $mediator.send(request).unwrap() {
                     error!("Error: {}", err);
                     log::error!("mediator_level: {}", err);
+                    false
+                } else {
+                    true
                 }
             }
             Err(err) => {
                 warn!("Warning: {}", err);
                 log::warn!("parser_level: {}", err);
+                false
             }
         }
     };
@@ -89,14 +106,17 @@ macro_rules! handle {
                 if let Err(err) = $mediator.send(request).unwrap() {
                     error!("Error: {}", err);
                     log::error!("mediator_level: {}", err);
+                    false
                 } else {
                     success!("{}", $success);
                     log::info!("{}", $success);
+                    true
                 }
             }
             Err(err) => {
                 warn!("Warning: {}", err);
                 log::warn!("parser_level: {}", err);
+                false
             }
         }
     };
@@ -107,26 +127,33 @@ macro_rules! handle {
                 if let Err(err) = $mediator.send(request).unwrap() {
                     error!("Error: {}", err);
                     log::error!("mediator_level: {}", err);
+                    false
                 } else {
                     success!("{}", $success);
                     log::info!("{}", $success);
 
                     $call_back_fn($($arg)*);
+                    true
                 }
             }
             Err(err) => {
                 warn!("Warning: {}", err);
                 log::warn!("parser_level: {}", err);
+                false
             }
         }
     };
 
     ($parser_fn:tt, $input:expr, $call_back_fn:tt $(, $arg:tt)*) => {
         match CliParser::$parser_fn($input) {
-            Ok(_) => $call_back_fn($($arg)*),
+            Ok(_) => {
+                $call_back_fn($($arg)*);
+                true
+            }
             Err(err) => {
                 warn!("Warning: {}", err);
                 log::warn!("parser_level: {}", err);
+                false
             }
         }
     };
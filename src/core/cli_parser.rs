@@ -1,46 +1,161 @@
+use crate::application::commands::badblocks::BadBlocksRequest;
 use crate::application::commands::cd::ChangeDirectoryRequest;
 use crate::application::commands::cp::CopyRequest;
 use crate::application::commands::create::CreateRequest;
 use crate::application::commands::defrag::DefragmentRequest;
 use crate::application::commands::del::DeleteRequest;
+use crate::application::commands::echo::EchoRequest;
 use crate::application::commands::fmt::FormatRequest;
+use crate::application::commands::import::ImportRequest;
+use crate::application::commands::label::LabelRequest;
+use crate::application::commands::ln::LinkRequest;
 use crate::application::commands::mkdir::MakeDirectoryRequest;
+use crate::application::commands::mount::MountRequest;
+use crate::application::commands::redo::RedoRequest;
 use crate::application::commands::rename::RenameRequest;
+use crate::application::commands::resize::ResizeRequest;
+use crate::application::commands::rmdir::RemoveDirectoryRequest;
 use crate::application::commands::setattr::SetAttributesRequest;
+use crate::application::commands::trash::{TrashAction, TrashRequest};
+use crate::application::commands::truncate::TruncateRequest;
+use crate::application::commands::umount::UnmountRequest;
+use crate::application::commands::undo::UndoRequest;
+use crate::application::commands::writecluster::WriteClusterRequest;
+use crate::application::queries::bootinfo::BootInfoRequest;
 use crate::application::queries::cat::CatRequest;
+use crate::application::queries::chkdsk::CheckDiskRequest;
+use crate::application::queries::cksum::CksumRequest;
+use crate::application::queries::clear::ClearRequest;
+use crate::application::queries::df::DfRequest;
+use crate::application::queries::diff::DiffRequest;
+use crate::application::queries::dumpfat::DumpFatRequest;
+use crate::application::queries::export::ExportRequest;
+use crate::application::queries::fraginfo::FragInfoRequest;
+use crate::application::queries::grep::GrepRequest;
+use crate::application::queries::head::HeadRequest;
 use crate::application::queries::help::HelpRequest;
+use crate::application::queries::hexdump::HexdumpRequest;
 use crate::application::queries::ls::ListRequest;
 use crate::application::queries::neofetch::NeofetchRequest;
 use crate::application::queries::pwd::PwdRequest;
+use crate::application::queries::readcluster::ReadClusterRequest;
+use crate::application::queries::stat::StatRequest;
+use crate::application::queries::tail::TailRequest;
+use crate::application::queries::version::VersionRequest;
+use crate::application::queries::wc::{WcMetric, WcRequest};
 use crate::application::Void;
 use crate::core::content_type::ContentType;
 use crate::core::filter_type::FilterType;
 use crate::core::sort_type::SortType;
 use crate::domain::file_entry::FileEntryAttributes;
 use crate::{info, CONFIG};
-use chrono::Utc;
+use chrono::{DateTime, NaiveDate, NaiveDateTime, TimeZone, Utc};
 use color_print::cprintln;
 use std::error::Error;
+use std::fmt::Display;
+use std::ops::Range;
 
 /// CliParser is used to parse the input from the user based on the regex patterns defined in the
 /// config file.
 pub(crate) struct CliParser;
 
 impl CliParser {
+    /// Validates a name or an extension of a new file entry: path separators, dots and control
+    /// characters would break the path resolution and the 8.3 model, so they are rejected.
+    fn validate_entry_name_part(value: &str, label: &str, max_length: usize) -> Void {
+        if value.contains(['/', '\\']) {
+            return Err(Box::try_from(format!(
+                "{} must not contain path separators ('/' or '\\')!",
+                label
+            ))
+            .unwrap());
+        }
+
+        if value.contains('.') {
+            return Err(Box::try_from(format!("{} must not contain dots!", label)).unwrap());
+        }
+
+        if value.chars().any(char::is_control) {
+            return Err(
+                Box::try_from(format!("{} must not contain control characters!", label)).unwrap(),
+            );
+        }
+
+        if value.len() > max_length {
+            return Err(Box::try_from(format!(
+                "{} must be {} characters or less!",
+                label, max_length
+            ))
+            .unwrap());
+        }
+
+        Ok(())
+    }
+
+    /// Builds the error of an invalid part of the input: the message followed by the input with carets
+    /// under the part at fault, e.g.
+    /// ```text
+    /// Invalid content type foo, expected one of alpha, num, hex, pattern=<pattern>, random, zero or stdin!
+    ///     create a.txt 10 -foo
+    ///                      ^^^
+    /// ```
+    fn diagnostic(input: &str, part: Range<usize>, message: impl Display) -> Box<dyn Error> {
+        let offset = input[..part.start].chars().count();
+        let width = input[part].chars().count().max(1);
+
+        Box::try_from(format!(
+            "{}\n    {}\n    {}{}",
+            message,
+            input.trim_end(),
+            " ".repeat(offset),
+            "^".repeat(width)
+        ))
+        .unwrap()
+    }
+
+    /// Parses a size in bytes given to the option with the given label.
+    fn parse_size(value: &str, label: &str) -> Result<u32, Box<dyn Error>> {
+        value.parse::<u32>().map_err(|_| {
+            Box::try_from(format!("{} must be at most {} bytes!", label, u32::MAX)).unwrap()
+        })
+    }
+
+    /// Parses a datetime given to the option with the given label, either as a date (the start of the day),
+    /// a date and a time or an RFC 3339 datetime (the first two being in UTC).
+    fn parse_datetime(value: &str, label: &str) -> Result<DateTime<Utc>, Box<dyn Error>> {
+        if let Ok(date) = NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+            return Ok(Utc.from_utc_datetime(&date.and_hms_opt(0, 0, 0).unwrap()));
+        }
+
+        if let Ok(datetime) = NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S") {
+            return Ok(Utc.from_utc_datetime(&datetime));
+        }
+
+        match DateTime::parse_from_rfc3339(value) {
+            Ok(datetime) => Ok(datetime.with_timezone(&Utc)),
+            Err(_) => Err(Box::try_from(format!(
+                "Invalid {} date `{}` (expected YYYY-MM-DD, YYYY-MM-DDTHH:MM:SS or RFC 3339)!",
+                label, value
+            ))
+            .unwrap()),
+        }
+    }
+
     pub(crate) fn parse_help(input: &str) -> Result<HelpRequest, Box<dyn Error>> {
         log::info!("Parsing help command...");
 
         let regex = regex::Regex::new(CONFIG.commands.get("help").unwrap().regex.as_str()).unwrap();
-        let captures = regex.captures(input).unwrap();
+        let captures = regex.captures(input);
         let usage = CONFIG.commands.get("help").unwrap().usage.as_str();
 
-        if regex.is_match(input) {
+        if let Some(captures) = captures {
             log::info!("Help command parsed successfully!");
 
             let command = captures.name("command");
             let command = command.map(|command| command.as_str().to_string());
+            let all = captures.name("all").is_some();
 
-            Ok(HelpRequest::new(command))
+            Ok(HelpRequest::new(command, all))
         } else {
             info!("Usage: {}", usage);
             Err(Box::try_from("Invalid help command syntax!").unwrap())
@@ -62,6 +177,25 @@ impl CliParser {
         }
     }
 
+    /// Returns the command line timed by a time command.
+    pub(crate) fn parse_time(input: &str) -> Result<String, Box<dyn Error>> {
+        log::info!("Parsing time command...");
+
+        let regex = regex::Regex::new(CONFIG.commands.get("time").unwrap().regex.as_str()).unwrap();
+        let captures = regex.captures(input);
+        let usage = CONFIG.commands.get("time").unwrap().usage.as_str();
+
+        if let Some(captures) = captures {
+            let command = captures.name("command").unwrap().as_str();
+
+            log::info!("Time command parsed successfully: {}", input);
+            Ok(command.to_string())
+        } else {
+            info!("Usage: {}", usage);
+            Err(Box::try_from("Invalid time command syntax!").unwrap())
+        }
+    }
+
     pub(crate) fn parse_neofetch(input: &str) -> Result<NeofetchRequest, Box<dyn Error>> {
         log::info!("Parsing neofetch command...");
 
@@ -87,36 +221,34 @@ impl CliParser {
         let usage = CONFIG.commands.get("create").unwrap().usage.as_str();
 
         if let Some(captures) = captures {
-            let name = captures.name("name").unwrap().as_str();
-            let extension = captures.name("extension").unwrap().as_str();
-            let dim = captures
-                .name("dim")
-                .unwrap()
-                .as_str()
-                .parse::<u32>()
-                .unwrap();
-            let content_type = captures
-                .name("type")
-                .unwrap()
+            // validate each field on its own to point at the one at fault
+            let name = captures.name("name").unwrap();
+            Self::validate_entry_name_part(name.as_str(), "Name", 32)
+                .map_err(|err| Self::diagnostic(input, name.range(), err))?;
+            let name = name.as_str();
+
+            let extension = captures.name("extension").unwrap();
+            Self::validate_entry_name_part(extension.as_str(), "Extension", 3)
+                .map_err(|err| Self::diagnostic(input, extension.range(), err))?;
+            let extension = extension.as_str();
+
+            let dim = captures.name("dim").unwrap();
+            let dim = match dim.as_str().parse::<u32>() {
+                Ok(value) if value <= 10000 => value,
+                _ => {
+                    return Err(Self::diagnostic(
+                        input,
+                        dim.range(),
+                        "Dimension must be 10000 or less!",
+                    ))
+                }
+            };
+
+            let content_type = captures.name("type").unwrap();
+            let content_type = content_type
                 .as_str()
                 .parse::<ContentType>()
-                .unwrap();
-
-            if name.len() > 8 {
-                return Err(Box::try_from("Name must be 8 characters or less!").unwrap());
-            }
-
-            if extension.len() > 3 {
-                return Err(Box::try_from("Extension must be 3 characters or less!").unwrap());
-            }
-
-            if dim > 10000 {
-                return Err(Box::try_from("Dimension must be 10000 or less!").unwrap());
-            }
-
-            if content_type == ContentType::Unknown {
-                return Err(Box::try_from("Invalid content type!").unwrap());
-            }
+                .map_err(|err| Self::diagnostic(input, content_type.range(), err))?;
 
             log::info!("Create command parsed successfully: {}", input);
             Ok(CreateRequest::new(
@@ -127,6 +259,7 @@ impl CliParser {
                     FileEntryAttributes::File,
                     FileEntryAttributes::ReadWrite,
                     FileEntryAttributes::Visible,
+                    FileEntryAttributes::Archive,
                 ]),
                 Utc::now(),
                 content_type,
@@ -145,13 +278,27 @@ impl CliParser {
         let usage = CONFIG.commands.get("ls").unwrap().usage.as_str();
 
         if let Some(captures) = captures {
+            let filter_recursive = captures.name("filter_recursive");
             let filter_basic = captures.name("filter_basic");
+            let filter_attributes = captures.name("filter_attributes");
             let filter_name = captures.name("filter_name");
             let filter_extension = captures.name("filter_extension");
+            let filter_min_size = captures.name("filter_min_size");
+            let filter_max_size = captures.name("filter_max_size");
+            let filter_after = captures.name("filter_after");
+            let filter_before = captures.name("filter_before");
             let sort = captures.name("sort");
+            let filter_glob = captures.name("filter_glob");
+            let filter_long = captures.name("filter_long");
+            let filter_json = captures.name("filter_json");
 
             let mut filters: Vec<FilterType> = Vec::new();
 
+            // parse the recursive flag if present
+            if filter_recursive.is_some() {
+                filters.push(FilterType::Recursive);
+            }
+
             // parse the basic filters if any (all from FilterType excepting Name and Extension)
             // from string and extract a vector of those
             if let Some(filter_basic) = filter_basic {
@@ -165,6 +312,17 @@ impl CliParser {
                 );
             }
 
+            // parse the attribute filters if any, each of them only keeping the entries having its attribute
+            if let Some(filter_attributes) = filter_attributes {
+                filters.extend(filter_attributes.as_str().split_whitespace().map(
+                    |filter_attribute| match filter_attribute {
+                        "-ro" => FilterType::ReadOnly,
+                        "-hidden-only" => FilterType::Hidden,
+                        _ => FilterType::System,
+                    },
+                ));
+            }
+
             // parse the file name filter and add it to the vector if present
             if let Some(filter_name) = filter_name {
                 let filter_name = filter_name.as_str();
@@ -177,6 +335,42 @@ impl CliParser {
                 filters.push(FilterType::Extension(filter_extension.to_string()));
             }
 
+            // parse the size range bounds and add them to the vector if present
+            if let Some(filter_min_size) = filter_min_size {
+                let min_size = Self::parse_size(filter_min_size.as_str(), "-minsize")?;
+                filters.push(FilterType::MinSize(min_size));
+            }
+            if let Some(filter_max_size) = filter_max_size {
+                let max_size = Self::parse_size(filter_max_size.as_str(), "-maxsize")?;
+                filters.push(FilterType::MaxSize(max_size));
+            }
+
+            // parse the date range bounds and add them to the vector if present
+            if let Some(filter_after) = filter_after {
+                let after = Self::parse_datetime(filter_after.as_str(), "-after")?;
+                filters.push(FilterType::After(after));
+            }
+            if let Some(filter_before) = filter_before {
+                let before = Self::parse_datetime(filter_before.as_str(), "-before")?;
+                filters.push(FilterType::Before(before));
+            }
+
+            // parse the wildcard pattern and add it to the vector if present
+            if let Some(filter_glob) = filter_glob {
+                let filter_glob = filter_glob.as_str();
+                filters.push(FilterType::Glob(filter_glob.to_string()));
+            }
+
+            // parse the long format flag if present (same as the l basic filter)
+            if filter_long.is_some() {
+                filters.push(FilterType::InLongFormat);
+            }
+
+            // parse the json flag if present
+            if filter_json.is_some() {
+                filters.push(FilterType::Json);
+            }
+
             let mut sort_option: Option<SortType> = None;
 
             // parse the sort option if present
@@ -201,6 +395,16 @@ impl CliParser {
         let captures = regex.captures(input);
         let usage = CONFIG.commands.get("rename").unwrap().usage.as_str();
 
+        // rename doesn't move entries between directories, so reject paths explicitly
+        // instead of reporting a bare syntax error (or taking the path as part of an extension)
+        if input.contains(['/', '\\']) {
+            info!("Usage: {}", usage);
+            return Err(Box::try_from(
+                "Rename only changes the name within the current directory, paths are not supported (copy the entry with cp and delete the original to move it)",
+            )
+            .unwrap());
+        }
+
         if let Some(captures) = captures {
             let old_name = captures.name("old_name").unwrap().as_str();
             let old_extension = captures.name("old_extension");
@@ -213,9 +417,9 @@ impl CliParser {
                     let old_extension = old_extension.as_str();
                     let new_extension = new_extension.as_str();
 
-                    if old_name.len() > 8 {
+                    if old_name.len() > 32 {
                         return Err(
-                            Box::try_from("Old name must be 8 characters or less!").unwrap()
+                            Box::try_from("Old name must be 32 characters or less!").unwrap()
                         );
                     }
 
@@ -225,9 +429,9 @@ impl CliParser {
                         );
                     }
 
-                    if new_name.len() > 8 {
+                    if new_name.len() > 32 {
                         return Err(
-                            Box::try_from("New name must be 8 characters or less!").unwrap()
+                            Box::try_from("New name must be 32 characters or less!").unwrap()
                         );
                     }
 
@@ -247,15 +451,15 @@ impl CliParser {
                 }
                 // when none of the extensions are present, rename a folder
                 (None, None) => {
-                    if old_name.len() > 8 {
+                    if old_name.len() > 32 {
                         return Err(
-                            Box::try_from("Old name must be 8 characters or less!").unwrap()
+                            Box::try_from("Old name must be 32 characters or less!").unwrap()
                         );
                     }
 
-                    if new_name.len() > 8 {
+                    if new_name.len() > 32 {
                         return Err(
-                            Box::try_from("New name must be 8 characters or less!").unwrap()
+                            Box::try_from("New name must be 32 characters or less!").unwrap()
                         );
                     }
 
@@ -290,16 +494,26 @@ impl CliParser {
                 None => "",
             };
 
-            if name.len() > 8 {
-                return Err(Box::try_from("Name must be 8 characters or less!").unwrap());
-            }
+            Self::validate_entry_name_part(name, "Name", 32)?;
+            Self::validate_entry_name_part(extension, "Extension", 3)?;
 
-            if extension.len() > 3 {
-                return Err(Box::try_from("Extension must be 3 characters or less!").unwrap());
-            }
+            let options: Vec<&str> = captures
+                .name("options")
+                .map_or("", |options| options.as_str())
+                .split_whitespace()
+                .collect();
+            let skip_confirmation = options.contains(&"-y") || options.contains(&"--yes");
+            let dry_run = options.contains(&"-n") || options.contains(&"--dry-run");
+            let recursive = options.contains(&"-r") || options.contains(&"--recursive");
 
             log::info!("Del command parsed successfully: {}", input);
-            Ok(DeleteRequest::new(name.to_string(), extension.to_string()))
+            Ok(DeleteRequest::new(
+                name.to_string(),
+                extension.to_string(),
+                skip_confirmation,
+                dry_run,
+                recursive,
+            ))
         } else {
             info!("Usage: {}", usage);
             Err(Box::try_from("Invalid delete command syntax!").unwrap())
@@ -314,19 +528,22 @@ impl CliParser {
         let usage = CONFIG.commands.get("cat").unwrap().usage.as_str();
 
         if let Some(captures) = captures {
-            let name = captures.name("name").unwrap().as_str();
-            let extension = captures.name("extension").unwrap().as_str();
+            // every file is split at its last dot into its name and its extension
+            let mut files = Vec::new();
+            for file in captures.name("files").unwrap().as_str().split_whitespace() {
+                let (name, extension) = file.rsplit_once('.').unwrap();
 
-            if name.len() > 8 {
-                return Err(Box::try_from("Name must be 8 characters or less!").unwrap());
-            }
+                Self::validate_entry_name_part(name, "Name", 32)?;
+                Self::validate_entry_name_part(extension, "Extension", 3)?;
 
-            if extension.len() > 3 {
-                return Err(Box::try_from("Extension must be 3 characters or less!").unwrap());
+                files.push((name.to_string(), extension.to_string()));
             }
+            let plain = captures.name("plain").is_some();
+
+            let (name, extension) = files.remove(0);
 
             log::info!("Cat command parsed successfully: {}", input);
-            Ok(CatRequest::new(name.to_string(), extension.to_string()))
+            Ok(CatRequest::new(name, extension).with_other_files(files, plain))
         } else {
             info!("Usage: {}", usage);
             Err(Box::try_from("Invalid cat command syntax!").unwrap())
@@ -345,6 +562,25 @@ impl CliParser {
             let src_extension = captures.name("src_extension");
             let dest_name = captures.name("dest_name").unwrap().as_str();
             let dest_extension = captures.name("dest_extension");
+            // the dest directory path is given without its trailing separator, unless it is the root
+            let dest_directory = captures.name("dest_directory").map(|dest_directory| {
+                match dest_directory.as_str().trim_end_matches('/') {
+                    "" => "/".to_string(),
+                    dest_directory => dest_directory.to_string(),
+                }
+            });
+            let options: Vec<&str> = captures
+                .name("options")
+                .map(|options| options.as_str().split_whitespace().collect())
+                .unwrap_or_default();
+            let force = options.contains(&"-f");
+            let verify = options.contains(&"--verify");
+            let src_label = captures
+                .name("src_label")
+                .map(|src_label| src_label.as_str().to_string());
+            let dest_label = captures
+                .name("dest_label")
+                .map(|dest_label| dest_label.as_str().to_string());
 
             match (src_extension, dest_extension) {
                 // when both extensions are present, copy a file
@@ -352,9 +588,9 @@ impl CliParser {
                     let src_extension = src_extension.as_str();
                     let dest_extension = dest_extension.as_str();
 
-                    if src_name.len() > 8 {
+                    if src_name.len() > 32 {
                         return Err(
-                            Box::try_from("Source name must be 8 characters or less!").unwrap()
+                            Box::try_from("Source name must be 32 characters or less!").unwrap()
                         );
                     }
 
@@ -365,9 +601,9 @@ impl CliParser {
                         .unwrap());
                     }
 
-                    if dest_name.len() > 8 {
+                    if dest_name.len() > 32 {
                         return Err(Box::try_from(
-                            "Destination name must be 8 characters or less!",
+                            "Destination name must be 32 characters or less!",
                         )
                         .unwrap());
                     }
@@ -385,19 +621,23 @@ impl CliParser {
                         src_extension.to_string(),
                         dest_name.to_string(),
                         dest_extension.to_string(),
-                    ))
+                        dest_directory,
+                        force,
+                    )
+                    .with_labels(src_label, dest_label)
+                    .with_verification(verify))
                 }
                 // when both extensions are missing, copy a directory
                 (None, None) => {
-                    if src_name.len() > 8 {
+                    if src_name.len() > 32 {
                         return Err(
-                            Box::try_from("Source name must be 8 characters or less!").unwrap()
+                            Box::try_from("Source name must be 32 characters or less!").unwrap()
                         );
                     }
 
-                    if dest_name.len() > 8 {
+                    if dest_name.len() > 32 {
                         return Err(Box::try_from(
-                            "Destination name must be 8 characters or less!",
+                            "Destination name must be 32 characters or less!",
                         )
                         .unwrap());
                     }
@@ -408,7 +648,11 @@ impl CliParser {
                         "".to_string(),
                         dest_name.to_string(),
                         "".to_string(),
-                    ))
+                        dest_directory,
+                        force,
+                    )
+                    .with_labels(src_label, dest_label)
+                    .with_verification(verify))
                 }
                 // otherwise, the command is invalid
                 _ => {
@@ -435,9 +679,20 @@ impl CliParser {
         if let Some(captures) = captures {
             let fat_type = captures.name("fat_type").unwrap().as_str();
             let fat_type = fat_type.parse::<u16>()?;
+            let cluster_size = captures
+                .name("cluster_size")
+                .map(|cluster_size| cluster_size.as_str().parse::<u16>())
+                .transpose()?;
+            let skip_confirmation = captures.name("yes").is_some();
+            let dry_run = captures.name("dry_run").is_some();
 
             log::info!("Format command parsed successfully: {}", input);
-            Ok(FormatRequest::new(fat_type))
+            Ok(FormatRequest::new(
+                fat_type,
+                cluster_size,
+                skip_confirmation,
+                dry_run,
+            ))
         } else {
             info!("Usage: {}", usage);
             Err(Box::try_from("Invalid format command syntax!").unwrap())
@@ -471,7 +726,7 @@ impl CliParser {
         if let Some(captures) = captures {
             let name = captures.name("name").unwrap().as_str();
             let extension = captures.name("extension");
-            let attributes = captures.name("attributes").unwrap().as_str();
+            let attributes_match = captures.name("attributes").unwrap();
 
             let extension = match extension {
                 Some(extension) => extension.as_str(),
@@ -479,17 +734,33 @@ impl CliParser {
             };
 
             // split attributes into chunks of 2 characters
-            // then parse each chunk into a FileEntryAttributes
-            // and collect them into a vector
-            let attributes = attributes
+            // then parse each chunk into a FileEntryAttributes,
+            // pointing at the first chunk which isn't a valid attribute
+            let mut attributes = Vec::new();
+            let mut chunk_start = attributes_match.start();
+            for chunk in attributes_match
+                .as_str()
                 .chars()
                 .collect::<Vec<_>>()
                 .chunks(2)
-                .map(|chunk| chunk.iter().collect::<String>())
-                .collect::<Vec<_>>()
-                .iter()
-                .map(|attr_str| attr_str.parse::<FileEntryAttributes>().unwrap())
-                .collect::<Vec<_>>();
+            {
+                let chunk = chunk.iter().collect::<String>();
+                let chunk_range = chunk_start..chunk_start + chunk.len();
+                attributes.push(
+                    chunk
+                        .parse::<FileEntryAttributes>()
+                        .map_err(|err| Self::diagnostic(input, chunk_range.clone(), err))?,
+                );
+                chunk_start = chunk_range.end;
+            }
+
+            if attributes.len() > 4 {
+                return Err(Self::diagnostic(
+                    input,
+                    attributes_match.range(),
+                    "At most 4 attributes (w, h, s and a) can be set at once!",
+                ));
+            }
 
             Ok(SetAttributesRequest::new(
                 name.to_string(),
@@ -511,9 +782,8 @@ impl CliParser {
         if let Some(captures) = captures {
             let name = captures.name("name").unwrap().as_str();
 
-            if name.len() > 8 {
-                return Err(Box::try_from("Name must be 8 characters or less!").unwrap());
-            }
+            // nested paths such as `a/b` are rejected instead of creating the intermediate directories
+            Self::validate_entry_name_part(name, "Name", 32)?;
 
             log::info!("Mkdir command parsed successfully: {}", input);
             Ok(MakeDirectoryRequest::new(
@@ -539,8 +809,13 @@ impl CliParser {
         if let Some(captures) = captures {
             let name = captures.name("name").unwrap().as_str();
 
-            if name.len() > 8 {
-                return Err(Box::try_from("Name must be 8 characters or less!").unwrap());
+            // validate each segment of the path individually
+            if let Some(segment) = name.split('/').find(|segment| segment.len() > 32) {
+                return Err(Box::try_from(format!(
+                    "Name {} must be 32 characters or less!",
+                    segment
+                ))
+                .unwrap());
             }
 
             log::info!("Cd command parsed successfully: {}", input);
@@ -564,7 +839,7 @@ impl CliParser {
         }
     }
 
-    pub(crate) fn parse_rmdir(input: &str) -> Result<DeleteRequest, Box<dyn Error>> {
+    pub(crate) fn parse_rmdir(input: &str) -> Result<RemoveDirectoryRequest, Box<dyn Error>> {
         let regex =
             regex::Regex::new(CONFIG.commands.get("rmdir").unwrap().regex.as_str()).unwrap();
         let captures = regex.captures(input);
@@ -573,15 +848,884 @@ impl CliParser {
         if let Some(captures) = captures {
             let name = captures.name("name").unwrap().as_str();
 
-            if name.len() > 8 {
-                return Err(Box::try_from("Name must be 8 characters or less!").unwrap());
+            if name.len() > 32 {
+                return Err(Box::try_from("Name must be 32 characters or less!").unwrap());
             }
 
             log::info!("Rmdir command parsed successfully: {}", input);
-            Ok(DeleteRequest::new(name.to_string(), "".to_string()))
+            Ok(RemoveDirectoryRequest::new(name.to_string()))
         } else {
             info!("Usage: {}", usage);
             Err(Box::try_from("Invalid rmdir command syntax!").unwrap())
         }
     }
+
+    pub(crate) fn parse_echo(input: &str) -> Result<EchoRequest, Box<dyn Error>> {
+        let regex = regex::Regex::new(CONFIG.commands.get("echo").unwrap().regex.as_str()).unwrap();
+        let captures = regex.captures(input);
+        let usage = CONFIG.commands.get("echo").unwrap().usage.as_str();
+
+        if let Some(captures) = captures {
+            let text = captures.name("text").unwrap().as_str();
+            let name = captures.name("name").unwrap().as_str();
+            let extension = captures.name("extension").unwrap().as_str();
+            let append = captures.name("redirect").unwrap().as_str() == ">>";
+
+            Self::validate_entry_name_part(name, "Name", 32)?;
+            Self::validate_entry_name_part(extension, "Extension", 3)?;
+
+            if text.is_empty() {
+                return Err(Box::try_from("Text must not be empty!").unwrap());
+            }
+
+            if text.len() > 10000 {
+                return Err(Box::try_from("Text must be 10000 bytes or less!").unwrap());
+            }
+
+            log::info!("Echo command parsed successfully: {}", input);
+            Ok(EchoRequest::new(
+                name.to_string(),
+                extension.to_string(),
+                text.to_string(),
+                append,
+            ))
+        } else {
+            info!("Usage: {}", usage);
+            Err(Box::try_from("Invalid echo command syntax!").unwrap())
+        }
+    }
+
+    pub(crate) fn parse_truncate(input: &str) -> Result<TruncateRequest, Box<dyn Error>> {
+        let regex =
+            regex::Regex::new(CONFIG.commands.get("truncate").unwrap().regex.as_str()).unwrap();
+        let captures = regex.captures(input);
+        let usage = CONFIG.commands.get("truncate").unwrap().usage.as_str();
+
+        if let Some(captures) = captures {
+            let name = captures.name("name").unwrap().as_str();
+            let extension = captures.name("extension").unwrap().as_str();
+            let size = captures.name("size").unwrap().as_str().parse::<u32>()?;
+
+            Self::validate_entry_name_part(name, "Name", 32)?;
+            Self::validate_entry_name_part(extension, "Extension", 3)?;
+
+            if size > 10000 {
+                return Err(Box::try_from("Size must be 10000 or less!").unwrap());
+            }
+
+            log::info!("Truncate command parsed successfully: {}", input);
+            Ok(TruncateRequest::new(
+                name.to_string(),
+                extension.to_string(),
+                size,
+            ))
+        } else {
+            info!("Usage: {}", usage);
+            Err(Box::try_from("Invalid truncate command syntax!").unwrap())
+        }
+    }
+
+    pub(crate) fn parse_stat(input: &str) -> Result<StatRequest, Box<dyn Error>> {
+        let regex = regex::Regex::new(CONFIG.commands.get("stat").unwrap().regex.as_str()).unwrap();
+        let captures = regex.captures(input);
+        let usage = CONFIG.commands.get("stat").unwrap().usage.as_str();
+
+        if let Some(captures) = captures {
+            let name = captures.name("name").unwrap().as_str();
+            let extension = match captures.name("extension") {
+                Some(extension) => extension.as_str(),
+                None => "",
+            };
+
+            Self::validate_entry_name_part(name, "Name", 32)?;
+            Self::validate_entry_name_part(extension, "Extension", 3)?;
+
+            log::info!("Stat command parsed successfully: {}", input);
+            Ok(StatRequest::new(name.to_string(), extension.to_string()))
+        } else {
+            info!("Usage: {}", usage);
+            Err(Box::try_from("Invalid stat command syntax!").unwrap())
+        }
+    }
+
+    pub(crate) fn parse_chkdsk(input: &str) -> Result<CheckDiskRequest, Box<dyn Error>> {
+        let regex =
+            regex::Regex::new(CONFIG.commands.get("chkdsk").unwrap().regex.as_str()).unwrap();
+        let usage = CONFIG.commands.get("chkdsk").unwrap().usage.as_str();
+
+        if regex.is_match(input) {
+            log::info!("Chkdsk command parsed successfully: {}", input);
+            Ok(CheckDiskRequest::new())
+        } else {
+            info!("Usage: {}", usage);
+            Err(Box::try_from("Invalid chkdsk command syntax!").unwrap())
+        }
+    }
+
+    pub(crate) fn parse_df(input: &str) -> Result<DfRequest, Box<dyn Error>> {
+        let regex = regex::Regex::new(CONFIG.commands.get("df").unwrap().regex.as_str()).unwrap();
+        let usage = CONFIG.commands.get("df").unwrap().usage.as_str();
+
+        if regex.is_match(input) {
+            log::info!("Df command parsed successfully: {}", input);
+            Ok(DfRequest::new())
+        } else {
+            info!("Usage: {}", usage);
+            Err(Box::try_from("Invalid df command syntax!").unwrap())
+        }
+    }
+
+    pub(crate) fn parse_bootinfo(input: &str) -> Result<BootInfoRequest, Box<dyn Error>> {
+        let regex =
+            regex::Regex::new(CONFIG.commands.get("bootinfo").unwrap().regex.as_str()).unwrap();
+        let usage = CONFIG.commands.get("bootinfo").unwrap().usage.as_str();
+
+        if regex.is_match(input) {
+            log::info!("Bootinfo command parsed successfully: {}", input);
+            Ok(BootInfoRequest::new())
+        } else {
+            info!("Usage: {}", usage);
+            Err(Box::try_from("Invalid bootinfo command syntax!").unwrap())
+        }
+    }
+
+    pub(crate) fn parse_dumpfat(input: &str) -> Result<DumpFatRequest, Box<dyn Error>> {
+        let regex =
+            regex::Regex::new(CONFIG.commands.get("dumpfat").unwrap().regex.as_str()).unwrap();
+        let captures = regex.captures(input);
+        let usage = CONFIG.commands.get("dumpfat").unwrap().usage.as_str();
+
+        if let Some(captures) = captures {
+            let start = match captures.name("start") {
+                Some(start) => Some(start.as_str().parse::<usize>()?),
+                None => None,
+            };
+            let count = match captures.name("count") {
+                Some(count) => Some(count.as_str().parse::<usize>()?),
+                None => None,
+            };
+
+            log::info!("Dumpfat command parsed successfully: {}", input);
+            Ok(DumpFatRequest::new(start, count))
+        } else {
+            info!("Usage: {}", usage);
+            Err(Box::try_from("Invalid dumpfat command syntax!").unwrap())
+        }
+    }
+
+    pub(crate) fn parse_export(input: &str) -> Result<ExportRequest, Box<dyn Error>> {
+        let regex =
+            regex::Regex::new(CONFIG.commands.get("export").unwrap().regex.as_str()).unwrap();
+        let captures = regex.captures(input);
+        let usage = CONFIG.commands.get("export").unwrap().usage.as_str();
+
+        if let Some(captures) = captures {
+            let name = captures.name("name").unwrap().as_str();
+            let extension = match captures.name("extension") {
+                Some(extension) => extension.as_str(),
+                None => "",
+            };
+            let host_path = captures.name("host_path").unwrap().as_str();
+
+            Self::validate_entry_name_part(name, "Name", 32)?;
+            Self::validate_entry_name_part(extension, "Extension", 3)?;
+
+            log::info!("Export command parsed successfully: {}", input);
+            Ok(ExportRequest::new(
+                name.to_string(),
+                extension.to_string(),
+                host_path.to_string(),
+            ))
+        } else {
+            info!("Usage: {}", usage);
+            Err(Box::try_from("Invalid export command syntax!").unwrap())
+        }
+    }
+
+    pub(crate) fn parse_import(input: &str) -> Result<ImportRequest, Box<dyn Error>> {
+        let regex =
+            regex::Regex::new(CONFIG.commands.get("import").unwrap().regex.as_str()).unwrap();
+        let captures = regex.captures(input);
+        let usage = CONFIG.commands.get("import").unwrap().usage.as_str();
+
+        if let Some(captures) = captures {
+            let host_path = captures.name("host_path").unwrap().as_str();
+            let name = captures.name("name").map(|name| name.as_str().to_string());
+            let extension = captures
+                .name("extension")
+                .map(|extension| extension.as_str().to_string());
+
+            if name.as_ref().is_some_and(|name| name.len() > 32) {
+                return Err(Box::try_from("Name must be 32 characters or less!").unwrap());
+            }
+
+            if extension
+                .as_ref()
+                .is_some_and(|extension| extension.len() > 3)
+            {
+                return Err(Box::try_from("Extension must be 3 characters or less!").unwrap());
+            }
+
+            log::info!("Import command parsed successfully: {}", input);
+            Ok(ImportRequest::new(host_path.to_string(), name, extension))
+        } else {
+            info!("Usage: {}", usage);
+            Err(Box::try_from("Invalid import command syntax!").unwrap())
+        }
+    }
+
+    pub(crate) fn parse_hexdump(input: &str) -> Result<HexdumpRequest, Box<dyn Error>> {
+        let regex =
+            regex::Regex::new(CONFIG.commands.get("hexdump").unwrap().regex.as_str()).unwrap();
+        let captures = regex.captures(input);
+        let usage = CONFIG.commands.get("hexdump").unwrap().usage.as_str();
+
+        if let Some(captures) = captures {
+            let name = captures.name("name").unwrap().as_str();
+            let extension = captures.name("extension").unwrap().as_str();
+            let offset = match captures.name("offset") {
+                Some(offset) => Some(offset.as_str().parse::<usize>()?),
+                None => None,
+            };
+            let length = match captures.name("length") {
+                Some(length) => Some(length.as_str().parse::<usize>()?),
+                None => None,
+            };
+
+            Self::validate_entry_name_part(name, "Name", 32)?;
+            Self::validate_entry_name_part(extension, "Extension", 3)?;
+
+            log::info!("Hexdump command parsed successfully: {}", input);
+            Ok(HexdumpRequest::new(
+                name.to_string(),
+                extension.to_string(),
+                offset,
+                length,
+            ))
+        } else {
+            info!("Usage: {}", usage);
+            Err(Box::try_from("Invalid hexdump command syntax!").unwrap())
+        }
+    }
+
+    pub(crate) fn parse_cksum(input: &str) -> Result<CksumRequest, Box<dyn Error>> {
+        let regex =
+            regex::Regex::new(CONFIG.commands.get("cksum").unwrap().regex.as_str()).unwrap();
+        let captures = regex.captures(input);
+        let usage = CONFIG.commands.get("cksum").unwrap().usage.as_str();
+
+        if let Some(captures) = captures {
+            let name = captures.name("name").unwrap().as_str();
+            let extension = captures.name("extension").unwrap().as_str();
+
+            Self::validate_entry_name_part(name, "Name", 32)?;
+            Self::validate_entry_name_part(extension, "Extension", 3)?;
+
+            log::info!("Cksum command parsed successfully: {}", input);
+            Ok(CksumRequest::new(name.to_string(), extension.to_string()))
+        } else {
+            info!("Usage: {}", usage);
+            Err(Box::try_from("Invalid cksum command syntax!").unwrap())
+        }
+    }
+
+    pub(crate) fn parse_diff(input: &str) -> Result<DiffRequest, Box<dyn Error>> {
+        let regex = regex::Regex::new(CONFIG.commands.get("diff").unwrap().regex.as_str()).unwrap();
+        let captures = regex.captures(input);
+        let usage = CONFIG.commands.get("diff").unwrap().usage.as_str();
+
+        if let Some(captures) = captures {
+            let first_name = captures.name("first_name").unwrap().as_str();
+            let first_extension = captures.name("first_extension").unwrap().as_str();
+            let second_name = captures.name("second_name").unwrap().as_str();
+            let second_extension = captures.name("second_extension").unwrap().as_str();
+
+            if first_name.len() > 32 || second_name.len() > 32 {
+                return Err(Box::try_from("Name must be 32 characters or less!").unwrap());
+            }
+
+            if first_extension.len() > 3 || second_extension.len() > 3 {
+                return Err(Box::try_from("Extension must be 3 characters or less!").unwrap());
+            }
+
+            log::info!("Diff command parsed successfully: {}", input);
+            Ok(DiffRequest::new(
+                first_name.to_string(),
+                first_extension.to_string(),
+                second_name.to_string(),
+                second_extension.to_string(),
+            ))
+        } else {
+            info!("Usage: {}", usage);
+            Err(Box::try_from("Invalid diff command syntax!").unwrap())
+        }
+    }
+
+    pub(crate) fn parse_version(input: &str) -> Result<VersionRequest, Box<dyn Error>> {
+        log::info!("Parsing version command...");
+
+        let regex =
+            regex::Regex::new(CONFIG.commands.get("version").unwrap().regex.as_str()).unwrap();
+        let usage = CONFIG.commands.get("version").unwrap().usage.as_str();
+
+        if regex.is_match(input) {
+            log::info!("Version command parsed successfully!");
+            Ok(VersionRequest::new())
+        } else {
+            info!("Usage: {}", usage);
+            Err(Box::try_from("Invalid version command syntax!").unwrap())
+        }
+    }
+
+    pub(crate) fn parse_fraginfo(input: &str) -> Result<FragInfoRequest, Box<dyn Error>> {
+        let regex =
+            regex::Regex::new(CONFIG.commands.get("fraginfo").unwrap().regex.as_str()).unwrap();
+        let usage = CONFIG.commands.get("fraginfo").unwrap().usage.as_str();
+
+        if regex.is_match(input) {
+            log::info!("Fraginfo command parsed successfully: {}", input);
+            Ok(FragInfoRequest::new())
+        } else {
+            info!("Usage: {}", usage);
+            Err(Box::try_from("Invalid fraginfo command syntax!").unwrap())
+        }
+    }
+
+    pub(crate) fn parse_badblocks(input: &str) -> Result<BadBlocksRequest, Box<dyn Error>> {
+        let regex =
+            regex::Regex::new(CONFIG.commands.get("badblocks").unwrap().regex.as_str()).unwrap();
+        let captures = regex.captures(input);
+        let usage = CONFIG.commands.get("badblocks").unwrap().usage.as_str();
+
+        if let Some(captures) = captures {
+            let cluster_index = captures
+                .name("cluster_index")
+                .unwrap()
+                .as_str()
+                .parse::<usize>()?;
+
+            log::info!("Badblocks command parsed successfully: {}", input);
+            Ok(BadBlocksRequest::new(cluster_index))
+        } else {
+            info!("Usage: {}", usage);
+            Err(Box::try_from("Invalid badblocks command syntax!").unwrap())
+        }
+    }
+
+    pub(crate) fn parse_grep(input: &str) -> Result<GrepRequest, Box<dyn Error>> {
+        log::info!("Parsing grep command...");
+
+        let regex = regex::Regex::new(CONFIG.commands.get("grep").unwrap().regex.as_str()).unwrap();
+        let captures = regex.captures(input);
+        let usage = CONFIG.commands.get("grep").unwrap().usage.as_str();
+
+        if let Some(captures) = captures {
+            let recursive = captures.name("recursive").is_some();
+            let ignore_case = captures.name("ignore_case").is_some();
+            let pattern = captures.name("pattern").unwrap().as_str();
+            let file_pattern = captures.name("file_pattern");
+
+            // strip the double quotes wrapping a pattern containing spaces
+            let pattern = pattern
+                .strip_prefix('"')
+                .and_then(|pattern| pattern.strip_suffix('"'))
+                .unwrap_or(pattern);
+
+            if file_pattern.is_none() && !recursive {
+                info!("Usage: {}", usage);
+                return Err(
+                    Box::try_from("A file must be given unless searching recursively!").unwrap(),
+                );
+            }
+
+            log::info!("Grep command parsed successfully: {}", input);
+            Ok(GrepRequest::new(
+                pattern.to_string(),
+                file_pattern.map(|file_pattern| file_pattern.as_str().to_string()),
+                recursive,
+                ignore_case,
+            ))
+        } else {
+            info!("Usage: {}", usage);
+            Err(Box::try_from("Invalid grep command syntax!").unwrap())
+        }
+    }
+
+    pub(crate) fn parse_head(input: &str) -> Result<HeadRequest, Box<dyn Error>> {
+        log::info!("Parsing head command...");
+
+        let regex = regex::Regex::new(CONFIG.commands.get("head").unwrap().regex.as_str()).unwrap();
+        let captures = regex.captures(input);
+        let usage = CONFIG.commands.get("head").unwrap().usage.as_str();
+
+        if let Some(captures) = captures {
+            let name = captures.name("name").unwrap().as_str();
+            let extension = captures.name("extension").unwrap().as_str();
+            let lines_count = captures
+                .name("lines_count")
+                .map(|lines_count| lines_count.as_str().parse::<usize>())
+                .transpose()?;
+
+            Self::validate_entry_name_part(name, "Name", 32)?;
+            Self::validate_entry_name_part(extension, "Extension", 3)?;
+
+            log::info!("Head command parsed successfully: {}", input);
+            Ok(HeadRequest::new(
+                name.to_string(),
+                extension.to_string(),
+                lines_count,
+            ))
+        } else {
+            info!("Usage: {}", usage);
+            Err(Box::try_from("Invalid head command syntax!").unwrap())
+        }
+    }
+
+    pub(crate) fn parse_tail(input: &str) -> Result<TailRequest, Box<dyn Error>> {
+        log::info!("Parsing tail command...");
+
+        let regex = regex::Regex::new(CONFIG.commands.get("tail").unwrap().regex.as_str()).unwrap();
+        let captures = regex.captures(input);
+        let usage = CONFIG.commands.get("tail").unwrap().usage.as_str();
+
+        if let Some(captures) = captures {
+            let name = captures.name("name").unwrap().as_str();
+            let extension = captures.name("extension").unwrap().as_str();
+            let lines_count = captures
+                .name("lines_count")
+                .map(|lines_count| lines_count.as_str().parse::<usize>())
+                .transpose()?;
+
+            Self::validate_entry_name_part(name, "Name", 32)?;
+            Self::validate_entry_name_part(extension, "Extension", 3)?;
+
+            log::info!("Tail command parsed successfully: {}", input);
+            Ok(TailRequest::new(
+                name.to_string(),
+                extension.to_string(),
+                lines_count,
+            ))
+        } else {
+            info!("Usage: {}", usage);
+            Err(Box::try_from("Invalid tail command syntax!").unwrap())
+        }
+    }
+
+    pub(crate) fn parse_wc(input: &str) -> Result<WcRequest, Box<dyn Error>> {
+        log::info!("Parsing wc command...");
+
+        let regex = regex::Regex::new(CONFIG.commands.get("wc").unwrap().regex.as_str()).unwrap();
+        let captures = regex.captures(input);
+        let usage = CONFIG.commands.get("wc").unwrap().usage.as_str();
+
+        if let Some(captures) = captures {
+            let name = captures.name("name").unwrap().as_str();
+            let extension = captures.name("extension").unwrap().as_str();
+            let metric = captures
+                .name("metric")
+                .and_then(|metric| metric.as_str().chars().next())
+                .map(WcMetric::from);
+
+            Self::validate_entry_name_part(name, "Name", 32)?;
+            Self::validate_entry_name_part(extension, "Extension", 3)?;
+
+            log::info!("Wc command parsed successfully: {}", input);
+            Ok(WcRequest::new(
+                name.to_string(),
+                extension.to_string(),
+                metric,
+            ))
+        } else {
+            info!("Usage: {}", usage);
+            Err(Box::try_from("Invalid wc command syntax!").unwrap())
+        }
+    }
+
+    pub(crate) fn parse_ln(input: &str) -> Result<LinkRequest, Box<dyn Error>> {
+        log::info!("Parsing ln command...");
+
+        let regex = regex::Regex::new(CONFIG.commands.get("ln").unwrap().regex.as_str()).unwrap();
+        let captures = regex.captures(input);
+        let usage = CONFIG.commands.get("ln").unwrap().usage.as_str();
+
+        if let Some(captures) = captures {
+            let src_name = captures.name("src_name").unwrap().as_str();
+            let src_extension = captures.name("src_extension").unwrap().as_str();
+            let link_name = captures.name("link_name").unwrap().as_str();
+            let link_extension = captures.name("link_extension").unwrap().as_str();
+
+            Self::validate_entry_name_part(src_name, "Source name", 32)?;
+            Self::validate_entry_name_part(src_extension, "Source extension", 3)?;
+            Self::validate_entry_name_part(link_name, "Link name", 32)?;
+            Self::validate_entry_name_part(link_extension, "Link extension", 3)?;
+
+            log::info!("Ln command parsed successfully: {}", input);
+            Ok(LinkRequest::new(
+                src_name.to_string(),
+                src_extension.to_string(),
+                link_name.to_string(),
+                link_extension.to_string(),
+            ))
+        } else {
+            info!("Usage: {}", usage);
+            Err(Box::try_from("Invalid ln command syntax!").unwrap())
+        }
+    }
+
+    pub(crate) fn parse_clear(input: &str) -> Result<ClearRequest, Box<dyn Error>> {
+        log::info!("Parsing clear command...");
+
+        let regex =
+            regex::Regex::new(CONFIG.commands.get("clear").unwrap().regex.as_str()).unwrap();
+        let usage = CONFIG.commands.get("clear").unwrap().usage.as_str();
+
+        if regex.is_match(input) {
+            log::info!("Clear command parsed successfully!");
+            Ok(ClearRequest::new())
+        } else {
+            info!("Usage: {}", usage);
+            Err(Box::try_from("Invalid clear command syntax!").unwrap())
+        }
+    }
+
+    pub(crate) fn parse_mount(input: &str) -> Result<MountRequest, Box<dyn Error>> {
+        log::info!("Parsing mount command...");
+
+        let regex =
+            regex::Regex::new(CONFIG.commands.get("mount").unwrap().regex.as_str()).unwrap();
+        let captures = regex.captures(input);
+        let usage = CONFIG.commands.get("mount").unwrap().usage.as_str();
+
+        if let Some(captures) = captures {
+            let image_path = captures.name("image_path").unwrap().as_str();
+            let label = captures.name("label").unwrap().as_str();
+            let read_only = captures.name("read_only").is_some();
+
+            log::info!("Mount command parsed successfully: {}", input);
+            Ok(MountRequest::new(
+                image_path.to_string(),
+                label.to_string(),
+                read_only,
+            ))
+        } else {
+            info!("Usage: {}", usage);
+            Err(Box::try_from("Invalid mount command syntax!").unwrap())
+        }
+    }
+
+    pub(crate) fn parse_umount(input: &str) -> Result<UnmountRequest, Box<dyn Error>> {
+        log::info!("Parsing umount command...");
+
+        let regex =
+            regex::Regex::new(CONFIG.commands.get("umount").unwrap().regex.as_str()).unwrap();
+        let captures = regex.captures(input);
+        let usage = CONFIG.commands.get("umount").unwrap().usage.as_str();
+
+        if let Some(captures) = captures {
+            let label = captures.name("label").unwrap().as_str();
+
+            log::info!("Umount command parsed successfully: {}", input);
+            Ok(UnmountRequest::new(label.to_string()))
+        } else {
+            info!("Usage: {}", usage);
+            Err(Box::try_from("Invalid umount command syntax!").unwrap())
+        }
+    }
+
+    pub(crate) fn parse_resize(input: &str) -> Result<ResizeRequest, Box<dyn Error>> {
+        log::info!("Parsing resize command...");
+
+        let regex =
+            regex::Regex::new(CONFIG.commands.get("resize").unwrap().regex.as_str()).unwrap();
+        let captures = regex.captures(input);
+        let usage = CONFIG.commands.get("resize").unwrap().usage.as_str();
+
+        if let Some(captures) = captures {
+            let cluster_count = captures.name("cluster_count").unwrap().as_str();
+            let cluster_count = cluster_count.parse::<u16>().map_err(|_| {
+                format!(
+                    "Invalid cluster count {}, a disk has at most {} clusters",
+                    cluster_count,
+                    u16::MAX
+                )
+            })?;
+
+            log::info!("Resize command parsed successfully: {}", input);
+            Ok(ResizeRequest::new(cluster_count))
+        } else {
+            info!("Usage: {}", usage);
+            Err(Box::try_from("Invalid resize command syntax!").unwrap())
+        }
+    }
+
+    pub(crate) fn parse_undo(input: &str) -> Result<UndoRequest, Box<dyn Error>> {
+        log::info!("Parsing undo command...");
+
+        let regex = regex::Regex::new(CONFIG.commands.get("undo").unwrap().regex.as_str()).unwrap();
+        let usage = CONFIG.commands.get("undo").unwrap().usage.as_str();
+
+        if regex.is_match(input) {
+            log::info!("Undo command parsed successfully: {}", input);
+            Ok(UndoRequest::new())
+        } else {
+            info!("Usage: {}", usage);
+            Err(Box::try_from("Invalid undo command syntax!").unwrap())
+        }
+    }
+
+    pub(crate) fn parse_redo(input: &str) -> Result<RedoRequest, Box<dyn Error>> {
+        log::info!("Parsing redo command...");
+
+        let regex = regex::Regex::new(CONFIG.commands.get("redo").unwrap().regex.as_str()).unwrap();
+        let usage = CONFIG.commands.get("redo").unwrap().usage.as_str();
+
+        if regex.is_match(input) {
+            log::info!("Redo command parsed successfully: {}", input);
+            Ok(RedoRequest::new())
+        } else {
+            info!("Usage: {}", usage);
+            Err(Box::try_from("Invalid redo command syntax!").unwrap())
+        }
+    }
+
+    pub(crate) fn parse_label(input: &str) -> Result<LabelRequest, Box<dyn Error>> {
+        log::info!("Parsing label command...");
+
+        let regex =
+            regex::Regex::new(CONFIG.commands.get("label").unwrap().regex.as_str()).unwrap();
+        let usage = CONFIG.commands.get("label").unwrap().usage.as_str();
+
+        if let Some(captures) = regex.captures(input) {
+            let new_label = captures
+                .name("new_label")
+                .map(|new_label| new_label.as_str().to_string());
+
+            log::info!("Label command parsed successfully: {}", input);
+            Ok(LabelRequest::new(new_label))
+        } else {
+            info!("Usage: {}", usage);
+            Err(Box::try_from("Invalid label command syntax!").unwrap())
+        }
+    }
+
+    pub(crate) fn parse_trash(input: &str) -> Result<TrashRequest, Box<dyn Error>> {
+        log::info!("Parsing trash command...");
+
+        let regex =
+            regex::Regex::new(CONFIG.commands.get("trash").unwrap().regex.as_str()).unwrap();
+        let usage = CONFIG.commands.get("trash").unwrap().usage.as_str();
+
+        if let Some(captures) = regex.captures(input) {
+            let action = match (captures.name("empty"), captures.name("name")) {
+                (Some(_), _) => TrashAction::Empty,
+                (None, Some(name)) => TrashAction::Restore {
+                    name: name.as_str().to_string(),
+                    extension: captures
+                        .name("extension")
+                        .map(|extension| extension.as_str().to_string())
+                        .unwrap_or_default(),
+                },
+                (None, None) => TrashAction::List,
+            };
+
+            log::info!("Trash command parsed successfully: {}", input);
+            Ok(TrashRequest::new(action))
+        } else {
+            info!("Usage: {}", usage);
+            Err(Box::try_from("Invalid trash command syntax!").unwrap())
+        }
+    }
+
+    pub(crate) fn parse_readcluster(input: &str) -> Result<ReadClusterRequest, Box<dyn Error>> {
+        log::info!("Parsing readcluster command...");
+
+        let regex =
+            regex::Regex::new(CONFIG.commands.get("readcluster").unwrap().regex.as_str()).unwrap();
+        let usage = CONFIG.commands.get("readcluster").unwrap().usage.as_str();
+
+        if let Some(captures) = regex.captures(input) {
+            let cluster_index = captures
+                .name("cluster_index")
+                .unwrap()
+                .as_str()
+                .parse::<usize>()?;
+
+            log::info!("Readcluster command parsed successfully: {}", input);
+            Ok(ReadClusterRequest::new(cluster_index))
+        } else {
+            info!("Usage: {}", usage);
+            Err(Box::try_from("Invalid readcluster command syntax!").unwrap())
+        }
+    }
+
+    pub(crate) fn parse_writecluster(input: &str) -> Result<WriteClusterRequest, Box<dyn Error>> {
+        log::info!("Parsing writecluster command...");
+
+        let regex =
+            regex::Regex::new(CONFIG.commands.get("writecluster").unwrap().regex.as_str()).unwrap();
+        let usage = CONFIG.commands.get("writecluster").unwrap().usage.as_str();
+
+        if let Some(captures) = regex.captures(input) {
+            // the raw write bypasses the FAT, so it must be asked for explicitly
+            if captures.name("unsafe").is_none() {
+                return Err(Box::try_from(
+                    "writecluster doesn't update the FAT nor the directory tables, pass --unsafe to write anyway!",
+                )
+                .unwrap());
+            }
+
+            let cluster_index = captures
+                .name("cluster_index")
+                .unwrap()
+                .as_str()
+                .parse::<usize>()?;
+
+            let hex_bytes = captures.name("data").unwrap().as_str();
+            if hex_bytes.len() % 2 != 0 {
+                return Err(
+                    Box::try_from("The bytes must be given as pairs of hex digits!").unwrap(),
+                );
+            }
+            let data = (0..hex_bytes.len())
+                .step_by(2)
+                .map(|i| u8::from_str_radix(&hex_bytes[i..i + 2], 16))
+                .collect::<Result<Vec<u8>, _>>()?;
+
+            log::info!("Writecluster command parsed successfully: {}", input);
+            Ok(WriteClusterRequest::new(cluster_index, data))
+        } else {
+            info!("Usage: {}", usage);
+            Err(Box::try_from("Invalid writecluster command syntax!").unwrap())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_diff_reads_both_file_names() {
+        let request = CliParser::parse_diff("diff first.txt second.bin").unwrap();
+
+        assert_eq!(request.first_name, "first");
+        assert_eq!(request.first_extension, "txt");
+        assert_eq!(request.second_name, "second");
+        assert_eq!(request.second_extension, "bin");
+    }
+
+    #[test]
+    fn parse_diff_rejects_a_single_file_or_a_long_extension() {
+        assert_eq!(
+            CliParser::parse_diff("diff first.txt")
+                .err()
+                .unwrap()
+                .to_string(),
+            "Invalid diff command syntax!"
+        );
+        assert_eq!(
+            CliParser::parse_diff("diff first.text second.txt")
+                .err()
+                .unwrap()
+                .to_string(),
+            "Extension must be 3 characters or less!"
+        );
+    }
+
+    #[test]
+    fn parse_rename_rejects_paths_with_an_explicit_message() {
+        let expected_error = "Rename only changes the name within the current directory, paths are not supported (copy the entry with cp and delete the original to move it)";
+
+        assert_eq!(
+            CliParser::parse_rename("rename a/old.txt b/new.txt")
+                .err()
+                .unwrap()
+                .to_string(),
+            expected_error
+        );
+        assert_eq!(
+            CliParser::parse_rename("rename a\\old.txt b\\new.txt")
+                .err()
+                .unwrap()
+                .to_string(),
+            expected_error
+        );
+    }
+
+    fn mkdir_error(input: &str) -> String {
+        CliParser::parse_mkdir(input).err().unwrap().to_string()
+    }
+
+    #[test]
+    fn parse_mkdir_accepts_a_plain_name() {
+        let request = CliParser::parse_mkdir("mkdir my_dir1").unwrap();
+
+        assert_eq!(request.name, "my_dir1");
+    }
+
+    #[test]
+    fn parse_mkdir_rejects_path_separators() {
+        let expected_error = "Name must not contain path separators ('/' or '\\')!";
+
+        assert_eq!(mkdir_error("mkdir a/b"), expected_error);
+        assert_eq!(mkdir_error("mkdir /a"), expected_error);
+        assert_eq!(mkdir_error("mkdir a\\b"), expected_error);
+    }
+
+    #[test]
+    fn parse_mkdir_rejects_dots() {
+        assert_eq!(mkdir_error("mkdir a.txt"), "Name must not contain dots!");
+        assert_eq!(mkdir_error("mkdir .."), "Name must not contain dots!");
+    }
+
+    #[test]
+    fn parse_mkdir_rejects_control_characters() {
+        assert_eq!(
+            mkdir_error("mkdir a\u{1b}b"),
+            "Name must not contain control characters!"
+        );
+    }
+
+    #[test]
+    fn parse_mkdir_rejects_a_name_longer_than_32_characters() {
+        assert!(CliParser::parse_mkdir(&format!("mkdir {}", "a".repeat(32))).is_ok());
+        assert_eq!(
+            mkdir_error(&format!("mkdir {}", "a".repeat(33))),
+            "Name must be 32 characters or less!"
+        );
+    }
+
+    #[test]
+    fn parse_create_rejects_an_invalid_name() {
+        let error = CliParser::parse_create("create a/b.txt 10 -alpha")
+            .err()
+            .unwrap()
+            .to_string();
+
+        assert!(error.contains("Name must not contain path separators"));
+    }
+
+    #[test]
+    fn parse_create_rejects_an_unknown_content_type() {
+        let error = CliParser::parse_create("create a.txt 10 -foo")
+            .err()
+            .unwrap()
+            .to_string();
+
+        assert!(error.contains("Invalid content type foo"), "{}", error);
+    }
+
+    #[test]
+    fn parse_fmt_reads_the_fat_type_and_the_optional_cluster_size() {
+        let request = CliParser::parse_fmt("fmt 12").unwrap();
+        assert_eq!((request.fat_type, request.cluster_size), (12, None));
+
+        let request = CliParser::parse_fmt("fmt -y --dry-run 32 16").unwrap();
+        assert_eq!((request.fat_type, request.cluster_size), (32, Some(16)));
+        assert!(request.skip_confirmation);
+        assert!(request.dry_run);
+
+        assert!(CliParser::parse_fmt("fmt 8").is_err());
+        assert!(CliParser::parse_fmt("fmt 16 64").is_err());
+    }
 }
@@ -0,0 +1,208 @@
+use std::collections::VecDeque;
+use std::io;
+
+/// CommandHistory keeps the last `capacity` commands typed in the REPL and tracks the position reached while
+/// navigating through them (independent of the terminal, the line editor only asks for the older/newer entry).
+/// # Fields
+/// * `entries` - the commands, from the oldest to the newest
+/// * `capacity` - the maximum number of commands kept (the oldest ones are dropped first)
+/// * `position` - the index of the entry currently recalled, if any
+/// * `draft` - the line that was being typed when the navigation started (restored when moving past the newest entry)
+#[derive(Debug, Clone)]
+pub(crate) struct CommandHistory {
+    entries: VecDeque<String>,
+    capacity: usize,
+    position: Option<usize>,
+    draft: String,
+}
+
+impl CommandHistory {
+    pub(crate) fn new(capacity: usize) -> Self {
+        Self {
+            entries: VecDeque::new(),
+            capacity,
+            position: None,
+            draft: String::new(),
+        }
+    }
+
+    /// Loads the history from the given file (one command per line), keeping only the last `capacity` commands.
+    /// A missing file results in an empty history.
+    pub(crate) fn load(file_path: &str, capacity: usize) -> io::Result<Self> {
+        let mut history = Self::new(capacity);
+
+        match std::fs::read_to_string(file_path) {
+            Ok(content) => content.lines().for_each(|line| {
+                history.add(line);
+            }),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+            Err(err) => return Err(err),
+        }
+
+        Ok(history)
+    }
+
+    /// Saves the history to the given file, one command per line.
+    pub(crate) fn save(&self, file_path: &str) -> io::Result<()> {
+        let content: String = self
+            .entries
+            .iter()
+            .map(|entry| format!("{}\n", entry))
+            .collect();
+
+        std::fs::write(file_path, content)
+    }
+
+    /// Appends a command to the history and resets the navigation.
+    /// Blank commands and repetitions of the newest command are not recorded (returns whether the command was added).
+    pub(crate) fn add(&mut self, command: &str) -> bool {
+        self.reset_navigation();
+
+        let command = command.trim();
+        if command.is_empty() || self.entries.back().map(String::as_str) == Some(command) {
+            return false;
+        }
+
+        self.entries.push_back(command.to_string());
+        while self.entries.len() > self.capacity {
+            self.entries.pop_front();
+        }
+
+        true
+    }
+
+    /// Moves one entry back in the history and returns it (staying at the oldest one once reached).
+    /// `current_line` is remembered when the navigation starts so that it can be restored by `newer`.
+    pub(crate) fn older(&mut self, current_line: &str) -> Option<&str> {
+        if self.entries.is_empty() {
+            return None;
+        }
+
+        let position = match self.position {
+            None => {
+                self.draft = current_line.to_string();
+                self.entries.len() - 1
+            }
+            Some(position) => position.saturating_sub(1),
+        };
+        self.position = Some(position);
+
+        self.entries.get(position).map(String::as_str)
+    }
+
+    /// Moves one entry forward in the history and returns it, or returns the line that was being typed before
+    /// the navigation started when moving past the newest entry (`None` if no navigation is in progress).
+    pub(crate) fn newer(&mut self) -> Option<&str> {
+        let position = self.position?;
+
+        if position + 1 < self.entries.len() {
+            self.position = Some(position + 1);
+            self.entries.get(position + 1).map(String::as_str)
+        } else {
+            self.position = None;
+            Some(self.draft.as_str())
+        }
+    }
+
+    /// Stops the navigation, so that the next call to `older` starts again from the newest entry.
+    pub(crate) fn reset_navigation(&mut self) {
+        self.position = None;
+        self.draft.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn history_of(commands: &[&str], capacity: usize) -> CommandHistory {
+        let mut history = CommandHistory::new(capacity);
+        commands.iter().for_each(|command| {
+            history.add(command);
+        });
+
+        history
+    }
+
+    #[test]
+    fn add_skips_blank_commands_and_repetitions_of_the_newest_one() {
+        let mut history = CommandHistory::new(10);
+
+        assert!(history.add("ls"));
+        assert!(!history.add("ls"));
+        assert!(!history.add("  ls  "));
+        assert!(!history.add("   "));
+        assert!(history.add("cd docs"));
+        assert!(history.add("ls"));
+
+        assert_eq!(history.entries, ["ls", "cd docs", "ls"]);
+    }
+
+    #[test]
+    fn add_drops_the_oldest_commands_beyond_the_capacity() {
+        let history = history_of(&["a", "b", "c", "d"], 3);
+
+        assert_eq!(history.entries, ["b", "c", "d"]);
+    }
+
+    #[test]
+    fn older_stops_at_the_oldest_command() {
+        let mut history = history_of(&["a", "b", "c"], 10);
+
+        assert_eq!(history.older(""), Some("c"));
+        assert_eq!(history.older(""), Some("b"));
+        assert_eq!(history.older(""), Some("a"));
+        assert_eq!(history.older(""), Some("a"));
+    }
+
+    #[test]
+    fn newer_goes_back_to_the_line_being_typed() {
+        let mut history = history_of(&["a", "b"], 10);
+
+        assert_eq!(history.newer(), None);
+        assert_eq!(history.older("draft"), Some("b"));
+        assert_eq!(history.older("b"), Some("a"));
+        assert_eq!(history.newer(), Some("b"));
+        assert_eq!(history.newer(), Some("draft"));
+        assert_eq!(history.newer(), None);
+    }
+
+    #[test]
+    fn add_restarts_the_navigation_from_the_newest_command() {
+        let mut history = history_of(&["a", "b"], 10);
+        history.older("");
+        history.older("");
+
+        history.add("a");
+
+        assert_eq!(history.older(""), Some("a"));
+        assert_eq!(history.older(""), Some("b"));
+    }
+
+    #[test]
+    fn an_empty_history_recalls_nothing() {
+        let mut history = CommandHistory::new(10);
+
+        assert_eq!(history.older("draft"), None);
+        assert_eq!(history.newer(), None);
+    }
+
+    #[test]
+    fn the_history_is_saved_and_loaded_back() {
+        let file_path = std::env::temp_dir()
+            .join(format!("rodos-history-{}.txt", std::process::id()))
+            .to_string_lossy()
+            .into_owned();
+        let history = history_of(&["a", "b", "c"], 10);
+
+        history.save(&file_path).unwrap();
+        let loaded_history = CommandHistory::load(&file_path, 2).unwrap();
+        std::fs::remove_file(&file_path).unwrap();
+
+        assert_eq!(loaded_history.entries, ["b", "c"]);
+        assert_eq!(
+            CommandHistory::load(&file_path, 2).unwrap().entries.len(),
+            0
+        );
+    }
+}
@@ -0,0 +1,170 @@
+use crate::application::queries::ls::ListRequest;
+use crate::core::Arm;
+use crate::domain::i_disk_manager::IDiskManager;
+
+/// Completer completes the word under the cursor: against the command names when it is the first word of the input
+/// and against the (visible) entries of the working directory otherwise.
+/// # Fields
+/// * `command_names` - the names of the available commands
+/// * `disk_manager` - the disk manager the working directory entries are listed from
+pub(crate) struct Completer {
+    command_names: Vec<String>,
+    disk_manager: Arm<dyn IDiskManager>,
+}
+
+impl Completer {
+    pub(crate) fn new(command_names: Vec<String>, disk_manager: Arm<dyn IDiskManager>) -> Self {
+        Self {
+            command_names,
+            disk_manager,
+        }
+    }
+
+    /// Returns the sorted candidates for the word ending at the given cursor (a char index in the input).
+    pub(crate) fn complete(&self, input: &str, cursor: usize) -> Vec<String> {
+        let chars: Vec<char> = input.chars().collect();
+        let cursor = cursor.min(chars.len());
+        let word_start = get_word_start(&chars, cursor);
+        let word: String = chars[word_start..cursor].iter().collect();
+
+        match chars[..word_start].iter().all(|c| c.is_whitespace()) {
+            true => complete_word(&word, &self.command_names),
+            false => complete_word(&word, &self.list_entry_names()),
+        }
+    }
+
+    fn list_entry_names(&self) -> Vec<String> {
+        match self.disk_manager.lock() {
+            Ok(mut disk_manager) => {
+                disk_manager.pull_sync();
+
+                match disk_manager.list_files(&ListRequest::new(vec![], None)) {
+                    Ok(file_entries) => file_entries
+                        .iter()
+                        .filter(|file_entry| file_entry.name != "." && file_entry.name != "..")
+                        .map(|file_entry| file_entry.get_full_name())
+                        .collect(),
+                    Err(err) => {
+                        log::warn!("Unable to list the entries to complete: {}", err);
+                        vec![]
+                    }
+                }
+            }
+            Err(_) => vec![],
+        }
+    }
+}
+
+/// Returns the index of the first char of the word ending at the given cursor (words are separated by whitespaces).
+pub(crate) fn get_word_start(chars: &[char], cursor: usize) -> usize {
+    chars[..cursor]
+        .iter()
+        .rposition(|c| c.is_whitespace())
+        .map_or(0, |position| position + 1)
+}
+
+/// Returns the sorted candidates starting with the given word, ignoring the ASCII case
+/// (as names are case-insensitive).
+fn complete_word(word: &str, candidates: &[String]) -> Vec<String> {
+    let word = word.to_ascii_lowercase();
+
+    let mut completions: Vec<String> = candidates
+        .iter()
+        .filter(|candidate| candidate.to_ascii_lowercase().starts_with(&word))
+        .cloned()
+        .collect();
+    completions.sort();
+    completions.dedup();
+
+    completions
+}
+
+/// Returns the longest prefix shared by all the completions, ignoring the ASCII case
+/// (the casing of the first completion is kept).
+pub(crate) fn get_common_prefix(completions: &[String]) -> String {
+    let first: Vec<char> = match completions.first() {
+        Some(first) => first.chars().collect(),
+        None => return String::new(),
+    };
+
+    let length = completions
+        .iter()
+        .skip(1)
+        .fold(first.len(), |length, completion| {
+            completion
+                .chars()
+                .zip(first.iter())
+                .take(length)
+                .take_while(|(a, b)| a.eq_ignore_ascii_case(b))
+                .count()
+        });
+
+    first[..length].iter().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::test_disk::TestDisk;
+    use std::sync::{Arc, Mutex};
+
+    /// Builds a completer over a few commands and a disk holding `report.txt`, `readme.md` and the directory `docs`.
+    fn completer() -> (TestDisk, Completer) {
+        let mut disk = TestDisk::new();
+        disk.write("report", "txt", "report").unwrap();
+        disk.write("readme", "md", "readme").unwrap();
+        disk.mkdir("docs").unwrap();
+        let command_names = ["cat", "cd", "cp", "create", "del"]
+            .iter()
+            .map(|command_name| command_name.to_string())
+            .collect();
+        let completer = Completer::new(command_names, Arc::new(Mutex::new(disk.reopen())));
+
+        (disk, completer)
+    }
+
+    #[test]
+    fn the_first_word_completes_to_command_names() {
+        let (_disk, completer) = completer();
+
+        assert_eq!(completer.complete("cr", 2), vec!["create"]);
+        assert_eq!(
+            completer.complete("  C", 3),
+            vec!["cat", "cd", "cp", "create"]
+        );
+        assert!(completer.complete("x", 1).is_empty());
+    }
+
+    #[test]
+    fn the_other_words_complete_to_the_entries_of_the_working_directory() {
+        let (_disk, completer) = completer();
+
+        assert_eq!(completer.complete("cat rep", 7), vec!["report.txt"]);
+        assert_eq!(
+            completer.complete("cp re", 5),
+            vec!["readme.md", "report.txt"]
+        );
+        assert_eq!(completer.complete("cd D", 4), vec!["docs"]);
+        assert_eq!(
+            completer.complete("cat ", 4),
+            vec!["docs", "readme.md", "report.txt"]
+        );
+    }
+
+    #[test]
+    fn only_the_word_before_the_cursor_is_completed() {
+        let (_disk, completer) = completer();
+
+        assert_eq!(completer.complete("cat rep other", 7), vec!["report.txt"]);
+        assert_eq!(completer.complete("cr", 100), vec!["create"]);
+    }
+
+    #[test]
+    fn the_common_prefix_of_the_completions_ignores_the_case() {
+        let completions = vec!["Readme.md".to_string(), "report.txt".to_string()];
+
+        assert_eq!(get_common_prefix(&completions), "Re");
+        assert_eq!(get_common_prefix(&["create".to_string()]), "create");
+        assert_eq!(get_common_prefix(&[]), "");
+    }
+}
@@ -1,5 +1,6 @@
+use crate::domain::boot_sector::BootSector;
 use serde::Deserialize;
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 
 /// The configuration of the shell:
 /// - `os`: The name of the operating system.
@@ -11,6 +12,20 @@ use std::collections::HashMap;
 /// - `storage_file_path`: The path to the storage file.
 /// - `stdin_file_path`: The path to the stdin file.
 /// - `temp_file_path`: The path to the temp buffer file.
+/// - `history_file_path`: The path to the file the history of the REPL commands is persisted to.
+/// - `history_size`: The maximum number of commands kept in the history.
+/// - `confirm_destructive`: Whether to ask for confirmation before running destructive commands (`del` and `fmt`).
+/// - `audit_log_file_path`: The path to the file every executed command is appended to as a JSON line (empty to disable it).
+/// - `autosave_delay_ms`: The delay in milliseconds the disks must stay idle before their modifications are written
+///   to their storage files (0 to write them after every command).
+/// - `read_only`: Whether to open the disk read-only, i.e. rejecting every command modifying it and never writing the storage file.
+/// - `use_trash`: Whether `del` moves the deleted file entries to the hidden `.trash` directory of the root
+///   instead of freeing their clusters (see `trash`).
+/// - `max_directory_entries`: The maximum number of entries of every directory, the root included (0 for no quota,
+///   the root being still limited by its `root_entry_count`).
+/// - `random_seed`: The seed of the content generated by `create -random`, for a reproducible content
+///   (a new seed on every run if none, the `RODOS_SEED` environment variable overriding it).
+/// - `disk`: The geometry used when a new storage file is created.
 #[derive(Debug, Clone, Deserialize)]
 pub(crate) struct Config {
     pub(crate) os: String,
@@ -22,19 +37,108 @@ pub(crate) struct Config {
     pub(crate) storage_file_path: String,
     pub(crate) stdin_file_path: String,
     pub(crate) temp_file_path: String,
+    #[serde(default = "default_history_file_path")]
+    pub(crate) history_file_path: String,
+    #[serde(default = "default_history_size")]
+    pub(crate) history_size: usize,
+    #[serde(default = "default_confirm_destructive")]
+    pub(crate) confirm_destructive: bool,
+    #[serde(default = "default_audit_log_file_path")]
+    pub(crate) audit_log_file_path: String,
+    #[serde(default)]
+    pub(crate) autosave_delay_ms: u64,
+    #[serde(default)]
+    pub(crate) read_only: bool,
+    #[serde(default)]
+    pub(crate) use_trash: bool,
+    #[serde(default)]
+    pub(crate) max_directory_entries: usize,
+    #[serde(default)]
+    pub(crate) random_seed: Option<u64>,
+    #[serde(default)]
+    pub(crate) disk: DiskGeometry,
+}
+
+fn default_history_file_path() -> String {
+    "disk/history.txt".to_string()
+}
+
+fn default_history_size() -> usize {
+    100
+}
+
+fn default_confirm_destructive() -> bool {
+    true
+}
+
+fn default_audit_log_file_path() -> String {
+    "disk/audit.log".to_string()
+}
+
+impl Config {
+    /// Loads the configuration from the given TOML file (the default configuration is used if it cannot be read)
+    /// and creates the disk folder along with the stdin and temp files it refers to if they don't exist
+    /// (as well as their missing parent folders).
+    ///
+    /// Fails if the config file is invalid or if the disk folder or the files can't be created.
+    pub(crate) fn load(config_file_path: &str) -> Result<Self, String> {
+        let config_res = std::fs::read_to_string(config_file_path);
+
+        let config: Config = match config_res {
+            Ok(config_str) => toml::from_str(&config_str)
+                .map_err(|err| format!("Invalid config file {}: {}", config_file_path, err))?,
+            Err(..) => Config::default(),
+        };
+
+        // create disk folder (and the folder of the storage file) if it doesn't exist
+        std::fs::create_dir_all(&config.disk_dir_path).map_err(|err| {
+            format!(
+                "Unable to create disk folder {}: {}",
+                config.disk_dir_path, err
+            )
+        })?;
+        Self::create_parent_dir(&config.storage_file_path)?;
+
+        // create stdin and temp files if they don't exist
+        Self::create_file(&config.stdin_file_path, "stdin")?;
+        Self::create_file(&config.temp_file_path, "temp")?;
+
+        Ok(config)
+    }
+
+    /// Creates the missing parent folders of the given file.
+    fn create_parent_dir(file_path: &str) -> Result<(), String> {
+        match std::path::Path::new(file_path).parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => std::fs::create_dir_all(parent)
+                .map_err(|err| format!("Unable to create folder {}: {}", parent.display(), err)),
+            _ => Ok(()),
+        }
+    }
+
+    /// Creates the given (stdin or temp) file along with its missing parent folders if it doesn't exist.
+    fn create_file(file_path: &str, kind: &str) -> Result<(), String> {
+        if std::path::Path::new(file_path).exists() {
+            return Ok(());
+        }
+
+        Self::create_parent_dir(file_path)?;
+        std::fs::File::create(file_path)
+            .map(|_| ())
+            .map_err(|err| format!("Unable to create {} file {}: {}", kind, file_path, err))
+    }
 }
 
 impl Default for Config {
     fn default() -> Self {
-        let mut commands = HashMap::new();
+        let mut commands = BTreeMap::new();
 
         commands.insert(
             "help".to_string(),
             Command {
                 name: "help".to_string(),
                 description: "Display the list of available commands".to_string(),
-                usage: "help [command]".to_string(),
-                regex: r"^\s*help(\s+(?P<command>\S+))?\s*$".to_string(),
+                usage: "help [command | --all]\n--all: show the description and the usage of every command".to_string(),
+                regex: r"^\s*help(\s+(?P<all>--all|-v)|\s+(?P<command>\S+))?\s*$".to_string(),
             },
         );
 
@@ -63,7 +167,7 @@ impl Default for Config {
             Command {
                 name: "create".to_string(),
                 description: "Create a new file".to_string(),
-                usage: "create <file_name>.<file_extension> <file_size> -<file_content_type>".to_string(),
+                usage: "create <file_name>.<file_extension> <file_size> -<file_content_type>\n-<file_content_type>:\n\t-alpha: fill with A-Z\n\t-num: fill with 0-9\n\t-hex: fill with 0-F\n\t-pattern=<pattern>: fill by repeating the given pattern\n\t-random: fill with pseudo-random bytes (reproducible with random_seed or RODOS_SEED)\n\t-zero: fill with zero bytes\n\t-stdin: fill with the lines typed until a lone `.` line (the file size is given by the typed content)".to_string(),
                 regex: r"^\s*create\s+(?P<name>\S+)\.(?P<extension>\S+)\s+(?P<dim>\d+)\s+-(?P<type>\S+)\s*$".to_string(),
             },
         );
@@ -73,8 +177,8 @@ impl Default for Config {
             Command {
                 name: "ls".to_string(),
                 description: "List files and directories from the current directory by applying given filters and sorting options".to_string(),
-                usage: "ls [-<filter>] [-name=<file_name>] [-ext=<file_extension>] [-<sort>]\n-<filter>:\n\t-a: show all visible files and directories\n\t-h: show all files and directories including hidden ones\n\t-s: show files and directories in short format (name and extension)\n\t-l: show files and directories in detailed format (attributes, name, extension, last modification date and size in bytes)\n\t-f: show all files\n\t-d: show all directories\n-<sort>:\n\t-n: sort by name\n\t-t: sort by last modification date\n\t-sz: sort by size\n\t-*a: sort in ascending order\n\t-*d: sort in descending order".to_string(),
-                regex: r"^\s*ls(\s+-(?P<filter_basic>(a|h)(s|l)?(f|d)?))?(\s+-name=(?P<filter_name>\S+))?(\s+-ext=(?P<filter_extension>\S+))?(\s+-(?P<sort>(n|t|sz)(a|d)))?\s*$".to_string(),
+                usage: "ls [-R] [-<filter>] [-ro] [-hidden-only] [-system-only] [-name=<file_name>] [-ext=<file_extension>] [-minsize=<bytes>] [-maxsize=<bytes>] [-after=<date>] [-before=<date>] [-<sort>] [<pattern>] [--long] [--json]\n-R: list the subdirectories recursively (depth-first, hidden ones only with -h)\n-<filter>:\n\t-a: show all visible files and directories\n\t-h: show all files and directories including hidden ones\n\t-s: show files and directories in short format (name and extension)\n\t-l: show files and directories in long format, aligned in a table under a header row (attributes, name, extension, last modification timestamp, size in bytes, first cluster and number of clusters)\n\t-f: show all files\n\t-d: show all directories\n-ro: only show the read-only entries\n-hidden-only: only show the hidden entries\n-system-only: only show the system entries\n-minsize=<bytes>: only show the entries of at least <bytes> bytes\n-maxsize=<bytes>: only show the entries of at most <bytes> bytes\n-after=<date>: only show the entries last modified at or after <date> (YYYY-MM-DD, YYYY-MM-DDTHH:MM:SS or RFC 3339, in UTC unless an offset is given)\n-before=<date>: only show the entries last modified before <date> (same formats as -after)\n-<sort>:\n\t-n: sort by name\n\t-t: sort by last modification date\n\t-sz: sort by size\n\t-e: sort by extension (then by name)\n\t-*a: sort in ascending order\n\t-*d: sort in descending order\n<pattern>: only show the files and directories whose full name matches the wildcard pattern (* and ?)\n--long: same as -l\n--json: print the listing as a JSON array (name, extension, size, attributes, is_file, last_modification_datetime, first_cluster and directory)".to_string(),
+                regex: r"^\s*ls(\s+-(?P<filter_recursive>R))?(\s+-(?P<filter_basic>(a|h)(s|l)?(f|d)?|(s|l)(f|d)?|f|d))?(?P<filter_attributes>(\s+-(ro|hidden-only|system-only))+)?(\s+-name=(?P<filter_name>\S+))?(\s+-ext=(?P<filter_extension>\S+))?(\s+-minsize=(?P<filter_min_size>\d+))?(\s+-maxsize=(?P<filter_max_size>\d+))?(\s+-after=(?P<filter_after>\S+))?(\s+-before=(?P<filter_before>\S+))?(\s+-(?P<sort>(n|t|sz|e)(a|d)))?(\s+(?P<filter_glob>[a-zA-Z0-9_*?.]+))?(\s+(?P<filter_long>--long))?(\s+(?P<filter_json>--json))?\s*$".to_string(),
             },
         );
 
@@ -83,7 +187,7 @@ impl Default for Config {
             Command {
                 name: "rename".to_string(),
                 description: "Rename a file or a directory".to_string(),
-                usage: "rename <old_name> <new_name>".to_string(),
+                usage: "rename <old_name> <new_name>\nThe entry is renamed within the current directory (it cannot be moved to another one)".to_string(),
                 regex: r"^\s*rename\s+(?P<old_name>[a-zA-Z0-9_]+)(\.(?P<old_extension>\S+))?\s+(?P<new_name>[a-zA-Z0-9_]+)(\.(?P<new_extension>\S+))?\s*$".to_string(),
             },
         );
@@ -93,8 +197,8 @@ impl Default for Config {
             Command {
                 name: "del".to_string(),
                 description: "Delete a file or a directory".to_string(),
-                usage: "del <file_name>.<file_extension> or del <directory_name>".to_string(),
-                regex: r"^\s*del\s+(?P<name>[a-zA-Z0-9_]+)(\.(?P<extension>\S+))?\s*$".to_string(),
+                usage: "del [-y|--yes] [-n|--dry-run] [-r|--recursive] <file_name>.<file_extension> or del [-y|--yes] [-n|--dry-run] [-r|--recursive] <directory_name> (the name may contain the * and ? wildcards, -y skips the confirmation, -n only lists the entries which would be deleted, -r deletes the matching entries of the subdirectories as well)".to_string(),
+                regex: r"^\s*del(?P<options>(\s+(-y|--yes|-n|--dry-run|-r|--recursive))*)\s+(?P<name>[a-zA-Z0-9_*?]+)(\.(?P<extension>\S+))?\s*$".to_string(),
             },
         );
 
@@ -102,9 +206,9 @@ impl Default for Config {
             "cat".to_string(),
             Command {
                 name: "cat".to_string(),
-                description: "Display the content of a file".to_string(),
-                usage: "cat <file_name>.<file_extension>".to_string(),
-                regex: r"^\s*cat\s+(?P<name>\S+)\.(?P<extension>\S+)\s*$".to_string(),
+                description: "Display the content of files".to_string(),
+                usage: "cat [-p|--plain] <file_name>.<file_extension> [<file_name>.<file_extension> ...]\nThe names may contain the * and ? wildcards; the files are shown in order, each under a header unless -p (or --plain) is given".to_string(),
+                regex: r"^\s*cat(?P<plain>\s+(-p|--plain))?(?P<files>(\s+\S+\.\S+)+)\s*$".to_string(),
             },
         );
 
@@ -112,9 +216,9 @@ impl Default for Config {
             "cp".to_string(),
             Command {
                 name: "cp".to_string(),
-                description: "Copy a file".to_string(),
-                usage: "cp <file_name>.<file_extension> <new_file_name>.<new_file_extension>".to_string(),
-                regex: r"^\s*cp\s+(?P<src_name>[a-zA-Z0-9_]+)(\.(?P<src_extension>\S+))?\s+(?P<dest_name>[a-zA-Z0-9_]+)(\.(?P<dest_extension>\S+))?\s*$".to_string(),
+                description: "Copy a file or a directory".to_string(),
+                usage: "cp [-f] [--verify] [<label>:]<file_name>.<file_extension> [<label>:][<path>/]<new_file_name>.<new_file_extension> or cp [-f] [--verify] <directory_name> [<path>/]<new_directory_name>\n-f: overwrite the destination if it already exists\n--verify: compare the copy against its source (sizes and checksums, recursively) and delete it if they do not match\n<label>: the label of the mounted disk of the file, the main disk if none (only files can be copied across disks)\n<path>: the (relative or absolute) path of the directory to copy into, the working directory if none".to_string(),
+                regex: r"^\s*cp(?P<options>(\s+(-f|--verify))*)\s+((?P<src_label>[a-zA-Z0-9_]+):)?(?P<src_name>[a-zA-Z0-9_]+)(\.(?P<src_extension>\S+))?\s+((?P<dest_label>[a-zA-Z0-9_]+):)?(?P<dest_directory>\S*/)?(?P<dest_name>[a-zA-Z0-9_]+)(\.(?P<dest_extension>\S+))?\s*$".to_string(),
             },
         );
 
@@ -123,10 +227,10 @@ impl Default for Config {
             Command {
                 name: "fmt".to_string(),
                 description:
-                    "Format the disk using the specified FAT cluster size and reboot the system"
+                    "Format the disk using the specified FAT type and cluster size and reboot the system"
                         .to_string(),
-                usage: "fmt 16/32".to_string(),
-                regex: r"^\s*fmt\s+(?P<fat_type>(16|32))\s*$".to_string(),
+                usage: "fmt [-y|--yes] [--dry-run] <12|16|32> [16|32]\n--dry-run: print the geometry of the formatted disk without formatting it\n<12|16|32>: the FAT type, i.e. the width in bits of a FAT cell\n[16|32]: the cluster size in bytes (the current one if not given)".to_string(),
+                regex: r"^\s*fmt(\s+(?P<yes>-y|--yes))?(\s+(?P<dry_run>--dry-run))?\s+(?P<fat_type>(12|16|32))(\s+(?P<cluster_size>(16|32)))?\s*$".to_string(),
             },
         );
 
@@ -145,8 +249,8 @@ impl Default for Config {
             Command {
                 name: "setattr".to_string(),
                 description: "Set the attributes of a file or a directory".to_string(),
-                usage: "setattr <file_name>.<file_extension> <attributes>(max 2 blocks, e.g. +w-h, but not +w-h+h)\n<attributes>:\n\t+w: make read-write\n\t-w: make read-only\n\t+h: make hidden\n\t-h: make visible".to_string(),
-                regex: r"^\s*setattr\s+(?P<name>[a-zA-Z0-9_]+)(\.(?P<extension>\S+))?\s+(?P<attributes>((\+|-)(w|h)){1,2})\s*$".to_string(),
+                usage: "setattr <file_name>.<file_extension> <attributes>(max 4 blocks, e.g. +w-h+s-a, but not +w-h+h)\nThe name may contain the * and ? wildcards (e.g. setattr *.tmp +h), in which case every matching entry is updated\n<attributes>:\n\t+w: make read-write\n\t-w: make read-only\n\t+h: make hidden\n\t-h: make visible\n\t+s: make system\n\t-s: make non-system\n\t+a: set the archive bit\n\t-a: clear the archive bit".to_string(),
+                regex: r"^\s*setattr\s+(?P<name>[a-zA-Z0-9_*?]+)(\.(?P<extension>\S+))?\s+(?P<attributes>\S+)\s*$".to_string(),
             }
         );
 
@@ -165,7 +269,7 @@ impl Default for Config {
             Command {
                 name: "cd".to_string(),
                 description: "Change the current directory".to_string(),
-                usage: "cd <directory_name>".to_string(),
+                usage: "cd <directory_path>\n<directory_path>: a directory name, `.`, `..`, `/` or a path of those separated by `/`, e.g. /docs/reports or ../sibling/child, or `-` for the previous directory".to_string(),
                 regex: r"^\s*cd\s+(?P<name>\S+)\s*$".to_string(),
             },
         );
@@ -184,12 +288,326 @@ impl Default for Config {
             "rmdir".to_string(),
             Command {
                 name: "rmdir".to_string(),
-                description: "Delete a directory".to_string(),
+                description: "Delete an empty directory".to_string(),
                 usage: "rmdir <directory_name>".to_string(),
                 regex: r"^\s*rmdir\s+(?P<name>\S+)\s*$".to_string(),
             },
         );
 
+        commands.insert(
+            "echo".to_string(),
+            Command {
+                name: "echo".to_string(),
+                description: "Write or append a text into a file, creating the file if it doesn't exist".to_string(),
+                usage: "echo \"<text>\" > <file_name>.<file_extension> or echo \"<text>\" >> <file_name>.<file_extension>\n>: overwrite the content of the file\n>>: append to the content of the file".to_string(),
+                regex: r#"^\s*echo\s+"(?P<text>[^"]*)"\s*(?P<redirect>>>?)\s*(?P<name>[a-zA-Z0-9_]+)\.(?P<extension>\S+)\s*$"#.to_string(),
+            },
+        );
+
+        commands.insert(
+            "truncate".to_string(),
+            Command {
+                name: "truncate".to_string(),
+                description: "Shrink or grow a file to a given size, the new bytes being filled with zeros".to_string(),
+                usage: "truncate <file_name>.<file_extension> <file_size>".to_string(),
+                regex: r"^\s*truncate\s+(?P<name>[a-zA-Z0-9_]+)\.(?P<extension>\S+)\s+(?P<size>\d+)\s*$".to_string(),
+            },
+        );
+
+        commands.insert(
+            "chkdsk".to_string(),
+            Command {
+                name: "chkdsk".to_string(),
+                description: "Check the consistency of the FAT table against the directory tree and report any problems".to_string(),
+                usage: "chkdsk".to_string(),
+                regex: r"^\s*chkdsk\s*$".to_string(),
+            },
+        );
+
+        commands.insert(
+            "stat".to_string(),
+            Command {
+                name: "stat".to_string(),
+                description: "Show the metadata of a file or directory (attributes, size, first cluster, creation, modification and access times)".to_string(),
+                usage: "stat <file_name>[.<file_extension>]".to_string(),
+                regex: r"^\s*stat\s+(?P<name>[a-zA-Z0-9_]+)(\.(?P<extension>\S+))?\s*$".to_string(),
+            },
+        );
+
+        commands.insert(
+            "df".to_string(),
+            Command {
+                name: "df".to_string(),
+                description: "Show the total, used and free space of the disk".to_string(),
+                usage: "df".to_string(),
+                regex: r"^\s*df\s*$".to_string(),
+            },
+        );
+
+        commands.insert(
+            "bootinfo".to_string(),
+            Command {
+                name: "bootinfo".to_string(),
+                description: "Dump the fields of the boot sector".to_string(),
+                usage: "bootinfo".to_string(),
+                regex: r"^\s*bootinfo\s*$".to_string(),
+            },
+        );
+
+        commands.insert(
+            "dumpfat".to_string(),
+            Command {
+                name: "dumpfat".to_string(),
+                description: "Show a range of FAT cells with their decoded values".to_string(),
+                usage: "dumpfat [<start> [<count>]]\n<start>: the index of the first FAT cell (defaults to the first cell after the reserved region)\n<count>: the number of FAT cells to show (defaults to 32)".to_string(),
+                regex: r"^\s*dumpfat(\s+(?P<start>\d+)(\s+(?P<count>\d+))?)?\s*$".to_string(),
+            },
+        );
+
+        commands.insert(
+            "export".to_string(),
+            Command {
+                name: "export".to_string(),
+                description: "Copy a file or directory (recursively) to the host filesystem".to_string(),
+                usage: "export <file_name>.<file_extension> <host_path> or export <directory_name> <host_path>\n<host_path>: the path on the host where the file or directory is written (it must not exist)".to_string(),
+                regex: r"^\s*export\s+(?P<name>[a-zA-Z0-9_]+)(\.(?P<extension>[a-zA-Z0-9_]+))?\s+(?P<host_path>\S+)\s*$".to_string(),
+            },
+        );
+
+        commands.insert(
+            "import".to_string(),
+            Command {
+                name: "import".to_string(),
+                description: "Copy a file or directory (recursively) from the host filesystem into the current directory".to_string(),
+                usage: "import <host_path> [<file_name>.<file_extension>] or import <host_path> [<directory_name>]\n<host_path>: the path of the file or directory on the host\nThe name defaults to the host one (invalid characters are replaced with `_` and too long names are truncated)".to_string(),
+                regex: r"^\s*import\s+(?P<host_path>\S+)(\s+(?P<name>[a-zA-Z0-9_]+)(\.(?P<extension>[a-zA-Z0-9_]+))?)?\s*$".to_string(),
+            },
+        );
+
+        commands.insert(
+            "hexdump".to_string(),
+            Command {
+                name: "hexdump".to_string(),
+                description: "Show the raw bytes of a file as rows of offset, hex bytes and ASCII characters".to_string(),
+                usage: "hexdump <file_name>.<file_extension> [<offset> [<length>]]\n<offset>: the offset of the first dumped byte (defaults to 0)\n<length>: the number of dumped bytes (defaults to the rest of the file)".to_string(),
+                regex: r"^\s*hexdump\s+(?P<name>[a-zA-Z0-9_]+)\.(?P<extension>[a-zA-Z0-9_]+)(\s+(?P<offset>\d+)(\s+(?P<length>\d+))?)?\s*$".to_string(),
+            },
+        );
+
+        commands.insert(
+            "cksum".to_string(),
+            Command {
+                name: "cksum".to_string(),
+                description: "Compute the CRC-32 checksum and the size of a file".to_string(),
+                usage: "cksum <file_name>.<file_extension>".to_string(),
+                regex: r"^\s*cksum\s+(?P<name>[a-zA-Z0-9_]+)\.(?P<extension>[a-zA-Z0-9_]+)\s*$"
+                    .to_string(),
+            },
+        );
+
+        commands.insert(
+            "diff".to_string(),
+            Command {
+                name: "diff".to_string(),
+                description: "Compare two files byte by byte".to_string(),
+                usage: "diff <file_name>.<file_extension> <other_file_name>.<other_file_extension>".to_string(),
+                regex: r"^\s*diff\s+(?P<first_name>[a-zA-Z0-9_]+)\.(?P<first_extension>[a-zA-Z0-9_]+)\s+(?P<second_name>[a-zA-Z0-9_]+)\.(?P<second_extension>[a-zA-Z0-9_]+)\s*$".to_string(),
+            },
+        );
+
+        commands.insert(
+            "version".to_string(),
+            Command {
+                name: "version".to_string(),
+                description: "Print the version of the OS".to_string(),
+                usage: "version".to_string(),
+                regex: r"^\s*version\s*$".to_string(),
+            },
+        );
+
+        commands.insert(
+            "fraginfo".to_string(),
+            Command {
+                name: "fraginfo".to_string(),
+                description: "Show the fragmented files and the total fragmentation of the disk"
+                    .to_string(),
+                usage: "fraginfo".to_string(),
+                regex: r"^\s*fraginfo\s*$".to_string(),
+            },
+        );
+
+        commands.insert(
+            "badblocks".to_string(),
+            Command {
+                name: "badblocks".to_string(),
+                description: "Mark a free cluster as bad, so that it is never allocated"
+                    .to_string(),
+                usage: "badblocks <cluster_index>".to_string(),
+                regex: r"^\s*badblocks\s+(?P<cluster_index>\d+)\s*$".to_string(),
+            },
+        );
+
+        commands.insert(
+            "grep".to_string(),
+            Command {
+                name: "grep".to_string(),
+                description: "Search the lines matching a regex inside files".to_string(),
+                usage: "grep [-r] [-i] <pattern> [<file_name>.<file_extension>]\n-r: search the files of the subdirectories of the working directory as well (every file if no file is given)\n-i: ignore the case of the lines\n<pattern>: the regex the lines are matched against (wrapped in double quotes if it contains spaces)\n<file_name>.<file_extension>: the file to search (the name may contain the * and ? wildcards), required unless -r is given".to_string(),
+                regex: r#"^\s*grep(\s+-(?P<recursive>r))?(\s+-(?P<ignore_case>i))?\s+(?P<pattern>"[^"]*"|[^\s"]\S*)(\s+(?P<file_pattern>\S+))?\s*$"#.to_string(),
+            },
+        );
+
+        commands.insert(
+            "head".to_string(),
+            Command {
+                name: "head".to_string(),
+                description: "Display the first lines of a file".to_string(),
+                usage: "head [-n <lines_count>] <file_name>.<file_extension>\n-n <lines_count>: the number of lines to show (defaults to 10)".to_string(),
+                regex: r"^\s*head(\s+-n\s*(?P<lines_count>\d+))?\s+(?P<name>\S+)\.(?P<extension>\S+)\s*$".to_string(),
+            },
+        );
+
+        commands.insert(
+            "tail".to_string(),
+            Command {
+                name: "tail".to_string(),
+                description: "Display the last lines of a file".to_string(),
+                usage: "tail [-n <lines_count>] <file_name>.<file_extension>\n-n <lines_count>: the number of lines to show (defaults to 10)".to_string(),
+                regex: r"^\s*tail(\s+-n\s*(?P<lines_count>\d+))?\s+(?P<name>\S+)\.(?P<extension>\S+)\s*$".to_string(),
+            },
+        );
+
+        commands.insert(
+            "wc".to_string(),
+            Command {
+                name: "wc".to_string(),
+                description: "Count the bytes, lines and words of a file".to_string(),
+                usage: "wc [-c|-l|-w] <file_name>.<file_extension>\n-c: only show the number of bytes\n-l: only show the number of lines (newline characters)\n-w: only show the number of words (whitespace-separated)\nWithout a flag, the lines, the words and the bytes are shown in this order (only the bytes for binary files)".to_string(),
+                regex: r"^\s*wc(\s+-(?P<metric>c|l|w))?\s+(?P<name>\S+)\.(?P<extension>\S+)\s*$".to_string(),
+            },
+        );
+
+        commands.insert(
+            "ln".to_string(),
+            Command {
+                name: "ln".to_string(),
+                description: "Create a hard link to a file".to_string(),
+                usage: "ln <file_name>.<file_extension> <link_name>.<link_extension>\nThe link shares the content of the file, which is only freed once all its links are deleted".to_string(),
+                regex: r"^\s*ln\s+(?P<src_name>\S+)\.(?P<src_extension>\S+)\s+(?P<link_name>\S+)\.(?P<link_extension>\S+)\s*$".to_string(),
+            },
+        );
+
+        commands.insert(
+            "clear".to_string(),
+            Command {
+                name: "clear".to_string(),
+                description: "Clear the terminal".to_string(),
+                usage: "clear\nNothing but a newline is printed when the output is not a terminal"
+                    .to_string(),
+                regex: r"^\s*clear\s*$".to_string(),
+            },
+        );
+
+        commands.insert(
+            "mount".to_string(),
+            Command {
+                name: "mount".to_string(),
+                description: "Mount another disk image under a label".to_string(),
+                usage: "mount [-ro] <image_path> <label>\n-ro: mount the disk read-only, rejecting every command modifying it\n<image_path>: the path of the storage file on the host (a new disk is created if it doesn't exist)\nThe commands address the mounted disk by prefixing an argument with `<label>:`, e.g. `cd <label>:/docs` or `cat <label>:notes.txt`".to_string(),
+                regex: r"^\s*mount(\s+-(?P<read_only>ro))?\s+(?P<image_path>\S+)\s+(?P<label>[a-zA-Z0-9_]+)\s*$".to_string(),
+            },
+        );
+
+        commands.insert(
+            "umount".to_string(),
+            Command {
+                name: "umount".to_string(),
+                description: "Unmount a disk mounted under a label".to_string(),
+                usage: "umount <label>".to_string(),
+                regex: r"^\s*umount\s+(?P<label>[a-zA-Z0-9_]+)\s*$".to_string(),
+            },
+        );
+
+        commands.insert(
+            "resize".to_string(),
+            Command {
+                name: "resize".to_string(),
+                description: "Grow or shrink the disk to a number of clusters, preserving its content".to_string(),
+                usage: "resize <cluster_count>\n<cluster_count>: the new total number of clusters of the disk (the reserved ones included)".to_string(),
+                regex: r"^\s*resize\s+(?P<cluster_count>\d+)\s*$".to_string(),
+            },
+        );
+
+        commands.insert(
+            "time".to_string(),
+            Command {
+                name: "time".to_string(),
+                description: "Run a command and print how long it took along with the clusters it read and wrote".to_string(),
+                usage: "time <command>\n<command>: the command to run, e.g. `time ls -l`\nThe elapsed time and the number of clusters read from and written to the storage files of the disks are printed after it".to_string(),
+                regex: r"^\s*time\s+(?P<command>\S.*?)\s*$".to_string(),
+            },
+        );
+
+        commands.insert(
+            "undo".to_string(),
+            Command {
+                name: "undo".to_string(),
+                description: "Undo the last command modifying the disk".to_string(),
+                usage: "undo\nOnly the last command modifying the disk can be undone, and a mounted disk is undone with `undo <label>:`".to_string(),
+                regex: r"^\s*undo\s*$".to_string(),
+            },
+        );
+
+        commands.insert(
+            "redo".to_string(),
+            Command {
+                name: "redo".to_string(),
+                description: "Redo the last command undone by undo".to_string(),
+                usage: "redo\nOnly the last undone command can be redone, as long as no other command has modified the disk since, and a mounted disk is redone with `redo <label>:`".to_string(),
+                regex: r"^\s*redo\s*$".to_string(),
+            },
+        );
+
+        commands.insert(
+            "label".to_string(),
+            Command {
+                name: "label".to_string(),
+                description: "Show or set the volume label of the disk".to_string(),
+                usage: "label [<new_label>]\n<new_label>: up to 11 letters, digits, `_` and `-` (stored in uppercase), the current label being shown if omitted\nThe label is stored in the spare bytes of the boot sector, so the disk must have 32-byte clusters".to_string(),
+                regex: r"^\s*label(\s+(?P<new_label>\S+))?\s*$".to_string(),
+            },
+        );
+
+        commands.insert(
+            "trash".to_string(),
+            Command {
+                name: "trash".to_string(),
+                description: "List, restore or permanently delete the file entries moved to the trash by del (when use_trash is enabled)".to_string(),
+                usage: "trash [--empty | --restore <name>[.<extension>]]\n\t(no option): list the trashed files and directories\n\t--empty: permanently delete the trashed entries, reclaiming their space\n\t--restore: move a trashed entry back to the working directory".to_string(),
+                regex: r"^\s*trash(\s+(?P<empty>--empty)|\s+--restore\s+(?P<name>[^\s.]+)(\.(?P<extension>\S+))?)?\s*$".to_string(),
+            },
+        );
+
+        commands.insert(
+            "readcluster".to_string(),
+            Command {
+                name: "readcluster".to_string(),
+                description: "Show the raw bytes of a cluster".to_string(),
+                usage: "readcluster <cluster_index>".to_string(),
+                regex: r"^\s*readcluster\s+(?P<cluster_index>\d+)\s*$".to_string(),
+            },
+        );
+
+        commands.insert(
+            "writecluster".to_string(),
+            Command {
+                name: "writecluster".to_string(),
+                description: "Overwrite the raw bytes of a data cluster, without updating the FAT (debugging tool)".to_string(),
+                usage: "writecluster --unsafe <cluster_index> <hex_bytes>\n--unsafe: required, as the FAT and the directory tables are not updated\n<hex_bytes>: the bytes written at the start of the cluster, as pairs of hex digits (the rest of the cluster is zero-filled)".to_string(),
+                regex: r"^\s*writecluster(?P<unsafe>\s+--unsafe)?\s+(?P<cluster_index>\d+)\s+(?P<data>[0-9a-fA-F]+)\s*$".to_string(),
+            },
+        );
+
         Self {
             os: "RoDOS".to_string(),
             version: "0.1.0".to_string(),
@@ -200,6 +618,16 @@ impl Default for Config {
             storage_file_path: "disk/storage.bin".to_string(),
             stdin_file_path: "disk/stdin.in".to_string(),
             temp_file_path: "disk/temp".to_string(),
+            history_file_path: default_history_file_path(),
+            history_size: default_history_size(),
+            confirm_destructive: default_confirm_destructive(),
+            audit_log_file_path: default_audit_log_file_path(),
+            autosave_delay_ms: 0,
+            read_only: false,
+            use_trash: false,
+            max_directory_entries: 0,
+            random_seed: None,
+            disk: DiskGeometry::default(),
         }
     }
 }
@@ -210,6 +638,9 @@ impl Default for Config {
 /// - `user`: the user name
 /// - `path_prefix`: the prefix of the path
 /// - `terminator`: the terminator of the prompt
+/// - `max_path_segments`: the number of trailing segments of the working directory path shown, the leading ones being
+///   replaced by an ellipsis (0 to show the whole path)
+/// - `show_free_space`: whether to show the free space of the disk after the path
 #[derive(Debug, Clone, Deserialize)]
 pub(crate) struct Prompt {
     pub(crate) host: String,
@@ -217,6 +648,38 @@ pub(crate) struct Prompt {
     pub(crate) user: String,
     pub(crate) path_prefix: String,
     pub(crate) terminator: String,
+    #[serde(default)]
+    pub(crate) max_path_segments: usize,
+    #[serde(default)]
+    pub(crate) show_free_space: bool,
+}
+
+impl Prompt {
+    /// Renders the working directory path as shown by the prompt, keeping only its last `max_path_segments` segments
+    /// behind an ellipsis (e.g. `…/c/d` for `/a/b/c/d` with 2 segments).
+    pub(crate) fn render_path(&self, working_directory_path: &str) -> String {
+        let segments: Vec<&str> = working_directory_path
+            .split('/')
+            .filter(|segment| !segment.is_empty())
+            .collect();
+
+        if self.max_path_segments == 0 || segments.len() <= self.max_path_segments {
+            return working_directory_path.to_string();
+        }
+
+        format!(
+            "…/{}",
+            segments[segments.len() - self.max_path_segments..].join("/")
+        )
+    }
+
+    /// Renders the free space indicator shown after the path (e.g. ` [1024 B free]`), empty if it is disabled.
+    pub(crate) fn render_free_space(&self, free_space: u64) -> String {
+        match self.show_free_space {
+            true => format!(" [{} B free]", free_space),
+            false => String::new(),
+        }
+    }
 }
 
 impl Default for Prompt {
@@ -227,7 +690,63 @@ impl Default for Prompt {
             user: "rouser".to_string(),
             path_prefix: ":".to_string(),
             terminator: "$".to_string(),
+            max_path_segments: 0,
+            show_free_space: false,
+        }
+    }
+}
+
+/// The geometry of a newly created disk, every missing field falling back to the `BootSector` default:
+/// - `fat_type`: the width in bits of a FAT cell (12, 16 or 32)
+/// - `cluster_size`: the number of bytes per cluster (16 or 32)
+/// - `cluster_count`: the total number of clusters
+/// - `root_entry_count`: the number of file entries in the root directory
+#[derive(Debug, Clone, Default, Deserialize)]
+pub(crate) struct DiskGeometry {
+    pub(crate) fat_type: Option<u16>,
+    pub(crate) cluster_size: Option<u16>,
+    pub(crate) cluster_count: Option<u16>,
+    pub(crate) root_entry_count: Option<u16>,
+}
+
+impl DiskGeometry {
+    /// Builds the boot sector of a new disk from the configured geometry, checking that it is a valid one
+    /// and that the root table fills a whole number of clusters.
+    pub(crate) fn to_boot_sector(&self) -> Result<BootSector, String> {
+        let default_boot_sector = BootSector::default();
+        let boot_sector = BootSector {
+            fat_cell_bits: self.fat_type.unwrap_or(default_boot_sector.fat_cell_bits),
+            cluster_size: self
+                .cluster_size
+                .unwrap_or(default_boot_sector.cluster_size),
+            cluster_count: self
+                .cluster_count
+                .unwrap_or(default_boot_sector.cluster_count),
+            root_entry_count: self
+                .root_entry_count
+                .unwrap_or(default_boot_sector.root_entry_count),
+            ..default_boot_sector
+        };
+
+        if boot_sector.cluster_size != 16 && boot_sector.cluster_size != 32 {
+            return Err(format!(
+                "cluster_size must be 16 or 32, got {}",
+                boot_sector.cluster_size
+            ));
+        }
+
+        boot_sector.validate()?;
+
+        let root_size =
+            boot_sector.root_entry_cell_size as u32 * boot_sector.root_entry_count as u32;
+        if root_size % boot_sector.cluster_size as u32 != 0 {
+            return Err(format!(
+                "the root table size ({} B) must be a multiple of cluster_size ({} B)",
+                root_size, boot_sector.cluster_size
+            ));
         }
+
+        Ok(boot_sector)
     }
 }
 
@@ -244,5 +763,5 @@ pub(crate) struct Command {
     pub(crate) regex: String,
 }
 
-/// A hashmap of commands
-pub(crate) type Commands = HashMap<String, Command>;
+/// The commands by name, sorted so that iterating over them gives a stable, alphabetical order
+pub(crate) type Commands = BTreeMap<String, Command>;
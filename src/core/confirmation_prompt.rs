@@ -0,0 +1,51 @@
+use std::error::Error;
+use std::io::Write;
+
+/// IConfirmationPrompt is the source of the answers to the yes/no questions asked before running destructive commands
+/// (abstracted away so that the answers can be provided by something other than the user, e.g. a script or a mock).
+pub(crate) trait IConfirmationPrompt: Send {
+    /// Asks the given yes/no question and returns whether the answer is affirmative.
+    fn confirm(&mut self, question: &str) -> Result<bool, Box<dyn Error>>;
+}
+
+/// StdinConfirmationPrompt prints the question to stdout and reads the answer from stdin
+/// (anything but `y` is considered a no).
+pub(crate) struct StdinConfirmationPrompt;
+
+impl IConfirmationPrompt for StdinConfirmationPrompt {
+    fn confirm(&mut self, question: &str) -> Result<bool, Box<dyn Error>> {
+        print!("{} [y/N] ", question);
+        std::io::stdout().flush()?;
+
+        let mut answer = String::new();
+        std::io::stdin().read_line(&mut answer)?;
+
+        Ok(answer.trim().eq_ignore_ascii_case("y"))
+    }
+}
+
+/// ScriptedConfirmationPrompt gives the same answer to every question, recording the questions asked
+/// (so that the tests of the destructive commands can check what they ask).
+#[cfg(test)]
+pub(crate) struct ScriptedConfirmationPrompt {
+    answer: bool,
+    pub(crate) questions: std::sync::Arc<std::sync::Mutex<Vec<String>>>,
+}
+
+#[cfg(test)]
+impl ScriptedConfirmationPrompt {
+    pub(crate) fn new(answer: bool) -> Self {
+        Self {
+            answer,
+            questions: Default::default(),
+        }
+    }
+}
+
+#[cfg(test)]
+impl IConfirmationPrompt for ScriptedConfirmationPrompt {
+    fn confirm(&mut self, question: &str) -> Result<bool, Box<dyn Error>> {
+        self.questions.lock().unwrap().push(question.to_string());
+        Ok(self.answer)
+    }
+}
@@ -1,4 +1,5 @@
-use crate::CONFIG;
+use crate::core::config::Config;
+use chrono::Utc;
 use std::fmt::Display;
 use std::str::FromStr;
 
@@ -13,6 +14,9 @@ const HEX_CHARS: [char; 16] = [
     '0', '1', '2', '3', '4', '5', '6', '7', '8', '9', 'A', 'B', 'C', 'D', 'E', 'F',
 ];
 
+/// The environment variable overriding the seed of the random content (`random_seed` in the config)
+const RANDOM_SEED_VAR: &str = "RODOS_SEED";
+
 /// ContentGenerator is used to generate content based on the content type
 pub(crate) struct ContentGenerator;
 
@@ -53,6 +57,57 @@ impl ContentGenerator {
         result
     }
 
+    /// generate_pattern generates a cyclic vector of bytes repeating the given pattern
+    fn generate_pattern(pattern: &str, size: u32) -> Vec<u8> {
+        pattern.bytes().cycle().take(size as usize).collect()
+    }
+
+    /// generate_random generates a vector of pseudo-random bytes (SplitMix64), the same seed always giving the same bytes
+    fn generate_random(size: u32, seed: u64) -> Vec<u8> {
+        let mut state = seed;
+        let mut next_word = || {
+            state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+            let mut word = state;
+            word = (word ^ (word >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+            word = (word ^ (word >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+            word ^ (word >> 31)
+        };
+
+        let mut result = Vec::with_capacity(size as usize);
+        while result.len() < size as usize {
+            result.extend_from_slice(&next_word().to_le_bytes());
+        }
+        result.truncate(size as usize);
+
+        result
+    }
+
+    /// generate_zero generates a vector of zero bytes
+    fn generate_zero(size: u32) -> Vec<u8> {
+        vec![0; size as usize]
+    }
+
+    /// random_seed returns the seed of the random content: the `RODOS_SEED` environment variable if set,
+    /// else the `random_seed` of the config, else the current time (a different content on every run)
+    fn random_seed(config: &Config) -> u64 {
+        match std::env::var(RANDOM_SEED_VAR).map(|seed| seed.trim().parse::<u64>()) {
+            Ok(Ok(seed)) => seed,
+            Ok(Err(_)) => {
+                log::warn!(
+                    "Ignoring the invalid {} environment variable",
+                    RANDOM_SEED_VAR
+                );
+                config.random_seed.unwrap_or_else(Self::clock_seed)
+            }
+            Err(_) => config.random_seed.unwrap_or_else(Self::clock_seed),
+        }
+    }
+
+    /// clock_seed returns a seed derived from the current time
+    fn clock_seed() -> u64 {
+        Utc::now().timestamp_nanos() as u64
+    }
+
     /// generate_from_file generates a vector of bytes containing the content of a file
     fn generate_from_file(file_path: &str) -> Vec<u8> {
         let mut result = Vec::new();
@@ -66,14 +121,17 @@ impl ContentGenerator {
     }
 
     /// launch the generation of content based on the content type
-    pub(crate) fn generate(content_type: ContentType, size: u32) -> Vec<u8> {
+    /// (the stdin and temp contents are read from the files of the given config)
+    pub(crate) fn generate(content_type: ContentType, size: u32, config: &Config) -> Vec<u8> {
         match content_type {
             ContentType::Alpha => Self::generate_alpha(size),
             ContentType::Num => Self::generate_num(size),
             ContentType::Hex => Self::generate_hex(size),
-            ContentType::Stdin => Self::generate_from_file(CONFIG.stdin_file_path.as_str()),
-            ContentType::Temp => Self::generate_from_file(CONFIG.temp_file_path.as_str()),
-            ContentType::Unknown => Vec::default(),
+            ContentType::Pattern(pattern) => Self::generate_pattern(&pattern, size),
+            ContentType::Random => Self::generate_random(size, Self::random_seed(config)),
+            ContentType::Zero => Self::generate_zero(size),
+            ContentType::Stdin => Self::generate_from_file(config.stdin_file_path.as_str()),
+            ContentType::Temp => Self::generate_from_file(config.temp_file_path.as_str()),
         }
     }
 }
@@ -82,16 +140,21 @@ impl ContentGenerator {
 /// - Alpha: A-Z
 /// - Num: 0-9
 /// - Hex: 0-F
+/// - Pattern: The given literal pattern, repeated
+/// - Random: Pseudo-random bytes (reproducible with a fixed seed)
+/// - Zero: Zero bytes
+/// - Stdin: Content read from the user input and saved into the stdin file
 /// - Temp: Content from the temp buffer file (used especially for the defragmentation)
-#[derive(Debug, PartialEq, Clone, Copy)]
+#[derive(Debug, PartialEq, Clone)]
 pub(crate) enum ContentType {
     Alpha,
     Num,
     Hex,
-    #[allow(dead_code)]
+    Pattern(String),
+    Random,
+    Zero,
     Stdin,
     Temp,
-    Unknown,
 }
 
 impl FromStr for ContentType {
@@ -102,7 +165,17 @@ impl FromStr for ContentType {
             "alpha" => Ok(ContentType::Alpha),
             "num" => Ok(ContentType::Num),
             "hex" => Ok(ContentType::Hex),
-            _ => Ok(ContentType::Unknown),
+            "random" => Ok(ContentType::Random),
+            "zero" => Ok(ContentType::Zero),
+            "stdin" => Ok(ContentType::Stdin),
+            _ if s.starts_with("pattern=") => match &s["pattern=".len()..] {
+                "" => Err("The pattern must not be empty!".to_string()),
+                pattern => Ok(ContentType::Pattern(pattern.to_string())),
+            },
+            _ => Err(format!(
+                "Invalid content type {}, expected one of alpha, num, hex, pattern=<pattern>, random, zero or stdin!",
+                s
+            )),
         }
     }
 }
@@ -113,9 +186,141 @@ impl Display for ContentType {
             ContentType::Alpha => write!(f, "alpha"),
             ContentType::Num => write!(f, "num"),
             ContentType::Hex => write!(f, "hex"),
+            ContentType::Pattern(pattern) => write!(f, "pattern={}", pattern),
+            ContentType::Random => write!(f, "random"),
+            ContentType::Zero => write!(f, "zero"),
             ContentType::Stdin => write!(f, "stdin"),
             ContentType::Temp => write!(f, "temp"),
-            ContentType::Unknown => write!(f, "unknown"),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stdin_content_type_round_trips_through_its_name() {
+        assert_eq!("stdin".parse::<ContentType>(), Ok(ContentType::Stdin));
+        assert_eq!(ContentType::Stdin.to_string(), "stdin");
+    }
+
+    #[test]
+    fn the_content_types_of_create_are_parsed() {
+        assert_eq!("alpha".parse::<ContentType>(), Ok(ContentType::Alpha));
+        assert_eq!("num".parse::<ContentType>(), Ok(ContentType::Num));
+        assert_eq!("hex".parse::<ContentType>(), Ok(ContentType::Hex));
+        assert_eq!("stdin".parse::<ContentType>(), Ok(ContentType::Stdin));
+    }
+
+    #[test]
+    fn the_cyclic_content_types_yield_their_alphabet() {
+        let config = Config::default();
+        let generate = |content_type| {
+            String::from_utf8(ContentGenerator::generate(content_type, 40, &config)).unwrap()
+        };
+
+        assert_eq!(
+            generate(ContentType::Alpha),
+            "ABCDEFGHIJKLMNOPQRSTUVWXYZABCDEFGHIJKLMN"
+        );
+        assert_eq!(
+            generate(ContentType::Num),
+            "0123456789012345678901234567890123456789"
+        );
+        assert_eq!(
+            generate(ContentType::Hex),
+            "0123456789ABCDEF0123456789ABCDEF01234567"
+        );
+    }
+
+    #[test]
+    fn the_same_seed_gives_the_same_random_content_of_the_requested_size() {
+        let config = |random_seed| Config {
+            random_seed: Some(random_seed),
+            ..Config::default()
+        };
+
+        for size in [0, 1, 7, 8, 9, 1000] {
+            let content = ContentGenerator::generate(ContentType::Random, size, &config(42));
+
+            assert_eq!(content.len(), size as usize);
+            assert_eq!(
+                ContentGenerator::generate(ContentType::Random, size, &config(42)),
+                content
+            );
+        }
+        assert_ne!(
+            ContentGenerator::generate(ContentType::Random, 64, &config(42)),
+            ContentGenerator::generate(ContentType::Random, 64, &config(43))
+        );
+        // the shorter content is the prefix of the longer one, the bytes being drawn in the same order
+        assert_eq!(
+            ContentGenerator::generate(ContentType::Random, 9, &config(7))[..],
+            ContentGenerator::generate(ContentType::Random, 100, &config(7))[..9]
+        );
+    }
+
+    #[test]
+    fn the_zero_content_is_made_of_zero_bytes() {
+        assert_eq!("random".parse::<ContentType>(), Ok(ContentType::Random));
+        assert_eq!("zero".parse::<ContentType>(), Ok(ContentType::Zero));
+
+        assert_eq!(
+            ContentGenerator::generate(ContentType::Zero, 20, &Config::default()),
+            vec![0; 20]
+        );
+    }
+
+    #[test]
+    fn the_pattern_is_repeated_to_the_requested_size() {
+        let pattern: ContentType = "pattern=ABC".parse().unwrap();
+        assert_eq!(pattern, ContentType::Pattern("ABC".to_string()));
+        assert_eq!(pattern.to_string(), "pattern=ABC");
+
+        for (size, expected_content) in
+            [(0, ""), (2, "AB"), (3, "ABC"), (20, "ABCABCABCABCABCABCAB")]
+        {
+            assert_eq!(
+                ContentGenerator::generate(pattern.clone(), size, &Config::default()),
+                expected_content.as_bytes()
+            );
+        }
+    }
+
+    #[test]
+    fn an_empty_pattern_is_rejected() {
+        assert_eq!(
+            "pattern=".parse::<ContentType>(),
+            Err("The pattern must not be empty!".to_string())
+        );
+    }
+
+    #[test]
+    fn an_unknown_content_type_is_rejected() {
+        for content_type in ["foo", "", "Alpha", "temp"] {
+            assert!(
+                content_type.parse::<ContentType>().is_err(),
+                "{}",
+                content_type
+            );
+        }
+    }
+
+    #[test]
+    fn stdin_content_is_read_from_the_stdin_file() {
+        let stdin_file_path =
+            std::env::temp_dir().join(format!("rodos-test-{}-content-type.in", std::process::id()));
+        std::fs::write(&stdin_file_path, "typed content").unwrap();
+        let config = Config {
+            stdin_file_path: stdin_file_path.to_string_lossy().into_owned(),
+            ..Config::default()
+        };
+
+        // the requested size is ignored, the whole stdin file being the content
+        let content = ContentGenerator::generate(ContentType::Stdin, 3, &config);
+        std::fs::remove_file(&stdin_file_path).unwrap();
+
+        assert_eq!(content, b"typed content");
+    }
+}
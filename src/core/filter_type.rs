@@ -1,12 +1,24 @@
+use chrono::{DateTime, Utc};
+
 /// FilterType is used to filter the output of the ls command:
 /// - `Name`: filter by name
 /// - `Extension`: filter by extension
 /// - `Files`: show only files
 /// - `Directories`: show only directories
 /// - `InShortFormat`: show in short format (just the name and extension)
-/// - `InLongFormat`: show in long format (name, extension, size, date, etc.)
+/// - `InLongFormat`: show in long format (attributes, full name, size, first cluster, clusters count and timestamp)
 /// - `AllAndHidden`: show all files and hidden files
 /// - `All`: show all files
+/// - `Recursive`: descend into subdirectories as well (depth-first)
+/// - `Glob`: filter by a wildcard pattern matched against the full name (e.g. `*.txt`)
+/// - `Json`: print the listing as a JSON array instead of the human-readable format
+/// - `MinSize`: show only the entries of at least the given size in bytes
+/// - `MaxSize`: show only the entries of at most the given size in bytes
+/// - `After`: show only the entries last modified at or after the given datetime
+/// - `Before`: show only the entries last modified strictly before the given datetime
+/// - `ReadOnly`: show only the read-only entries
+/// - `Hidden`: show only the hidden entries
+/// - `System`: show only the system entries
 #[derive(Debug, Clone, PartialEq)]
 pub(crate) enum FilterType {
     Name(String),
@@ -17,6 +29,16 @@ pub(crate) enum FilterType {
     InLongFormat,
     AllAndHidden,
     All,
+    Recursive,
+    Glob(String),
+    Json,
+    MinSize(u32),
+    MaxSize(u32),
+    After(DateTime<Utc>),
+    Before(DateTime<Utc>),
+    ReadOnly,
+    Hidden,
+    System,
 }
 
 impl Default for FilterType {
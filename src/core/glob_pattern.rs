@@ -0,0 +1,73 @@
+use regex::Regex;
+
+/// GlobPattern is a shell-like wildcard pattern matched against the full names of file entries
+/// (`name.extension` for files and `name` for directories):
+/// - `*`: matches any sequence of characters, including an empty one
+/// - `?`: matches exactly one character
+///
+/// The matching is case-insensitive, just like the lookup of file entries by name.
+pub(crate) struct GlobPattern {
+    regex: Regex,
+}
+
+impl GlobPattern {
+    pub(crate) fn new(pattern: &str) -> Self {
+        // escape everything but the wildcards which are translated into their regex counterparts
+        let regex = pattern
+            .chars()
+            .map(|c| match c {
+                '*' => ".*".to_string(),
+                '?' => ".".to_string(),
+                c => regex::escape(&c.to_string()),
+            })
+            .collect::<String>();
+
+        Self {
+            regex: Regex::new(format!("(?i)^{}$", regex).as_str()).unwrap(),
+        }
+    }
+
+    /// Checks whether the given pattern contains any wildcard (otherwise it is a literal name).
+    pub(crate) fn is_glob(pattern: &str) -> bool {
+        pattern.contains(['*', '?'])
+    }
+
+    pub(crate) fn is_match(&self, full_name: &str) -> bool {
+        self.regex.is_match(full_name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_star_matches_any_sequence_of_characters() {
+        let pattern = GlobPattern::new("tmp*.log");
+
+        assert!(pattern.is_match("tmp.log"));
+        assert!(pattern.is_match("tmp123.log"));
+        assert!(pattern.is_match("TMP1.LOG"));
+        assert!(!pattern.is_match("tmp1.txt"));
+        assert!(!pattern.is_match("old_tmp1.log"));
+    }
+
+    #[test]
+    fn a_question_mark_matches_exactly_one_character() {
+        let pattern = GlobPattern::new("read?.md");
+
+        assert!(pattern.is_match("read1.md"));
+        assert!(!pattern.is_match("read.md"));
+        assert!(!pattern.is_match("read12.md"));
+    }
+
+    #[test]
+    fn the_other_characters_match_literally() {
+        let pattern = GlobPattern::new("a+b.(c)");
+
+        assert!(pattern.is_match("a+b.(c)"));
+        assert!(!pattern.is_match("aab.(c)"));
+        assert!(!GlobPattern::is_glob("a+b.(c)"));
+        assert!(GlobPattern::is_glob("a?b.c"));
+    }
+}
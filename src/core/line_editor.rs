@@ -0,0 +1,359 @@
+use crate::core::command_history::CommandHistory;
+use crate::core::completion::{get_common_prefix, get_word_start, Completer};
+use std::io::{self, BufRead, IsTerminal, Read, Write};
+
+/// LineEditor reads the commands typed in the REPL, supporting in-line editing (left/right arrows, home/end,
+/// backspace/delete), history recall (up/down arrows) and tab completion when stdin is a terminal.
+/// Otherwise (e.g. when the input is piped), lines are read as they are.
+/// # Fields
+/// * `history` - the commands typed so far
+/// * `history_file_path` - the file the history is persisted to after every command
+/// * `completer` - the source of the tab completions
+/// * `print_prompt` - prints the prompt again after the ambiguous completions are listed
+pub(crate) struct LineEditor {
+    history: CommandHistory,
+    history_file_path: String,
+    completer: Completer,
+    print_prompt: fn(),
+}
+
+/// Key is a key press decoded from the bytes read from a terminal in raw mode
+enum Key {
+    Char(char),
+    Enter,
+    Tab,
+    Backspace,
+    Delete,
+    Left,
+    Right,
+    Up,
+    Down,
+    Home,
+    End,
+    ClearLine,
+    EndOfFile,
+    Ignored,
+}
+
+impl LineEditor {
+    pub(crate) fn new(
+        history_file_path: String,
+        history_size: usize,
+        completer: Completer,
+        print_prompt: fn(),
+    ) -> Self {
+        let history = match CommandHistory::load(&history_file_path, history_size) {
+            Ok(history) => history,
+            Err(err) => {
+                log::warn!(
+                    "Unable to load the history from {}: {}",
+                    history_file_path,
+                    err
+                );
+                CommandHistory::new(history_size)
+            }
+        };
+
+        Self {
+            history,
+            history_file_path,
+            completer,
+            print_prompt,
+        }
+    }
+
+    /// Reads a line (the prompt is expected to be printed already) and records it in the history.
+    /// Returns `None` once the input is exhausted (or on Ctrl-D on an empty line).
+    pub(crate) fn read_line(&mut self) -> io::Result<Option<String>> {
+        let line = if io::stdin().is_terminal() {
+            match raw_mode::RawModeGuard::enable() {
+                Ok(_guard) => self.read_line_interactively()?,
+                Err(err) => {
+                    log::warn!("Unable to switch the terminal to raw mode: {}", err);
+                    read_plain_line()?
+                }
+            }
+        } else {
+            read_plain_line()?
+        };
+
+        if let Some(line) = &line {
+            if self.history.add(line) {
+                if let Err(err) = self.history.save(&self.history_file_path) {
+                    log::warn!(
+                        "Unable to save the history to {}: {}",
+                        self.history_file_path,
+                        err
+                    );
+                }
+            }
+        }
+
+        Ok(line)
+    }
+
+    fn read_line_interactively(&mut self) -> io::Result<Option<String>> {
+        let mut stdin = io::stdin().lock();
+        let mut stdout = io::stdout();
+        let mut buffer: Vec<char> = vec![];
+        let mut cursor = 0;
+
+        loop {
+            let previous_cursor = cursor;
+
+            match read_key(&mut stdin)? {
+                Key::Char(c) => {
+                    buffer.insert(cursor, c);
+                    cursor += 1;
+                }
+                Key::Enter => {
+                    write!(stdout, "\r\n")?;
+                    stdout.flush()?;
+                    return Ok(Some(buffer.into_iter().collect()));
+                }
+                Key::Tab => {
+                    let (completed_buffer, completed_cursor) =
+                        self.complete(&mut stdout, &buffer, cursor)?;
+                    buffer = completed_buffer;
+                    cursor = completed_cursor;
+                }
+                Key::Backspace if cursor > 0 => {
+                    cursor -= 1;
+                    buffer.remove(cursor);
+                }
+                Key::Delete if cursor < buffer.len() => {
+                    buffer.remove(cursor);
+                }
+                Key::Left if cursor > 0 => cursor -= 1,
+                Key::Right if cursor < buffer.len() => cursor += 1,
+                Key::Home => cursor = 0,
+                Key::End => cursor = buffer.len(),
+                Key::Up => {
+                    let current_line: String = buffer.iter().collect();
+                    if let Some(entry) = self.history.older(&current_line) {
+                        buffer = entry.chars().collect();
+                        cursor = buffer.len();
+                    }
+                }
+                Key::Down => {
+                    if let Some(entry) = self.history.newer() {
+                        buffer = entry.chars().collect();
+                        cursor = buffer.len();
+                    }
+                }
+                Key::ClearLine => {
+                    buffer.clear();
+                    cursor = 0;
+                }
+                Key::EndOfFile if buffer.is_empty() => {
+                    write!(stdout, "\r\n")?;
+                    stdout.flush()?;
+                    return Ok(None);
+                }
+                _ => continue,
+            }
+
+            redraw(&mut stdout, &buffer, previous_cursor, cursor)?;
+        }
+    }
+
+    /// Completes the word before the cursor and returns the new buffer and cursor:
+    /// - a single candidate replaces the word (followed by a space)
+    /// - several candidates extend the word to their common prefix, or are listed below the line (followed by the
+    ///   prompt and the line again) if there is nothing left to extend
+    /// - no candidate rings the bell
+    fn complete(
+        &self,
+        stdout: &mut impl Write,
+        buffer: &[char],
+        cursor: usize,
+    ) -> io::Result<(Vec<char>, usize)> {
+        let input: String = buffer.iter().collect();
+        let completions = self.completer.complete(&input, cursor);
+        let word_start = get_word_start(buffer, cursor);
+
+        let replacement = match completions.len() {
+            0 => {
+                write!(stdout, "\x07")?;
+                return Ok((buffer.to_vec(), cursor));
+            }
+            1 => format!("{} ", completions[0]),
+            _ => {
+                let common_prefix = get_common_prefix(&completions);
+                if common_prefix.chars().count() <= cursor - word_start {
+                    write!(stdout, "\r\n{}\r\n", completions.join("  "))?;
+                    (self.print_prompt)();
+                    write!(stdout, "{}", input)?;
+                    // move back to where the cursor was, as expected by the redraw
+                    if buffer.len() > cursor {
+                        write!(stdout, "\x1b[{}D", buffer.len() - cursor)?;
+                    }
+                    return Ok((buffer.to_vec(), cursor));
+                }
+                common_prefix
+            }
+        };
+
+        let mut completed_buffer: Vec<char> = buffer[..word_start].to_vec();
+        completed_buffer.extend(replacement.chars());
+        let completed_cursor = completed_buffer.len();
+        completed_buffer.extend_from_slice(&buffer[cursor..]);
+
+        Ok((completed_buffer, completed_cursor))
+    }
+}
+
+/// Reads a line as it is (used when stdin is not a terminal), returning `None` at the end of the input.
+fn read_plain_line() -> io::Result<Option<String>> {
+    let mut line = String::new();
+
+    match io::stdin().lock().read_line(&mut line)? {
+        0 => Ok(None),
+        _ => Ok(Some(line)),
+    }
+}
+
+/// Rewrites the edited line in place: moves back to its beginning (where the cursor was before the key press),
+/// prints it again, clears whatever was left from the previous version and moves to the new cursor position.
+fn redraw(
+    stdout: &mut impl Write,
+    buffer: &[char],
+    previous_cursor: usize,
+    cursor: usize,
+) -> io::Result<()> {
+    if previous_cursor > 0 {
+        write!(stdout, "\x1b[{}D", previous_cursor)?;
+    }
+    write!(stdout, "{}\x1b[K", buffer.iter().collect::<String>())?;
+    if buffer.len() > cursor {
+        write!(stdout, "\x1b[{}D", buffer.len() - cursor)?;
+    }
+
+    stdout.flush()
+}
+
+fn read_byte(stdin: &mut impl Read) -> io::Result<Option<u8>> {
+    let mut byte = [0u8; 1];
+
+    match stdin.read(&mut byte)? {
+        0 => Ok(None),
+        _ => Ok(Some(byte[0])),
+    }
+}
+
+/// Decodes the next key press: control characters, ANSI escape sequences and UTF-8 encoded characters.
+fn read_key(stdin: &mut impl Read) -> io::Result<Key> {
+    let byte = match read_byte(stdin)? {
+        Some(byte) => byte,
+        None => return Ok(Key::EndOfFile),
+    };
+
+    let key = match byte {
+        b'\r' | b'\n' => Key::Enter,
+        b'\t' => Key::Tab,
+        0x7f | 0x08 => Key::Backspace,
+        0x01 => Key::Home,
+        0x05 => Key::End,
+        0x15 => Key::ClearLine,
+        0x04 => Key::EndOfFile,
+        0x1b => match (read_byte(stdin)?, read_byte(stdin)?) {
+            (Some(b'['), Some(b'A')) => Key::Up,
+            (Some(b'['), Some(b'B')) => Key::Down,
+            (Some(b'['), Some(b'C')) => Key::Right,
+            (Some(b'['), Some(b'D')) => Key::Left,
+            (Some(b'[' | b'O'), Some(b'H')) => Key::Home,
+            (Some(b'[' | b'O'), Some(b'F')) => Key::End,
+            (Some(b'['), Some(b'3')) => match read_byte(stdin)? {
+                Some(b'~') => Key::Delete,
+                _ => Key::Ignored,
+            },
+            _ => Key::Ignored,
+        },
+        byte if byte < 0x20 => Key::Ignored,
+        byte => {
+            // the number of continuation bytes is given by the leading byte of a UTF-8 sequence
+            let length = match byte {
+                0xc0..=0xdf => 2,
+                0xe0..=0xef => 3,
+                0xf0..=0xf7 => 4,
+                _ => 1,
+            };
+
+            let mut bytes = vec![byte];
+            for _ in 1..length {
+                match read_byte(stdin)? {
+                    Some(byte) => bytes.push(byte),
+                    None => break,
+                }
+            }
+
+            match std::str::from_utf8(&bytes)
+                .ok()
+                .and_then(|s| s.chars().next())
+            {
+                Some(c) => Key::Char(c),
+                None => Key::Ignored,
+            }
+        }
+    };
+
+    Ok(key)
+}
+
+/// Switching the terminal to raw mode (no line buffering, no echo) while a line is being edited.
+#[cfg(unix)]
+mod raw_mode {
+    use std::io;
+
+    /// RawModeGuard restores the original terminal settings when dropped
+    pub(super) struct RawModeGuard {
+        original: libc::termios,
+    }
+
+    impl RawModeGuard {
+        pub(super) fn enable() -> io::Result<Self> {
+            // SAFETY: termios is a plain C struct filled in by tcgetattr before being used
+            let mut original: libc::termios = unsafe { std::mem::zeroed() };
+            if unsafe { libc::tcgetattr(libc::STDIN_FILENO, &mut original) } != 0 {
+                return Err(io::Error::last_os_error());
+            }
+
+            // keep the signals (Ctrl-C still interrupts the shell) and the output processing
+            let mut raw = original;
+            raw.c_lflag &= !(libc::ICANON | libc::ECHO);
+            raw.c_iflag &= !libc::ICRNL;
+            raw.c_cc[libc::VMIN] = 1;
+            raw.c_cc[libc::VTIME] = 0;
+            if unsafe { libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, &raw) } != 0 {
+                return Err(io::Error::last_os_error());
+            }
+
+            Ok(Self { original })
+        }
+    }
+
+    impl Drop for RawModeGuard {
+        fn drop(&mut self) {
+            unsafe {
+                libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, &self.original);
+            }
+        }
+    }
+}
+
+/// Raw mode is not supported on this platform, so lines are read as they are.
+#[cfg(not(unix))]
+mod raw_mode {
+    use std::io;
+
+    pub(super) struct RawModeGuard;
+
+    impl RawModeGuard {
+        pub(super) fn enable() -> io::Result<Self> {
+            Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                "raw mode is not supported on this platform",
+            ))
+        }
+    }
+}
@@ -0,0 +1,310 @@
+use crate::application::Void;
+use crate::core::Arm;
+use crate::domain::i_disk_manager::IDiskManager;
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::path::{Path, PathBuf};
+
+/// Opens (or creates, if the file doesn't exist or is empty) the disk stored in the given storage file,
+/// read-only if asked to (in which case the storage file must already hold a disk).
+pub(crate) type DiskOpener = fn(&str, bool) -> Result<Arm<dyn IDiskManager>, String>;
+
+/// A command line routed to a mounted disk: the disk manager of the disk and the command line to run against it.
+pub(crate) type RoutedCommand = (Arm<dyn IDiskManager>, String);
+
+/// MountedDisk is a disk mounted next to the main one
+/// # Fields
+/// * `image_path` - the path of the storage file of the disk, as given when mounting it
+/// * `disk_manager` - the disk manager of the disk
+struct MountedDisk {
+    image_path: String,
+    disk_manager: Arm<dyn IDiskManager>,
+}
+
+/// MountTable is the registry of the disks mounted next to the main one, keyed by their labels.
+///
+/// A command addresses a mounted disk by prefixing one of its arguments with the label of the disk
+/// (e.g. `cd data:/docs` or `cat data:notes.txt`), in which case it is run against that disk instead of the main one.
+pub(crate) struct MountTable {
+    main_image_path: String,
+    open_disk: DiskOpener,
+    mounted_disks: BTreeMap<String, MountedDisk>,
+}
+
+impl MountTable {
+    pub(crate) fn new(main_image_path: String, open_disk: DiskOpener) -> Self {
+        Self {
+            main_image_path,
+            open_disk,
+            mounted_disks: BTreeMap::new(),
+        }
+    }
+
+    /// Opens the disk stored in the given storage file (read-only if asked to) and mounts it under the given label.
+    ///
+    /// Fails if the label is already taken or if the storage file is already in use (by the main disk
+    /// or by another mounted one), as two disk managers syncing the same file would overwrite each other.
+    pub(crate) fn mount(&mut self, image_path: &str, label: &str, read_only: bool) -> Void {
+        if self.mounted_disks.contains_key(label) {
+            return Err(Box::try_from(format!("Label {} is already mounted", label)).unwrap());
+        }
+
+        let path = Path::new(image_path);
+        if path.is_dir() {
+            return Err(Box::try_from(format!("{} is a directory", image_path)).unwrap());
+        }
+        if let Some(parent) = path.parent() {
+            if !parent.as_os_str().is_empty() && !parent.is_dir() {
+                return Err(Box::try_from(format!(
+                    "Directory {} does not exist",
+                    parent.display()
+                ))
+                .unwrap());
+            }
+        }
+
+        let canonical_path = Self::canonicalize(image_path);
+        let in_use = std::iter::once(self.main_image_path.as_str())
+            .chain(
+                self.mounted_disks
+                    .values()
+                    .map(|mounted_disk| mounted_disk.image_path.as_str()),
+            )
+            .any(|used_path| Self::canonicalize(used_path) == canonical_path);
+        if in_use {
+            return Err(Box::try_from(format!("{} is already in use", image_path)).unwrap());
+        }
+
+        let disk_manager = (self.open_disk)(image_path, read_only)?;
+        self.mounted_disks.insert(
+            label.to_string(),
+            MountedDisk {
+                image_path: image_path.to_string(),
+                disk_manager,
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Unmounts the disk mounted under the given label, writing its modifications not yet written by the autosave.
+    pub(crate) fn unmount(&mut self, label: &str) -> Void {
+        match self.mounted_disks.remove(label) {
+            Some(mounted_disk) => {
+                if let Ok(mut disk_manager) = mounted_disk.disk_manager.lock() {
+                    disk_manager.flush();
+                }
+                Ok(())
+            }
+            None => Err(Box::try_from(format!("Label {} is not mounted", label)).unwrap()),
+        }
+    }
+
+    /// Returns the disk manager of the disk mounted under the given label, if any.
+    pub(crate) fn get(&self, label: &str) -> Option<Arm<dyn IDiskManager>> {
+        self.mounted_disks
+            .get(label)
+            .map(|mounted_disk| mounted_disk.disk_manager.clone())
+    }
+
+    /// Returns the disk managers of all the mounted disks.
+    pub(crate) fn disk_managers(&self) -> Vec<Arm<dyn IDiskManager>> {
+        self.mounted_disks
+            .values()
+            .map(|mounted_disk| mounted_disk.disk_manager.clone())
+            .collect()
+    }
+
+    /// Returns the disk manager of the disk addressed by the given command line along with the command line
+    /// stripped of the `label:` prefixes, or `None` if the command line doesn't address any mounted disk.
+    ///
+    /// Only the arguments are looked at (not the command itself nor the double-quoted texts),
+    /// and a command line can't address more than one mounted disk.
+    pub(crate) fn route(&self, input: &str) -> Result<Option<RoutedCommand>, Box<dyn Error>> {
+        let mut prefixes: Vec<(usize, &str)> = Vec::new();
+        let mut in_quotes = false;
+        let mut after_whitespace = false;
+
+        for (index, character) in input.char_indices() {
+            if character == '"' {
+                in_quotes = !in_quotes;
+            }
+
+            if !in_quotes && after_whitespace && !character.is_whitespace() {
+                if let Some(label) = self
+                    .mounted_disks
+                    .keys()
+                    .find(|label| input[index..].starts_with(&format!("{}:", label)))
+                {
+                    prefixes.push((index, label.as_str()));
+                }
+            }
+
+            after_whitespace = character.is_whitespace();
+        }
+
+        let label = match prefixes.first() {
+            Some((_, label)) => *label,
+            None => return Ok(None),
+        };
+        if prefixes
+            .iter()
+            .any(|(_, other_label)| *other_label != label)
+        {
+            return Err(Box::try_from("A command can only address a single mounted disk").unwrap());
+        }
+
+        // strip the prefixes from the last one, so that the indices of the previous ones stay valid
+        let mut routed_input = input.to_string();
+        for (index, label) in prefixes.iter().rev() {
+            routed_input.replace_range(*index..index + label.len() + 1, "");
+        }
+
+        Ok(Some((
+            self.mounted_disks[label].disk_manager.clone(),
+            routed_input,
+        )))
+    }
+
+    /// Returns the absolute path of a storage file, resolving the symbolic links if it exists.
+    fn canonicalize(image_path: &str) -> PathBuf {
+        std::fs::canonicalize(image_path).unwrap_or_else(|_| {
+            std::env::current_dir()
+                .map(|current_dir| current_dir.join(image_path))
+                .unwrap_or_else(|_| PathBuf::from(image_path))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::test_disk::TestDisk;
+    use std::sync::Arc;
+
+    /// Returns an empty mount table next to the given main disk, along with the path of another storage file
+    /// in the folder of the disk.
+    fn mount_table(disk: &TestDisk) -> (MountTable, String) {
+        let config = disk.config.lock().unwrap().clone();
+        let image_path = Path::new(&config.disk_dir_path)
+            .join("data.bin")
+            .to_string_lossy()
+            .into_owned();
+
+        (
+            MountTable::new(config.storage_file_path, crate::open_mounted_disk),
+            image_path,
+        )
+    }
+
+    #[test]
+    fn a_mounted_disk_is_routed_to_by_its_label() {
+        let disk = TestDisk::new();
+        let (mut mount_table, image_path) = mount_table(&disk);
+
+        mount_table.mount(&image_path, "data", false).unwrap();
+
+        assert!(Path::new(&image_path).exists());
+        let (disk_manager, routed_input) = mount_table.route("cd data:/docs").unwrap().unwrap();
+        assert!(Arc::ptr_eq(
+            &disk_manager,
+            &mount_table.get("data").unwrap()
+        ));
+        assert_eq!(routed_input, "cd /docs");
+        let (_, routed_input) = mount_table
+            .route("cp data:a.txt data:b.txt")
+            .unwrap()
+            .unwrap();
+        assert_eq!(routed_input, "cp a.txt b.txt");
+    }
+
+    #[test]
+    fn a_command_without_a_mounted_label_is_not_routed() {
+        let disk = TestDisk::new();
+        let (mut mount_table, image_path) = mount_table(&disk);
+        mount_table.mount(&image_path, "data", false).unwrap();
+
+        assert!(mount_table.route("ls").unwrap().is_none());
+        assert!(mount_table.route("cat other:a.txt").unwrap().is_none());
+        assert!(mount_table.route("data:a.txt").unwrap().is_none());
+        assert!(mount_table
+            .route("echo \"data:a\" > a.txt")
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn a_command_addressing_two_mounted_disks_is_rejected() {
+        let disk = TestDisk::new();
+        let (mut mount_table, image_path) = mount_table(&disk);
+        mount_table.mount(&image_path, "data", false).unwrap();
+        let other_image_path = image_path.replace("data.bin", "other.bin");
+        mount_table
+            .mount(&other_image_path, "other", false)
+            .unwrap();
+
+        let result = mount_table.route("diff data:a.txt other:a.txt");
+
+        assert_eq!(
+            result.err().unwrap().to_string(),
+            "A command can only address a single mounted disk"
+        );
+    }
+
+    #[test]
+    fn mounting_a_taken_label_or_a_used_storage_file_is_rejected() {
+        let disk = TestDisk::new();
+        let (mut mount_table, image_path) = mount_table(&disk);
+        let storage_file_path = disk.config.lock().unwrap().storage_file_path.clone();
+        mount_table.mount(&image_path, "data", false).unwrap();
+
+        assert_eq!(
+            mount_table
+                .mount(&image_path.replace("data.bin", "other.bin"), "data", false)
+                .unwrap_err()
+                .to_string(),
+            "Label data is already mounted"
+        );
+        assert_eq!(
+            mount_table
+                .mount(&image_path, "other", false)
+                .unwrap_err()
+                .to_string(),
+            format!("{} is already in use", image_path)
+        );
+        assert_eq!(
+            mount_table
+                .mount(&storage_file_path, "main", false)
+                .unwrap_err()
+                .to_string(),
+            format!("{} is already in use", storage_file_path)
+        );
+        assert!(mount_table
+            .mount(
+                &image_path.replace("data.bin", "missing/data.bin"),
+                "other",
+                false
+            )
+            .unwrap_err()
+            .to_string()
+            .starts_with("Directory "));
+    }
+
+    #[test]
+    fn an_unmounted_disk_is_not_routed_to_anymore() {
+        let disk = TestDisk::new();
+        let (mut mount_table, image_path) = mount_table(&disk);
+        mount_table.mount(&image_path, "data", false).unwrap();
+
+        mount_table.unmount("data").unwrap();
+
+        assert!(mount_table.get("data").is_none());
+        assert!(mount_table.route("cd data:/").unwrap().is_none());
+        assert_eq!(
+            mount_table.unmount("data").unwrap_err().to_string(),
+            "Label data is not mounted"
+        );
+        // the storage file can be mounted again, under any label
+        mount_table.mount(&image_path, "again", false).unwrap();
+    }
+}
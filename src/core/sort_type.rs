@@ -5,6 +5,8 @@
 /// - `DateDesc`: sort by date descending
 /// - `SizeAsc`: sort by size ascending
 /// - `SizeDesc`: sort by size descending
+/// - `ExtAsc`: sort by extension ascending (then by name)
+/// - `ExtDesc`: sort by extension descending (then by name)
 #[derive(Debug, Clone, PartialEq)]
 pub(crate) enum SortType {
     NameAsc,
@@ -13,6 +15,8 @@ pub(crate) enum SortType {
     DateDesc,
     SizeAsc,
     SizeDesc,
+    ExtAsc,
+    ExtDesc,
 }
 
 impl Default for SortType {
@@ -30,7 +34,21 @@ impl From<&str> for SortType {
             "td" => Self::DateDesc,
             "sza" => Self::SizeAsc,
             "szd" => Self::SizeDesc,
+            "ea" => Self::ExtAsc,
+            "ed" => Self::ExtDesc,
             _ => Self::default(),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extension_sorts_are_parsed_from_their_flags() {
+        assert_eq!(SortType::from("ea"), SortType::ExtAsc);
+        assert_eq!(SortType::from("ed"), SortType::ExtDesc);
+        assert_eq!(SortType::from("e"), SortType::NameAsc);
+    }
+}
@@ -1,4 +1,6 @@
 pub(crate) mod boot_sector;
+pub(crate) mod consistency_error;
 pub(crate) mod fat;
 pub(crate) mod file_entry;
+pub(crate) mod frag_report;
 pub(crate) mod i_disk_manager;
@@ -1,5 +1,15 @@
+use crate::domain::fat::FatValue;
 use crate::infrastructure::ByteArray;
 
+/// The signature written in every boot sector (`RD` in ASCII), used to recognize RoDOS storage files.
+pub(crate) const BOOT_SECTOR_SIGNATURE: u16 = 0x5244;
+
+/// The number of bytes of a serialized boot sector.
+pub(crate) const BOOT_SECTOR_SIZE: usize = 16;
+
+/// The maximum number of characters of a volume label (as in FAT).
+pub(crate) const VOLUME_LABEL_MAX_LENGTH: usize = 11;
+
 /// The `boot sector` of the disk is a special reserved sector that contains
 /// configuration information about the disk which is required when initializing the disk or formatting it.
 ///
@@ -10,16 +20,179 @@ use crate::infrastructure::ByteArray;
 /// - cluster_count: 2 bytes (total number of clusters)
 /// - root_entry_cell_size: 2 bytes (size of a file entry cell in bytes)
 /// - root_entry_count: 2 bytes (total number of file entries in the root directory)
-/// - fat_cell_size: 2 bytes (size of a FAT cell in bytes)
+/// - fat_cell_bits: 2 bytes (size of a FAT cell in bits, i.e. the FAT type: 12, 16 or 32)
 /// - clusters_per_boot_sector: 2 bytes (number of cluster occupied by the boot sector)
+/// - signature: 2 bytes (always `BOOT_SECTOR_SIGNATURE` for a valid boot sector)
+/// - working_directory_cluster: 2 bytes (first cluster of the working directory when the disk was last synced, 0 for the root)
+/// - label: 11 bytes (volume label, padded with zeros), stored in the spare bytes following the fields above,
+///   hence only on disks whose clusters are large enough (an empty label is not stored)
 #[derive(Debug, Clone)]
 pub(crate) struct BootSector {
     pub(crate) cluster_size: u16,
     pub(crate) cluster_count: u16,
     pub(crate) root_entry_cell_size: u16,
     pub(crate) root_entry_count: u16,
-    pub(crate) fat_cell_size: u16,
+    pub(crate) fat_cell_bits: u16,
     pub(crate) clusters_per_boot_sector: u16,
+    pub(crate) signature: u16,
+    pub(crate) working_directory_cluster: u16,
+    pub(crate) label: String,
+}
+
+impl BootSector {
+    /// Returns the number of bytes occupied by the cells of the FAT table.
+    pub(crate) fn get_fat_size(&self) -> u32 {
+        (self.fat_cell_bits as u32 * self.cluster_count as u32).div_ceil(8)
+    }
+
+    /// Returns the number of clusters occupied by the FAT table (the last one may be partially used).
+    pub(crate) fn get_fat_clusters_count(&self) -> u16 {
+        self.get_fat_size().div_ceil(self.cluster_size as u32) as u16
+    }
+
+    /// Returns the number of clusters occupied by the root table.
+    pub(crate) fn get_root_clusters_count(&self) -> u16 {
+        (self.root_entry_cell_size as u32 * self.root_entry_count as u32 / self.cluster_size as u32)
+            as u16
+    }
+
+    /// Returns the number of clusters reserved for the boot sector, the FAT table and the root table.
+    pub(crate) fn get_reserved_clusters_count(&self) -> u16 {
+        self.clusters_per_boot_sector
+            + self.get_fat_clusters_count()
+            + self.get_root_clusters_count()
+    }
+
+    /// Returns the total size of the disk in bytes.
+    pub(crate) fn get_disk_size(&self) -> u32 {
+        self.cluster_size as u32 * self.cluster_count as u32
+    }
+
+    /// Checks whether the boot sector has spare bytes to store a volume label in.
+    pub(crate) fn can_hold_label(&self) -> bool {
+        self.cluster_size as usize >= BOOT_SECTOR_SIZE + VOLUME_LABEL_MAX_LENGTH
+    }
+
+    /// Checks that the given volume label is made of at most `VOLUME_LABEL_MAX_LENGTH` letters, digits,
+    /// underscores and dashes, returning it in uppercase (volume labels are case-insensitive, as in FAT).
+    pub(crate) fn normalize_label(label: &str) -> Result<String, String> {
+        if label.is_empty() || label.len() > VOLUME_LABEL_MAX_LENGTH {
+            return Err(format!(
+                "Volume label {} must be between 1 and {} characters",
+                label, VOLUME_LABEL_MAX_LENGTH
+            ));
+        }
+
+        if let Some(character) = label
+            .chars()
+            .find(|character| !character.is_ascii_alphanumeric() && !"_-".contains(*character))
+        {
+            return Err(format!(
+                "Volume label {} contains the invalid character `{}` (only letters, digits, `_` and `-` are allowed)",
+                label, character
+            ));
+        }
+
+        Ok(label.to_ascii_uppercase())
+    }
+
+    /// Returns the boot sector of the disk formatted with the given FAT type (12, 16 or 32) and cluster size.
+    ///
+    /// The disk size is preserved as long as the FAT type can address all its clusters, otherwise the disk is shrunk
+    /// (e.g. FAT12 addresses at most 4087 clusters, the top cell values being reserved for sentinels).
+    /// The volume label is kept, unless the new clusters are too small to hold it.
+    pub(crate) fn with_format(&self, fat_type: u16, cluster_size: u16) -> BootSector {
+        let cluster_count = (self.get_disk_size() / cluster_size as u32)
+            .min(FatValue::get_max_clusters_count(fat_type)) as u16;
+
+        let mut boot_sector = BootSector {
+            cluster_size,
+            cluster_count,
+            fat_cell_bits: fat_type,
+            ..self.clone()
+        };
+        if !boot_sector.can_hold_label() {
+            boot_sector.label.clear();
+        }
+
+        boot_sector
+    }
+
+    /// Checks that the boot sector describes a usable disk, i.e. that it carries the RoDOS signature,
+    /// that all its sizes are nonzero, that the cluster size is a power of two dividing the size of a root entry
+    /// (each root entry spanning whole clusters) and that some clusters are left for the data region after
+    /// the reserved ones.
+    pub(crate) fn validate(&self) -> Result<(), String> {
+        if self.signature != BOOT_SECTOR_SIGNATURE {
+            return Err(format!(
+                "invalid signature {:#06x} (expected {:#06x}), this is not a RoDOS disk",
+                self.signature, BOOT_SECTOR_SIGNATURE
+            ));
+        }
+
+        if self.cluster_size == 0
+            || self.cluster_count == 0
+            || self.root_entry_cell_size == 0
+            || self.root_entry_count == 0
+            || self.fat_cell_bits == 0
+            || self.clusters_per_boot_sector == 0
+        {
+            return Err("all the sizes and counts must be greater than 0".to_string());
+        }
+
+        if ![12, 16, 32].contains(&self.fat_cell_bits) {
+            return Err(format!(
+                "FAT cell size of {} bits is not supported (FAT12, FAT16 or FAT32 expected)",
+                self.fat_cell_bits
+            ));
+        }
+
+        if self.cluster_count as u32 > FatValue::get_max_clusters_count(self.fat_cell_bits) {
+            return Err(format!(
+                "cluster count {} exceeds the {} clusters addressable by FAT{}",
+                self.cluster_count,
+                FatValue::get_max_clusters_count(self.fat_cell_bits),
+                self.fat_cell_bits
+            ));
+        }
+
+        if !self.cluster_size.is_power_of_two() {
+            return Err(format!(
+                "cluster size {} is not a power of two",
+                self.cluster_size
+            ));
+        }
+
+        if self.cluster_size > self.root_entry_cell_size
+            || self.root_entry_cell_size % self.cluster_size != 0
+        {
+            return Err(format!(
+                "cluster size {} must divide the {} bytes of a root entry",
+                self.cluster_size, self.root_entry_cell_size
+            ));
+        }
+
+        if (self.cluster_size as usize) < BOOT_SECTOR_SIZE {
+            return Err(format!(
+                "cluster size {} is too small to hold the {} bytes of the boot sector",
+                self.cluster_size, BOOT_SECTOR_SIZE
+            ));
+        }
+
+        // the reserved clusters count is computed on u32 to avoid overflowing on nonsense geometries
+        let reserved_clusters_count = self.clusters_per_boot_sector as u32
+            + self.get_fat_size().div_ceil(self.cluster_size as u32)
+            + self.root_entry_cell_size as u32 * self.root_entry_count as u32
+                / self.cluster_size as u32;
+        if reserved_clusters_count >= self.cluster_count as u32 {
+            return Err(format!(
+                "cluster count {} must be greater than the {} reserved clusters",
+                self.cluster_count, reserved_clusters_count
+            ));
+        }
+
+        Ok(())
+    }
 }
 
 /// Default values for a `BootSector`.
@@ -30,40 +203,75 @@ impl Default for BootSector {
             cluster_count: 8192,
             root_entry_cell_size: 32,
             root_entry_count: 64,
-            fat_cell_size: 2,
+            fat_cell_bits: 16,
             clusters_per_boot_sector: 1,
+            signature: BOOT_SECTOR_SIGNATURE,
+            working_directory_cluster: 0,
+            label: String::new(),
         }
     }
 }
 
 /// Deserializes a `ByteArray` into a `BootSector`.
+///
+/// The bytes missing from a truncated `ByteArray` are read as zeros, so the result has to be checked
+/// with `BootSector::validate` before being used.
 impl From<ByteArray> for BootSector {
     fn from(value: ByteArray) -> Self {
+        let read_u16 = |offset: usize| {
+            u16::from_be_bytes([
+                value.get(offset).copied().unwrap_or(0),
+                value.get(offset + 1).copied().unwrap_or(0),
+            ])
+        };
+
         // cluster_size
-        let cluster_size = u16::from_be_bytes([value[0], value[1]]);
+        let cluster_size = read_u16(0);
 
         // cluster_count
-        let cluster_count = u16::from_be_bytes([value[2], value[3]]);
+        let cluster_count = read_u16(2);
 
         // root_entry_cell_size
-        let root_entry_cell_size = u16::from_be_bytes([value[4], value[5]]);
+        let root_entry_cell_size = read_u16(4);
 
         // root_entry_count
-        let root_entry_count = u16::from_be_bytes([value[6], value[7]]);
+        let root_entry_count = read_u16(6);
 
-        // fat_cell_size
-        let fat_cell_size = u16::from_be_bytes([value[8], value[9]]);
+        // fat_cell_bits
+        let fat_cell_bits = read_u16(8);
 
         // clusters_per_boot_sector
-        let clusters_per_boot_sector = u16::from_be_bytes([value[10], value[11]]);
+        let clusters_per_boot_sector = read_u16(10);
+
+        // signature
+        let signature = read_u16(12);
+
+        // working_directory_cluster
+        let working_directory_cluster = read_u16(14);
+
+        // label (the bytes up to the first zero, left empty if they don't form a valid label)
+        let label_bytes: Vec<u8> = value
+            .iter()
+            .skip(BOOT_SECTOR_SIZE)
+            .take(VOLUME_LABEL_MAX_LENGTH)
+            .take_while(|byte| **byte != 0)
+            .copied()
+            .collect();
+        let label = String::from_utf8(label_bytes)
+            .ok()
+            .and_then(|label| BootSector::normalize_label(&label).ok())
+            .unwrap_or_default();
 
         Self {
             cluster_size,
             cluster_count,
             root_entry_cell_size,
             root_entry_count,
-            fat_cell_size,
+            fat_cell_bits,
             clusters_per_boot_sector,
+            signature,
+            working_directory_cluster,
+            label,
         }
     }
 }
@@ -95,16 +303,185 @@ impl Into<ByteArray> for BootSector {
         result[6] = root_entry_count[0];
         result[7] = root_entry_count[1];
 
-        // fat_cell_size
-        let fat_cell_size = self.fat_cell_size.to_be_bytes();
-        result[8] = fat_cell_size[0];
-        result[9] = fat_cell_size[1];
+        // fat_cell_bits
+        let fat_cell_bits = self.fat_cell_bits.to_be_bytes();
+        result[8] = fat_cell_bits[0];
+        result[9] = fat_cell_bits[1];
 
         // clusters_per_boot_sector
         let clusters_per_boot_sector = self.clusters_per_boot_sector.to_be_bytes();
         result[10] = clusters_per_boot_sector[0];
         result[11] = clusters_per_boot_sector[1];
 
+        // signature
+        let signature = self.signature.to_be_bytes();
+        result[12] = signature[0];
+        result[13] = signature[1];
+
+        // working_directory_cluster
+        let working_directory_cluster = self.working_directory_cluster.to_be_bytes();
+        result[14] = working_directory_cluster[0];
+        result[15] = working_directory_cluster[1];
+
+        // label
+        if self.can_hold_label() {
+            let label = self.label.as_bytes();
+            let label_length = label.len().min(VOLUME_LABEL_MAX_LENGTH);
+            result[BOOT_SECTOR_SIZE..BOOT_SECTOR_SIZE + label_length]
+                .copy_from_slice(&label[..label_length]);
+        }
+
         result
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_default_boot_sector_derives_its_regions() {
+        let boot_sector = BootSector::default();
+
+        // 8192 cells of 16 bits take 16 KiB, i.e. 1024 clusters of 16 bytes,
+        // while 64 root entries of 32 bytes take 128 clusters
+        assert_eq!(boot_sector.get_disk_size(), 8192 * 16);
+        assert_eq!(boot_sector.get_fat_clusters_count(), 1024);
+        assert_eq!(boot_sector.get_root_clusters_count(), 128);
+        assert_eq!(boot_sector.get_reserved_clusters_count(), 1 + 1024 + 128);
+    }
+
+    #[test]
+    fn a_partially_used_fat_cluster_is_counted_whole() {
+        let boot_sector = BootSector {
+            fat_cell_bits: 12,
+            cluster_count: 4000,
+            ..BootSector::default()
+        };
+
+        // 4000 cells of 12 bits take 6000 bytes, i.e. 375 clusters of 16 bytes
+        assert_eq!(boot_sector.get_fat_size(), 6000);
+        assert_eq!(boot_sector.get_fat_clusters_count(), 375);
+
+        let boot_sector = BootSector {
+            cluster_count: 4001,
+            ..boot_sector
+        };
+        assert_eq!(boot_sector.get_fat_size(), 6002);
+        assert_eq!(boot_sector.get_fat_clusters_count(), 376);
+    }
+
+    /// Overwrites the big endian u16 at the given offset of the default boot sector bytes,
+    /// then loads and validates them.
+    fn validate_corrupted(offset: usize, value: u16) -> Result<(), String> {
+        let mut bytes: ByteArray = BootSector::default().into();
+        bytes[offset..offset + 2].copy_from_slice(&value.to_be_bytes());
+
+        BootSector::from(bytes).validate()
+    }
+
+    #[test]
+    fn a_valid_boot_sector_round_trips_and_validates() {
+        let boot_sector = BootSector {
+            fat_cell_bits: 32,
+            cluster_size: 32,
+            cluster_count: 4096,
+            ..BootSector::default()
+        };
+
+        let bytes: ByteArray = boot_sector.clone().into();
+        assert_eq!(
+            bytes[12..14],
+            BOOT_SECTOR_SIGNATURE.to_be_bytes(),
+            "the signature follows the geometry"
+        );
+        let loaded_boot_sector = BootSector::from(bytes);
+
+        assert_eq!(loaded_boot_sector.validate(), Ok(()));
+        assert_eq!(loaded_boot_sector.fat_cell_bits, 32);
+        assert_eq!(loaded_boot_sector.cluster_size, 32);
+        assert_eq!(loaded_boot_sector.cluster_count, 4096);
+        assert_eq!(loaded_boot_sector.signature, BOOT_SECTOR_SIGNATURE);
+    }
+
+    #[test]
+    fn a_foreign_signature_is_rejected() {
+        assert_eq!(
+            validate_corrupted(12, 0x4D5A),
+            Err(format!(
+                "invalid signature 0x4d5a (expected {:#06x}), this is not a RoDOS disk",
+                BOOT_SECTOR_SIGNATURE
+            ))
+        );
+    }
+
+    #[test]
+    fn zero_sizes_are_rejected() {
+        for offset in [0, 2, 4, 6, 8, 10] {
+            assert_eq!(
+                validate_corrupted(offset, 0),
+                Err("all the sizes and counts must be greater than 0".to_string()),
+                "offset {}",
+                offset
+            );
+        }
+    }
+
+    #[test]
+    fn a_cluster_size_which_is_not_a_power_of_two_is_rejected() {
+        assert_eq!(
+            validate_corrupted(0, 24),
+            Err("cluster size 24 is not a power of two".to_string())
+        );
+    }
+
+    #[test]
+    fn a_cluster_count_without_data_clusters_is_rejected() {
+        assert_eq!(
+            validate_corrupted(2, 100),
+            Err("cluster count 100 must be greater than the 142 reserved clusters".to_string())
+        );
+    }
+
+    #[test]
+    fn a_cluster_count_reaching_the_sentinel_range_is_rejected() {
+        for (fat_cell_bits, max_clusters_count) in [(12, 0x0FF7), (16, 0xFFF7), (32, 0xFFF7)] {
+            let boot_sector = BootSector {
+                fat_cell_bits,
+                cluster_count: max_clusters_count,
+                ..BootSector::default()
+            };
+            assert_eq!(boot_sector.validate(), Ok(()), "FAT{}", fat_cell_bits);
+
+            let boot_sector = BootSector {
+                cluster_count: max_clusters_count + 1,
+                ..boot_sector
+            };
+            assert_eq!(
+                boot_sector.validate(),
+                Err(format!(
+                    "cluster count {} exceeds the {} clusters addressable by FAT{}",
+                    max_clusters_count + 1,
+                    max_clusters_count,
+                    fat_cell_bits
+                ))
+            );
+        }
+    }
+
+    #[test]
+    fn formatting_to_fat12_caps_the_cluster_count_below_the_sentinel_range() {
+        let boot_sector = BootSector::default().with_format(12, 16);
+
+        assert_eq!(boot_sector.cluster_count, 0x0FF7);
+        assert_eq!(boot_sector.validate(), Ok(()));
+    }
+
+    #[test]
+    fn a_truncated_boot_sector_is_rejected() {
+        let bytes: ByteArray = BootSector::default().into();
+
+        assert!(BootSector::from(bytes[..6].to_vec()).validate().is_err());
+        assert!(BootSector::from(Vec::new()).validate().is_err());
+    }
+}
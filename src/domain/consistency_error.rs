@@ -0,0 +1,80 @@
+use std::fmt::Display;
+
+/// A consistency error detected when checking the FAT table against the directory tree:
+/// - `LoopingChain`: the allocation chain of a file entry loops back to an already visited cluster
+/// - `BrokenChain`: the allocation chain of a file entry points to a free, reserved, bad or out of bounds cluster
+/// - `FreeFirstCluster`: the head of the allocation chain of a file entry is marked as free
+/// - `CrossLinkedChain`: a cluster is shared by the allocation chains of two file entries
+/// - `LostChain`: an allocated chain of clusters which is not reachable from any file entry
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum ConsistencyError {
+    LoopingChain {
+        entry_path: String,
+        cluster_index: usize,
+    },
+    BrokenChain {
+        entry_path: String,
+        cluster_index: usize,
+    },
+    FreeFirstCluster {
+        entry_path: String,
+        cluster_index: usize,
+    },
+    CrossLinkedChain {
+        entry_path: String,
+        other_entry_path: String,
+        cluster_index: usize,
+    },
+    LostChain {
+        first_cluster_index: usize,
+        cluster_count: usize,
+    },
+}
+
+impl Display for ConsistencyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConsistencyError::LoopingChain {
+                entry_path,
+                cluster_index,
+            } => write!(
+                f,
+                "Looping chain: the allocation chain of {} loops back to cluster {}",
+                entry_path, cluster_index
+            ),
+            ConsistencyError::BrokenChain {
+                entry_path,
+                cluster_index,
+            } => write!(
+                f,
+                "Broken chain: the allocation chain of {} points to the invalid cluster {}",
+                entry_path, cluster_index
+            ),
+            ConsistencyError::FreeFirstCluster {
+                entry_path,
+                cluster_index,
+            } => write!(
+                f,
+                "Free first cluster: {} starts at cluster {} which is marked as free",
+                entry_path, cluster_index
+            ),
+            ConsistencyError::CrossLinkedChain {
+                entry_path,
+                other_entry_path,
+                cluster_index,
+            } => write!(
+                f,
+                "Cross-linked chain: {} and {} share cluster {}",
+                entry_path, other_entry_path, cluster_index
+            ),
+            ConsistencyError::LostChain {
+                first_cluster_index,
+                cluster_count,
+            } => write!(
+                f,
+                "Lost chain: {} cluster(s) starting at cluster {} not reachable from any file entry",
+                cluster_count, first_cluster_index
+            ),
+        }
+    }
+}
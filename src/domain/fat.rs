@@ -1,9 +1,23 @@
-/// FAT16/32 possible values:
+use crate::infrastructure::ByteArray;
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// FAT12/16/32 possible values (shown for FAT16, the end of chain marker being 0xFFF for FAT12 and 0xFFFFFFFF for FAT32):
 /// - 0x0000: Free
 /// - 0x0001: Reserved
 /// - 0x0002: Bad
 /// - 0xFFFF: End of chain
-/// - 0x0003-0xFFFE: Data
+/// - 0x0003-0xFFF6: Data
+///
+/// As in the standard FAT layouts, the top of the cell range (0xFFF7-0xFFFF for FAT16, 0xFF7-0xFFF for FAT12 and
+/// 0xFFFFFFF7-0xFFFFFFFF for FAT32) is reserved for sentinels: when decoded, the first value of the range is read as
+/// `Bad` and the others as `EndOfChain` (only the all-ones value is ever written for the end of chain marker).
+///
+/// The encoding round-trips losslessly through `u16` only for `Data` values in 0x0003-0xFFF6: `Data(0)`, `Data(1)` and
+/// `Data(2)` would alias `Free`, `Reserved` and `Bad`, and `Data(0xFFF7..=0xFFFF)` would alias the sentinels.
+/// These values never occur, as the first clusters are always reserved (for the boot sector, the FAT and the root table)
+/// and the cluster count is capped by `FatValue::get_max_clusters_count`, so the last cluster index stays below the
+/// sentinel range (which is why `FromStr` rejects them).
 #[derive(Debug, Clone, PartialEq)]
 pub(crate) enum FatValue {
     Free,
@@ -13,6 +27,97 @@ pub(crate) enum FatValue {
     Bad,
 }
 
+impl FatValue {
+    /// Returns the end of chain marker of a FAT cell of the given width in bits, i.e. its all-ones value.
+    fn get_end_of_chain_cell(cell_bits: u16) -> u32 {
+        u32::MAX >> (32 - cell_bits)
+    }
+
+    /// Returns the first sentinel value of a FAT cell of the given width in bits: the values from it up to the
+    /// end of chain marker are never used as cluster indices.
+    fn get_first_sentinel_cell(cell_bits: u16) -> u32 {
+        Self::get_end_of_chain_cell(cell_bits) - 8
+    }
+
+    /// Returns the maximum number of clusters addressable by FAT cells of the given width in bits,
+    /// as the index of the last cluster must stay below the sentinel range (of both the cell and of `u16`).
+    pub(crate) fn get_max_clusters_count(cell_bits: u16) -> u32 {
+        Self::get_first_sentinel_cell(cell_bits).min(Self::get_first_sentinel_cell(16))
+    }
+
+    /// Returns whether the given cluster index can be linked to, i.e. it is neither one of the values aliasing
+    /// `Free`, `Reserved` and `Bad` nor in the sentinel range.
+    pub(crate) fn is_valid_cluster_index(cluster_index: u32, cell_bits: u16) -> bool {
+        cluster_index >= 0x0003 && cluster_index < Self::get_max_clusters_count(cell_bits)
+    }
+
+    /// Serializes a FAT table into a byte array of big endian cells of the given width in bits.
+    /// FAT12 cells are packed two by two in 3 bytes, the last byte being half-filled for an odd number of cells.
+    pub(crate) fn encode_cells(fat: &FatTable, cell_bits: u16) -> ByteArray {
+        let cells = fat.iter().map(|fat_value| match fat_value {
+            FatValue::EndOfChain => Self::get_end_of_chain_cell(cell_bits),
+            fat_value => u16::from(fat_value.clone()) as u32,
+        });
+
+        match cell_bits {
+            12 => {
+                let mut result = Vec::with_capacity((fat.len() * 3).div_ceil(2));
+                cells
+                    .collect::<Vec<_>>()
+                    .chunks(2)
+                    .for_each(|pair| match pair {
+                        [first, second] => result.extend_from_slice(&[
+                            (first >> 4) as u8,
+                            (((first & 0x0F) << 4) | (second >> 8)) as u8,
+                            (second & 0xFF) as u8,
+                        ]),
+                        [last] => result
+                            .extend_from_slice(&[(last >> 4) as u8, ((last & 0x0F) << 4) as u8]),
+                        _ => unreachable!(),
+                    });
+                result
+            }
+            32 => cells.flat_map(u32::to_be_bytes).collect(),
+            _ => cells.flat_map(|cell| (cell as u16).to_be_bytes()).collect(),
+        }
+    }
+
+    /// Deserializes the first `count` big endian cells of the given width in bits from a byte array into a FAT table
+    /// (the reverse of `FatValue::encode_cells`).
+    pub(crate) fn decode_cells(data: &[u8], cell_bits: u16, count: usize) -> FatTable {
+        let cells: Vec<u32> = match cell_bits {
+            12 => data
+                .chunks(3)
+                .flat_map(|bytes| {
+                    let byte = |index: usize| bytes.get(index).copied().unwrap_or(0) as u32;
+                    [
+                        (byte(0) << 4) | (byte(1) >> 4),
+                        ((byte(1) & 0x0F) << 8) | byte(2),
+                    ]
+                })
+                .collect(),
+            32 => data
+                .chunks_exact(4)
+                .map(|bytes| u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+                .collect(),
+            _ => data
+                .chunks_exact(2)
+                .map(|bytes| u16::from_be_bytes([bytes[0], bytes[1]]) as u32)
+                .collect(),
+        };
+
+        cells
+            .into_iter()
+            .take(count)
+            .map(|cell| match cell {
+                cell if cell == Self::get_first_sentinel_cell(cell_bits) => FatValue::Bad,
+                cell if cell > Self::get_first_sentinel_cell(cell_bits) => FatValue::EndOfChain,
+                cell => FatValue::from(cell as u16),
+            })
+            .collect()
+    }
+}
+
 /// Serializes a `FatValue` into a `u16`.
 impl From<FatValue> for u16 {
     fn from(value: FatValue) -> Self {
@@ -32,12 +137,224 @@ impl From<u16> for FatValue {
         match value {
             0x0000 => FatValue::Free,
             0x0001 => FatValue::Reserved,
-            0x0002 => FatValue::Bad,
-            0xFFFF => FatValue::EndOfChain,
+            0x0002 | 0xFFF7 => FatValue::Bad,
+            0xFFF8..=0xFFFF => FatValue::EndOfChain,
             value => FatValue::Data(value),
         }
     }
 }
 
+/// Displays a `FatValue` in a human-readable form: `Free`, `Reserved`, `EOC`, `Bad` or `->N` for a link to cluster N.
+impl Display for FatValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FatValue::Free => write!(f, "Free"),
+            FatValue::Reserved => write!(f, "Reserved"),
+            FatValue::EndOfChain => write!(f, "EOC"),
+            FatValue::Data(value) => write!(f, "->{}", value),
+            FatValue::Bad => write!(f, "Bad"),
+        }
+    }
+}
+
+/// Parses a `FatValue` from its human-readable form (the reverse of `Display`, ignoring the case of the keywords).
+/// The `Data` values which would alias other variants once encoded are rejected.
+impl FromStr for FatValue {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let value = value.trim();
+
+        match value.to_ascii_lowercase().as_str() {
+            "free" => Ok(FatValue::Free),
+            "reserved" => Ok(FatValue::Reserved),
+            "eoc" => Ok(FatValue::EndOfChain),
+            "bad" => Ok(FatValue::Bad),
+            _ => match value.strip_prefix("->").map(str::parse::<u16>) {
+                Some(Ok(next_cluster_index))
+                    if FatValue::is_valid_cluster_index(next_cluster_index as u32, 16) =>
+                {
+                    Ok(FatValue::Data(next_cluster_index))
+                }
+                Some(Ok(next_cluster_index)) => Err(format!(
+                    "Invalid FAT value: cluster {} cannot be linked to",
+                    next_cluster_index
+                )),
+                _ => Err(format!(
+                    "Invalid FAT value: {} (expected Free, Reserved, EOC, Bad or ->N)",
+                    value
+                )),
+            },
+        }
+    }
+}
+
 /// A FAT table.
 pub(crate) type FatTable = Vec<FatValue>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fat_values_are_displayed_as_dumpfat_shows_them() {
+        let displayed: Vec<String> = [
+            FatValue::Free,
+            FatValue::Reserved,
+            FatValue::EndOfChain,
+            FatValue::Bad,
+            FatValue::Data(1234),
+        ]
+        .iter()
+        .map(FatValue::to_string)
+        .collect();
+
+        assert_eq!(displayed, vec!["Free", "Reserved", "EOC", "Bad", "->1234"]);
+    }
+
+    #[test]
+    fn displayed_fat_values_parse_back() {
+        for fat_value in [
+            FatValue::Free,
+            FatValue::Reserved,
+            FatValue::EndOfChain,
+            FatValue::Bad,
+            FatValue::Data(3),
+            FatValue::Data(0xFFF6),
+        ] {
+            assert_eq!(fat_value.to_string().parse::<FatValue>(), Ok(fat_value));
+        }
+
+        assert_eq!(" eoc ".parse::<FatValue>(), Ok(FatValue::EndOfChain));
+        assert!("->2".parse::<FatValue>().is_err());
+        assert!("next".parse::<FatValue>().is_err());
+    }
+
+    #[test]
+    fn fat12_cells_are_packed_two_by_two_in_3_bytes() {
+        let fat = vec![
+            FatValue::Data(0x123),
+            FatValue::Data(0x456),
+            FatValue::EndOfChain,
+        ];
+
+        let encoded = FatValue::encode_cells(&fat, 12);
+
+        assert_eq!(encoded, vec![0x12, 0x34, 0x56, 0xFF, 0xF0]);
+        assert_eq!(FatValue::decode_cells(&encoded, 12, 3), fat);
+    }
+
+    #[test]
+    fn fat_cells_round_trip_for_every_width() {
+        for cell_bits in [12, 16, 32] {
+            for count in [1, 2, 7, 8] {
+                let fat: FatTable = [
+                    FatValue::Reserved,
+                    FatValue::Free,
+                    FatValue::Data(3),
+                    FatValue::EndOfChain,
+                    FatValue::Bad,
+                    FatValue::Data(FatValue::get_max_clusters_count(cell_bits) as u16 - 1),
+                    FatValue::Data(0x0ABC),
+                    FatValue::Free,
+                ]
+                .into_iter()
+                .take(count)
+                .collect();
+
+                let encoded = FatValue::encode_cells(&fat, cell_bits);
+
+                assert_eq!(encoded.len(), (count * cell_bits as usize).div_ceil(8));
+                assert_eq!(
+                    FatValue::decode_cells(&encoded, cell_bits, count),
+                    fat,
+                    "FAT{} with {} cells",
+                    cell_bits,
+                    count
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn fat_values_round_trip_through_u16() {
+        let data_values = (0x0003..=0xFFF6)
+            .step_by(0x0FFF)
+            .chain([0x0004, 0xFFF5, 0xFFF6]);
+        let fat_values = [
+            FatValue::Free,
+            FatValue::Reserved,
+            FatValue::EndOfChain,
+            FatValue::Bad,
+        ]
+        .into_iter()
+        .chain(data_values.map(FatValue::Data));
+
+        for fat_value in fat_values {
+            assert_eq!(
+                FatValue::from(u16::from(fat_value.clone())),
+                fat_value,
+                "{}",
+                fat_value
+            );
+        }
+    }
+
+    #[test]
+    fn data_values_outside_the_cluster_range_alias_the_other_variants() {
+        let aliases = [
+            (0x0000, FatValue::Free),
+            (0x0001, FatValue::Reserved),
+            (0x0002, FatValue::Bad),
+            (0xFFF7, FatValue::Bad),
+            (0xFFF8, FatValue::EndOfChain),
+            (0xFFFF, FatValue::EndOfChain),
+        ];
+
+        for (value, alias) in aliases {
+            assert_eq!(FatValue::from(u16::from(FatValue::Data(value))), alias);
+            assert!(format!("->{}", value).parse::<FatValue>().is_err());
+        }
+    }
+
+    #[test]
+    fn cells_around_the_sentinel_range_are_classified_for_every_width() {
+        for cell_bits in [12, 16, 32] {
+            let end_of_chain_cell = FatValue::get_end_of_chain_cell(cell_bits);
+            let last_cluster_cell = FatValue::get_max_clusters_count(cell_bits) - 1;
+            let cells = [
+                (0x0002, FatValue::Bad),
+                (0x0003, FatValue::Data(0x0003)),
+                (last_cluster_cell, FatValue::Data(last_cluster_cell as u16)),
+                (end_of_chain_cell - 8, FatValue::Bad),
+                (end_of_chain_cell - 7, FatValue::EndOfChain),
+                (end_of_chain_cell, FatValue::EndOfChain),
+            ];
+            let fat: FatTable = cells
+                .iter()
+                .map(|(cell, _)| FatValue::Data(*cell as u16))
+                .collect();
+            let data: ByteArray = match cell_bits {
+                32 => cells
+                    .iter()
+                    .flat_map(|(cell, _)| cell.to_be_bytes())
+                    .collect(),
+                _ => FatValue::encode_cells(&fat, cell_bits),
+            };
+
+            let decoded = FatValue::decode_cells(&data, cell_bits, cells.len());
+
+            let expected: FatTable = cells.into_iter().map(|(_, fat_value)| fat_value).collect();
+            assert_eq!(decoded, expected, "FAT{}", cell_bits);
+            assert!(FatValue::is_valid_cluster_index(
+                last_cluster_cell,
+                cell_bits
+            ));
+            assert!(!FatValue::is_valid_cluster_index(
+                last_cluster_cell + 1,
+                cell_bits
+            ));
+            assert!(!FatValue::is_valid_cluster_index(0x0002, cell_bits));
+        }
+    }
+}
@@ -1,5 +1,8 @@
 use crate::infrastructure::ByteArray;
-use chrono::{DateTime, Datelike, LocalResult, TimeZone, Timelike, Utc};
+use chrono::{DateTime, Datelike, LocalResult, NaiveDate, TimeZone, Timelike, Utc};
+use serde::ser::SerializeStruct;
+use serde::{Serialize, Serializer};
+use std::error::Error;
 use std::fmt::Display;
 use std::ops::BitOr;
 use std::str::FromStr;
@@ -8,11 +11,15 @@ use std::str::FromStr;
 /// - `Mode` (read-only or read-write): `bit 0`
 /// - `Visibility` (hidden or visible): `bit 1`
 /// - `Type` (file or directory): `bit 2`
+/// - `System` (system or non-system): `bit 3`
+/// - `Archive` (modified since the last backup or not): `bit 4`
 #[derive(Debug, Clone, Copy)]
 pub(crate) enum FileEntryAttributesFlags {
     Mode = 0x01,
     Visibility = 0x02,
     Type = 0x04,
+    System = 0x08,
+    Archive = 0x10,
 }
 
 /// An enum representing the value of each type of file entry attribute:
@@ -22,6 +29,10 @@ pub(crate) enum FileEntryAttributesFlags {
 /// - `Visible`: `unset bit`
 /// - `File`: `set bit`
 /// - `Directory`: `unset bit`
+/// - `System`: `set bit`
+/// - `NonSystem`: `unset bit`
+/// - `Archive`: `set bit`
+/// - `NonArchive`: `unset bit`
 #[derive(Debug, Clone, Copy)]
 pub(crate) enum FileEntryAttributes {
     /// Read-only file: `set bit`
@@ -36,6 +47,14 @@ pub(crate) enum FileEntryAttributes {
     File,
     /// Directory: `unset bit`
     Directory,
+    /// System file: `set bit`
+    System,
+    /// Non-system file: `unset bit`
+    NonSystem,
+    /// File modified since the last backup: `set bit`
+    Archive,
+    /// File not modified since the last backup: `unset bit`
+    NonArchive,
 }
 
 impl FileEntryAttributes {
@@ -58,6 +77,10 @@ impl Into<u8> for FileEntryAttributes {
             FileEntryAttributes::Visible => 0x00,
             FileEntryAttributes::File => 0x04,
             FileEntryAttributes::Directory => 0x00,
+            FileEntryAttributes::System => 0x08,
+            FileEntryAttributes::NonSystem => 0x00,
+            FileEntryAttributes::Archive => 0x10,
+            FileEntryAttributes::NonArchive => 0x00,
         }
     }
 }
@@ -79,6 +102,10 @@ impl BitOr for FileEntryAttributes {
 /// - `ReadWrite`: `+w`
 /// - `Hidden`: `+h`
 /// - `Visible`: `-h`
+/// - `System`: `+s`
+/// - `NonSystem`: `-s`
+/// - `Archive`: `+a`
+/// - `NonArchive`: `-a`
 impl FromStr for FileEntryAttributes {
     type Err = String;
 
@@ -88,28 +115,35 @@ impl FromStr for FileEntryAttributes {
             "+w" => Ok(FileEntryAttributes::ReadWrite),
             "-h" => Ok(FileEntryAttributes::Visible),
             "+h" => Ok(FileEntryAttributes::Hidden),
+            "-s" => Ok(FileEntryAttributes::NonSystem),
+            "+s" => Ok(FileEntryAttributes::System),
+            "-a" => Ok(FileEntryAttributes::NonArchive),
+            "+a" => Ok(FileEntryAttributes::Archive),
             _ => Err(format!("Invalid attribute {}", s)),
         }
     }
 }
 
 /// A file entry struct containing the necessary metadata to represent a file or directory:
-/// - `name`: the name of the file or directory (8 bytes)
+/// - `name`: the name of the file or directory (first 8 bytes, the rest is stored in the long name chain)
 /// - `extension`: the extension of the file or directory (3 bytes)
 /// - `size`: the size of the file or directory in bytes (4 bytes)
 /// - `first_cluster`: the index of the allocation chain's head of the file or directory (2 bytes)
 /// - `attributes`: the attributes of the file or directory (1 byte)
 /// - `last_modification_datetime`: the last modification date and time of the file or directory (4 bytes)
 /// where:
-///     - `year`: 7 bits (0-127) since 1980
+///     - `year`: 7 bits (0-127) since 1980, saturated to the 1980-2107 range
 ///     - `month`: 4 bits (1-12)
 ///     - `day`: 5 bits (1-31)
 ///     - `hour`: 5 bits (0-23)
 ///     - `minute`: 6 bits (0-59)
 ///     - `second`: 5 bits (0-59)
+/// - `creation_datetime`: the creation date and time of the file or directory (4 bytes, same layout)
+/// - `last_access_date`: the last access date of the file or directory (2 bytes, same layout)
+/// - `long_name_cluster`: the head of the allocation chain storing the rest of a long name (2 bytes, 0 if none)
 /// - `parent_entry`: the parent directory of the file or directory (none if root)
 /// - `children_entries`: the children files or directories of the file or directory (none if file)
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub(crate) struct FileEntry {
     pub(crate) name: String,
     pub(crate) extension: String,
@@ -117,11 +151,29 @@ pub(crate) struct FileEntry {
     pub(crate) first_cluster: u16,
     pub(crate) attributes: u8,
     pub(crate) last_modification_datetime: DateTime<Utc>,
+    pub(crate) creation_datetime: DateTime<Utc>,
+    pub(crate) last_access_date: NaiveDate,
+    pub(crate) long_name_cluster: u16,
     pub(crate) parent_entry: Option<Box<FileEntry>>,
     pub(crate) children_entries: Option<Vec<FileEntry>>,
 }
 
 impl FileEntry {
+    /// The first cluster of the files without an allocation chain, i.e. the empty ones created with a size of 0
+    /// (as for `long_name_cluster`, 0 never points to the data region since the first clusters are reserved).
+    pub(crate) const NO_CLUSTER: u16 = 0;
+
+    /// The maximum length of a file or directory name (the first 8 characters in the file entry, the rest in a long name).
+    pub(crate) const MAX_NAME_LENGTH: usize = 32;
+
+    /// The maximum length of a file extension.
+    pub(crate) const MAX_EXTENSION_LENGTH: usize = 3;
+
+    /// Returns a builder of a file entry with the given name.
+    pub(crate) fn builder(name: &str) -> FileEntryBuilder {
+        FileEntryBuilder::new(name)
+    }
+
     pub(crate) fn new(
         name: String,
         extension: String,
@@ -139,6 +191,9 @@ impl FileEntry {
             first_cluster,
             attributes,
             last_modification_datetime,
+            creation_datetime: last_modification_datetime,
+            last_access_date: last_modification_datetime.date_naive(),
+            long_name_cluster: 0,
             parent_entry,
             children_entries,
         }
@@ -152,11 +207,45 @@ impl FileEntry {
             first_cluster: 0,
             attributes: FileEntryAttributes::ReadOnly as u8,
             last_modification_datetime: Utc::now(),
+            creation_datetime: Utc::now(),
+            last_access_date: Utc::now().date_naive(),
+            long_name_cluster: 0,
             parent_entry: None,
             children_entries: Some(Vec::new()),
         }
     }
 
+    /// Checks whether the file entry has the given name and extension, ignoring the ASCII case
+    /// (names are case-insensitive but case-preserving, as in FAT).
+    pub(crate) fn name_matches(&self, name: &str, extension: &str) -> bool {
+        self.name.eq_ignore_ascii_case(name) && self.extension.eq_ignore_ascii_case(extension)
+    }
+
+    /// Checks whether the file entry denotes the same entry of a directory as the given one, i.e. whether they are
+    /// both files or both directories with the same name and extension (see `name_matches`): unlike `==`, their size,
+    /// clusters and dates are not compared, as they change during the lifetime of an entry.
+    pub(crate) fn same_name_as(&self, other: &FileEntry) -> bool {
+        self.is_file() == other.is_file() && self.name_matches(&other.name, &other.extension)
+    }
+
+    /// Checks whether the file entry is a file with the given name and extension (see `name_matches`).
+    pub(crate) fn matches_file(&self, name: &str, extension: &str) -> bool {
+        self.is_file() && self.name_matches(name, extension)
+    }
+
+    /// Checks whether the file entry is a directory with the given name (see `name_matches`).
+    pub(crate) fn matches_directory(&self, name: &str) -> bool {
+        !self.is_file() && self.name_matches(name, "")
+    }
+
+    /// Returns the full name of the file entry (`name.extension` for files and `name` for directories).
+    pub(crate) fn get_full_name(&self) -> String {
+        match self.is_file() {
+            true => format!("{}.{}", self.name, self.extension),
+            false => self.name.clone(),
+        }
+    }
+
     pub(crate) fn is_root(&self) -> bool {
         self.name == "/"
     }
@@ -195,11 +284,46 @@ impl FileEntry {
                         self.attributes &= !(FileEntryAttributesFlags::Visibility as u8);
                     }
                 }
+                FileEntryAttributes::System => {
+                    let system_bit = self.attributes & FileEntryAttributesFlags::System as u8;
+
+                    if system_bit == 0 {
+                        self.attributes |= FileEntryAttributesFlags::System as u8;
+                    }
+                }
+                FileEntryAttributes::NonSystem => {
+                    let system_bit = self.attributes & FileEntryAttributesFlags::System as u8;
+
+                    if system_bit != 0 {
+                        self.attributes &= !(FileEntryAttributesFlags::System as u8);
+                    }
+                }
+                FileEntryAttributes::Archive => {
+                    let archive_bit = self.attributes & FileEntryAttributesFlags::Archive as u8;
+
+                    if archive_bit == 0 {
+                        self.attributes |= FileEntryAttributesFlags::Archive as u8;
+                    }
+                }
+                FileEntryAttributes::NonArchive => {
+                    let archive_bit = self.attributes & FileEntryAttributesFlags::Archive as u8;
+
+                    if archive_bit != 0 {
+                        self.attributes &= !(FileEntryAttributesFlags::Archive as u8);
+                    }
+                }
                 _ => {}
             }
         }
     }
 
+    /// Marks the file entry as modified by updating its last modification date and time
+    /// and setting its archive bit.
+    pub(crate) fn mark_as_modified(&mut self) {
+        self.last_modification_datetime = Utc::now();
+        self.attributes |= FileEntryAttributesFlags::Archive as u8;
+    }
+
     pub(crate) fn get_attributes_as_string(&self) -> String {
         let mut result = String::new();
 
@@ -221,9 +345,37 @@ impl FileEntry {
             result.push('v');
         }
 
+        if self.attributes & FileEntryAttributesFlags::System as u8 != 0 {
+            result.push('s');
+        } else {
+            result.push('-');
+        }
+
+        if self.attributes & FileEntryAttributesFlags::Archive as u8 != 0 {
+            result.push('a');
+        } else {
+            result.push('-');
+        }
+
         result
     }
 
+    /// Marks the file entry as accessed by updating its last access date.
+    pub(crate) fn mark_as_accessed(&mut self) {
+        self.last_access_date = Utc::now().date_naive();
+    }
+
+    fn convert_date_time_to_u16_tuple(date_time: DateTime<Utc>) -> (u16, u16) {
+        let date_time = FileEntry::saturate_date_time(date_time);
+        let time = date_time.time();
+        let date = date_time.date_naive();
+
+        let time = (time.hour() << 11) | (time.minute() << 5) | (time.second() / 2);
+        let date = ((date.year() - 1980) << 9) as u32 | date.month() << 5 | date.day();
+
+        (date as u16, time as u16)
+    }
+
     fn convert_u16_tuple_to_date_time(value: (u16, u16)) -> LocalResult<DateTime<Utc>> {
         let year = (value.0 >> 9) + 1980;
         let month = (value.0 >> 5) & 0x0F;
@@ -242,6 +394,15 @@ impl FileEntry {
         )
     }
 
+    /// Clamps the given date and time to the range representable on disk (1980-01-01 00:00:00 to
+    /// 2107-12-31 23:59:58), as the year is stored on 7 bits since 1980.
+    fn saturate_date_time(date_time: DateTime<Utc>) -> DateTime<Utc> {
+        let min_date_time = Utc.with_ymd_and_hms(1980, 1, 1, 0, 0, 0).unwrap();
+        let max_date_time = Utc.with_ymd_and_hms(2107, 12, 31, 23, 59, 58).unwrap();
+
+        date_time.clamp(min_date_time, max_date_time)
+    }
+
     pub(crate) fn is_file(&self) -> bool {
         self.attributes & FileEntryAttributesFlags::Type as u8 != 0
     }
@@ -253,6 +414,195 @@ impl FileEntry {
     pub(crate) fn is_read_only(&self) -> bool {
         self.attributes & FileEntryAttributesFlags::Mode as u8 != 0
     }
+
+    pub(crate) fn is_system(&self) -> bool {
+        self.attributes & FileEntryAttributesFlags::System as u8 != 0
+    }
+
+    /// Checks whether the file entry owns an allocation chain (the empty files created with a size of 0 don't).
+    pub(crate) fn has_clusters(&self) -> bool {
+        self.first_cluster != Self::NO_CLUSTER
+    }
+
+    /// Serializes the file entry into a byte array without consuming it (its children entries are not part of it,
+    /// so a directory doesn't have to be cloned along with its whole subtree to be serialized).
+    pub(crate) fn to_byte_array(&self) -> ByteArray {
+        let mut result = Vec::new();
+        result.resize(32, 0);
+
+        let name = self.name.as_bytes();
+        let extension = self.extension.as_bytes();
+
+        // only the first 8 characters of the name are stored in the entry itself
+        name.iter()
+            .take(8)
+            .enumerate()
+            .for_each(|(index, &value)| result[index] = value);
+        extension
+            .iter()
+            .enumerate()
+            .for_each(|(index, &value)| result[index + 8] = value);
+
+        let size = self.size.to_be_bytes();
+        let first_cluster = self.first_cluster.to_be_bytes();
+
+        result[11] = size[0];
+        result[12] = size[1];
+        result[13] = size[2];
+        result[14] = size[3];
+
+        result[15] = first_cluster[0];
+        result[16] = first_cluster[1];
+
+        result[17] = self.attributes;
+
+        let (date, time) =
+            FileEntry::convert_date_time_to_u16_tuple(self.last_modification_datetime);
+
+        let time = time.to_be_bytes();
+        let date = date.to_be_bytes();
+
+        result[18] = time[0];
+        result[19] = time[1];
+        result[20] = date[0];
+        result[21] = date[1];
+
+        let (creation_date, creation_time) =
+            FileEntry::convert_date_time_to_u16_tuple(self.creation_datetime);
+
+        let creation_time = creation_time.to_be_bytes();
+        let creation_date = creation_date.to_be_bytes();
+
+        result[22] = creation_time[0];
+        result[23] = creation_time[1];
+        result[24] = creation_date[0];
+        result[25] = creation_date[1];
+
+        let (last_access_date, _) = FileEntry::convert_date_time_to_u16_tuple(
+            Utc.from_utc_datetime(&self.last_access_date.and_hms_opt(0, 0, 0).unwrap()),
+        );
+
+        let last_access_date = last_access_date.to_be_bytes();
+
+        result[26] = last_access_date[0];
+        result[27] = last_access_date[1];
+
+        let long_name_cluster = self.long_name_cluster.to_be_bytes();
+
+        result[28] = long_name_cluster[0];
+        result[29] = long_name_cluster[1];
+
+        result
+    }
+}
+
+/// FileEntryBuilder builds a `FileEntry` field by field, validating it on `build`
+/// # Defaults
+/// * `extension` - none
+/// * `size` - 0
+/// * `first_cluster` - `FileEntry::NO_CLUSTER`
+/// * `attributes` - a visible, read-write file
+/// * `last_modification_datetime` - now (the creation and last access dates being the same)
+/// * `parent_entry` and `children_entries` - none
+pub(crate) struct FileEntryBuilder {
+    file_entry: FileEntry,
+}
+
+impl FileEntryBuilder {
+    pub(crate) fn new(name: &str) -> Self {
+        Self {
+            file_entry: FileEntry::new(
+                name.to_string(),
+                String::new(),
+                0,
+                FileEntry::NO_CLUSTER,
+                FileEntryAttributes::combine(&[
+                    FileEntryAttributes::File,
+                    FileEntryAttributes::ReadWrite,
+                    FileEntryAttributes::Visible,
+                ]),
+                Utc::now(),
+                None,
+                None,
+            ),
+        }
+    }
+
+    pub(crate) fn extension(mut self, extension: &str) -> Self {
+        self.file_entry.extension = extension.to_string();
+        self
+    }
+
+    pub(crate) fn size(mut self, size: u32) -> Self {
+        self.file_entry.size = size;
+        self
+    }
+
+    pub(crate) fn first_cluster(mut self, first_cluster: u16) -> Self {
+        self.file_entry.first_cluster = first_cluster;
+        self
+    }
+
+    pub(crate) fn attributes(mut self, attributes: u8) -> Self {
+        self.file_entry.attributes = attributes;
+        self
+    }
+
+    /// Sets the last modification date and time, along with the creation and last access dates.
+    pub(crate) fn last_modification_datetime(mut self, datetime: DateTime<Utc>) -> Self {
+        self.file_entry.last_modification_datetime = datetime;
+        self.file_entry.creation_datetime = datetime;
+        self.file_entry.last_access_date = datetime.date_naive();
+        self
+    }
+
+    pub(crate) fn parent(mut self, parent_entry: &FileEntry) -> Self {
+        self.file_entry.parent_entry = Some(Box::new(parent_entry.clone()));
+        self
+    }
+
+    pub(crate) fn children(mut self, children_entries: Vec<FileEntry>) -> Self {
+        self.file_entry.children_entries = Some(children_entries);
+        self
+    }
+
+    /// Returns the file entry, unless its name is empty or too long, its extension is too long,
+    /// or it is a file with children or a directory with an extension.
+    pub(crate) fn build(self) -> Result<FileEntry, Box<dyn Error>> {
+        let file_entry = self.file_entry;
+
+        if file_entry.name.is_empty() || file_entry.name.len() > FileEntry::MAX_NAME_LENGTH {
+            return Err(Box::try_from(format!(
+                "Name {} must be between 1 and {} characters",
+                file_entry.name,
+                FileEntry::MAX_NAME_LENGTH
+            ))
+            .unwrap());
+        }
+
+        if file_entry.extension.len() > FileEntry::MAX_EXTENSION_LENGTH {
+            return Err(Box::try_from(format!(
+                "Extension {} must be {} characters or less",
+                file_entry.extension,
+                FileEntry::MAX_EXTENSION_LENGTH
+            ))
+            .unwrap());
+        }
+
+        match file_entry.is_file() {
+            true if file_entry.children_entries.is_some() => Err(Box::try_from(format!(
+                "File {} can't have children entries",
+                file_entry.get_full_name()
+            ))
+            .unwrap()),
+            false if !file_entry.extension.is_empty() => Err(Box::try_from(format!(
+                "Directory {} can't have an extension",
+                file_entry.name
+            ))
+            .unwrap()),
+            _ => Ok(file_entry),
+        }
+    }
 }
 
 impl Display for FileEntry {
@@ -279,6 +629,25 @@ impl Display for FileEntry {
     }
 }
 
+/// Serializes a file entry into a flat structure for the tools consuming the JSON output of `ls`
+/// (the parent and children entries are left out).
+impl Serialize for FileEntry {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("FileEntry", 7)?;
+        state.serialize_field("name", &self.name)?;
+        state.serialize_field("extension", &self.extension)?;
+        state.serialize_field("size", &self.size)?;
+        state.serialize_field("attributes", &self.get_attributes_as_string())?;
+        state.serialize_field("is_file", &self.is_file())?;
+        state.serialize_field(
+            "last_modification_datetime",
+            &self.last_modification_datetime.to_rfc3339(),
+        )?;
+        state.serialize_field("first_cluster", &self.first_cluster)?;
+        state.end()
+    }
+}
+
 /// Deserializes a byte array into a file entry.
 impl From<ByteArray> for FileEntry {
     fn from(value: ByteArray) -> Self {
@@ -303,13 +672,63 @@ impl From<ByteArray> for FileEntry {
         let time = u16::from_be_bytes([value[18], value[19]]);
         let date = u16::from_be_bytes([value[20], value[21]]);
 
-        let last_modification_datetime =
-            match FileEntry::convert_u16_tuple_to_date_time((date, time)) {
+        let last_modification_datetime = match FileEntry::convert_u16_tuple_to_date_time((
+            date, time,
+        )) {
+            LocalResult::Single(value) => value,
+            _ => {
+                log::warn!(
+                    "Invalid date and time {:#06x} {:#06x} for file entry {}.{}, falling back to epoch",
+                    date,
+                    time,
+                    name,
+                    extension
+                );
+                DateTime::<Utc>::default()
+            }
+        };
+
+        // entries written before the creation and access timestamps were introduced have them zeroed,
+        // so fall back to the last modification date and time for them
+        let creation_time = u16::from_be_bytes([value[22], value[23]]);
+        let creation_date = u16::from_be_bytes([value[24], value[25]]);
+
+        let creation_datetime = match (creation_date, creation_time) {
+            (0, 0) => last_modification_datetime,
+            _ => match FileEntry::convert_u16_tuple_to_date_time((creation_date, creation_time)) {
                 LocalResult::Single(value) => value,
                 _ => {
-                    panic!("Invalid date and time: {:?}", value);
+                    log::warn!(
+                        "Invalid creation date and time {:#06x} {:#06x} for file entry {}.{}",
+                        creation_date,
+                        creation_time,
+                        name,
+                        extension
+                    );
+                    last_modification_datetime
                 }
-            };
+            },
+        };
+
+        let long_name_cluster = u16::from_be_bytes([value[28], value[29]]);
+
+        let last_access_date = u16::from_be_bytes([value[26], value[27]]);
+
+        let last_access_date = match last_access_date {
+            0 => last_modification_datetime.date_naive(),
+            _ => match FileEntry::convert_u16_tuple_to_date_time((last_access_date, 0)) {
+                LocalResult::Single(value) => value.date_naive(),
+                _ => {
+                    log::warn!(
+                        "Invalid last access date {:#06x} for file entry {}.{}",
+                        last_access_date,
+                        name,
+                        extension
+                    );
+                    last_modification_datetime.date_naive()
+                }
+            },
+        };
 
         Self {
             name,
@@ -318,6 +737,9 @@ impl From<ByteArray> for FileEntry {
             first_cluster,
             attributes,
             last_modification_datetime,
+            creation_datetime,
+            last_access_date,
+            long_name_cluster,
             parent_entry: None,
             children_entries: None,
         }
@@ -327,50 +749,169 @@ impl From<ByteArray> for FileEntry {
 /// Serializes a file entry into a byte array.
 impl Into<ByteArray> for FileEntry {
     fn into(self) -> ByteArray {
-        let mut result = Vec::new();
-        result.resize(32, 0);
+        self.to_byte_array()
+    }
+}
 
-        let name = self.name.as_bytes();
-        let extension = self.extension.as_bytes();
+/// A root table is a list of file entries.
+pub(crate) type RootTable = Vec<FileEntry>;
 
-        name.iter()
-            .enumerate()
-            .for_each(|(index, &value)| result[index] = value);
-        extension
-            .iter()
-            .enumerate()
-            .for_each(|(index, &value)| result[index + 8] = value);
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-        let size = self.size.to_be_bytes();
-        let first_cluster = self.first_cluster.to_be_bytes();
+    /// Serializes a file entry modified at the given date and time, then deserializes it back.
+    fn round_trip_date_time(date_time: DateTime<Utc>) -> FileEntry {
+        let file_entry = FileEntry::builder("dated")
+            .extension("txt")
+            .attributes(FileEntryAttributesFlags::Type as u8)
+            .last_modification_datetime(date_time)
+            .build()
+            .unwrap();
 
-        result[11] = size[0];
-        result[12] = size[1];
-        result[13] = size[2];
-        result[14] = size[3];
+        FileEntry::from(Into::<ByteArray>::into(file_entry))
+    }
 
-        result[15] = first_cluster[0];
-        result[16] = first_cluster[1];
+    #[test]
+    fn the_first_representable_year_round_trips() {
+        let date_time = Utc.with_ymd_and_hms(1980, 1, 1, 0, 0, 0).unwrap();
 
-        result[17] = self.attributes;
+        let file_entry = round_trip_date_time(date_time);
 
-        let time = self.last_modification_datetime.time();
-        let date = self.last_modification_datetime.date_naive();
+        assert_eq!(file_entry.last_modification_datetime, date_time);
+        assert_eq!(file_entry.creation_datetime, date_time);
+        assert_eq!(file_entry.last_access_date, date_time.date_naive());
+    }
 
-        let time = (time.hour() << 11) | (time.minute() << 5) | (time.second() / 2);
-        let date = ((date.year() - 1980) << 9) as u32 | date.month() << 5 | date.day();
+    #[test]
+    fn the_last_representable_year_round_trips() {
+        let date_time = Utc.with_ymd_and_hms(2107, 12, 31, 23, 59, 58).unwrap();
 
-        let time = (time as u16).to_be_bytes();
-        let date = (date as u16).to_be_bytes();
+        let file_entry = round_trip_date_time(date_time);
 
-        result[18] = time[0];
-        result[19] = time[1];
-        result[20] = date[0];
-        result[21] = date[1];
+        assert_eq!(file_entry.last_modification_datetime, date_time);
+        assert_eq!(file_entry.creation_datetime, date_time);
+    }
 
-        result
+    #[test]
+    fn a_year_past_2107_saturates() {
+        let date_time = Utc.with_ymd_and_hms(2200, 6, 15, 12, 30, 0).unwrap();
+
+        let file_entry = round_trip_date_time(date_time);
+
+        assert_eq!(
+            file_entry.last_modification_datetime,
+            Utc.with_ymd_and_hms(2107, 12, 31, 23, 59, 58).unwrap()
+        );
     }
-}
 
-/// A root table is a list of file entries.
-pub(crate) type RootTable = Vec<FileEntry>;
+    #[test]
+    fn a_year_before_1980_saturates() {
+        let date_time = Utc.with_ymd_and_hms(1970, 1, 1, 0, 0, 0).unwrap();
+
+        let file_entry = round_trip_date_time(date_time);
+
+        assert_eq!(
+            file_entry.last_modification_datetime,
+            Utc.with_ymd_and_hms(1980, 1, 1, 0, 0, 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn a_corrupt_date_falls_back_to_the_epoch() {
+        let date_time = Utc.with_ymd_and_hms(2024, 2, 29, 10, 20, 30).unwrap();
+        let mut bytes: ByteArray = round_trip_date_time(date_time).into();
+
+        // month 0 doesn't exist
+        let corrupt_date = (44u16 << 9) | 1;
+        bytes[20..22].copy_from_slice(&corrupt_date.to_be_bytes());
+        let file_entry = FileEntry::from(bytes);
+
+        assert_eq!(
+            file_entry.last_modification_datetime,
+            DateTime::<Utc>::default()
+        );
+        assert_eq!(file_entry.creation_datetime, date_time);
+    }
+
+    #[test]
+    fn names_match_ignoring_the_ascii_case() {
+        let file_entry = FileEntry::builder("ReadMe")
+            .extension("Txt")
+            .attributes(FileEntryAttributesFlags::Type as u8)
+            .build()
+            .unwrap();
+
+        assert!(file_entry.name_matches("README", "TXT"));
+        assert!(file_entry.name_matches("readme", "txt"));
+        assert!(!file_entry.name_matches("readme", "md"));
+    }
+
+    #[test]
+    fn the_system_bit_toggles_independently_of_the_others() {
+        let mut file_entry = FileEntry::builder("sys")
+            .extension("bin")
+            .attributes(FileEntryAttributes::combine(&[
+                FileEntryAttributes::File,
+                FileEntryAttributes::ReadOnly,
+                FileEntryAttributes::Hidden,
+            ]))
+            .build()
+            .unwrap();
+        assert_eq!(file_entry.get_attributes_as_string(), "frh--");
+
+        file_entry.apply_attributes(&vec!["+s".parse().unwrap()]);
+        assert!(file_entry.is_system());
+        assert_eq!(file_entry.attributes, 0x0F);
+        assert_eq!(file_entry.get_attributes_as_string(), "frhs-");
+
+        file_entry.apply_attributes(&vec!["+w".parse().unwrap(), "-h".parse().unwrap()]);
+        assert!(file_entry.is_system());
+        assert_eq!(file_entry.get_attributes_as_string(), "fwvs-");
+
+        file_entry.apply_attributes(&vec!["-s".parse().unwrap()]);
+        assert!(!file_entry.is_system());
+        assert_eq!(file_entry.attributes, FileEntryAttributesFlags::Type as u8);
+        assert_eq!(file_entry.get_attributes_as_string(), "fwv--");
+    }
+
+    #[test]
+    fn the_system_bit_survives_serialization() {
+        let file_entry = FileEntry::builder("sys")
+            .extension("bin")
+            .attributes(FileEntryAttributes::File | FileEntryAttributes::System)
+            .build()
+            .unwrap();
+
+        let bytes: ByteArray = file_entry.into();
+
+        assert_eq!(bytes[17], 0x0C);
+        assert!(FileEntry::from(bytes).is_system());
+    }
+
+    #[test]
+    fn the_three_timestamps_round_trip_separately() {
+        let mut file_entry = FileEntry::builder("stamped")
+            .extension("txt")
+            .attributes(FileEntryAttributesFlags::Type as u8)
+            .last_modification_datetime(Utc.with_ymd_and_hms(2023, 5, 6, 7, 8, 10).unwrap())
+            .build()
+            .unwrap();
+        file_entry.creation_datetime = Utc.with_ymd_and_hms(2001, 2, 3, 4, 5, 6).unwrap();
+        file_entry.last_access_date = NaiveDate::from_ymd_opt(2024, 12, 31).unwrap();
+
+        let round_tripped_file_entry = FileEntry::from(Into::<ByteArray>::into(file_entry.clone()));
+        assert_eq!(
+            round_tripped_file_entry.last_modification_datetime,
+            file_entry.last_modification_datetime
+        );
+        assert_eq!(
+            round_tripped_file_entry.creation_datetime,
+            file_entry.creation_datetime
+        );
+        assert_eq!(
+            round_tripped_file_entry.last_access_date,
+            file_entry.last_access_date
+        );
+    }
+}
@@ -0,0 +1,89 @@
+/// The fragmentation of the allocation chain of a file entry:
+/// - `entry_path`: the path of the file entry (long name chains are reported as separate entries)
+/// - `clusters_count`: the number of clusters of the allocation chain
+/// - `fragmented_transitions`: the number of transitions from a cluster to a next cluster which is not the very next one
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct FileFragmentation {
+    pub(crate) entry_path: String,
+    pub(crate) clusters_count: usize,
+    pub(crate) fragmented_transitions: usize,
+}
+
+impl FileFragmentation {
+    /// Returns the number of transitions between consecutive clusters of the allocation chain.
+    pub(crate) fn get_transitions_count(&self) -> usize {
+        self.clusters_count.saturating_sub(1)
+    }
+
+    pub(crate) fn is_fragmented(&self) -> bool {
+        self.fragmented_transitions > 0
+    }
+}
+
+/// The fragmentation of all the allocation chains reachable from the directory tree, in root order.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct FragReport {
+    pub(crate) files: Vec<FileFragmentation>,
+}
+
+impl FragReport {
+    /// Returns the number of transitions between consecutive clusters of all the allocation chains.
+    pub(crate) fn get_transitions_count(&self) -> usize {
+        self.files
+            .iter()
+            .map(FileFragmentation::get_transitions_count)
+            .sum()
+    }
+
+    /// Returns the number of transitions to a next cluster which is not the very next one.
+    pub(crate) fn get_fragmented_transitions_count(&self) -> usize {
+        self.files
+            .iter()
+            .map(|file| file.fragmented_transitions)
+            .sum()
+    }
+
+    /// Returns the percentage of fragmented transitions (0 for a disk whose chains are all contiguous).
+    pub(crate) fn get_fragmentation_percentage(&self) -> f64 {
+        match self.get_transitions_count() {
+            0 => 0.0,
+            transitions_count => {
+                self.get_fragmented_transitions_count() as f64 * 100.0 / transitions_count as f64
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(clusters_count: usize, fragmented_transitions: usize) -> FileFragmentation {
+        FileFragmentation {
+            entry_path: "/file.txt".to_string(),
+            clusters_count,
+            fragmented_transitions,
+        }
+    }
+
+    #[test]
+    fn the_percentage_is_taken_over_the_transitions_of_all_the_files() {
+        let report = FragReport {
+            files: vec![file(5, 2), file(1, 0), file(3, 0), file(0, 0)],
+        };
+
+        assert_eq!(report.get_transitions_count(), 6);
+        assert_eq!(report.get_fragmented_transitions_count(), 2);
+        assert!((report.get_fragmentation_percentage() - 100.0 / 3.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn a_disk_without_transitions_is_not_fragmented() {
+        let report = FragReport {
+            files: vec![file(1, 0), file(0, 0)],
+        };
+
+        assert_eq!(report.get_fragmentation_percentage(), 0.0);
+        assert_eq!(FragReport::default().get_fragmentation_percentage(), 0.0);
+    }
+}
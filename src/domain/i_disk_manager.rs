@@ -1,25 +1,72 @@
+use crate::application::commands::badblocks::BadBlocksRequest;
 use crate::application::commands::cd::ChangeDirectoryRequest;
 use crate::application::commands::cp::CopyRequest;
 use crate::application::commands::create::CreateRequest;
 use crate::application::commands::del::DeleteRequest;
+use crate::application::commands::echo::EchoRequest;
 use crate::application::commands::fmt::FormatRequest;
+use crate::application::commands::label::LabelRequest;
+use crate::application::commands::ln::LinkRequest;
 use crate::application::commands::mkdir::MakeDirectoryRequest;
 use crate::application::commands::rename::RenameRequest;
+use crate::application::commands::resize::ResizeRequest;
+use crate::application::commands::rmdir::RemoveDirectoryRequest;
 use crate::application::commands::setattr::SetAttributesRequest;
+use crate::application::commands::truncate::TruncateRequest;
+use crate::application::commands::writecluster::WriteClusterRequest;
 use crate::application::queries::cat::CatRequest;
 use crate::application::queries::ls::ListRequest;
+use crate::application::queries::stat::StatRequest;
 use crate::application::Void;
 use crate::domain::boot_sector::BootSector;
-use crate::domain::file_entry::RootTable;
+use crate::domain::consistency_error::ConsistencyError;
+use crate::domain::fat::FatValue;
+use crate::domain::file_entry::{FileEntry, RootTable};
+use crate::domain::frag_report::FragReport;
 use std::error::Error;
+use std::time::Instant;
 
 pub(crate) trait IDiskManager: Sync + Send {
-    /// Propagates the latest changes from the disk manager to the storage file.
+    /// Propagates the latest changes from the disk manager to the storage file by writing only the modified clusters.
     /// This method should be called after every command request, i.e. every operation that modifies the storage.
+    /// It does nothing on a read-only disk, whose modifying operations all fail, and with the autosave
+    /// the modified clusters are only written later on, by `flush`.
     /// ## Errors
     /// * `Box<dyn Error>` - If the disk manager is not able to sync with the storage file.
     fn push_sync(&mut self);
 
+    /// Overwrites the whole storage file with the in-memory disk representation.
+    /// This method should be used only when the storage file is created from scratch, i.e. on the first boot
+    /// or after a format/defragmentation operation, as the storage file content is not related to the in-memory data anymore.
+    /// It does nothing on a read-only disk.
+    /// ## Errors
+    /// * `Box<dyn Error>` - If the disk manager is not able to sync with the storage file.
+    fn push_sync_all(&mut self);
+
+    /// Keeps the previous content of the clusters modified by the command which has just run as the snapshot
+    /// restored by `restore_snapshot` (forgetting the one restored by `reapply_snapshot`),
+    /// unless the command has modified nothing.
+    /// This method should be called after every command.
+    fn snapshot(&mut self);
+
+    /// Restores the disk as it was before the last command modifying it and keeps the undone content
+    /// for `reapply_snapshot`, returning whether there was a snapshot to restore.
+    /// ## Errors
+    /// * `Box<dyn Error>` - If the disk is read-only.
+    fn restore_snapshot(&mut self) -> Result<bool, Box<dyn Error>>;
+
+    /// Reapplies the last command undone by `restore_snapshot` (which can then be undone again),
+    /// returning whether there was an undone command to reapply.
+    /// ## Errors
+    /// * `Box<dyn Error>` - If the disk is read-only.
+    fn reapply_snapshot(&mut self) -> Result<bool, Box<dyn Error>>;
+
+    /// Writes the pushes not yet written to the storage file by the autosave (it does nothing without the autosave).
+    fn flush(&mut self);
+
+    /// Returns the time of the last push not yet written to the storage file by the autosave, if any.
+    fn get_last_unsaved_push(&self) -> Option<Instant>;
+
     /// Brings into the in-memory disk manager the latest changes from the storage file.
     /// This method should be called before every query request, i.e. every operation that only inquires the storage.
     /// ## Errors
@@ -49,7 +96,7 @@ pub(crate) trait IDiskManager: Sync + Send {
     /// * `Box<dyn Error>` - If the disk manager is not able to sync with the storage file.
     fn list_files(&mut self, request: &ListRequest) -> Result<RootTable, Box<dyn Error>>;
 
-    /// Renames a file with the given name.
+    /// Renames a file with the given name in the working directory (the rename is persisted by the next `push_sync`).
     /// ## Arguments
     /// * `request` - The request containing the old and the new names.
     /// ## Errors
@@ -63,6 +110,39 @@ pub(crate) trait IDiskManager: Sync + Send {
     /// * `Box<dyn Error>` - If the file does not exist.
     fn delete_file(&mut self, request: &DeleteRequest) -> Void;
 
+    /// Moves a file or directory with the given name (along with its whole content) to the trash directory
+    /// of the root instead of freeing its clusters, replacing a previously trashed entry with the same name
+    /// (deleting from the trash itself is permanent).
+    /// ## Arguments
+    /// * `request` - The request containing the file name and the file extension.
+    /// ## Errors
+    /// * `Box<dyn Error>` - If the file does not exist or there is not enough space in the disk to move it.
+    fn move_to_trash(&mut self, request: &DeleteRequest) -> Void;
+
+    /// Moves a file or directory with the given name from the trash back to the working directory.
+    /// ## Arguments
+    /// * `name` - The name of the file or directory.
+    /// * `extension` - The extension of the file (empty for a directory).
+    /// ## Errors
+    /// * `Box<dyn Error>` - If the entry is not in the trash, if the working directory already holds an entry
+    ///   with the same name or is in the trash, or if there is not enough space in the disk to move it.
+    fn restore_from_trash(&mut self, name: &str, extension: &str) -> Void;
+
+    /// Returns the file entries held by the trash directory (empty if there is no trash directory).
+    fn list_trash(&self) -> RootTable;
+
+    /// Deletes the trash directory along with its content, freeing its clusters.
+    /// ## Errors
+    /// * `Box<dyn Error>` - If the working directory is in the trash.
+    fn empty_trash(&mut self) -> Void;
+
+    /// Removes a directory with the given name, only if it contains no entries besides `.` and `..`.
+    /// ## Arguments
+    /// * `request` - The request containing the directory name.
+    /// ## Errors
+    /// * `Box<dyn Error>` - If the directory does not exist or it is not empty.
+    fn remove_empty_directory(&mut self, request: &RemoveDirectoryRequest) -> Void;
+
     /// Displays the content of a file with the given name.
     /// ## Arguments
     /// * `request` - The request containing the file name and the file extension.
@@ -70,6 +150,48 @@ pub(crate) trait IDiskManager: Sync + Send {
     /// * `Box<dyn Error>` - If the file does not exist.
     fn get_file_content(&mut self, request: &CatRequest) -> Result<String, Box<dyn Error>>;
 
+    /// Returns the raw bytes of a file with the given name, as stored in its cluster chain.
+    /// ## Arguments
+    /// * `request` - The request containing the file name and the file extension.
+    /// ## Errors
+    /// * `Box<dyn Error>` - If the file does not exist.
+    fn read_file_bytes(&mut self, request: &CatRequest) -> Result<Vec<u8>, Box<dyn Error>>;
+
+    /// Returns the raw bytes of the given file entry (from any directory), as stored in its cluster chain,
+    /// without marking it as accessed.
+    fn read_entry_bytes(&self, file_entry: &FileEntry) -> Vec<u8>;
+
+    /// Returns the file entry of a file or directory with the given name.
+    /// ## Arguments
+    /// * `request` - The request containing the name and the extension (empty for directories).
+    /// ## Errors
+    /// * `Box<dyn Error>` - If the file or directory does not exist.
+    fn get_file_entry(&mut self, request: &StatRequest) -> Result<FileEntry, Box<dyn Error>>;
+
+    /// Writes the given content into a file, creating the file if it doesn't exist
+    /// or replacing its whole content otherwise.
+    /// ## Arguments
+    /// * `request` - The request containing the file name, the file extension and the content to be written.
+    /// ## Errors
+    /// * `Box<dyn Error>` - If the file is read only or there is not enough space in the disk.
+    fn write_file_content(&mut self, request: &EchoRequest) -> Void;
+
+    /// Appends the given content to the end of a file, creating the file if it doesn't exist.
+    /// The slack of the last cluster of the file is filled first, then the allocation chain is extended as needed.
+    /// ## Arguments
+    /// * `request` - The request containing the file name, the file extension and the content to be appended.
+    /// ## Errors
+    /// * `Box<dyn Error>` - If the file is read only or there is not enough space in the disk.
+    fn append_to_file(&mut self, request: &EchoRequest) -> Void;
+
+    /// Shrinks or grows a file to the given size.
+    /// Shrinking frees the unused tail of the allocation chain, while growing extends it with clusters filled with zeros.
+    /// ## Arguments
+    /// * `request` - The request containing the file name, the file extension and the new size.
+    /// ## Errors
+    /// * `Box<dyn Error>` - If the file does not exist, it is read only or there is not enough space in the disk.
+    fn truncate_file(&mut self, request: &TruncateRequest) -> Void;
+
     /// Copies a file with the given name.
     /// ## Arguments
     /// * `request` - The request containing the file name and the file extension for the source file and the destination file.
@@ -77,6 +199,14 @@ pub(crate) trait IDiskManager: Sync + Send {
     /// * `Box<dyn Error>` - If the source file does not exist or a file with the same name as the destination file already exists.
     fn copy_file(&mut self, request: &CopyRequest) -> Void;
 
+    /// Creates a hard link to a file of the working directory: a second file entry sharing its allocation chain,
+    /// whose content is never freed as long as another entry links to it.
+    /// ## Arguments
+    /// * `request` - The request containing the file name and the file extension for the linked file and the link.
+    /// ## Errors
+    /// * `Box<dyn Error>` - If the linked file does not exist or is a directory, or the link already exists.
+    fn link_file(&mut self, request: &LinkRequest) -> Void;
+
     /// Set attributes for a given file or directory.
     /// ## Arguments
     /// * `request` - The request containing the file/directory name and the attributes to set.
@@ -84,18 +214,48 @@ pub(crate) trait IDiskManager: Sync + Send {
     /// * `Box<dyn Error>` - If the file/directory does not exist.
     fn set_attributes(&mut self, request: &SetAttributesRequest) -> Void;
 
-    /// Formats the disk
+    /// Sets the volume label of the disk (in uppercase).
+    /// ## Arguments
+    /// * `request` - The request containing the new label.
+    /// ## Errors
+    /// * `Box<dyn Error>` - If the label is too long or holds invalid characters,
+    ///   or if the clusters of the disk are too small to hold it.
+    fn set_volume_label(&mut self, request: &LabelRequest) -> Void;
+
+    /// Formats the disk (or does nothing on a dry run)
     /// ## Arguments
     /// * `request` - The request containing the FAT type.
     /// ## Errors
     /// * `Box<dyn Error>` - If the disk is not able to be formatted.
     fn format_disk(&mut self, request: &FormatRequest) -> Void;
 
-    /// Defragments the disk
+    /// Defragments the disk in place by moving the allocation chains, in root order, to the front of the data region
+    /// (the working directory is changed to the root).
     /// ## Errors
-    /// * `Box<dyn Error>` - If the disk is not able to be defragmented.
+    /// * `Box<dyn Error>` - If the disk is not able to be defragmented (e.g. it has consistency errors).
     fn defragment_disk(&mut self) -> Void;
 
+    /// Grows or shrinks the disk to the number of clusters of the request, preserving its content: the allocation
+    /// chains are moved, in root order, to the front of the data region of the resized disk, which replaces the
+    /// current one in the storage file (the working directory is changed to the root).
+    /// ## Arguments
+    /// * `request` - The request containing the new number of clusters.
+    /// ## Errors
+    /// * `Box<dyn Error>` - If the disk has consistency errors, if the new geometry is invalid
+    ///   or if the content of the disk doesn't fit in the resized one.
+    fn resize_disk(&mut self, request: &ResizeRequest) -> Void;
+
+    /// Returns the fragmentation of every allocation chain reachable from the directory tree, i.e. how many
+    /// of the transitions between its consecutive clusters don't lead to the very next cluster
+    fn fragmentation_report(&mut self) -> FragReport;
+
+    /// Marks a free cluster as bad, so that it is never allocated
+    /// ## Arguments
+    /// * `request` - The request containing the index of the cluster.
+    /// ## Errors
+    /// * `Box<dyn Error>` - If the cluster is out of bounds, reserved, in use or already marked as bad.
+    fn mark_bad_cluster(&mut self, request: &BadBlocksRequest) -> Void;
+
     /// Creates a new directory in the working directory.
     /// ## Arguments
     /// * `request` - The request containing the directory name.
@@ -110,15 +270,63 @@ pub(crate) trait IDiskManager: Sync + Send {
     /// * `Box<dyn Error>` - If the directory does not exist.
     fn change_working_directory(&mut self, request: &ChangeDirectoryRequest) -> Void;
 
+    /// Walks the whole in-memory directory tree depth-first, every directory being followed by its children,
+    /// and returns every entry (except for the `.` and `..` entries) along with its path from the root (e.g. `/dir/a.txt`)
+    fn walk(&self) -> Vec<(String, FileEntry)>;
+
+    /// Checks the consistency of the FAT table against the directory tree.
+    /// Returns all the detected consistency errors (looping, broken, cross-linked or lost chains
+    /// and file entries starting at a free cluster), or an empty vector if the disk is consistent.
+    fn check_disk(&mut self) -> Vec<ConsistencyError>;
+
+    /// Returns the raw bytes of a cluster, as held by the storage buffer.
+    /// ## Arguments
+    /// * `cluster_index` - The index of the cluster.
+    /// ## Errors
+    /// * `Box<dyn Error>` - If the cluster is out of bounds.
+    fn read_cluster(&self, cluster_index: usize) -> Result<Vec<u8>, Box<dyn Error>>;
+
+    /// Overwrites the raw bytes of a data cluster (zero-filling the rest of it), leaving the FAT untouched.
+    /// ## Arguments
+    /// * `request` - The request containing the index of the cluster and the bytes to write.
+    /// ## Errors
+    /// * `Box<dyn Error>` - If the cluster is out of bounds or reserved, or if the bytes don't fit in a cluster.
+    fn write_cluster(&mut self, request: &WriteClusterRequest) -> Void;
+
+    /// Returns at most `count` FAT cells starting from the `start` index, paired with their indices.
+    fn get_fat_slice(&self, start: usize, count: usize) -> Vec<(usize, FatValue)>;
+
+    /// Returns the clusters of the allocation chain starting at the given cluster, in order, by walking the FAT
+    /// (empty if the cluster doesn't start a chain, e.g. for an empty file); a looping chain stops before
+    /// its first repeated cluster.
+    fn cluster_chain(&self, first_cluster: usize) -> Vec<usize>;
+
     /// Returns the whole path to the working directory
     fn get_working_directory_full_path(&self) -> String;
 
+    /// Returns the number of used entries of the root table along with its maximum number of entries
+    /// (the subdirectories have no such physical limit, while every directory, the root included, is subject
+    /// to the `max_directory_entries` quota of the config if any).
+    fn root_capacity(&self) -> (usize, usize);
+
+    /// Returns the whole path to the working directory before the last `cd`, if any
+    fn get_previous_directory(&self) -> Option<String>;
+
+    /// Records the whole path to the working directory before a `cd`
+    fn set_previous_directory(&mut self, path: String);
+
     /// Get boot sector
     fn get_boot_sector(&self) -> &BootSector;
 
     /// Returns the free space in the disk with respect to the total number of empty clusters
     fn get_free_space(&mut self) -> u64;
 
+    /// Returns the space in the disk taken by the clusters marked as bad
+    fn get_bad_space(&self) -> u64;
+
     /// Returns the total space in the disk
     fn get_total_space(&self) -> u64;
+
+    /// Returns the number of clusters read from and written to the storage file since the disk has been opened
+    fn get_cluster_io_counts(&self) -> (u64, u64);
 }
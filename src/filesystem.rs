@@ -0,0 +1,171 @@
+use crate::application::commands::cd::ChangeDirectoryRequest;
+use crate::application::commands::del::DeleteRequest;
+use crate::application::commands::echo::EchoRequest;
+use crate::application::commands::mkdir::MakeDirectoryRequest;
+use crate::application::commands::rmdir::RemoveDirectoryRequest;
+use crate::application::queries::cat::CatRequest;
+use crate::application::queries::ls::ListRequest;
+use crate::application::Void;
+use crate::core::config::Config;
+use crate::domain::file_entry::{FileEntry, FileEntryAttributes};
+use crate::domain::i_disk_manager::IDiskManager;
+use crate::infrastructure::disk_manager::DiskManager;
+use crate::CONFIG;
+use chrono::{DateTime, Utc};
+use std::error::Error;
+use std::sync::{Arc, Mutex};
+
+/// EntryInfo describes a file or a directory listed by `Filesystem::list`
+/// # Fields
+/// * `name` - the name of the entry
+/// * `extension` - the extension of the entry (empty for directories)
+/// * `size` - the size of the entry in bytes
+/// * `is_file` - whether the entry is a file (otherwise it is a directory)
+/// * `last_modification_datetime` - the last modification date and time of the entry
+#[derive(Debug, Clone, PartialEq)]
+pub struct EntryInfo {
+    pub name: String,
+    pub extension: String,
+    pub size: u32,
+    pub is_file: bool,
+    pub last_modification_datetime: DateTime<Utc>,
+}
+
+impl From<&FileEntry> for EntryInfo {
+    fn from(file_entry: &FileEntry) -> Self {
+        Self {
+            name: file_entry.name.clone(),
+            extension: file_entry.extension.clone(),
+            size: file_entry.size,
+            is_file: file_entry.is_file(),
+            last_modification_datetime: file_entry.last_modification_datetime,
+        }
+    }
+}
+
+/// Filesystem is the public facade of a disk, meant to embed RoDOS without going through its shell.
+///
+/// Each operation runs against the disk like a command of the shell does: the disk is reloaded from its storage file
+/// first and, if the operation succeeds, its modifications are persisted to the storage file.
+pub struct Filesystem {
+    disk_manager: DiskManager,
+}
+
+impl Filesystem {
+    /// Opens the disk stored at the given path (its directory must exist), creating it with the configured geometry
+    /// if the file doesn't exist or is empty (the rest of the configuration is the one of the shell, except for
+    /// the autosave, as every operation is written to the storage file right away).
+    pub fn open(storage_file_path: &str) -> Result<Self, Box<dyn Error>> {
+        let config = Config {
+            storage_file_path: storage_file_path.to_string(),
+            autosave_delay_ms: 0,
+            ..CONFIG.clone()
+        };
+        let disk_manager = DiskManager::open(Arc::new(Mutex::new(config)))?;
+
+        Ok(Self { disk_manager })
+    }
+
+    /// Returns the full path of the working directory.
+    pub fn working_directory(&self) -> String {
+        self.disk_manager.get_working_directory_full_path()
+    }
+
+    /// Returns the visible entries of the working directory.
+    pub fn list(&mut self) -> Result<Vec<EntryInfo>, Box<dyn Error>> {
+        self.disk_manager.pull_sync();
+        let file_entries = self
+            .disk_manager
+            .list_files(&ListRequest::new(vec![], None))?;
+
+        Ok(file_entries.iter().map(EntryInfo::from).collect())
+    }
+
+    /// Returns the content of a file of the working directory.
+    pub fn read_file(&mut self, name: &str, extension: &str) -> Result<Vec<u8>, Box<dyn Error>> {
+        self.disk_manager.pull_sync();
+        self.disk_manager
+            .read_file_bytes(&CatRequest::new(name.to_string(), extension.to_string()))
+    }
+
+    /// Writes the given content into a file of the working directory, creating it if it doesn't exist.
+    pub fn write_file(&mut self, name: &str, extension: &str, content: &str) -> Void {
+        self.run(|disk_manager| {
+            disk_manager.write_file_content(&EchoRequest::new(
+                name.to_string(),
+                extension.to_string(),
+                content.to_string(),
+                false,
+            ))
+        })
+    }
+
+    /// Appends the given content to a file of the working directory, creating it if it doesn't exist.
+    pub fn append_to_file(&mut self, name: &str, extension: &str, content: &str) -> Void {
+        self.run(|disk_manager| {
+            disk_manager.append_to_file(&EchoRequest::new(
+                name.to_string(),
+                extension.to_string(),
+                content.to_string(),
+                true,
+            ))
+        })
+    }
+
+    /// Deletes a file of the working directory, or a directory along with its whole content (without confirmation).
+    pub fn delete(&mut self, name: &str, extension: &str) -> Void {
+        self.run(|disk_manager| {
+            disk_manager.delete_file(&DeleteRequest::new(
+                name.to_string(),
+                extension.to_string(),
+                true,
+                false,
+                false,
+            ))
+        })
+    }
+
+    /// Creates a directory in the working directory.
+    pub fn make_directory(&mut self, name: &str) -> Void {
+        self.run(|disk_manager| {
+            disk_manager.make_directory(&MakeDirectoryRequest::new(
+                name.to_string(),
+                FileEntryAttributes::combine(&[
+                    FileEntryAttributes::Directory,
+                    FileEntryAttributes::ReadWrite,
+                    FileEntryAttributes::Visible,
+                ]),
+                Utc::now(),
+            ))
+        })
+    }
+
+    /// Removes an empty directory of the working directory.
+    pub fn remove_directory(&mut self, name: &str) -> Void {
+        self.run(|disk_manager| {
+            disk_manager.remove_empty_directory(&RemoveDirectoryRequest::new(name.to_string()))
+        })
+    }
+
+    /// Changes the working directory along a (relative or absolute) path.
+    pub fn change_directory(&mut self, path: &str) -> Void {
+        self.run(|disk_manager| {
+            disk_manager.change_working_directory(&ChangeDirectoryRequest::new(path.to_string()))
+        })
+    }
+
+    /// Returns the free space of the disk in bytes.
+    pub fn free_space(&mut self) -> u64 {
+        self.disk_manager.pull_sync();
+        self.disk_manager.get_free_space()
+    }
+
+    /// Runs a mutating operation against the disk reloaded from its storage file, persisting it if it succeeds.
+    fn run(&mut self, operation: impl FnOnce(&mut DiskManager) -> Void) -> Void {
+        self.disk_manager.pull_sync();
+        operation(&mut self.disk_manager)?;
+        self.disk_manager.push_sync();
+
+        Ok(())
+    }
+}
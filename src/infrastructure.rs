@@ -1,5 +1,7 @@
 pub(crate) mod disk_manager;
 pub(crate) mod i_disk_manager_impl;
+#[cfg(test)]
+pub(crate) mod test_disk;
 
 pub(crate) type ByteArray = Vec<u8>;
 
@@ -1,5 +1,7 @@
 use crate::application::commands::cd::ChangeDirectoryRequest;
+use crate::application::commands::cp::CopyRequest;
 use crate::application::commands::create::CreateRequest;
+use crate::application::commands::del::DeleteRequest;
 use crate::application::commands::mkdir::MakeDirectoryRequest;
 use crate::application::queries::cat::CatRequest;
 use crate::application::Void;
@@ -8,12 +10,17 @@ use crate::core::content_type::ContentType;
 use crate::core::Arm;
 use crate::domain::boot_sector::BootSector;
 use crate::domain::fat::{FatTable, FatValue};
-use crate::domain::file_entry::{FileEntry, RootTable};
+use crate::domain::file_entry::{
+    FileEntry, FileEntryAttributes, FileEntryAttributesFlags, RootTable,
+};
+use crate::domain::frag_report::{FileFragmentation, FragReport};
 use crate::domain::i_disk_manager::IDiskManager;
 use crate::infrastructure::{ByteArray, StorageBuffer};
-use crate::CONFIG;
 use chrono::Utc;
-use std::io::{Read, Write};
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::error::Error;
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::time::Instant;
 
 /// The `DiskManager` is the main component of the application.
 ///
@@ -50,6 +57,52 @@ use std::io::{Read, Write};
 /// **`storage_file_path`**:
 /// - The storage file path is the path of the storage file.
 ///
+/// **`config`**:
+/// - The config the disk manager has been created with.
+/// - It provides the stdin and temp buffer files and it is handed down to the disk manager of a newly formatted disk.
+///
+/// **`free_cluster_indices`**:
+/// - The free cluster indices is an ordered set caching the indices of all the `Free` FAT cells.
+/// - It is kept in sync with the FAT table on every FAT cell update.
+/// - It spares the allocation of a cluster from a linear scan of the whole FAT table.
+///
+/// **`dirty_cluster_indices`**:
+/// - The dirty cluster indices is an ordered set of the clusters modified since the last sync to the storage file.
+/// - Only these clusters are written back to the storage file on `push_sync`.
+///
+/// **`link_counts`**:
+/// - The link counts is a side table counting the file entries sharing each allocation chain, by its first cluster.
+/// - A chain is shared by the hard links of a file (which always live in the same directory, as the trash keeps
+///   a copy of a linked file instead of moving it) and it is only freed when its last link is removed; the chains
+///   missing from the table have a single link.
+/// - It is rebuilt from the directory tree on every pull.
+///
+/// **`clusters_read`** and **`clusters_written`**:
+/// - The number of clusters read from and written to the storage file since the disk has been opened.
+///
+/// **`autosave`** and **`last_unsaved_push`**:
+/// - With the autosave, a push only syncs the storage buffer with the in-memory data structures and the dirty clusters
+///   are written to the storage file later on, by `flush`, once the disk has been idle for a while (see `core::autosave`).
+/// - The last unsaved push is the time of the last push whose clusters haven't been written yet; until then,
+///   the storage buffer is newer than the storage file, so a pull reloads the disk from the storage buffer only.
+///
+/// **`pre_command_buffer`**, **`undo_snapshot`** and **`redo_snapshot`**:
+/// - The pre-command buffer is a copy of the storage buffer taken by the first pull of a command, i.e. before the command
+///   modifies anything.
+/// - Once the command has run, the clusters it has modified (the boot sector aside, as it only records the working
+///   directory) are compared against that copy and their previous content becomes the undo snapshot, which `undo` restores.
+/// - A command modifying nothing keeps the undo snapshot of the previous one.
+/// - Undoing a command turns the content it had given to those clusters into the redo snapshot, which `redo` restores
+///   (turning it back into the undo snapshot), and the next command modifying the disk forgets it.
+///
+/// **`previous_directory`**:
+/// - The full path of the working directory before the last `cd`, which `cd -` goes back to.
+/// - It is only updated by the `cd` command, not by the directory changes of the other operations.
+///
+/// **`read_only`**:
+/// - Whether the disk is read-only, as set in the config the disk manager has been created with.
+/// - A read-only disk rejects every operation modifying it and never writes its storage file.
+///
 /// The storage buffer is initialized with the content of the storage file.
 /// The FAT table and Root table are initialized with the content of the storage buffer.
 #[derive(Debug, Clone)]
@@ -60,19 +113,40 @@ pub(crate) struct DiskManager {
     pub(in crate::infrastructure) boot_sector: BootSector,
     pub(in crate::infrastructure) storage_buffer: StorageBuffer,
     pub(in crate::infrastructure) storage_file_path: String,
+    pub(in crate::infrastructure) config: Arm<Config>,
+    pub(in crate::infrastructure) free_cluster_indices: BTreeSet<usize>,
+    pub(in crate::infrastructure) dirty_cluster_indices: BTreeSet<usize>,
+    pub(in crate::infrastructure) link_counts: HashMap<u16, usize>,
+    pub(in crate::infrastructure) clusters_read: u64,
+    pub(in crate::infrastructure) clusters_written: u64,
+    pub(in crate::infrastructure) pre_command_buffer: Option<StorageBuffer>,
+    pub(in crate::infrastructure) undo_snapshot: Option<Vec<(usize, ByteArray)>>,
+    pub(in crate::infrastructure) redo_snapshot: Option<Vec<(usize, ByteArray)>>,
+    pub(in crate::infrastructure) autosave: bool,
+    pub(in crate::infrastructure) last_unsaved_push: Option<Instant>,
+    pub(in crate::infrastructure) previous_directory: Option<String>,
+    pub(in crate::infrastructure) read_only: bool,
 }
 
 impl DiskManager {
+    /// The name of the hidden directory of the root the deleted file entries are moved to when the trash is used.
+    pub(in crate::infrastructure) const TRASH_DIRECTORY_NAME: &'static str = ".trash";
+
+    /// The error reported when the table of a directory has to grow but no free cluster is left.
+    pub(in crate::infrastructure) const DIRECTORY_TABLE_DISK_FULL_ERROR: &'static str =
+        "Disk full, no free cluster left for the table of the directory";
+
     /// Creates a new `DiskManager` based on the configuration and the boot sector provided.
     pub(crate) fn new(config: Arm<Config>, boot_sector: BootSector) -> Self {
         log::info!("Initializing the disk manager...");
-        let config = config.lock().expect("Unable to lock config");
-
-        // calculate the number of clusters occupied by the fat table and the root table
-        let fat_clusters =
-            boot_sector.fat_cell_size * boot_sector.cluster_count / boot_sector.cluster_size;
-        let root_clusters = boot_sector.root_entry_cell_size * boot_sector.root_entry_count
-            / boot_sector.cluster_size;
+        let (storage_file_path, autosave, read_only) = {
+            let config = config.lock().expect("Unable to lock config");
+            (
+                config.storage_file_path.clone(),
+                config.autosave_delay_ms > 0,
+                config.read_only,
+            )
+        };
 
         let mut fat = Vec::new();
         fat.resize(boot_sector.cluster_count as usize, FatValue::Free);
@@ -81,7 +155,7 @@ impl DiskManager {
         root.resize(boot_sector.root_entry_count as usize, FileEntry::default());
 
         // mark the clusters occupied by the boot sector, the fat table and the root table as reserved in the fat table
-        for i in 0..boot_sector.clusters_per_boot_sector + fat_clusters + root_clusters {
+        for i in 0..boot_sector.get_reserved_clusters_count() {
             fat[i as usize] = FatValue::Reserved;
         }
 
@@ -92,20 +166,219 @@ impl DiskManager {
             .iter_mut()
             .for_each(|cluster| cluster.resize(boot_sector.cluster_size as usize, 0));
 
+        // init the free cluster indices cache
+        let free_cluster_indices = Self::collect_free_cluster_indices(&fat);
+
         Self {
             fat,
             root,
             working_directory: FileEntry::root(),
             boot_sector,
             storage_buffer,
-            storage_file_path: config.storage_file_path.clone(),
+            storage_file_path,
+            config,
+            free_cluster_indices,
+            dirty_cluster_indices: BTreeSet::new(),
+            link_counts: HashMap::new(),
+            clusters_read: 0,
+            clusters_written: 0,
+            pre_command_buffer: None,
+            undo_snapshot: None,
+            redo_snapshot: None,
+            autosave,
+            last_unsaved_push: None,
+            previous_directory: None,
+            read_only,
+        }
+    }
+
+    /// Opens the disk stored in the storage file of the configuration: a new disk with the configured geometry is
+    /// created if the storage file doesn't exist or is empty, otherwise the disk is loaded from it and the working
+    /// directory of the last session is restored.
+    ///
+    /// Fails if the configured geometry is invalid (it is validated even when an existing disk is loaded)
+    /// or if the storage file holds a foreign, corrupted or truncated disk.
+    pub(crate) fn open(config: Arm<Config>) -> Result<Self, String> {
+        let (disk_geometry, storage_file_path) = {
+            let config = config.lock().expect("Unable to lock config");
+            (config.disk.clone(), config.storage_file_path.clone())
+        };
+
+        let boot_sector = disk_geometry
+            .to_boot_sector()
+            .map_err(|err| format!("Invalid disk geometry in config: {}", err))?;
+        let mut disk_manager = DiskManager::new(config.clone(), boot_sector);
+
+        // if storage file doesn't exist or is empty, create new storage file based on in-memory config
+        // otherwise, init disk manager from storage file
+        let storage_file_size = match std::path::Path::new(&storage_file_path).exists() {
+            true => std::fs::metadata(&storage_file_path)
+                .map_err(|err| format!("Unable to get storage file metadata: {}", err))?
+                .len(),
+            false => 0,
+        };
+
+        if storage_file_size == 0 {
+            if disk_manager.read_only {
+                return Err(format!(
+                    "Storage file {} holds no disk to open read-only",
+                    storage_file_path
+                ));
+            }
+            disk_manager.push_sync_all();
+            return Ok(disk_manager);
+        }
+
+        disk_manager.pull_boot_sector_sync(); // grab the boot sector from the storage file
+
+        // refuse to load a foreign, corrupted or truncated storage file instead of running on garbage geometry
+        let boot_sector = disk_manager.get_boot_sector();
+        boot_sector
+            .validate()
+            .and_then(
+                |_| match storage_file_size < boot_sector.get_disk_size() as u64 {
+                    true => Err(format!(
+                        "the storage file holds {} bytes instead of {}",
+                        storage_file_size,
+                        boot_sector.get_disk_size()
+                    )),
+                    false => Ok(()),
+                },
+            )
+            .map_err(|err| format!("Invalid storage file {}: {}", storage_file_path, err))?;
+
+        // create new disk manager according to the boot sector from the storage file
+        // this is necessary in order to tackle the inconsistencies between the in-memory
+        // data structures used to represent the disk when switching between FAT16 and FAT32 and vice-versa
+        let boot_sector = disk_manager.get_boot_sector().clone();
+        disk_manager = DiskManager::new(config, boot_sector.clone());
+
+        disk_manager.pull_sync(); // grab the rest of the data from the storage file
+
+        // go back to where the last session left off (read from the boot sector loaded before the first pull,
+        // as pulling records the current working directory, i.e. the root, in the boot sector)
+        // and record it again
+        disk_manager.restore_working_directory(boot_sector.working_directory_cluster);
+        disk_manager.push_sync();
+
+        Ok(disk_manager)
+    }
+
+    /// Fails if the disk is read-only, to be checked first by every operation modifying the disk.
+    pub(in crate::infrastructure) fn ensure_writable(&self) -> Void {
+        match self.read_only {
+            true => Err(Box::try_from("The disk is read-only").unwrap()),
+            false => Ok(()),
+        }
+    }
+
+    /// Returns the index and the previous content of every cluster modified since the pre-command buffer was taken
+    /// (the clusters of the boot sector aside), or nothing if the disk has been resized in the meantime.
+    pub(in crate::infrastructure) fn collect_modified_clusters(
+        &self,
+        pre_command_buffer: StorageBuffer,
+    ) -> Vec<(usize, ByteArray)> {
+        if pre_command_buffer.len() != self.storage_buffer.len() {
+            return Vec::new();
+        }
+
+        pre_command_buffer
+            .into_iter()
+            .enumerate()
+            .skip(self.boot_sector.clusters_per_boot_sector as usize)
+            .filter(|(index, cluster)| self.storage_buffer[*index] != *cluster)
+            .collect()
+    }
+
+    /// Overwrites the clusters of the given snapshot and reloads the disk from them,
+    /// returning the content they had before (i.e. the snapshot undoing this one).
+    pub(in crate::infrastructure) fn swap_snapshot_clusters(
+        &mut self,
+        snapshot: Vec<(usize, ByteArray)>,
+    ) -> Vec<(usize, ByteArray)> {
+        let replaced_clusters = snapshot
+            .into_iter()
+            .map(|(index, cluster)| {
+                let replaced_cluster = self.storage_buffer[index].clone();
+                self.set_cluster_data(index, cluster);
+                (index, replaced_cluster)
+            })
+            .collect();
+
+        // the restored clusters hold the FAT and root tables as well, so they are written as they are
+        // and the disk is reloaded from them (the root of the disk being the working directory in the meantime,
+        // as the previous one may not exist anymore)
+        match self.autosave {
+            true => self.last_unsaved_push = Some(Instant::now()),
+            false => self.write_dirty_clusters_to_file(),
+        }
+        let working_directory_path = self.get_working_directory_full_path();
+        self.working_directory = FileEntry::root();
+        self.pull_sync();
+
+        // go back to the previous working directory, unless the restored clusters don't hold it anymore
+        if let Err(err) = self.change_working_directory_along_path(&working_directory_path) {
+            log::warn!(
+                "Unable to go back to {} after restoring a snapshot: {}",
+                working_directory_path,
+                err
+            );
+        }
+
+        // restoring a snapshot is not recorded, so that it doesn't replace the snapshots
+        self.pre_command_buffer = None;
+
+        replaced_clusters
+    }
+
+    /// Collects the indices of all the free cells from a FAT table into an ordered set.
+    pub(in crate::infrastructure) fn collect_free_cluster_indices(
+        fat: &FatTable,
+    ) -> BTreeSet<usize> {
+        fat.iter()
+            .enumerate()
+            .filter(|(_, fat_value)| **fat_value == FatValue::Free)
+            .map(|(index, _)| index)
+            .collect()
+    }
+
+    /// Sets the value of a FAT cell and updates the free cluster indices cache accordingly.
+    pub(in crate::infrastructure) fn set_fat_value(&mut self, index: usize, value: FatValue) {
+        match value {
+            FatValue::Free => self.free_cluster_indices.insert(index),
+            _ => self.free_cluster_indices.remove(&index),
+        };
+
+        self.fat[index] = value;
+    }
+
+    /// Overwrites a cluster from the storage buffer and marks it as dirty if its content has changed.
+    pub(in crate::infrastructure) fn set_cluster_data(&mut self, index: usize, data: ByteArray) {
+        if self.storage_buffer[index] != data {
+            self.storage_buffer[index] = data;
+            self.dirty_cluster_indices.insert(index);
         }
     }
 
+    /// Returns the number of free clusters in the FAT table.
+    pub(in crate::infrastructure) fn get_free_clusters_count(&self) -> usize {
+        self.free_cluster_indices.len()
+    }
+
     /// Initializes the storage buffer with the content of the boot sector, the fat table and the root table
     /// from the in-memory data structures.
     pub(in crate::infrastructure) fn sync_to_buffer(&mut self) {
-        // sync boot sector to storage buffer
+        // keep a snapshot of the reserved clusters in order to detect which of them get modified
+        let reserved_clusters = self
+            .fat
+            .iter()
+            .take_while(|fat_value| **fat_value == FatValue::Reserved)
+            .count();
+        let reserved_clusters_snapshot = self.storage_buffer[..reserved_clusters].to_vec();
+
+        // sync boot sector to storage buffer, recording the working directory in order to restore it on the next boot
+        // (the root of a freshly formatted or defragmented disk is always recorded, as its disk manager starts there)
+        self.boot_sector.working_directory_cluster = self.working_directory.first_cluster;
         let boot_sector_clusters = self.boot_sector.clusters_per_boot_sector as usize;
         let mut boot_sector_data: ByteArray = self.boot_sector.clone().into();
 
@@ -122,34 +395,22 @@ impl DiskManager {
             });
 
         // sync fat to storage buffer
-        let fat_clusters = self.boot_sector.fat_cell_size as usize * self.fat.len()
-            / self.boot_sector.cluster_size as usize;
-        let fat_cells_per_cluster = self.boot_sector.cluster_size / self.boot_sector.fat_cell_size;
+        let fat_clusters = self.boot_sector.get_fat_clusters_count() as usize;
+        let cluster_size = self.boot_sector.cluster_size as usize;
 
-        self.fat
-            // split the fat table into chunks of fat_cells_per_cluster
-            .chunks(fat_cells_per_cluster as usize)
-            .zip(
-                self.storage_buffer
-                    .iter_mut()
-                    // skip the boot sector clusters from the storage buffer
-                    .skip(boot_sector_clusters)
-                    .take(fat_clusters),
-            )
-            // zip the fat table chunks with the storage buffer clusters
-            .for_each(|(fat_chunk, cluster)| {
-                fat_chunk
-                    .iter()
-                    .zip(cluster.chunks_mut(self.boot_sector.fat_cell_size as usize))
-                    // zip a fat table cell with a section of fat_cell_size bytes from the proper storage buffer cluster
-                    .for_each(|(fat_value, chunk)| {
-                        let value: u16 = fat_value.clone().into();
-
-                        // split the 2B fat value into 2 chunks of 1B
-                        chunk[0] = ((value & 0xFF00) >> 8) as u8;
-                        chunk[1] = (value & 0x00FF) as u8;
-                    });
-            });
+        // serialize the whole fat table at once, as FAT12 cells may be split across clusters,
+        // and pad it with zeros up to a whole number of clusters
+        let mut fat_data = FatValue::encode_cells(&self.fat, self.boot_sector.fat_cell_bits);
+        fat_data.resize(fat_clusters * cluster_size, 0);
+
+        self.storage_buffer
+            .iter_mut()
+            // skip the boot sector clusters from the storage buffer
+            .skip(boot_sector_clusters)
+            .take(fat_clusters)
+            // zip the storage buffer clusters with the corresponding sections of the serialized fat table
+            .zip(fat_data.chunks(cluster_size))
+            .for_each(|(cluster, fat_chunk)| cluster.copy_from_slice(fat_chunk));
 
         // sync root to storage buffer
         let root_clusters = self.boot_sector.root_entry_cell_size as usize * self.root.len()
@@ -171,7 +432,7 @@ impl DiskManager {
             )
             // zip the root table entries with the storage buffer clusters
             .for_each(|(file_entry, cluster)| {
-                let mut file_entry_data: ByteArray = file_entry.clone().into();
+                let mut file_entry_data: ByteArray = file_entry.to_byte_array();
 
                 let cluster_size = cluster[0].len();
                 // extract enough bytes from the file entry to fill the first cluster
@@ -190,13 +451,72 @@ impl DiskManager {
                     cluster[1].copy_from_slice(&file_entry_current_data);
                 }
             });
+
+        // mark the reserved clusters whose content has changed as dirty
+        reserved_clusters_snapshot
+            .iter()
+            .zip(self.storage_buffer.iter())
+            .enumerate()
+            .filter(|(_, (old_cluster, new_cluster))| old_cluster != new_cluster)
+            .for_each(|(index, _)| {
+                self.dirty_cluster_indices.insert(index);
+            });
     }
 
-    /// Overwrites the storage file with the content of the storage buffer.
+    /// Writes only the dirty clusters from the storage buffer to the storage file.
     pub(in crate::infrastructure) fn sync_to_file(&mut self) {
         // sync the storage buffer with the in-memory data structures
         self.sync_to_buffer();
+        self.write_dirty_clusters_to_file();
+
+        log::debug!("FAT: {:?}", self.fat);
+        log::debug!("Root: {:?}", self.root);
+    }
+
+    /// Writes the dirty clusters of the storage buffer, as is, to the storage file.
+    pub(in crate::infrastructure) fn write_dirty_clusters_to_file(&mut self) {
+        // if there is no storage file to patch, write the whole storage buffer instead
+        if !std::path::Path::new(&self.storage_file_path).exists() {
+            self.write_buffer_to_file();
+            return;
+        }
+
+        let mut storage_file = std::fs::File::options()
+            .write(true)
+            .open(&self.storage_file_path)
+            .expect("Unable to open storage file");
+        let cluster_size = self.boot_sector.cluster_size as u64;
+
+        // seek to the offset of each dirty cluster and overwrite it
+        self.dirty_cluster_indices.iter().for_each(|&index| {
+            storage_file
+                .seek(SeekFrom::Start(index as u64 * cluster_size))
+                .expect("Unable to seek in storage file");
+            storage_file
+                .write_all(&self.storage_buffer[index])
+                .expect("Unable to write to storage file");
+        });
+
+        log::debug!(
+            "Synced {} dirty cluster(s) to storage file",
+            self.dirty_cluster_indices.len()
+        );
+        self.clusters_written += self.dirty_cluster_indices.len() as u64;
+        self.dirty_cluster_indices.clear();
+    }
+
+    /// Overwrites the whole storage file with the content of the storage buffer.
+    pub(in crate::infrastructure) fn sync_all_to_file(&mut self) {
+        // sync the storage buffer with the in-memory data structures
+        self.sync_to_buffer();
+        self.write_buffer_to_file();
+
+        log::debug!("FAT: {:?}", self.fat);
+        log::debug!("Root: {:?}", self.root);
+    }
 
+    /// Overwrites the whole storage file with the storage buffer, as is.
+    pub(in crate::infrastructure) fn write_buffer_to_file(&mut self) {
         let mut storage_file =
             std::fs::File::create(&self.storage_file_path).expect("Unable to create storage file");
         self.storage_buffer
@@ -207,9 +527,8 @@ impl DiskManager {
                     .write_all(cluster)
                     .expect("Unable to write to storage file")
             });
-
-        log::debug!("FAT: {:?}", self.fat);
-        log::debug!("Root: {:?}", self.root);
+        self.clusters_written += self.storage_buffer.len() as u64;
+        self.dirty_cluster_indices.clear();
     }
 
     /// Overwrites the storage buffer with the content of the storage file.
@@ -221,12 +540,45 @@ impl DiskManager {
                 .read_exact(cluster)
                 .expect("Unable to read from storage file");
         });
+        self.clusters_read += self.storage_buffer.len() as u64;
+
+        // the storage buffer mirrors the storage file now, so there is nothing left to be written
+        self.dirty_cluster_indices.clear();
+    }
+
+    /// Overwrites the clusters of the storage buffer occupied by the boot sector with the beginning of the storage file
+    /// (the bytes missing from a truncated storage file are zeroed).
+    pub(in crate::infrastructure) fn sync_boot_sector_from_file(&mut self) {
+        let storage_file =
+            std::fs::File::open(&self.storage_file_path).expect("Unable to open storage file");
+        let boot_sector_clusters = self.boot_sector.clusters_per_boot_sector as usize;
+        let boot_sector_size = boot_sector_clusters * self.boot_sector.cluster_size as usize;
+
+        let mut boot_sector_data = Vec::new();
+        storage_file
+            .take(boot_sector_size as u64)
+            .read_to_end(&mut boot_sector_data)
+            .expect("Unable to read from storage file");
+        boot_sector_data.resize(boot_sector_size, 0);
+
+        self.storage_buffer
+            .iter_mut()
+            .take(boot_sector_clusters)
+            .zip(boot_sector_data.chunks(self.boot_sector.cluster_size as usize))
+            .for_each(|(cluster, data)| cluster.copy_from_slice(data));
+        self.clusters_read += boot_sector_clusters as u64;
     }
 
     /// Initializes the in-memory data structures from the storage file.
     pub(in crate::infrastructure) fn sync_from_buffer(&mut self, only_boot_sector: bool) {
-        // sync storage buffer from file
-        self.sync_from_file();
+        // sync storage buffer from file (only the boot sector when the rest of the disk is not needed,
+        // as the storage file may hold a disk of another geometry than the in-memory one)
+        match only_boot_sector {
+            true => self.sync_boot_sector_from_file(),
+            // the storage buffer holds pushes not yet written by the autosave, so it is newer than the storage file
+            false if self.last_unsaved_push.is_some() => {}
+            false => self.sync_from_file(),
+        }
 
         // sync boot sector from storage buffer
         let boot_sector_clusters = self.boot_sector.clusters_per_boot_sector as usize;
@@ -248,35 +600,23 @@ impl DiskManager {
         }
 
         // sync fat from storage buffer
-        let fat_clusters = self.boot_sector.fat_cell_size as usize * self.fat.len()
-            / self.boot_sector.cluster_size as usize;
-        let fat_cells_per_cluster = self.boot_sector.cluster_size / self.boot_sector.fat_cell_size;
+        let fat_clusters = self.boot_sector.get_fat_clusters_count() as usize;
 
-        self.storage_buffer
+        // gather the fat table clusters and deserialize the whole fat table at once,
+        // as FAT12 cells may be split across clusters
+        let fat_data: ByteArray = self
+            .storage_buffer
             .iter()
             .skip(boot_sector_clusters)
             .take(fat_clusters)
-            .enumerate()
-            // zip the fat table chunks with the storage buffer clusters
-            .for_each(|(cluster_index, cluster)| {
-                // grab the fat table chunk corresponding to the current storage buffer cluster
-                let fat_chunk = self
-                    .fat
-                    .chunks_mut(fat_cells_per_cluster as usize)
-                    .nth(cluster_index)
-                    .unwrap_or_default();
-
-                cluster
-                    .chunks(self.boot_sector.fat_cell_size as usize)
-                    .zip(fat_chunk)
-                    // zip each fat table cell from the current chunk with the corresponding storage buffer cluster section
-                    .for_each(|(chunk, fat_value)| {
-                        // convert the fat table cell bytes to a u16 value
-                        // the fat table cell is stored in big endian format
-                        let value: u16 = ((chunk[0] as u16) << 8) | (chunk[1] as u16);
-                        *fat_value = value.into();
-                    });
-            });
+            .flatten()
+            .copied()
+            .collect();
+        self.fat =
+            FatValue::decode_cells(&fat_data, self.boot_sector.fat_cell_bits, self.fat.len());
+
+        // rebuild the free cluster indices cache from the freshly loaded fat table
+        self.free_cluster_indices = Self::collect_free_cluster_indices(&self.fat);
 
         // sync root from storage buffer
         let clusters_per_root_entry =
@@ -311,6 +651,7 @@ impl DiskManager {
 
                         // parse the file entry from the cluster data and deserialize it
                         let mut file_entry_result = FileEntry::from(file_entry_data);
+                        self.read_long_name(&mut file_entry_result);
                         // set the parent entry to the root entry
                         file_entry_result.parent_entry = Some(Box::new(FileEntry::root()));
 
@@ -332,8 +673,10 @@ impl DiskManager {
 
         // set the root table
         self.root = root_table;
+        self.link_counts = self.collect_link_counts();
         // propagate any changes from the root table to the storage medium as soon as possible
-        self.sync_to_file();
+        // (pushing them, so that neither a read-only disk nor the autosave is bypassed)
+        self.push_sync();
 
         // init working directory from root
         if self.working_directory.is_root() {
@@ -344,11 +687,84 @@ impl DiskManager {
         }
     }
 
-    /// Iterate over the root table of a directory and link the directory tree to the current directory entry recursively.
+    /// Iterate over the root table of a directory and link the whole directory tree to the current directory entry.
+    ///
+    /// The directory tree is reconstructed iteratively using an explicit work-stack of pending directories,
+    /// so the depth of the tree is bounded by the heap rather than by the call stack.
     pub(in crate::infrastructure) fn link_root_table_to_directory(
         &mut self,
         directory_entry: &mut FileEntry,
     ) {
+        // flatten the directory tree into a vector of directory entries where every subdirectory
+        // is always stored after its parent directory
+        let mut directories: Vec<FileEntry> = vec![directory_entry.clone()];
+        // the root table of each directory, where every subdirectory entry holds its index from `directories`
+        let mut root_tables: Vec<Vec<(FileEntry, Option<usize>)>> = vec![Vec::new()];
+        // the indices of the directories whose root tables haven't been read yet
+        let mut pending_directories: Vec<usize> = vec![0];
+
+        while let Some(directory_index) = pending_directories.pop() {
+            let root_table = self.read_directory_root_table(&directories[directory_index]);
+
+            for file_entry in root_table {
+                // if the file entry is a file or a special directory entry, just attach it
+                if file_entry.is_file() || file_entry.name == "." || file_entry.name == ".." {
+                    root_tables[directory_index].push((file_entry, None));
+                } else {
+                    // if the file entry is a directory, schedule its directory tree to be reconstructed too
+                    let subdirectory_index = directories.len();
+                    directories.push(file_entry.clone());
+                    root_tables.push(Vec::new());
+                    root_tables[directory_index].push((file_entry, Some(subdirectory_index)));
+                    pending_directories.push(subdirectory_index);
+                }
+            }
+        }
+
+        // link the directories bottom-up, so that every subdirectory is complete before its parent directory
+        // (the complete subdirectories are moved into their parent directory rather than cloned, as cloning them
+        // at every level would copy the whole subtree over and over again)
+        for directory_index in (0..directories.len()).rev() {
+            let root_table: RootTable = std::mem::take(&mut root_tables[directory_index])
+                .into_iter()
+                .map(
+                    |(file_entry, subdirectory_index)| match subdirectory_index {
+                        Some(subdirectory_index) => {
+                            std::mem::take(&mut directories[subdirectory_index])
+                        }
+                        None => file_entry,
+                    },
+                )
+                .collect();
+
+            // compute the size of the current directory entry
+            // as the sum of the size of all the files in the directory and the size of the directory table
+            let size_of_file_entries = root_table.iter().map(|entry| entry.size).sum::<u32>();
+
+            let mut directory = std::mem::take(&mut directories[directory_index]);
+            directory.size = size_of_file_entries;
+            directory.children_entries = Some(root_table);
+
+            // propagate any changes from the root table to the storage medium as soon as possible (dir size in this case,
+            // so the table keeps its length and always fits in its own clusters)
+            if let Err(err) = self.sync_directory_root_table_to_storage(&directory) {
+                log::error!(
+                    "Unable to sync the table of directory {}: {}",
+                    directory.name,
+                    err
+                );
+            }
+            directories[directory_index] = directory;
+        }
+
+        let linked_directory_entry = directories.swap_remove(0);
+        directory_entry.size = linked_directory_entry.size;
+        directory_entry.children_entries = linked_directory_entry.children_entries;
+    }
+
+    /// Iterate over the allocation chain of a directory and deserialize its root table from the storage buffer
+    /// (the children directories are not linked to their own root tables).
+    fn read_directory_root_table(&self, directory_entry: &FileEntry) -> RootTable {
         let mut root_table = RootTable::default();
 
         // set the head of the allocation chain of the current directory entry
@@ -374,33 +790,15 @@ impl DiskManager {
 
             // deserialize the file entry from the cluster data and set the parent entry to the current directory entry
             let mut file_entry_result = FileEntry::from(file_entry_data);
+            self.read_long_name(&mut file_entry_result);
             file_entry_result.parent_entry = Some(Box::new(directory_entry.clone()));
-
-            // if the file entry is a file or a special directory entry, return it
-            if file_entry_result.is_file()
-                || (!file_entry_result.is_file()
-                    && (file_entry_result.name == "." || file_entry_result.name == ".."))
-            {
-                root_table.push(file_entry_result);
-            } else {
-                // if the file entry is a directory, iterate over its directory tree too in order to
-                // reconstruct its directory tree
-                self.link_root_table_to_directory(&mut file_entry_result);
-                root_table.push(file_entry_result);
-            }
+            root_table.push(file_entry_result);
 
             // set the next cluster index
             current_cluster_index = self.fat[current_cluster_index as usize].clone().into();
         }
 
-        // compute the size of the current directory entry
-        // as the sum of the size of all the files in the directory and the size of the directory table
-        let size_of_file_entries = root_table.iter().map(|entry| entry.size).sum::<u32>();
-
-        directory_entry.size = size_of_file_entries;
-        directory_entry.children_entries = Some(root_table.clone());
-        // propagate any changes from the root table to the storage medium as soon as possible (dir size in this case)
-        self.sync_directory_root_table_to_storage(directory_entry);
+        root_table
     }
 
     /// Initialize the working directory from the root directory.
@@ -410,33 +808,48 @@ impl DiskManager {
 
         // iterate down the directory tree from the root to the current working directory
         // and set the current working directory to the last entry in the path
+        // (only directories are looked at, as a file may have the same name as a directory of the path)
         let mut current_entry = self
             .root
             .iter()
-            .find(|entry| entry.name == path[1])
+            .find(|entry| entry.matches_directory(&path[1]))
             .unwrap();
         for path_part in path.iter().skip(2) {
             let children_entries = current_entry.children_entries.as_ref().unwrap();
             current_entry = children_entries
                 .iter()
-                .find(|&entry| entry.name == path_part.as_str())
+                .find(|&entry| entry.matches_directory(path_part))
                 .unwrap();
         }
 
         self.working_directory = current_entry.clone();
     }
 
+    /// Check whether a root-to-entry path is the same as or lies beneath another one (ignoring the ASCII case).
+    pub(in crate::infrastructure) fn is_path_within(
+        path: &[String],
+        ancestor_path: &[String],
+    ) -> bool {
+        path.len() >= ancestor_path.len()
+            && path
+                .iter()
+                .zip(ancestor_path.iter())
+                .all(|(path_part, ancestor_path_part)| {
+                    path_part.eq_ignore_ascii_case(ancestor_path_part)
+                })
+    }
+
     /// Get the path from the root to the current working directory.
-    fn get_path_from_root_to_entry(entry: &FileEntry) -> Vec<String> {
+    pub(in crate::infrastructure) fn get_path_from_root_to_entry(entry: &FileEntry) -> Vec<String> {
         let mut path: Vec<String> = Vec::new();
-        let mut current_entry = entry.clone();
+        let mut current_entry = entry;
         path.push(current_entry.name.clone());
 
         // iterate up the directory tree from the current working directory to the root
+        // (by reference, as cloning every ancestor along with its children entries would be slow on a deep tree)
         while let Some(parent_entry) = &current_entry.parent_entry {
-            let parent_entry = parent_entry.as_ref();
             path.push(parent_entry.name.clone());
-            current_entry = parent_entry.clone();
+            current_entry = parent_entry;
         }
 
         // reverse the path vector to get the path from the root to the current working directory
@@ -454,83 +867,335 @@ impl DiskManager {
             .as_ref()
             .unwrap()
             .iter()
-            .cloned()
-            .flat_map(|file_entry| {
-                let file_byte_array: ByteArray = file_entry.into();
-                file_byte_array
-            })
+            .flat_map(FileEntry::to_byte_array)
             .collect::<Vec<u8>>()
     }
 
-    /// Synchronize the root table of a directory to the storage medium.
+    /// Synchronize the root table of a directory to the storage medium, its allocation chain starting again
+    /// at its first cluster.
+    ///
+    /// Fails if the table doesn't fit in the free clusters along with the ones of its current chain,
+    /// which is checked before anything is freed.
     pub(in crate::infrastructure) fn sync_directory_root_table_to_storage(
         &mut self,
         dir_entry: &FileEntry,
-    ) {
-        // free old working directory data
-        self.free_clusters(dir_entry);
-
+    ) -> Void {
         // get updated working directory data
         let mut directory_data = Self::serialize_directory_root_table(dir_entry);
 
+        let cluster_size = self.boot_sector.cluster_size as usize;
+        let required_clusters = directory_data.len().div_ceil(cluster_size);
+        let released_clusters = self
+            .get_chain_clusters(dir_entry.first_cluster as usize)
+            .len();
+        if self.get_free_clusters_count() + released_clusters < required_clusters {
+            return Err(Box::try_from(format!(
+                "Disk full, unable to write the {} clusters of the table of directory {}",
+                required_clusters, dir_entry.name
+            ))
+            .unwrap());
+        }
+
+        // free old working directory data
+        self.free_clusters(dir_entry);
+
         // update fat and storage buffer
-        let mut current_cluster_index = dir_entry.first_cluster;
+        let mut current_cluster_index = dir_entry.first_cluster as usize;
 
         // while there is still data to be written to the storage
         while !directory_data.is_empty() {
-            let mut cluster_data: ByteArray = Vec::new();
-            cluster_data
-                .extend_from_slice(&directory_data[..self.boot_sector.cluster_size as usize]);
-            directory_data.drain(..self.boot_sector.cluster_size as usize);
-            // write the cluster data to the current cluster index
-            self.storage_buffer[current_cluster_index as usize] = cluster_data.clone();
-
-            let next_cluster_index = self
-                .get_next_free_cluster_index_gt(current_cluster_index as usize)
-                .unwrap();
-            // update the fat with the next cluster index or with the end of chain value
-            // depending on whether there is still data to be written to the storage
-            self.fat[current_cluster_index as usize] = match directory_data.is_empty() {
-                false => FatValue::from(next_cluster_index as u16),
-                true => FatValue::EndOfChain,
+            // write the data of the current cluster to the current cluster index
+            // and take the cluster before looking for the next one
+            let cluster_data = self.drain_cluster_data(&mut directory_data);
+            self.set_cluster_data(current_cluster_index, cluster_data);
+            self.set_fat_value(current_cluster_index, FatValue::EndOfChain);
+
+            if directory_data.is_empty() {
+                break;
+            }
+
+            // point the current cluster to the next one (past it if possible), as there is still data to be written
+            let next_cluster_index = match self
+                .get_next_free_cluster_index_gt(current_cluster_index)
+                .or_else(|| self.get_next_free_cluster_index_gt(0))
+            {
+                Some(next_cluster_index) => next_cluster_index,
+                None => return Err(Box::try_from(Self::DIRECTORY_TABLE_DISK_FULL_ERROR).unwrap()),
             };
+            self.set_fat_value(
+                current_cluster_index,
+                FatValue::from(next_cluster_index as u16),
+            );
 
             // set the current cluster index to the next cluster index
-            current_cluster_index = next_cluster_index as u16;
+            current_cluster_index = next_cluster_index;
         }
+
+        Ok(())
+    }
+
+    /// Extract the data of the next cluster from the start of the given data, padding it with 0
+    /// if less than a cluster is left.
+    ///
+    /// The validated geometries make a root entry span whole clusters, so the tables of the directories are always
+    /// a whole number of clusters long: the padding is only defensive, so that a table never gets truncated
+    /// nor makes the draining panic.
+    pub(in crate::infrastructure) fn drain_cluster_data(&self, data: &mut ByteArray) -> ByteArray {
+        let cluster_size = self.boot_sector.cluster_size as usize;
+        let mut cluster_data: ByteArray = data.drain(..cluster_size.min(data.len())).collect();
+        cluster_data.resize(cluster_size, 0);
+
+        cluster_data
     }
 
-    /// Get the next free cluster index greater than the current cluster index.
+    /// Get the next free cluster index greater than the current cluster index
+    /// by querying the free cluster indices cache.
     pub(in crate::infrastructure) fn get_next_free_cluster_index_gt(
         &self,
         current_cluster_index: usize,
     ) -> Option<usize> {
-        self.fat.iter().enumerate().position(|(index, fat_value)| {
-            *fat_value == FatValue::Free && index > current_cluster_index
-        })
+        self.free_cluster_indices
+            .range(current_cluster_index + 1..)
+            .next()
+            .copied()
+    }
+
+    /// Allocate a zero filled head for the allocation chain of a file of the working directory which has none
+    /// (i.e. an empty one), returning its updated file entry.
+    pub(in crate::infrastructure) fn allocate_first_cluster(
+        &mut self,
+        file_entry: &FileEntry,
+    ) -> Result<FileEntry, Box<dyn Error>> {
+        let first_cluster = match self.get_next_free_cluster_index_gt(0) {
+            Some(first_cluster) => first_cluster,
+            None => return Err(Box::try_from("No space in fat".to_string()).unwrap()),
+        };
+
+        self.set_cluster_data(
+            first_cluster,
+            vec![0; self.boot_sector.cluster_size as usize],
+        );
+        self.set_fat_value(first_cluster, FatValue::EndOfChain);
+
+        let file_entry = self
+            .get_root_table_for_working_directory()
+            .iter_mut()
+            .find(|entry| entry.matches_file(&file_entry.name, &file_entry.extension))
+            .unwrap();
+        file_entry.first_cluster = first_cluster as u16;
+
+        Ok(file_entry.clone())
     }
 
     /// Iterate over the allocation chain of a file entry and free the clusters associated with it
     /// by setting their fat values to free (the storage remains unchanged).
+    ///
+    /// The walk stops at the first cell which is not part of a chain (free, reserved, bad or out of range),
+    /// and it gives up after as many steps as there are clusters, so looping chains are not walked forever.
     pub(in crate::infrastructure) fn free_clusters(&mut self, file_entry: &FileEntry) {
+        // the special entries share the allocation chain of an actual directory
+        if file_entry.name == "." || file_entry.name == ".." {
+            return;
+        }
+
+        // the empty files have no allocation chain to free
+        if file_entry.is_file() && !file_entry.has_clusters() {
+            return;
+        }
+
+        // the allocation chain of a file is kept as long as another link shares it
+        if file_entry.is_file() {
+            match self.get_link_count(file_entry) {
+                0 | 1 => {
+                    self.link_counts.remove(&file_entry.first_cluster);
+                }
+                link_count => {
+                    self.link_counts
+                        .insert(file_entry.first_cluster, link_count - 1);
+                    return;
+                }
+            }
+        }
+
         // delete file entry associated data
         let mut cluster_index = file_entry.first_cluster as usize;
-        while self.fat[cluster_index] != FatValue::EndOfChain {
-            let next_cluster_index: u16 = self.fat[cluster_index].clone().into();
-            self.fat[cluster_index] = FatValue::Free;
+        for _ in 0..self.fat.len() {
+            match self.fat.get(cluster_index) {
+                Some(FatValue::Data(next_cluster_index)) => {
+                    let next_cluster_index = *next_cluster_index as usize;
+                    self.set_fat_value(cluster_index, FatValue::Free);
+                    cluster_index = next_cluster_index;
+                }
+                Some(FatValue::EndOfChain) => {
+                    self.set_fat_value(cluster_index, FatValue::Free);
+                    return;
+                }
+                _ => {
+                    log::warn!(
+                        "The allocation chain of {} stops at cluster {} which is not allocated",
+                        file_entry.get_full_name(),
+                        cluster_index
+                    );
+                    return;
+                }
+            }
+        }
+
+        log::warn!(
+            "The allocation chain of {} is looping, stopped freeing it at cluster {}",
+            file_entry.get_full_name(),
+            cluster_index
+        );
+    }
+
+    /// Count the file entries of the directory tree sharing each allocation chain, by its first cluster.
+    pub(in crate::infrastructure) fn collect_link_counts(&self) -> HashMap<u16, usize> {
+        let mut link_counts: HashMap<u16, usize> = HashMap::new();
+
+        // walk the directory tree by reference, as cloning every entry along with its subtree (like `walk` does)
+        // would slow down every pull on a deep tree
+        let mut pending_entries: Vec<&FileEntry> = self.root.iter().collect();
+        while let Some(file_entry) = pending_entries.pop() {
+            if file_entry.is_file() {
+                if file_entry.has_clusters() {
+                    *link_counts.entry(file_entry.first_cluster).or_insert(0) += 1;
+                }
+            } else if file_entry.name != "." && file_entry.name != ".." {
+                pending_entries.extend(file_entry.children_entries.iter().flatten());
+            }
+        }
+
+        link_counts.retain(|_, link_count| *link_count > 1);
+        link_counts
+    }
+
+    /// Get the number of file entries sharing the allocation chain of the given file (1 if it is not linked).
+    pub(in crate::infrastructure) fn get_link_count(&self, file_entry: &FileEntry) -> usize {
+        self.link_counts
+            .get(&file_entry.first_cluster)
+            .copied()
+            .unwrap_or(1)
+    }
+
+    /// Propagate the size and the last modification datetime of the given file to its other links
+    /// in the working directory, as they share its content.
+    pub(in crate::infrastructure) fn sync_links(&mut self, file_entry: &FileEntry) {
+        if self.get_link_count(file_entry) < 2 {
+            return;
+        }
+
+        self.get_root_table_for_working_directory()
+            .iter_mut()
+            .filter(|entry| {
+                entry.is_file()
+                    && entry.first_cluster == file_entry.first_cluster
+                    && !entry.same_name_as(file_entry)
+            })
+            .for_each(|entry| {
+                entry.size = file_entry.size;
+                entry.last_modification_datetime = file_entry.last_modification_datetime;
+            });
+    }
+
+    /// Get the number of clusters required to store the characters of a name past the first 8 ones.
+    pub(in crate::infrastructure) fn get_long_name_clusters_count(&self, name: &str) -> usize {
+        let long_name_size = name.len().saturating_sub(8);
+
+        (long_name_size as f64 / self.boot_sector.cluster_size as f64).ceil() as usize
+    }
+
+    /// Store the characters of the name of a file entry past the first 8 ones into a new allocation chain
+    /// and point the file entry to its head (nothing is allocated if the name fits in 8 characters).
+    pub(in crate::infrastructure) fn allocate_long_name(
+        &mut self,
+        file_entry: &mut FileEntry,
+    ) -> Void {
+        file_entry.long_name_cluster = 0;
+
+        let required_clusters = self.get_long_name_clusters_count(&file_entry.name);
+        if required_clusters == 0 {
+            return Ok(());
+        }
+
+        if self.get_free_clusters_count() < required_clusters {
+            return Err(Box::try_from(format!(
+                "Not enough space in fat to store the name {}",
+                file_entry.name
+            ))
+            .unwrap());
+        }
+
+        // split the rest of the name into zero-padded clusters
+        let cluster_size = self.boot_sector.cluster_size as usize;
+        let mut long_name_data = file_entry.name.as_bytes()[8..].to_vec();
+        long_name_data.resize(required_clusters * cluster_size, 0);
+
+        let mut cluster_indices: Vec<usize> = Vec::with_capacity(required_clusters);
+        for cluster_data in long_name_data.chunks(cluster_size) {
+            let cluster_index = self
+                .get_next_free_cluster_index_gt(*cluster_indices.last().unwrap_or(&0))
+                .unwrap();
+
+            self.set_cluster_data(cluster_index, cluster_data.to_vec());
+            // reserve the cluster right away so that the next free cluster lookup skips it
+            self.set_fat_value(cluster_index, FatValue::EndOfChain);
+            cluster_indices.push(cluster_index);
+        }
+
+        // link the clusters into a chain
+        for window in cluster_indices.windows(2) {
+            self.set_fat_value(window[0], FatValue::Data(window[1] as u16));
+        }
+
+        file_entry.long_name_cluster = cluster_indices[0] as u16;
+        Ok(())
+    }
+
+    /// Free the allocation chain storing the long name of a file entry, if any.
+    pub(in crate::infrastructure) fn free_long_name(&mut self, file_entry: &FileEntry) {
+        if file_entry.long_name_cluster == 0 {
+            return;
+        }
+
+        let mut cluster_index = file_entry.long_name_cluster as usize;
+        while let FatValue::Data(next_cluster_index) = self.fat[cluster_index] {
+            self.set_fat_value(cluster_index, FatValue::Free);
             cluster_index = next_cluster_index as usize;
         }
-        self.fat[cluster_index] = FatValue::Free;
+        self.set_fat_value(cluster_index, FatValue::Free);
+    }
+
+    /// Append the characters stored in the long name chain of a file entry, if any, to its name.
+    pub(in crate::infrastructure) fn read_long_name(&self, file_entry: &mut FileEntry) {
+        if file_entry.long_name_cluster == 0 {
+            return;
+        }
+
+        let mut cluster_index = file_entry.long_name_cluster as usize;
+        loop {
+            self.storage_buffer[cluster_index]
+                .iter()
+                .take_while(|byte| **byte != 0x00)
+                .for_each(|byte| file_entry.name.push(*byte as char));
+
+            match self.fat[cluster_index] {
+                FatValue::Data(next_cluster_index) => cluster_index = next_cluster_index as usize,
+                _ => break,
+            }
+        }
     }
 
-    /// Delete a file entry from the root table of a directory.
+    /// Delete a file entry from the root table of a directory (along with its long name chain, if any).
     pub(in crate::infrastructure) fn free_file_entry(&mut self, file_entry: &FileEntry) {
+        self.free_long_name(file_entry);
+
         // delete the actual file entry
         match self.working_directory.is_root() {
             true => {
-                let file_entry_index = self.root.iter().position(|entry| {
-                    entry.name == file_entry.name && entry.extension == file_entry.extension
-                });
+                let file_entry_index = self
+                    .root
+                    .iter()
+                    .position(|entry| entry.same_name_as(file_entry));
                 // if in root, set the file entry to default
                 if let Some(file_entry_index) = file_entry_index {
                     self.root[file_entry_index] = FileEntry::default();
@@ -538,24 +1203,55 @@ impl DiskManager {
             }
             false => {
                 // otherwise, remove the file entry from the working directory children entries
-                self.get_root_table_for_working_directory().retain(|entry| {
-                    !(entry.name == file_entry.name && entry.extension == file_entry.extension)
-                });
+                self.get_root_table_for_working_directory()
+                    .retain(|entry| !entry.same_name_as(file_entry));
             }
         }
     }
 
     /// Change the current working directory to the root directory.
+    ///
+    /// Fails instead of walking up blindly if the parent chain of the working directory doesn't end at the root
+    /// (e.g. a directory reconstructed without its parent) or if the root isn't reached in as many steps
+    /// as the working directory has ancestors.
     pub(in crate::infrastructure) fn change_working_directory_to_root(&mut self) -> Void {
-        while !self.working_directory.is_root() {
+        Self::check_parent_chain(&self.working_directory)?;
+
+        let depth = Self::get_path_from_root_to_entry(&self.working_directory).len() - 1;
+        for _ in 0..depth {
+            if self.working_directory.is_root() {
+                break;
+            }
+
             let cd_request = ChangeDirectoryRequest::new("..".to_string());
             self.pull_sync();
             self.change_working_directory(&cd_request)?;
         }
 
+        if !self.working_directory.is_root() {
+            return Err(Box::try_from(format!(
+                "Unable to reach the root from directory {} in {} step(s)",
+                self.working_directory.name, depth
+            ))
+            .unwrap());
+        }
+
         Ok(())
     }
 
+    /// Check that the parent chain of a directory ends at the root.
+    pub(in crate::infrastructure) fn check_parent_chain(directory: &FileEntry) -> Void {
+        let path = Self::get_path_from_root_to_entry(directory);
+        match path[0].as_str() {
+            "/" => Ok(()),
+            top_directory_name => Err(Box::try_from(format!(
+                "The parent chain of directory {} is broken, it ends at {} instead of the root",
+                directory.name, top_directory_name
+            ))
+            .unwrap()),
+        }
+    }
+
     /// Change the current working directory to a directory entry.
     pub(in crate::infrastructure) fn change_working_directory_to(
         &mut self,
@@ -574,101 +1270,287 @@ impl DiskManager {
         Ok(())
     }
 
-    /// Start from a source directory entry and recursively copy all its children entries to a
-    /// destination directory entry (useful for copying directories).
-    pub(in crate::infrastructure) fn inflate_directory_tree_inline(
+    /// Change the current working directory by applying each segment of a (relative or absolute) path in order.
+    /// If any segment cannot be applied, the original working directory is restored.
+    pub(in crate::infrastructure) fn change_working_directory_along_path(
         &mut self,
-        src_dir_entry: &FileEntry,
-        dest_dir_name: String,
+        path: &str,
     ) -> Void {
-        // change working directory to dir_entry
-        let cd_request = ChangeDirectoryRequest::new(dest_dir_name);
-        self.pull_sync();
-        self.change_working_directory(&cd_request)?;
+        let original_working_directory = self.working_directory.clone();
 
-        // iterate over all children entries
-        let root_table = src_dir_entry.children_entries.as_ref();
+        // an absolute path starts from the root directory
+        if path.starts_with('/') {
+            self.change_working_directory_to_root()?;
+        }
 
-        if let Some(root_table) = root_table {
-            for entry in root_table.iter() {
-                if entry.name == "." || entry.name == ".." {
-                    continue;
-                }
+        for segment in path.split('/').filter(|segment| !segment.is_empty()) {
+            let cd_request = ChangeDirectoryRequest::new(segment.to_string());
+            self.pull_sync();
 
-                match entry.is_file() {
-                    true => {
-                        // save the current working directory
-                        let current_working_directory = self.working_directory.clone();
+            if let Err(err) = self.change_working_directory(&cd_request) {
+                // restore the original working directory
+                self.change_working_directory_to(&original_working_directory)?;
 
-                        // change working directory to src directory
-                        self.change_working_directory_to(src_dir_entry)?;
+                return Err(
+                    Box::try_from(format!("Cannot change directory to {}: {}", path, err)).unwrap(),
+                );
+            }
+        }
 
-                        // get file content
-                        let cat_request =
-                            CatRequest::new(entry.name.clone(), entry.extension.clone());
-                        let file_content = self.get_file_content(&cat_request)?;
+        Ok(())
+    }
 
-                        // change working directory back to dest directory
-                        self.change_working_directory_to(&current_working_directory)?;
+    /// Restore the working directory starting at the given cluster (as recorded in the boot sector by the last sync
+    /// to the storage file), staying in the root directory if it cannot be found anymore.
+    pub(crate) fn restore_working_directory(&mut self, working_directory_cluster: u16) {
+        if working_directory_cluster == 0 {
+            return;
+        }
 
-                        // write the file content to the temp buffer file
-                        DiskManager::write_to_temp_buffer(file_content.as_str())?;
+        match self.find_directory_path_by_first_cluster(working_directory_cluster) {
+            Some(path) => match self.change_working_directory_along_path(&path) {
+                Ok(()) => log::info!("Restored working directory {}", path),
+                Err(err) => log::warn!("Unable to restore working directory {}: {}", path, err),
+            },
+            None => log::warn!(
+                "Unable to restore working directory: no directory starts at cluster {}",
+                working_directory_cluster
+            ),
+        }
+    }
 
-                        // create the file entry
-                        let create_request = CreateRequest::new(
-                            entry.name.clone(),
-                            entry.extension.clone(),
-                            file_content.len() as u32,
-                            entry.attributes,
-                            Utc::now(),
-                            ContentType::Temp,
-                        );
-                        self.create_file(&create_request)?;
-                        self.push_sync();
+    /// Search the directory tree for the directory starting at the given cluster and return its path from the root.
+    fn find_directory_path_by_first_cluster(&self, first_cluster: u16) -> Option<String> {
+        self.walk()
+            .into_iter()
+            .find(|(_, file_entry)| {
+                !file_entry.is_file() && file_entry.first_cluster == first_cluster
+            })
+            .map(|(entry_path, _)| entry_path)
+    }
+
+    /// Copy the given entry of the src directory into the working directory under the name of the dest of the request
+    /// (the working directory being either the src directory itself or the dest directory of the request).
+    pub(in crate::infrastructure) fn copy_entry_into_working_directory(
+        &mut self,
+        src_file_entry: &FileEntry,
+        src_directory: &FileEntry,
+        request: &CopyRequest,
+    ) -> Void {
+        // check that a directory is not copied into itself or into one of its subdirectories,
+        // which would make the inflation of the copied tree recurse forever
+        let src_directory_path = Self::get_path_from_root_to_entry(src_directory);
+        let dest_directory_path = Self::get_path_from_root_to_entry(&self.working_directory);
+        if request.src_extension.is_empty() {
+            let src_path = [src_directory_path.clone(), vec![request.src_name.clone()]].concat();
+            let dest_path = [dest_directory_path.clone(), vec![request.dest_name.clone()]].concat();
+
+            if Self::is_path_within(&dest_path, &src_path) {
+                return Err(Box::try_from(format!(
+                    "Invalid path: cannot copy directory {} into itself or one of its subdirectories",
+                    request.src_name
+                ))
+                .unwrap());
+            }
+        }
+
+        // check if the dest file already exists in the working directory root table
+        let dest_file_entry = self
+            .get_root_table_for_working_directory()
+            .iter()
+            .find(|file_entry| file_entry.name_matches(&request.dest_name, &request.dest_extension))
+            .cloned();
+
+        if let Some(dest_file_entry) = dest_file_entry {
+            if !request.force {
+                return Err(Box::try_from(format!(
+                    "File {}.{} already exists",
+                    request.dest_name, request.dest_extension
+                ))
+                .unwrap());
+            }
+
+            // overwrite the dest file only if it is not the src file itself and it is not read only
+            if dest_file_entry.name_matches(&request.src_name, &request.src_extension)
+                && dest_directory_path == src_directory_path
+            {
+                return Err(Box::try_from(format!(
+                    "Cannot copy {}.{} onto itself",
+                    request.src_name, request.src_extension
+                ))
+                .unwrap());
+            }
+
+            if dest_file_entry.is_read_only() {
+                return Err(Box::try_from(format!(
+                    "File {}.{} is read only",
+                    request.dest_name, request.dest_extension
+                ))
+                .unwrap());
+            }
+
+            // free the dest file entry and its cluster chain before writing the copy
+            match dest_file_entry.is_file() {
+                true => {
+                    self.free_clusters(&dest_file_entry);
+                    self.free_file_entry(&dest_file_entry);
+
+                    if !self.working_directory.is_root() {
+                        self.sync_directory_root_table_to_storage(&self.working_directory.clone())?;
                     }
-                    false => {
-                        // create the directory entry
-                        let make_directory_request = MakeDirectoryRequest::new(
-                            entry.name.clone(),
-                            entry.attributes,
-                            Utc::now(),
+                }
+                false => {
+                    let delete_request = DeleteRequest::new(
+                        dest_file_entry.name.clone(),
+                        dest_file_entry.extension.clone(),
+                        true,
+                        false,
+                        false,
+                    );
+                    self.delete_file(&delete_request)?;
+                }
+            }
+
+            // persist the deletion of the dest file, so that a failing copy doesn't restore it
+            self.push_sync();
+            log::info!(
+                "Overwriting {}.{} with {}.{}",
+                request.dest_name,
+                request.dest_extension,
+                request.src_name,
+                request.src_extension
+            );
+        }
+
+        // check if there is room for the entry in the working directory
+        self.check_directory_capacity()?;
+
+        // check if there is enough space in fat (for both the content and the long name, if any)
+        let required_clusters = (src_file_entry.size as f32 / self.boot_sector.cluster_size as f32)
+            .ceil() as usize
+            + self.get_long_name_clusters_count(&request.dest_name);
+
+        if self.get_free_clusters_count() < required_clusters {
+            return match request.force {
+                true => Err(Box::try_from(format!(
+                    "Not enough space in fat, {}.{} has not been copied (the destination, if any, has been deleted)",
+                    request.src_name, request.src_extension
+                ))
+                .unwrap()),
+                false => Err(Box::try_from("Not enough space in fat").unwrap()),
+            };
+        }
+
+        match src_file_entry.is_file() {
+            true => {
+                let out_of_space_error_message = format!(
+                    "Not enough space in fat, {}.{} has not been copied to {}.{}",
+                    request.src_name,
+                    request.src_extension,
+                    request.dest_name,
+                    request.dest_extension
+                );
+
+                // create the dest file entry (without an allocation chain if the src file is empty)
+                let dest_file_first_cluster = match src_file_entry.has_clusters() {
+                    true => match self.get_next_free_cluster_index_gt(0) {
+                        Some(dest_file_first_cluster) => dest_file_first_cluster as u16,
+                        None => return Err(Box::try_from(out_of_space_error_message).unwrap()),
+                    },
+                    false => FileEntry::NO_CLUSTER,
+                };
+                let dest_file_entry = FileEntry::builder(&request.dest_name)
+                    .extension(&request.dest_extension)
+                    .size(src_file_entry.size)
+                    .first_cluster(dest_file_first_cluster)
+                    .attributes(src_file_entry.attributes | FileEntryAttributesFlags::Archive as u8)
+                    .parent(&self.working_directory)
+                    .build()?;
+
+                if src_file_entry.has_clusters() {
+                    // iterate through the cluster chain and copy the file content from the storage buffer
+                    let mut current_src_cluster_index = src_file_entry.first_cluster as usize;
+                    let mut current_dest_cluster_index = dest_file_entry.first_cluster as usize;
+
+                    while self.fat[current_src_cluster_index] != FatValue::EndOfChain {
+                        // copy the content of the current cluster in src to the current cluster in dest
+                        self.set_cluster_data(
+                            current_dest_cluster_index,
+                            self.storage_buffer[current_src_cluster_index].clone(),
                         );
-                        self.pull_sync();
-                        self.make_directory(&make_directory_request)?;
-                        self.push_sync();
 
-                        // iterate over the directory's root table and recreate the dir tree in the new disk representation
-                        self.inflate_directory_tree_inline(entry, entry.name.clone())?;
+                        // take the current dest cluster before looking for the next one (past it if possible)
+                        self.set_fat_value(current_dest_cluster_index, FatValue::EndOfChain);
+                        let next_dest_cluster_index = match self
+                            .get_next_free_cluster_index_gt(current_dest_cluster_index)
+                            .or_else(|| self.get_next_free_cluster_index_gt(0))
+                        {
+                            Some(next_dest_cluster_index) => next_dest_cluster_index as u16,
+                            None => {
+                                // release the part of the chain already copied
+                                self.free_clusters(&dest_file_entry);
+                                return Err(Box::try_from(out_of_space_error_message).unwrap());
+                            }
+                        };
+
+                        // point the current dest cluster to the next dest cluster
+                        self.set_fat_value(
+                            current_dest_cluster_index,
+                            FatValue::Data(next_dest_cluster_index),
+                        );
+                        current_dest_cluster_index = next_dest_cluster_index as usize;
+
+                        // advance to the next src cluster as well
+                        let next_src_cluster_index: u16 =
+                            self.fat[current_src_cluster_index].clone().into();
+                        current_src_cluster_index = next_src_cluster_index as usize;
                     }
+
+                    // copy the content of the last cluster in src to the last cluster in dest
+                    // and mark the last dest cluster as end of chain
+                    self.set_cluster_data(
+                        current_dest_cluster_index,
+                        self.storage_buffer[current_src_cluster_index].clone(),
+                    );
+                    self.set_fat_value(current_dest_cluster_index, FatValue::EndOfChain);
                 }
+
+                // append the dest file entry to the root table of the working directory
+                self.append_to_root_table_of_working_dir(dest_file_entry)?;
+            }
+            false => {
+                // create the directory entry
+                let make_directory_request = MakeDirectoryRequest::new(
+                    request.dest_name.clone(),
+                    src_file_entry.attributes,
+                    Utc::now(),
+                );
+                self.make_directory(&make_directory_request)?;
+                // update the disk
+                self.push_sync();
+
+                // iterate over the src directory's root table and recreate the dir tree in the dest directory
+                self.inflate_directory_tree_inline(src_file_entry, request.dest_name.clone())?;
             }
         }
 
-        // change working directory back to parent
-        let cd_request = ChangeDirectoryRequest::new("..".to_string());
-        self.pull_sync();
-        self.change_working_directory(&cd_request)?;
-
         Ok(())
     }
 
-    /// Start from a source directory entry and recursively mimic all its children entries to a
-    /// destination directory entry from a different in-memory disk representation
-    /// (useful for disk defragmentation as it doesn't require to write to disk up to a certain point in order to preserve the consistency).
-    pub(in crate::infrastructure) fn inflate_directory_tree(
+    /// Start from a source directory entry and recursively copy all its children entries to a
+    /// destination directory entry (useful for copying directories).
+    pub(in crate::infrastructure) fn inflate_directory_tree_inline(
         &mut self,
-        disk_manager: &mut DiskManager,
-        dir_entry: &FileEntry,
+        src_dir_entry: &FileEntry,
+        dest_dir_name: String,
     ) -> Void {
         // change working directory to dir_entry
-        let cd_request = ChangeDirectoryRequest::new(dir_entry.name.clone());
-        disk_manager.change_working_directory(&cd_request)?;
-
+        let cd_request = ChangeDirectoryRequest::new(dest_dir_name);
         self.pull_sync();
         self.change_working_directory(&cd_request)?;
 
         // iterate over all children entries
-        let root_table = dir_entry.children_entries.as_ref();
+        let root_table = src_dir_entry.children_entries.as_ref();
 
         if let Some(root_table) = root_table {
             for entry in root_table.iter() {
@@ -678,14 +1560,22 @@ impl DiskManager {
 
                 match entry.is_file() {
                     true => {
+                        // save the current working directory
+                        let current_working_directory = self.working_directory.clone();
+
+                        // change working directory to src directory
+                        self.change_working_directory_to(src_dir_entry)?;
+
                         // get file content
-                        self.pull_sync();
                         let cat_request =
                             CatRequest::new(entry.name.clone(), entry.extension.clone());
-                        let file_content = self.get_file_content(&cat_request)?;
+                        let file_content = self.read_file_bytes(&cat_request)?;
+
+                        // change working directory back to dest directory
+                        self.change_working_directory_to(&current_working_directory)?;
 
                         // write the file content to the temp buffer file
-                        DiskManager::write_to_temp_buffer(file_content.as_str())?;
+                        self.write_to_temp_buffer(&file_content)?;
 
                         // create the file entry
                         let create_request = CreateRequest::new(
@@ -693,22 +1583,25 @@ impl DiskManager {
                             entry.extension.clone(),
                             file_content.len() as u32,
                             entry.attributes,
-                            entry.last_modification_datetime,
+                            Utc::now(),
                             ContentType::Temp,
                         );
-                        disk_manager.create_file(&create_request)?;
+                        self.create_file(&create_request)?;
+                        self.push_sync();
                     }
                     false => {
                         // create the directory entry
                         let make_directory_request = MakeDirectoryRequest::new(
                             entry.name.clone(),
                             entry.attributes,
-                            entry.last_modification_datetime,
+                            Utc::now(),
                         );
-                        disk_manager.make_directory(&make_directory_request)?;
+                        self.pull_sync();
+                        self.make_directory(&make_directory_request)?;
+                        self.push_sync();
 
                         // iterate over the directory's root table and recreate the dir tree in the new disk representation
-                        self.inflate_directory_tree(disk_manager, entry)?;
+                        self.inflate_directory_tree_inline(entry, entry.name.clone())?;
                     }
                 }
             }
@@ -716,8 +1609,6 @@ impl DiskManager {
 
         // change working directory back to parent
         let cd_request = ChangeDirectoryRequest::new("..".to_string());
-        disk_manager.change_working_directory(&cd_request)?;
-
         self.pull_sync();
         self.change_working_directory(&cd_request)?;
 
@@ -733,43 +1624,43 @@ impl DiskManager {
     ) -> Void {
         // create the cluster chain in fat and write the file data to the storage buffer
         let mut current_cluster_index = file_entry.first_cluster as usize;
-        let mut remaining_file_size = file_entry.size as u16;
+        let cluster_size = self.boot_sector.cluster_size as usize;
+        let mut remaining_file_size = file_entry.size as usize;
 
         // while there is still data to write
         while remaining_file_size > 0 {
-            match self.get_next_free_cluster_index_gt(current_cluster_index) {
+            // fill the current cluster with data extracted from the file data
+            // and add the remaining padding as 0 at the end of the cluster if needed
+            let mut cluster_data: ByteArray = file_data
+                .drain(..std::cmp::min(cluster_size, remaining_file_size))
+                .collect();
+            cluster_data.resize(cluster_size, 0);
+            self.set_cluster_data(current_cluster_index, cluster_data);
+
+            // take the current cluster before looking for the next one
+            self.set_fat_value(current_cluster_index, FatValue::EndOfChain);
+            if remaining_file_size <= cluster_size {
+                break;
+            }
+            remaining_file_size -= cluster_size;
+
+            // point the current cluster to the next free one (past it if possible), as there is still data to write
+            match self
+                .get_next_free_cluster_index_gt(current_cluster_index)
+                .or_else(|| self.get_next_free_cluster_index_gt(0))
+            {
                 Some(next_cluster_index) => {
-                    // mark the current cluster as data and point it to the next cluster
-                    self.fat[current_cluster_index] = FatValue::Data(next_cluster_index as u16);
-
-                    // fill the current cluster with data extracted from the file data
-                    self.storage_buffer[current_cluster_index] = file_data
-                        .drain(
-                            ..std::cmp::min(
-                                self.boot_sector.cluster_size as usize,
-                                remaining_file_size as usize,
-                            ),
-                        )
-                        .collect();
-
-                    if remaining_file_size > self.boot_sector.cluster_size {
-                        // update the remaining file size and the current cluster index
-                        remaining_file_size -= self.boot_sector.cluster_size;
-                        current_cluster_index = next_cluster_index;
-                    } else {
-                        // add the remaining padding as 0 at the end of the cluster
-                        self.storage_buffer[current_cluster_index]
-                            .resize(self.boot_sector.cluster_size as usize, 0);
-                        self.fat[current_cluster_index] = FatValue::EndOfChain;
-                        remaining_file_size = 0;
-                    }
+                    self.set_fat_value(
+                        current_cluster_index,
+                        FatValue::Data(next_cluster_index as u16),
+                    );
+                    current_cluster_index = next_cluster_index;
                 }
                 None => {
-                    // mark the current cluster as end of chain and free the chain cluster and the file entry
-                    self.fat[current_cluster_index] = FatValue::EndOfChain;
+                    // free the chain cluster and the file entry
                     self.free_clusters(file_entry);
                     self.free_file_entry(file_entry);
-                    self.sync_directory_root_table_to_storage(&self.working_directory.clone());
+                    self.sync_directory_root_table_to_storage(&self.working_directory.clone())?;
 
                     return Err(Box::try_from("No space in fat".to_string()).unwrap());
                 }
@@ -781,9 +1672,10 @@ impl DiskManager {
 
     /// Write the file data to the temp buffer file in order to be read its data later when
     /// recreating the file entry in the new disk representation after defragmentation.
-    pub(in crate::infrastructure) fn write_to_temp_buffer(file_content: &str) -> Void {
-        let mut temp_file = std::fs::File::create(CONFIG.temp_file_path.clone())?;
-        temp_file.write_all(file_content.as_bytes())?;
+    pub(in crate::infrastructure) fn write_to_temp_buffer(&self, file_content: &[u8]) -> Void {
+        let temp_file_path = self.config.lock().unwrap().temp_file_path.clone();
+        let mut temp_file = std::fs::File::create(temp_file_path)?;
+        temp_file.write_all(file_content)?;
 
         Ok(())
     }
@@ -798,27 +1690,109 @@ impl DiskManager {
         }
     }
 
+    /// Return the number of entries of the working directory (the free ones of the root, `.` and `..` aside).
+    pub(in crate::infrastructure) fn get_working_directory_entries_count(&self) -> usize {
+        match self.working_directory.is_root() {
+            true => self.root_capacity().0,
+            false => self
+                .working_directory
+                .children_entries
+                .as_deref()
+                .unwrap_or_default()
+                .iter()
+                .filter(|file_entry| {
+                    !file_entry.name.is_empty() && file_entry.name != "." && file_entry.name != ".."
+                })
+                .count(),
+        }
+    }
+
+    /// Check that a new file entry fits in the working directory: the root holds at most `root_entry_count` entries,
+    /// and every directory (the root included) at most `max_directory_entries` ones if the config sets such a quota
+    /// (the `.` and `..` entries of a subdirectory aren't counted).
+    pub(in crate::infrastructure) fn check_directory_capacity(&self) -> Void {
+        self.check_directory_capacity_releasing(0)
+    }
+
+    /// Check that a new file entry fits in the working directory once the given number of its entries are freed,
+    /// as when an entry is replaced by a new one.
+    pub(in crate::infrastructure) fn check_directory_capacity_releasing(
+        &self,
+        released_entries_count: usize,
+    ) -> Void {
+        let max_directory_entries = self.config.lock().unwrap().max_directory_entries;
+
+        let used_entries_count = self
+            .get_working_directory_entries_count()
+            .saturating_sub(released_entries_count);
+        if self.working_directory.is_root() {
+            let max_entries_count = self.boot_sector.root_entry_count as usize;
+            if used_entries_count >= max_entries_count {
+                let subdirectory_limit = match max_directory_entries {
+                    0 => "as subdirectories have no entry limit".to_string(),
+                    _ => format!(
+                        "as subdirectories hold up to {} entries each",
+                        max_directory_entries
+                    ),
+                };
+
+                return Err(Box::try_from(format!(
+                        "The root directory is full ({}/{} entries used), create the entry inside a subdirectory instead, {}",
+                        used_entries_count, max_entries_count, subdirectory_limit
+                    ))
+                    .unwrap());
+            }
+        }
+
+        if max_directory_entries == 0 || used_entries_count < max_directory_entries {
+            return Ok(());
+        }
+
+        Err(Box::try_from(format!(
+            "The directory {} has reached its quota ({}/{} entries used, see max_directory_entries in the config)",
+            self.get_working_directory_full_path(),
+            used_entries_count,
+            max_directory_entries
+        ))
+        .unwrap())
+    }
+
     /// Attach a new file entry to the root table of the working directory.
     pub(in crate::infrastructure) fn append_to_root_table_of_working_dir(
         &mut self,
-        file_entry: FileEntry,
+        mut file_entry: FileEntry,
     ) -> Void {
+        self.check_directory_capacity()?;
+
         match self.working_directory.is_root() {
             true => {
                 let dir_file_entry_index = self
                     .root
                     .iter()
-                    .position(|file_entry| file_entry.name.is_empty());
+                    .position(|file_entry| file_entry.name.is_empty())
+                    .unwrap();
 
                 // if the working directory is the root, just append the file entry to the root table
-                if let Some(dir_file_entry_index) = dir_file_entry_index {
-                    self.root[dir_file_entry_index] = file_entry;
-                    Ok(())
-                } else {
-                    Err(Box::try_from("No space left in the root folder").unwrap())
-                }
+                self.allocate_long_name(&mut file_entry)?;
+                self.root[dir_file_entry_index] = file_entry;
+                Ok(())
             }
             false => {
+                // check that the entry and its long name, if any, fit in the free clusters before touching the fat
+                let cluster_size = self.boot_sector.cluster_size as usize;
+                let required_clusters = file_entry.to_byte_array().len().div_ceil(cluster_size)
+                    + self.get_long_name_clusters_count(&file_entry.name);
+                if self.get_free_clusters_count() < required_clusters {
+                    return Err(Box::try_from(format!(
+                        "Disk full, unable to add {} to the table of directory {}",
+                        file_entry.get_full_name(),
+                        self.working_directory.name
+                    ))
+                    .unwrap());
+                }
+
+                self.allocate_long_name(&mut file_entry)?;
+
                 // add to root table
                 self.working_directory
                     .children_entries
@@ -827,41 +1801,1024 @@ impl DiskManager {
                     .push(file_entry.clone());
 
                 // get to the last cluster in the allocation chain of the working directory's root table
-                let mut current_cluster_index = self.working_directory.first_cluster;
-                while self.fat[current_cluster_index as usize] != FatValue::EndOfChain {
-                    current_cluster_index = self.fat[current_cluster_index as usize].clone().into();
+                let mut current_cluster_index = self.working_directory.first_cluster as usize;
+                while self.fat[current_cluster_index] != FatValue::EndOfChain {
+                    let next_cluster_index: u16 = self.fat[current_cluster_index].clone().into();
+                    current_cluster_index = next_cluster_index as usize;
                 }
 
                 // serialize the file entry and write it to the storage buffer
                 let mut file_entry_data: ByteArray = file_entry.into();
-                let mut next_cluster_index = self.get_next_free_cluster_index_gt(0).unwrap();
-                self.fat[current_cluster_index as usize] =
-                    FatValue::from(next_cluster_index as u16);
 
                 // while there is still data to write
                 while !file_entry_data.is_empty() {
-                    let mut cluster_data: ByteArray = Vec::new();
-                    cluster_data.extend_from_slice(
-                        &file_entry_data[..self.boot_sector.cluster_size as usize],
+                    // get the next free cluster index (past the current one if possible) and point the current cluster
+                    // to it in the fat table
+                    let next_cluster_index = match self
+                        .get_next_free_cluster_index_gt(current_cluster_index)
+                        .or_else(|| self.get_next_free_cluster_index_gt(0))
+                    {
+                        Some(next_cluster_index) => next_cluster_index,
+                        None => {
+                            return Err(
+                                Box::try_from(Self::DIRECTORY_TABLE_DISK_FULL_ERROR).unwrap()
+                            )
+                        }
+                    };
+                    self.set_fat_value(
+                        current_cluster_index,
+                        FatValue::from(next_cluster_index as u16),
                     );
-                    file_entry_data.drain(..self.boot_sector.cluster_size as usize);
-                    // extract from the file entry data the data that will be written to the current cluster
-                    self.storage_buffer[next_cluster_index] = cluster_data.clone();
-
-                    // if there is still data to write, get the next free cluster index and point the current cluster to it
-                    // in the fat table
-                    current_cluster_index = next_cluster_index as u16;
-                    next_cluster_index = self
-                        .get_next_free_cluster_index_gt(current_cluster_index as usize)
-                        .unwrap();
-                    self.fat[current_cluster_index as usize] =
-                        FatValue::from(next_cluster_index as u16);
+
+                    // extract from the file entry data the data that will be written to the next cluster
+                    // and take it right away as the last cluster of the chain
+                    let cluster_data = self.drain_cluster_data(&mut file_entry_data);
+                    self.set_cluster_data(next_cluster_index, cluster_data);
+                    self.set_fat_value(next_cluster_index, FatValue::EndOfChain);
+
+                    current_cluster_index = next_cluster_index;
                 }
-                // mark the last cluster as end of chain
-                self.fat[current_cluster_index as usize] = FatValue::EndOfChain;
 
                 Ok(())
             }
         }
     }
+
+    /// Collect the heads of all the allocation chains reachable from the directory tree in root order
+    /// (every directory is followed by its children), the long name chain of an entry coming before its data chain.
+    pub(in crate::infrastructure) fn collect_chain_heads(&self) -> Vec<usize> {
+        let mut visited_chain_heads: HashSet<usize> = HashSet::new();
+
+        self.walk()
+            .iter()
+            .flat_map(|(_, file_entry)| {
+                let long_name_chain_head = match file_entry.long_name_cluster {
+                    0 => None,
+                    long_name_cluster => Some(long_name_cluster as usize),
+                };
+
+                long_name_chain_head
+                    .into_iter()
+                    .chain([file_entry.first_cluster as usize])
+            })
+            // the hard links of a file share its chain, which must only be moved once
+            .filter(|chain_head| visited_chain_heads.insert(*chain_head))
+            .collect()
+    }
+
+    /// Iterate over the allocation chain starting at the given cluster and return the indices of its clusters
+    /// (the walk stops at the first cell which is not part of a chain and before the first cluster visited twice,
+    /// as a looping chain would be walked forever).
+    pub(in crate::infrastructure) fn get_chain_clusters(&self, first_cluster: usize) -> Vec<usize> {
+        let mut chain_clusters: Vec<usize> = Vec::new();
+        let mut visited_cluster_indices: HashSet<usize> = HashSet::new();
+        let mut cluster_index = first_cluster;
+
+        while visited_cluster_indices.insert(cluster_index) {
+            match self.fat.get(cluster_index) {
+                Some(FatValue::Data(next_cluster_index)) => {
+                    chain_clusters.push(cluster_index);
+                    cluster_index = *next_cluster_index as usize;
+                }
+                Some(FatValue::EndOfChain) => {
+                    chain_clusters.push(cluster_index);
+                    break;
+                }
+                _ => break,
+            }
+        }
+
+        chain_clusters
+    }
+
+    /// Move the clusters of the given chains, in order, to the front of the data region by swapping them in place
+    /// with whatever occupies their destination (except for the bad clusters, which are never moved), then update the FAT cells to point to the moved clusters.
+    ///
+    /// Returns the new index of every cluster (the clusters which were not moved keep their own index).
+    pub(in crate::infrastructure) fn compact_chains(
+        &mut self,
+        chain_heads: &[usize],
+    ) -> Vec<usize> {
+        // the current index of every original cluster and the original cluster found at every index
+        let mut relocations: Vec<usize> = (0..self.fat.len()).collect();
+        let mut occupants: Vec<usize> = (0..self.fat.len()).collect();
+        // the bad clusters stay where they are, so they are skipped as destinations
+        let first_data_cluster = self.boot_sector.get_reserved_clusters_count() as usize;
+        let destinations: Vec<usize> = (first_data_cluster..self.fat.len())
+            .filter(|&cluster_index| self.fat[cluster_index] != FatValue::Bad)
+            .collect();
+
+        // walk all the chains before moving anything, as the FAT cells are moved along with their clusters
+        let chain_clusters: Vec<usize> = chain_heads
+            .iter()
+            .flat_map(|&chain_head| self.get_chain_clusters(chain_head))
+            .collect();
+
+        for (destination, cluster_index) in destinations.into_iter().zip(chain_clusters) {
+            let current_index = relocations[cluster_index];
+            if current_index == destination {
+                continue;
+            }
+
+            self.storage_buffer.swap(current_index, destination);
+            self.fat.swap(current_index, destination);
+            self.dirty_cluster_indices.insert(current_index);
+            self.dirty_cluster_indices.insert(destination);
+
+            // the cluster found at the destination takes the place of the moved one
+            let displaced_cluster = occupants[destination];
+            relocations[displaced_cluster] = current_index;
+            occupants[current_index] = displaced_cluster;
+            relocations[cluster_index] = destination;
+            occupants[destination] = cluster_index;
+        }
+
+        // the FAT cells still hold the original indices of the next clusters
+        for fat_value in self.fat.iter_mut() {
+            if let FatValue::Data(next_cluster_index) = fat_value {
+                *next_cluster_index = relocations[*next_cluster_index as usize] as u16;
+            }
+        }
+        self.free_cluster_indices = Self::collect_free_cluster_indices(&self.fat);
+
+        relocations
+    }
+
+    /// Checks whether the working directory is the trash directory or one of its subdirectories.
+    pub(in crate::infrastructure) fn is_working_directory_in_trash(&self) -> bool {
+        let path = Self::get_path_from_root_to_entry(&self.working_directory);
+        path.get(1)
+            .is_some_and(|name| name == Self::TRASH_DIRECTORY_NAME)
+    }
+
+    /// Creates the (hidden) trash directory in the root, unless it already exists.
+    pub(in crate::infrastructure) fn ensure_trash_directory(&mut self) -> Void {
+        if self
+            .root
+            .iter()
+            .any(|file_entry| file_entry.matches_directory(Self::TRASH_DIRECTORY_NAME))
+        {
+            return Ok(());
+        }
+
+        let working_directory_path = self.get_working_directory_full_path();
+        self.change_working_directory_to_root()?;
+
+        let result = self.make_directory(&MakeDirectoryRequest::new(
+            Self::TRASH_DIRECTORY_NAME.to_string(),
+            FileEntryAttributes::combine(&[
+                FileEntryAttributes::Directory,
+                FileEntryAttributes::Hidden,
+                FileEntryAttributes::System,
+            ]),
+            Utc::now(),
+        ));
+
+        self.push_sync();
+        self.pull_sync();
+        self.change_working_directory_along_path(&working_directory_path)?;
+
+        result
+    }
+
+    /// Moves a file entry of the working directory (along with its content, for a directory) into the directory
+    /// at the given absolute path, without copying its data: only the file entry is detached from the working
+    /// directory and attached to the destination directory (and the `..` entry of a directory is updated).
+    ///
+    /// The working directory is kept.
+    pub(in crate::infrastructure) fn move_file_entry(
+        &mut self,
+        file_entry: &FileEntry,
+        destination_path: &str,
+    ) -> Void {
+        // check that the file entry can be attached before detaching it (a cluster for the destination table to grow,
+        // along with the ones of its long name)
+        if self.get_free_clusters_count() <= self.get_long_name_clusters_count(&file_entry.name) {
+            return Err(Box::try_from(format!(
+                "Not enough space in FAT to move {}",
+                file_entry.get_full_name()
+            ))
+            .unwrap());
+        }
+
+        // detach the file entry from the working directory
+        let working_directory_path = self.get_working_directory_full_path();
+        self.free_file_entry(file_entry);
+        if !self.working_directory.is_root() {
+            self.sync_directory_root_table_to_storage(&self.working_directory.clone())?;
+        }
+        self.push_sync();
+        self.pull_sync();
+
+        // attach it to the destination directory
+        self.change_working_directory_along_path(destination_path)?;
+
+        let mut moved_file_entry = file_entry.clone();
+        moved_file_entry.parent_entry = Some(Box::new(self.working_directory.clone()));
+        if let Some(children_entries) = moved_file_entry.children_entries.as_mut() {
+            if let Some(double_dot_dir_entry) = children_entries
+                .iter_mut()
+                .find(|child_entry| child_entry.name == "..")
+            {
+                // the parent special dir entry now refers to the destination directory
+                let attributes = double_dot_dir_entry.attributes;
+                *double_dot_dir_entry = self.working_directory.clone();
+                double_dot_dir_entry.name = "..".to_string();
+                double_dot_dir_entry.extension = "".to_string();
+                double_dot_dir_entry.long_name_cluster = 0;
+                double_dot_dir_entry.attributes = attributes;
+            }
+            self.sync_directory_root_table_to_storage(&moved_file_entry)?;
+        }
+        let result = self.append_to_root_table_of_working_dir(moved_file_entry);
+
+        self.push_sync();
+        self.pull_sync();
+        self.change_working_directory_along_path(&working_directory_path)?;
+
+        result
+    }
+
+    /// Copy the allocation chains, in root order, to the front of the data region of another (empty) disk
+    /// along with the directory tree pointing to them; the bad clusters keep their index if it is still
+    /// in the data region of the other disk.
+    pub(in crate::infrastructure) fn move_chains_to_disk(
+        &self,
+        disk_manager: &mut DiskManager,
+    ) -> Void {
+        let first_data_cluster = disk_manager.boot_sector.get_reserved_clusters_count() as usize;
+        for cluster_index in first_data_cluster..disk_manager.fat.len().min(self.fat.len()) {
+            if self.fat[cluster_index] == FatValue::Bad {
+                disk_manager.set_fat_value(cluster_index, FatValue::Bad);
+            }
+        }
+
+        let chain_clusters: Vec<usize> = self
+            .collect_chain_heads()
+            .iter()
+            .flat_map(|&chain_head| self.get_chain_clusters(chain_head))
+            .collect();
+        let destinations: Vec<usize> = disk_manager.free_cluster_indices.iter().copied().collect();
+        if chain_clusters.len() > destinations.len() {
+            return Err(Box::try_from(format!(
+                "The {} used cluster(s) don't fit in the {} free cluster(s) of the resized disk",
+                chain_clusters.len(),
+                destinations.len()
+            ))
+            .unwrap());
+        }
+
+        // the new index of every moved cluster (the other ones are never referenced)
+        let mut relocations: Vec<usize> = (0..self.fat.len()).collect();
+        for (&cluster_index, &destination) in chain_clusters.iter().zip(&destinations) {
+            relocations[cluster_index] = destination;
+        }
+
+        for &cluster_index in chain_clusters.iter() {
+            let fat_value = match self.fat[cluster_index] {
+                FatValue::Data(next_cluster_index) => {
+                    FatValue::Data(relocations[next_cluster_index as usize] as u16)
+                }
+                ref fat_value => fat_value.clone(),
+            };
+            disk_manager.set_fat_value(relocations[cluster_index], fat_value);
+            disk_manager.set_cluster_data(
+                relocations[cluster_index],
+                self.storage_buffer[cluster_index].clone(),
+            );
+        }
+
+        // the directory tree keeps its layout, only its cluster references change
+        disk_manager.root = self.root.clone();
+        disk_manager.relocate_file_entries(&relocations);
+        disk_manager.rewrite_directory_root_tables();
+
+        Ok(())
+    }
+
+    /// Point the file entries of the whole directory tree (including the special dir entries) to the new indices
+    /// of their first clusters and of their long name chains.
+    pub(in crate::infrastructure) fn relocate_file_entries(&mut self, relocations: &[usize]) {
+        let mut pending_entries: Vec<&mut FileEntry> = self.root.iter_mut().collect();
+
+        while let Some(file_entry) = pending_entries.pop() {
+            if file_entry.name.is_empty() {
+                continue;
+            }
+
+            file_entry.first_cluster = relocations[file_entry.first_cluster as usize] as u16;
+            if file_entry.long_name_cluster != 0 {
+                file_entry.long_name_cluster =
+                    relocations[file_entry.long_name_cluster as usize] as u16;
+            }
+
+            if let Some(children_entries) = file_entry.children_entries.as_mut() {
+                pending_entries.extend(children_entries.iter_mut());
+            }
+        }
+    }
+
+    /// Overwrite the root tables of all the directories with their in-memory representations, in place
+    /// (unlike `sync_directory_root_table_to_storage`, the clusters of their allocation chains are kept).
+    pub(in crate::infrastructure) fn rewrite_directory_root_tables(&mut self) {
+        let mut pending_directories: Vec<FileEntry> = self
+            .root
+            .iter()
+            .filter(|file_entry| !file_entry.name.is_empty() && !file_entry.is_file())
+            .cloned()
+            .collect();
+
+        while let Some(directory) = pending_directories.pop() {
+            let mut directory_data = Self::serialize_directory_root_table(&directory);
+
+            for cluster_index in self.get_chain_clusters(directory.first_cluster as usize) {
+                if directory_data.is_empty() {
+                    break;
+                }
+
+                let cluster_data = self.drain_cluster_data(&mut directory_data);
+                self.set_cluster_data(cluster_index, cluster_data);
+            }
+
+            pending_directories.extend(
+                directory
+                    .children_entries
+                    .iter()
+                    .flatten()
+                    .filter(|child_entry| {
+                        !child_entry.is_file()
+                            && child_entry.name != "."
+                            && child_entry.name != ".."
+                    })
+                    .cloned(),
+            );
+        }
+    }
+
+    /// Check whether a chain goes from a cluster to the very next one (the bad clusters in between being skipped,
+    /// as they can never be allocated).
+    fn is_contiguous_transition(&self, cluster_index: usize, next_cluster_index: usize) -> bool {
+        next_cluster_index > cluster_index
+            && self.fat[cluster_index + 1..next_cluster_index]
+                .iter()
+                .all(|fat_value| *fat_value == FatValue::Bad)
+    }
+
+    /// Walk the allocation chains reachable from the directory tree in root order and count, for each of them,
+    /// the transitions to a next cluster which is not the very next one.
+    pub(in crate::infrastructure) fn build_fragmentation_report(&self) -> FragReport {
+        let mut report = FragReport::default();
+
+        for (entry_path, file_entry) in self.walk() {
+            let mut chains = vec![(file_entry.first_cluster as usize, entry_path.clone())];
+            if file_entry.long_name_cluster != 0 {
+                chains.push((
+                    file_entry.long_name_cluster as usize,
+                    format!("{} (long name)", entry_path),
+                ));
+            }
+
+            for (first_cluster_index, chain_owner_path) in chains {
+                let chain_clusters = self.get_chain_clusters(first_cluster_index);
+                report.files.push(FileFragmentation {
+                    entry_path: chain_owner_path,
+                    clusters_count: chain_clusters.len(),
+                    fragmented_transitions: chain_clusters
+                        .windows(2)
+                        .filter(|window| !self.is_contiguous_transition(window[0], window[1]))
+                        .count(),
+                });
+            }
+        }
+
+        report
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::commands::fmt::FormatRequest;
+    use crate::application::commands::truncate::TruncateRequest;
+    use crate::core::config::DiskGeometry;
+    use crate::domain::file_entry::FileEntryAttributes;
+    use crate::infrastructure::test_disk::TestDisk;
+
+    /// Returns the index of the first free FAT cell after the given one, by scanning the whole FAT table.
+    fn scan_next_free_cluster_index_gt(
+        fat: &FatTable,
+        current_cluster_index: usize,
+    ) -> Option<usize> {
+        fat.iter()
+            .enumerate()
+            .skip(current_cluster_index + 1)
+            .find(|(_, fat_value)| **fat_value == FatValue::Free)
+            .map(|(index, _)| index)
+    }
+
+    /// Checks that the free cluster indices cache holds exactly the free cells of the FAT table
+    /// and that querying it gives the clusters a scan of the FAT table would give.
+    fn assert_free_cluster_indices_in_sync(disk_manager: &DiskManager) {
+        assert_eq!(
+            disk_manager.free_cluster_indices,
+            DiskManager::collect_free_cluster_indices(&disk_manager.fat)
+        );
+
+        for current_cluster_index in (0..disk_manager.fat.len()).step_by(37) {
+            assert_eq!(
+                disk_manager.get_next_free_cluster_index_gt(current_cluster_index),
+                scan_next_free_cluster_index_gt(&disk_manager.fat, current_cluster_index)
+            );
+        }
+    }
+
+    #[test]
+    fn free_cluster_indices_stay_in_sync_with_the_fat() {
+        let mut disk = TestDisk::new();
+        assert_free_cluster_indices_in_sync(&disk);
+
+        disk.create("a", "txt", 100).unwrap();
+        disk.create("b", "txt", 1000).unwrap();
+        disk.create("c", "txt", 0).unwrap();
+        assert_free_cluster_indices_in_sync(&disk);
+
+        // free a chain in the middle of the data region, so that the next allocations fill the hole first
+        disk.delete("a", "txt").unwrap();
+        assert_free_cluster_indices_in_sync(&disk);
+
+        disk.create("d", "txt", 250).unwrap();
+        disk.append("b", "txt", "appended to b").unwrap();
+        disk.write("c", "txt", "written into c").unwrap();
+        assert_eq!(disk.read("c", "txt"), b"written into c");
+        disk.mkdir("dir").unwrap();
+        disk.cd("dir").unwrap();
+        disk.create("e", "txt", 300).unwrap();
+        disk.cd("..").unwrap();
+        assert_free_cluster_indices_in_sync(&disk);
+
+        disk.run(|disk_manager| {
+            disk_manager.truncate_file(&TruncateRequest::new(
+                "b".to_string(),
+                "txt".to_string(),
+                10,
+            ))
+        })
+        .unwrap();
+        disk.delete("dir", "").unwrap();
+        assert_free_cluster_indices_in_sync(&disk);
+
+        // the cache is rebuilt from the FAT table of the storage file by the next session
+        let disk_manager = disk.reopen();
+        assert_free_cluster_indices_in_sync(&disk_manager);
+        assert_eq!(disk_manager.free_cluster_indices, disk.free_cluster_indices);
+    }
+
+    #[test]
+    fn allocation_reuses_the_lowest_free_clusters() {
+        let mut disk = TestDisk::new();
+        disk.create("a", "txt", 64).unwrap();
+        disk.create("b", "txt", 64).unwrap();
+        let first_cluster_of_a = disk.walk()[0].1.first_cluster as usize;
+
+        disk.delete("a", "txt").unwrap();
+        disk.create("c", "txt", 16).unwrap();
+
+        let (_, file_entry) = disk
+            .walk()
+            .into_iter()
+            .find(|(path, _)| path == "/c.txt")
+            .unwrap();
+        assert_eq!(file_entry.first_cluster as usize, first_cluster_of_a);
+        assert_free_cluster_indices_in_sync(&disk);
+    }
+
+    #[test]
+    fn push_sync_writes_only_the_dirty_clusters() {
+        let mut disk = TestDisk::new();
+        disk.create("a", "txt", 10).unwrap();
+        let cluster_size = disk.boot_sector.cluster_size as usize;
+
+        // create another file, syncing the in-memory disk to its storage buffer only
+        disk.pull_sync();
+        disk.create_file(&CreateRequest::new(
+            "b".to_string(),
+            "txt".to_string(),
+            20,
+            FileEntryAttributes::combine(&[
+                FileEntryAttributes::File,
+                FileEntryAttributes::ReadWrite,
+                FileEntryAttributes::Visible,
+            ]),
+            Utc::now(),
+            ContentType::Alpha,
+        ))
+        .unwrap();
+        disk.sync_to_buffer();
+        let dirty_cluster_indices = disk.dirty_cluster_indices.clone();
+        let first_cluster = disk.root[1].first_cluster as usize;
+        assert!(dirty_cluster_indices.contains(&first_cluster));
+
+        // scribble over a free cluster of the storage file behind the back of the disk manager,
+        // which rewriting the whole storage file would undo
+        let free_cluster = *disk.free_cluster_indices.last().unwrap();
+        let mut storage_file_content = std::fs::read(&disk.storage_file_path).unwrap();
+        storage_file_content[free_cluster * cluster_size..(free_cluster + 1) * cluster_size]
+            .fill(0xAA);
+        std::fs::write(&disk.storage_file_path, &storage_file_content).unwrap();
+
+        disk.sync_to_file();
+        assert!(disk.dirty_cluster_indices.is_empty());
+
+        // the dirty clusters have been written at their offsets, while the other ones are left as they were
+        let written_content = std::fs::read(&disk.storage_file_path).unwrap();
+        assert_eq!(written_content.len(), storage_file_content.len());
+        for (index, cluster) in written_content.chunks(cluster_size).enumerate() {
+            let offset = index * cluster_size;
+            match dirty_cluster_indices.contains(&index) {
+                true => assert_eq!(cluster, disk.storage_buffer[index].as_slice()),
+                false => assert_eq!(
+                    cluster,
+                    &storage_file_content[offset..offset + cluster_size]
+                ),
+            }
+        }
+        assert!(
+            written_content[free_cluster * cluster_size..(free_cluster + 1) * cluster_size]
+                .iter()
+                .all(|byte| *byte == 0xAA)
+        );
+    }
+
+    #[test]
+    fn push_sync_without_modifications_writes_nothing() {
+        let mut disk = TestDisk::new();
+        disk.create("a", "txt", 10).unwrap();
+        let storage_file_content = std::fs::read(&disk.storage_file_path).unwrap();
+
+        disk.pull_sync();
+        disk.push_sync();
+
+        assert!(disk.dirty_cluster_indices.is_empty());
+        assert_eq!(
+            std::fs::read(&disk.storage_file_path).unwrap(),
+            storage_file_content
+        );
+    }
+
+    #[test]
+    fn a_new_disk_reserves_the_clusters_of_its_boot_sector() {
+        let disk = TestDisk::new();
+        let boot_sector = BootSector::default();
+        let reserved_clusters_count = boot_sector.get_reserved_clusters_count() as usize;
+
+        let disk_manager = DiskManager::new(disk.config.clone(), boot_sector);
+
+        assert_eq!(disk_manager.fat.len(), 8192);
+        assert!(disk_manager.fat[..reserved_clusters_count]
+            .iter()
+            .all(|fat_value| *fat_value == FatValue::Reserved));
+        assert!(disk_manager.fat[reserved_clusters_count..]
+            .iter()
+            .all(|fat_value| *fat_value == FatValue::Free));
+        assert_eq!(
+            disk_manager.get_free_clusters_count(),
+            8192 - reserved_clusters_count
+        );
+    }
+
+    #[test]
+    fn disk_managers_on_different_storage_files_do_not_interfere() {
+        let mut disk = TestDisk::new();
+        let mut other_disk = TestDisk::new();
+        assert_ne!(disk.storage_file_path, other_disk.storage_file_path);
+
+        disk.create("a", "txt", 40).unwrap();
+        disk.mkdir("docs").unwrap();
+        other_disk.write("b", "txt", "other").unwrap();
+        disk.cd("docs").unwrap();
+
+        let names = |disk_manager: &DiskManager| -> Vec<String> {
+            disk_manager
+                .walk()
+                .into_iter()
+                .map(|(path, _)| path)
+                .collect()
+        };
+        assert_eq!(names(&disk.reopen()), vec!["/a.txt", "/docs"]);
+        assert_eq!(names(&other_disk.reopen()), vec!["/b.txt"]);
+        assert_eq!(other_disk.read("b", "txt"), b"other");
+        assert_eq!(other_disk.reopen().get_working_directory_full_path(), "/");
+        assert_eq!(disk.reopen().get_working_directory_full_path(), "/docs");
+    }
+
+    /// Returns the file entry found at the given path by walking the whole tree.
+    fn find_entry(disk: &TestDisk, path: &str) -> FileEntry {
+        disk.walk()
+            .into_iter()
+            .find(|(entry_path, _)| entry_path == path)
+            .map(|(_, file_entry)| file_entry)
+            .unwrap()
+    }
+
+    #[test]
+    fn freeing_an_empty_file_leaves_the_fat_untouched() {
+        let mut disk = TestDisk::new();
+        disk.create("empty", "txt", 0).unwrap();
+        disk.create("other", "txt", 40).unwrap();
+        let mut empty_file = find_entry(&disk, "/empty.txt");
+        let fat = disk.fat.clone();
+
+        // an empty file pointing at a cell which is not allocated (as older disks may hold) frees nothing either
+        empty_file.first_cluster = *disk.free_cluster_indices.first().unwrap() as u16;
+        disk.free_clusters(&empty_file);
+        assert_eq!(disk.fat, fat);
+    }
+
+    #[test]
+    fn freeing_a_looping_chain_frees_each_cluster_once_and_stops() {
+        let mut disk = TestDisk::new();
+        disk.create("loop", "txt", 48).unwrap();
+        let file_entry = find_entry(&disk, "/loop.txt");
+        let chain = disk.get_chain_clusters(file_entry.first_cluster as usize);
+        assert_eq!(chain.len(), 3);
+        let free_clusters_count = disk.get_free_clusters_count();
+
+        // link the end of the chain back to its start
+        disk.set_fat_value(chain[2], FatValue::Data(chain[0] as u16));
+        disk.free_clusters(&file_entry);
+
+        assert!(chain
+            .iter()
+            .all(|&cluster_index| disk.fat[cluster_index] == FatValue::Free));
+        assert_eq!(disk.get_free_clusters_count(), free_clusters_count + 3);
+    }
+
+    #[test]
+    fn freeing_a_chain_stops_at_a_reserved_cell() {
+        let mut disk = TestDisk::new();
+        disk.create("broken", "txt", 32).unwrap();
+        let file_entry = find_entry(&disk, "/broken.txt");
+        let chain = disk.get_chain_clusters(file_entry.first_cluster as usize);
+
+        // point the chain into the reserved region
+        disk.set_fat_value(chain[0], FatValue::Data(1));
+        disk.free_clusters(&file_entry);
+
+        assert_eq!(disk.fat[chain[0]], FatValue::Free);
+        assert_eq!(disk.fat[chain[1]], FatValue::EndOfChain);
+        assert_eq!(disk.fat[1], FatValue::Reserved);
+    }
+
+    #[test]
+    fn a_directory_table_ending_inside_a_cluster_is_padded_with_zeros() {
+        let disk = TestDisk::new();
+        // a root entry only fills half of a 64 bytes cluster, which the validation of a boot sector rejects
+        let boot_sector = BootSector {
+            cluster_size: 64,
+            ..BootSector::default()
+        };
+        let mut disk_manager = DiskManager::new(disk.config.clone(), boot_sector);
+        let first_cluster = *disk_manager.free_cluster_indices.first().unwrap();
+        let children_entries = ["a", "b", "c"]
+            .iter()
+            .map(|name| FileEntry::builder(name).extension("txt").build().unwrap())
+            .collect();
+        let directory = FileEntry::builder("docs")
+            .first_cluster(first_cluster as u16)
+            .attributes(FileEntryAttributes::combine(&[
+                FileEntryAttributes::Directory,
+                FileEntryAttributes::ReadWrite,
+                FileEntryAttributes::Visible,
+            ]))
+            .children(children_entries)
+            .build()
+            .unwrap();
+
+        disk_manager
+            .sync_directory_root_table_to_storage(&directory)
+            .unwrap();
+
+        // the 96 bytes of the 3 entries take 2 clusters, the second one being half filled
+        let chain = disk_manager.get_chain_clusters(first_cluster);
+        assert_eq!(chain.len(), 2);
+        let table: Vec<u8> = chain
+            .iter()
+            .flat_map(|&cluster_index| disk_manager.storage_buffer[cluster_index].clone())
+            .collect();
+        assert_eq!(
+            table[..96],
+            DiskManager::serialize_directory_root_table(&directory)[..]
+        );
+        assert!(table[96..].iter().all(|byte| *byte == 0));
+    }
+
+    #[test]
+    fn a_directory_table_growing_on_a_full_disk_is_rejected_before_anything_is_freed() {
+        let mut disk = TestDisk::new();
+        disk.mkdir("docs").unwrap();
+        let free_clusters_count = disk.get_free_clusters_count() as u32;
+        // leave a single free cluster
+        disk.create("fill", "bin", (free_clusters_count - 1) * 16)
+            .unwrap();
+        assert_eq!(disk.get_free_clusters_count(), 1);
+
+        let mut directory = find_entry(&disk, "/docs");
+        let chain = disk.get_chain_clusters(directory.first_cluster as usize);
+        let fat = disk.fat.clone();
+        // a third entry takes 2 more clusters for the table of the directory
+        let file_entry = FileEntry::builder("new")
+            .extension("txt")
+            .parent(&directory)
+            .build()
+            .unwrap();
+        directory
+            .children_entries
+            .as_mut()
+            .unwrap()
+            .push(file_entry);
+
+        assert!(disk
+            .sync_directory_root_table_to_storage(&directory)
+            .is_err());
+        assert_eq!(disk.fat, fat);
+        assert_eq!(
+            disk.get_chain_clusters(directory.first_cluster as usize),
+            chain
+        );
+    }
+
+    #[test]
+    fn creating_a_file_in_a_subdirectory_of_a_full_disk_fails_without_panicking() {
+        let mut disk = TestDisk::new();
+        disk.mkdir("docs").unwrap();
+        let free_clusters_count = disk.get_free_clusters_count() as u32;
+        // leave the 2 clusters a root entry takes
+        disk.create("fill", "bin", (free_clusters_count - 2) * 16)
+            .unwrap();
+        disk.cd("docs").unwrap();
+
+        // the entry lands in the last free clusters
+        disk.create("a", "txt", 0).unwrap();
+        assert_eq!(disk.get_free_clusters_count(), 0);
+        let directory = find_entry(&disk, "/docs");
+        let chain = disk.get_chain_clusters(directory.first_cluster as usize);
+        let fat = disk.fat.clone();
+
+        let result = disk.create("b", "txt", 0);
+
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Disk full, unable to add b.txt to the table of directory docs"
+        );
+        assert_eq!(disk.fat, fat);
+        assert_eq!(
+            disk.get_chain_clusters(directory.first_cluster as usize),
+            chain
+        );
+    }
+
+    fn geometry(
+        fat_type: Option<u16>,
+        cluster_size: Option<u16>,
+        cluster_count: Option<u16>,
+        root_entry_count: Option<u16>,
+    ) -> DiskGeometry {
+        DiskGeometry {
+            fat_type,
+            cluster_size,
+            cluster_count,
+            root_entry_count,
+        }
+    }
+
+    #[test]
+    fn a_new_disk_is_created_with_the_configured_geometry() {
+        let valid_geometries = [
+            (
+                geometry(Some(16), Some(32), Some(4096), Some(32)),
+                16,
+                32,
+                4096,
+                32,
+            ),
+            (
+                geometry(Some(12), Some(16), Some(2000), Some(16)),
+                12,
+                16,
+                2000,
+                16,
+            ),
+            (geometry(None, None, Some(1500), None), 16, 16, 1500, 64),
+        ];
+
+        for (disk_geometry, fat_cell_bits, cluster_size, cluster_count, root_entry_count) in
+            valid_geometries
+        {
+            let disk = TestDisk::with_config(|config| config.disk = disk_geometry.clone());
+
+            for disk_manager in [&disk.disk_manager, &disk.reopen()] {
+                assert_eq!(disk_manager.boot_sector.fat_cell_bits, fat_cell_bits);
+                assert_eq!(disk_manager.boot_sector.cluster_size, cluster_size);
+                assert_eq!(disk_manager.boot_sector.cluster_count, cluster_count);
+                assert_eq!(disk_manager.boot_sector.root_entry_count, root_entry_count);
+                assert_eq!(disk_manager.fat.len(), cluster_count as usize);
+            }
+            assert_eq!(
+                std::fs::metadata(&disk.storage_file_path).unwrap().len(),
+                cluster_size as u64 * cluster_count as u64
+            );
+        }
+    }
+
+    #[test]
+    fn an_invalid_geometry_fails_to_open_the_disk() {
+        let invalid_geometries = [
+            (
+                geometry(None, Some(64), None, None),
+                "cluster_size must be 16 or 32, got 64",
+            ),
+            (
+                geometry(Some(12), None, Some(8192), None),
+                "cluster count 8192 exceeds the",
+            ),
+            (
+                geometry(Some(8), None, None, None),
+                "FAT cell size of 8 bits is not supported",
+            ),
+            (
+                geometry(None, None, Some(0), None),
+                "all the sizes and counts must be greater than 0",
+            ),
+            (
+                geometry(None, None, Some(100), None),
+                "cluster count 100 must be greater than the 142 reserved clusters",
+            ),
+        ];
+
+        for (disk_geometry, expected_error) in invalid_geometries {
+            let disk = TestDisk::new();
+            disk.config.lock().unwrap().disk = disk_geometry;
+
+            let error = DiskManager::open(disk.config.clone()).err().unwrap();
+
+            assert!(
+                error.starts_with("Invalid disk geometry in config: "),
+                "{}",
+                error
+            );
+            assert!(error.contains(expected_error), "{}", error);
+        }
+    }
+
+    #[test]
+    fn a_foreign_or_truncated_storage_file_is_refused() {
+        let disk = TestDisk::new();
+        let storage_file_path = disk.storage_file_path.clone();
+        let storage_file_content = std::fs::read(&storage_file_path).unwrap();
+
+        std::fs::write(&storage_file_path, "not a RoDOS disk, just some text").unwrap();
+        let error = DiskManager::open(disk.config.clone()).err().unwrap();
+        assert!(
+            error.starts_with(&format!(
+                "Invalid storage file {}: invalid signature",
+                storage_file_path
+            )),
+            "{}",
+            error
+        );
+
+        std::fs::write(&storage_file_path, &storage_file_content[..1000]).unwrap();
+        let error = DiskManager::open(disk.config.clone()).err().unwrap();
+        assert_eq!(
+            error,
+            format!(
+                "Invalid storage file {}: the storage file holds 1000 bytes instead of {}",
+                storage_file_path,
+                storage_file_content.len()
+            )
+        );
+
+        // the refused storage file is left as it is
+        assert_eq!(std::fs::read(&storage_file_path).unwrap().len(), 1000);
+    }
+
+    #[test]
+    fn the_working_directory_is_restored_by_the_next_session() {
+        let mut disk = TestDisk::new();
+        disk.mkdir("docs").unwrap();
+        disk.cd("docs").unwrap();
+        disk.mkdir("reports").unwrap();
+        disk.cd("reports").unwrap();
+
+        let mut disk_manager = disk.reopen();
+
+        assert_eq!(
+            disk_manager.get_working_directory_full_path(),
+            "/docs/reports"
+        );
+        // the restored working directory is linked to its parents
+        disk_manager.pull_sync();
+        disk_manager
+            .change_working_directory(&ChangeDirectoryRequest::new("..".to_string()))
+            .unwrap();
+        assert_eq!(disk_manager.get_working_directory_full_path(), "/docs");
+    }
+
+    #[test]
+    fn the_next_session_starts_at_the_root_if_the_working_directory_is_gone() {
+        let mut disk = TestDisk::new();
+        disk.mkdir("docs").unwrap();
+        disk.cd("docs").unwrap();
+        let mut other_session = disk.reopen();
+
+        // another session deletes the working directory of the first one
+        other_session
+            .change_working_directory(&ChangeDirectoryRequest::new("/".to_string()))
+            .unwrap();
+        other_session
+            .delete_file(&DeleteRequest::new(
+                "docs".to_string(),
+                String::new(),
+                true,
+                false,
+                false,
+            ))
+            .unwrap();
+        other_session.push_sync();
+
+        assert_eq!(disk.reopen().get_working_directory_full_path(), "/");
+    }
+
+    #[test]
+    fn a_formatted_disk_starts_at_the_root() {
+        let mut disk = TestDisk::new();
+        disk.mkdir("docs").unwrap();
+        disk.cd("docs").unwrap();
+
+        disk.pull_sync();
+        disk.format_disk(&FormatRequest::new(16, None, true, false))
+            .unwrap();
+
+        assert_eq!(disk.reopen().get_working_directory_full_path(), "/");
+    }
+
+    #[test]
+    fn pull_sync_reconstructs_a_deeply_nested_directory_tree() {
+        const DEPTH: usize = 500;
+
+        // every level is created by a command of its own, i.e. `mkdir` followed by `cd` once the disk is reloaded
+        let mut disk = TestDisk::new();
+        for level in 0..DEPTH {
+            disk.run(|disk_manager| {
+                let name = format!("d{}", level);
+                disk_manager.make_directory(&MakeDirectoryRequest::new(
+                    name.clone(),
+                    FileEntryAttributes::combine(&[
+                        FileEntryAttributes::Directory,
+                        FileEntryAttributes::ReadWrite,
+                        FileEntryAttributes::Visible,
+                    ]),
+                    Utc::now(),
+                ))?;
+                disk_manager.change_working_directory(&ChangeDirectoryRequest::new(name))
+            })
+            .unwrap();
+        }
+        disk.write("leaf", "txt", "bottom").unwrap();
+
+        // go back to the root at once, as `cd /` walks up (and reloads the disk) one level at a time
+        disk.working_directory = FileEntry::root();
+        disk.push_sync();
+
+        // a fresh disk manager reconstructs the whole tree from the storage file
+        let mut disk_manager = disk.reopen();
+        disk_manager.pull_sync();
+
+        let mut directory = disk_manager
+            .root
+            .iter()
+            .find(|file_entry| file_entry.matches_directory("d0"))
+            .unwrap();
+        for level in 1..DEPTH {
+            directory = directory
+                .children_entries
+                .as_ref()
+                .unwrap()
+                .iter()
+                .find(|file_entry| file_entry.matches_directory(&format!("d{}", level)))
+                .unwrap();
+        }
+        let leaf = directory
+            .children_entries
+            .as_ref()
+            .unwrap()
+            .iter()
+            .find(|file_entry| file_entry.matches_file("leaf", "txt"))
+            .unwrap();
+
+        let path = DiskManager::get_path_from_root_to_entry(leaf);
+        assert_eq!(path.len(), DEPTH + 2);
+        assert_eq!(path[DEPTH], format!("d{}", DEPTH - 1));
+        assert_eq!(disk_manager.read_entry_bytes(leaf), b"bottom");
+    }
 }
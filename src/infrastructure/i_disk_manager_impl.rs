@@ -1,33 +1,118 @@
+use crate::application::commands::badblocks::BadBlocksRequest;
 use crate::application::commands::cd::ChangeDirectoryRequest;
 use crate::application::commands::cp::CopyRequest;
 use crate::application::commands::create::CreateRequest;
 use crate::application::commands::del::DeleteRequest;
+use crate::application::commands::echo::EchoRequest;
 use crate::application::commands::fmt::FormatRequest;
+use crate::application::commands::label::LabelRequest;
+use crate::application::commands::ln::LinkRequest;
 use crate::application::commands::mkdir::MakeDirectoryRequest;
 use crate::application::commands::rename::RenameRequest;
+use crate::application::commands::resize::ResizeRequest;
+use crate::application::commands::rmdir::RemoveDirectoryRequest;
 use crate::application::commands::setattr::SetAttributesRequest;
+use crate::application::commands::truncate::TruncateRequest;
+use crate::application::commands::writecluster::WriteClusterRequest;
 use crate::application::queries::cat::CatRequest;
 use crate::application::queries::ls::ListRequest;
+use crate::application::queries::stat::StatRequest;
 use crate::application::Void;
 use crate::core::content_type::{ContentGenerator, ContentType};
 use crate::core::filter_type::FilterType;
+use crate::core::glob_pattern::GlobPattern;
 use crate::core::sort_type::SortType;
 use crate::domain::boot_sector::BootSector;
+use crate::domain::consistency_error::ConsistencyError;
 use crate::domain::fat::FatValue;
-use crate::domain::file_entry::{FileEntry, FileEntryAttributes, RootTable};
+use crate::domain::file_entry::{
+    FileEntry, FileEntryAttributes, FileEntryAttributesFlags, RootTable,
+};
+use crate::domain::frag_report::FragReport;
 use crate::domain::i_disk_manager::IDiskManager;
 use crate::infrastructure::disk_manager::DiskManager;
-use crate::CONFIG_ARC;
+use crate::infrastructure::ByteArray;
 use chrono::Utc;
+use std::collections::{BTreeSet, HashMap, HashSet};
 use std::error::Error;
+use std::time::Instant;
 
 impl IDiskManager for DiskManager {
     fn push_sync(&mut self) {
-        self.sync_to_file();
+        // a read-only disk never writes its storage file (the working directory recorded in it is left as is)
+        if self.read_only {
+            return;
+        }
+
+        match self.autosave {
+            true => {
+                self.sync_to_buffer();
+                if !self.dirty_cluster_indices.is_empty() {
+                    self.last_unsaved_push = Some(Instant::now());
+                }
+            }
+            false => self.sync_to_file(),
+        }
+    }
+
+    fn push_sync_all(&mut self) {
+        if !self.read_only {
+            self.sync_all_to_file();
+            self.last_unsaved_push = None;
+        }
+    }
+
+    fn flush(&mut self) {
+        if self.last_unsaved_push.take().is_some() {
+            self.write_dirty_clusters_to_file();
+        }
+    }
+
+    fn get_last_unsaved_push(&self) -> Option<Instant> {
+        self.last_unsaved_push
     }
 
     fn pull_sync(&mut self) {
         self.sync_from_buffer(false);
+
+        // the first pull of a command keeps the state of the disk before the command
+        if self.pre_command_buffer.is_none() {
+            self.pre_command_buffer = Some(self.storage_buffer.clone());
+        }
+    }
+
+    fn snapshot(&mut self) {
+        if let Some(pre_command_buffer) = self.pre_command_buffer.take() {
+            let modified_clusters = self.collect_modified_clusters(pre_command_buffer);
+            if !modified_clusters.is_empty() {
+                self.undo_snapshot = Some(modified_clusters);
+                self.redo_snapshot = None;
+            }
+        }
+    }
+
+    fn restore_snapshot(&mut self) -> Result<bool, Box<dyn Error>> {
+        self.ensure_writable()?;
+
+        let undo_snapshot = match self.undo_snapshot.take() {
+            Some(undo_snapshot) => undo_snapshot,
+            None => return Ok(false),
+        };
+        self.redo_snapshot = Some(self.swap_snapshot_clusters(undo_snapshot));
+
+        Ok(true)
+    }
+
+    fn reapply_snapshot(&mut self) -> Result<bool, Box<dyn Error>> {
+        self.ensure_writable()?;
+
+        let redo_snapshot = match self.redo_snapshot.take() {
+            Some(redo_snapshot) => redo_snapshot,
+            None => return Ok(false),
+        };
+        self.undo_snapshot = Some(self.swap_snapshot_clusters(redo_snapshot));
+
+        Ok(true)
     }
 
     fn pull_boot_sector_sync(&mut self) {
@@ -35,13 +120,13 @@ impl IDiskManager for DiskManager {
     }
 
     fn create_file(&mut self, request: &CreateRequest) -> Void {
+        self.ensure_writable()?;
+
         // check if file already exists in root
         if self
             .get_root_table_for_working_directory()
             .iter()
-            .any(|file_entry| {
-                file_entry.name == request.name && file_entry.extension == request.extension
-            })
+            .any(|file_entry| file_entry.name_matches(&request.name, &request.extension))
         {
             return Err(Box::try_from(format!(
                 "File {}.{} already exists",
@@ -50,46 +135,39 @@ impl IDiskManager for DiskManager {
             .unwrap());
         }
 
-        // check if there is enough space in root
-        if self.working_directory.is_root()
-            && self
-                .root
-                .iter()
-                .all(|file_entry| !file_entry.name.is_empty())
-        {
-            return Err(Box::try_from("No space in root".to_string()).unwrap());
-        }
+        // check if there is room for the entry in the working directory
+        self.check_directory_capacity()?;
 
-        // check if there is enough space in fat
-        let required_clusters =
-            (request.size as f64 / self.boot_sector.cluster_size as f64).ceil() as usize;
-        if self
-            .fat
-            .iter()
-            .filter(|&fat_value| *fat_value == FatValue::Free)
-            .count()
-            < required_clusters
-        {
+        // check if there is enough space in fat (for both the content and the long name, if any)
+        let required_clusters = (request.size as f64 / self.boot_sector.cluster_size as f64).ceil()
+            as usize
+            + self.get_long_name_clusters_count(&request.name);
+        if self.get_free_clusters_count() < required_clusters {
             return Err(Box::try_from("No space in fat".to_string()).unwrap());
         }
 
-        // find a free cluster in fat
-        let first_cluster = self.get_next_free_cluster_index_gt(0).unwrap();
-
-        // create file entry in root
-        let file_entry = FileEntry::new(
-            request.name.to_owned(),
-            request.extension.to_owned(),
-            request.size.to_owned(),
-            first_cluster as u16,
-            request.attributes,
-            request.last_modification_datetime,
-            Some(Box::new(self.working_directory.clone())),
-            None,
-        );
+        // find a free cluster in fat (the empty files don't get any)
+        let first_cluster = match request.size {
+            0 => FileEntry::NO_CLUSTER as usize,
+            _ => self.get_next_free_cluster_index_gt(0).unwrap(),
+        };
+
+        // create file entry in root (a new file is always archived, as it has never been backed up)
+        let file_entry = FileEntry::builder(&request.name)
+            .extension(&request.extension)
+            .size(request.size)
+            .first_cluster(first_cluster as u16)
+            .attributes(request.attributes | FileEntryAttributesFlags::Archive as u8)
+            .last_modification_datetime(request.last_modification_datetime)
+            .parent(&self.working_directory)
+            .build()?;
 
         // update fat and storage
-        let mut file_data = ContentGenerator::generate(request.content_type, request.size);
+        let mut file_data = ContentGenerator::generate(
+            request.content_type.clone(),
+            request.size,
+            &self.config.lock().unwrap(),
+        );
         self.write_data_to_disk(&file_entry, &mut file_data)?;
 
         // update the root table
@@ -107,15 +185,35 @@ impl IDiskManager for DiskManager {
             .cloned()
             .collect();
 
-        // apply filters if any
-        if !request.filters.is_empty() {
+        // apply filters if any (the recursive and json flags are handled by the caller, so they are not filters per se)
+        if request
+            .filters
+            .iter()
+            .any(|filter| !matches!(filter, FilterType::Recursive | FilterType::Json))
+        {
             file_entries.retain(|file_entry| {
                 request.filters.iter().all(|filter| match filter {
-                    FilterType::Name(name) => file_entry.name == *name,
-                    FilterType::Extension(extension) => file_entry.extension == *extension,
+                    FilterType::Name(name) => file_entry.name.eq_ignore_ascii_case(name),
+                    FilterType::Extension(extension) => {
+                        file_entry.extension.eq_ignore_ascii_case(extension)
+                    }
+                    FilterType::Glob(pattern) => {
+                        GlobPattern::new(pattern).is_match(&file_entry.get_full_name())
+                    }
                     FilterType::Files => file_entry.is_file(),
                     FilterType::Directories => !file_entry.is_file(),
                     FilterType::All => !file_entry.is_hidden(),
+                    FilterType::MinSize(min_size) => file_entry.size >= *min_size,
+                    FilterType::MaxSize(max_size) => file_entry.size <= *max_size,
+                    FilterType::After(datetime) => {
+                        file_entry.last_modification_datetime >= *datetime
+                    }
+                    FilterType::Before(datetime) => {
+                        file_entry.last_modification_datetime < *datetime
+                    }
+                    FilterType::ReadOnly => file_entry.is_read_only(),
+                    FilterType::Hidden => file_entry.is_hidden(),
+                    FilterType::System => file_entry.is_system(),
                     _ => true,
                 })
             });
@@ -151,6 +249,20 @@ impl IDiskManager for DiskManager {
                 SortType::SizeDesc => {
                     file_entries.sort_by(|a, b| b.size.cmp(&a.size));
                 }
+                SortType::ExtAsc => {
+                    file_entries.sort_by(|a, b| {
+                        a.extension
+                            .cmp(&b.extension)
+                            .then_with(|| a.name.cmp(&b.name))
+                    });
+                }
+                SortType::ExtDesc => {
+                    file_entries.sort_by(|a, b| {
+                        b.extension
+                            .cmp(&a.extension)
+                            .then_with(|| a.name.cmp(&b.name))
+                    });
+                }
             }
         }
 
@@ -158,154 +270,98 @@ impl IDiskManager for DiskManager {
     }
 
     fn rename_file(&mut self, request: &RenameRequest) -> Void {
-        match self.working_directory.is_root() {
-            false => {
-                // check if the old file exists in root table
-                if !self
-                    .get_root_table_for_working_directory()
-                    .iter()
-                    .any(|file_entry| {
-                        file_entry.name == request.old_name
-                            && file_entry.extension == request.old_extension
-                    })
-                {
-                    let error_message = match request.old_extension.is_empty() {
-                        true => format!("Directory {} does not exist", request.old_name),
-                        false => format!(
-                            "File {}.{} does not exist",
-                            request.old_name, request.old_extension
-                        ),
-                    };
-
-                    return Err(Box::try_from(error_message).unwrap());
-                }
+        self.ensure_writable()?;
 
-                // check if a file with the new name already exists in root table
-                if self
-                    .get_root_table_for_working_directory()
-                    .iter()
-                    .any(|file_entry| {
-                        file_entry.name == request.new_name
-                            && file_entry.extension == request.new_extension
-                    })
-                {
-                    let error_message = match request.new_extension.is_empty() {
-                        true => format!("Directory {} already exists", request.new_name),
-                        false => format!(
-                            "File {}.{} already exists",
-                            request.new_name, request.new_extension
-                        ),
-                    };
-
-                    return Err(Box::try_from(error_message).unwrap());
-                }
+        // check if the old file exists in the root table of the working directory
+        if !self
+            .get_root_table_for_working_directory()
+            .iter()
+            .any(|file_entry| file_entry.name_matches(&request.old_name, &request.old_extension))
+        {
+            let error_message = match request.old_extension.is_empty() {
+                true => format!("Directory {} does not exist", request.old_name),
+                false => format!(
+                    "File {}.{} does not exist",
+                    request.old_name, request.old_extension
+                ),
+            };
+
+            return Err(Box::try_from(error_message).unwrap());
+        }
 
-                // get the index of the file entry in root table
-                let root_table = self.get_root_table_for_working_directory();
+        // check if a file with the new name already exists in the root table of the working directory
+        // (changing only the case of the old name is allowed)
+        if self
+            .get_root_table_for_working_directory()
+            .iter()
+            .any(|file_entry| {
+                file_entry.name_matches(&request.new_name, &request.new_extension)
+                    && !file_entry.name_matches(&request.old_name, &request.old_extension)
+            })
+        {
+            let error_message = match request.new_extension.is_empty() {
+                true => format!("Directory {} already exists", request.new_name),
+                false => format!(
+                    "File {}.{} already exists",
+                    request.new_name, request.new_extension
+                ),
+            };
 
-                let file_entry_index = root_table
-                    .iter()
-                    .position(|file_entry| {
-                        file_entry.name == request.old_name
-                            && file_entry.extension == request.old_extension
-                    })
-                    .unwrap();
+            return Err(Box::try_from(error_message).unwrap());
+        }
 
-                // check if the file is read only
-                if root_table[file_entry_index].is_read_only() {
-                    let error_message = match request.old_extension.is_empty() {
-                        true => format!("Directory {} is read only", request.old_name),
-                        false => format!(
-                            "File {}.{} is read only",
-                            request.old_name, request.old_extension
-                        ),
-                    };
-
-                    return Err(Box::try_from(error_message).unwrap());
-                }
+        // get the index of the file entry in the root table of the working directory
+        let root_table = self.get_root_table_for_working_directory();
 
-                // rename the file in root table
-                root_table[file_entry_index].name = request.new_name.to_owned();
-                root_table[file_entry_index].extension = request.new_extension.to_owned();
-                root_table[file_entry_index].last_modification_datetime = Utc::now();
+        let file_entry_index = root_table
+            .iter()
+            .position(|file_entry| {
+                file_entry.name_matches(&request.old_name, &request.old_extension)
+            })
+            .unwrap();
 
-                self.sync_directory_root_table_to_storage(&self.working_directory.clone());
-            }
-            true => {
-                // check if the old file exists in root table
-                if !self.root.iter().any(|file_entry| {
-                    file_entry.name == request.old_name
-                        && file_entry.extension == request.old_extension
-                }) {
-                    let error_message = match request.old_extension.is_empty() {
-                        true => format!("Directory {} does not exist", request.old_name),
-                        false => format!(
-                            "File {}.{} does not exist",
-                            request.old_name, request.old_extension
-                        ),
-                    };
-
-                    return Err(Box::try_from(error_message).unwrap());
-                }
+        // check if the file is read only
+        if root_table[file_entry_index].is_read_only() {
+            let error_message = match request.old_extension.is_empty() {
+                true => format!("Directory {} is read only", request.old_name),
+                false => format!(
+                    "File {}.{} is read only",
+                    request.old_name, request.old_extension
+                ),
+            };
 
-                // check if a file with the new name already exists in root table
-                if self.root.iter().any(|file_entry| {
-                    file_entry.name == request.new_name
-                        && file_entry.extension == request.new_extension
-                }) {
-                    let error_message = match request.new_extension.is_empty() {
-                        true => format!("Directory {} already exists", request.new_name),
-                        false => format!(
-                            "File {}.{} already exists",
-                            request.new_name, request.new_extension
-                        ),
-                    };
-
-                    return Err(Box::try_from(error_message).unwrap());
-                }
+            return Err(Box::try_from(error_message).unwrap());
+        }
 
-                // get the index of the file entry in root table
-                let file_entry_index = self
-                    .root
-                    .iter()
-                    .position(|file_entry| {
-                        file_entry.name == request.old_name
-                            && file_entry.extension == request.old_extension
-                    })
-                    .unwrap();
+        // rename the file in the root table, storing the new long name (if any) before freeing the old one
+        let old_file_entry = root_table[file_entry_index].clone();
+        let mut file_entry = old_file_entry.clone();
+        file_entry.name = request.new_name.to_owned();
+        file_entry.extension = request.new_extension.to_owned();
+        file_entry.mark_as_modified();
 
-                // check if the file is read only
-                if self.root[file_entry_index].is_read_only() {
-                    let error_message = match request.old_extension.is_empty() {
-                        true => format!("Directory {} is read only", request.old_name),
-                        false => format!(
-                            "File {}.{} is read only",
-                            request.old_name, request.old_extension
-                        ),
-                    };
-
-                    return Err(Box::try_from(error_message).unwrap());
-                }
+        self.allocate_long_name(&mut file_entry)?;
+        self.free_long_name(&old_file_entry);
+        self.get_root_table_for_working_directory()[file_entry_index] = file_entry;
 
-                // rename the file in root table
-                self.root[file_entry_index].name = request.new_name.to_owned();
-                self.root[file_entry_index].extension = request.new_extension.to_owned();
-                self.root[file_entry_index].last_modification_datetime = Utc::now();
-            }
+        // the root table is not written back here: it is written (along with the FAT) on every sync, so a rename
+        // in the root is persisted by the `push_sync` following it, like any other modification of the disk,
+        // while the table of a subdirectory lives in its own clusters and has to be written back to the storage buffer
+        if !self.working_directory.is_root() {
+            self.sync_directory_root_table_to_storage(&self.working_directory.clone())?;
         }
 
         Ok(())
     }
 
     fn delete_file(&mut self, request: &DeleteRequest) -> Void {
+        self.ensure_writable()?;
+
         // check if the file exists in the root table of the working directory
         if !self
             .get_root_table_for_working_directory()
             .iter()
-            .any(|file_entry| {
-                file_entry.name == request.file_name
-                    && file_entry.extension == request.file_extension
-            })
+            .any(|file_entry| file_entry.name_matches(&request.file_name, &request.file_extension))
         {
             let error_message = match request.file_extension.is_empty() {
                 true => format!("Directory {} does not exist", request.file_name),
@@ -321,10 +377,7 @@ impl IDiskManager for DiskManager {
         let file_entry = self
             .get_root_table_for_working_directory()
             .iter()
-            .find(|file_entry| {
-                file_entry.name == request.file_name
-                    && file_entry.extension == request.file_extension
-            })
+            .find(|file_entry| file_entry.name_matches(&request.file_name, &request.file_extension))
             .cloned()
             .unwrap();
 
@@ -342,9 +395,17 @@ impl IDiskManager for DiskManager {
                     continue;
                 }
 
-                let delete_request =
-                    DeleteRequest::new(file_entry.name.to_owned(), file_entry.extension.to_owned());
+                let delete_request = DeleteRequest::new(
+                    file_entry.name.to_owned(),
+                    file_entry.extension.to_owned(),
+                    true,
+                    false,
+                    false,
+                );
                 self.delete_file(&delete_request)?;
+
+                // push the clusters freed along with the file entry, as the next pull reloads the FAT table
+                self.push_sync();
             }
 
             // change working directory back
@@ -361,22 +422,237 @@ impl IDiskManager for DiskManager {
         // sync the working directory root table to storage iff the working directory is not root
         // as by default the root table is synced to storage on every push
         if !self.working_directory.is_root() {
-            self.sync_directory_root_table_to_storage(&self.working_directory.clone());
+            self.sync_directory_root_table_to_storage(&self.working_directory.clone())?;
         }
 
         Ok(())
     }
 
-    fn get_file_content(&mut self, request: &CatRequest) -> Result<String, Box<dyn Error>> {
+    fn move_to_trash(&mut self, request: &DeleteRequest) -> Void {
+        self.ensure_writable()?;
+
+        // deleting from the trash itself is permanent
+        if self.is_working_directory_in_trash() {
+            return self.delete_file(request);
+        }
+
+        // get the file entry from the root table of the working directory
+        let file_entry = match self
+            .get_root_table_for_working_directory()
+            .iter()
+            .find(|file_entry| file_entry.name_matches(&request.file_name, &request.file_extension))
+            .cloned()
+        {
+            Some(file_entry) => file_entry,
+            None => {
+                let error_message = match request.file_extension.is_empty() {
+                    true => format!("Directory {} does not exist", request.file_name),
+                    false => format!(
+                        "File {}.{} does not exist",
+                        request.file_name, request.file_extension
+                    ),
+                };
+                return Err(Box::try_from(error_message).unwrap());
+            }
+        };
+
+        self.ensure_trash_directory()?;
+
+        // permanently delete a previously trashed entry with the same name
+        let trash_path = format!("/{}", DiskManager::TRASH_DIRECTORY_NAME);
+        let working_directory_path = self.get_working_directory_full_path();
+        self.change_working_directory_along_path(&trash_path)?;
+        let result = match self
+            .get_root_table_for_working_directory()
+            .iter()
+            .any(|trashed_entry| {
+                trashed_entry.name_matches(&file_entry.name, &file_entry.extension)
+            }) {
+            true => self.delete_file(&DeleteRequest::new(
+                file_entry.name.clone(),
+                file_entry.extension.clone(),
+                true,
+                false,
+                false,
+            )),
+            false => Ok(()),
+        };
+        self.push_sync();
+        self.pull_sync();
+        self.change_working_directory_along_path(&working_directory_path)?;
+        result?;
+
+        // the links of a file must share its directory to be kept in sync, so a file which is still linked
+        // is copied into the trash and only this link is removed
+        if file_entry.is_file() && self.get_link_count(&file_entry) > 1 {
+            self.write_to_temp_buffer(&self.read_entry_bytes(&file_entry))?;
+
+            self.change_working_directory_along_path(&trash_path)?;
+            let result = self.create_file(&CreateRequest::new(
+                file_entry.name.clone(),
+                file_entry.extension.clone(),
+                file_entry.size,
+                file_entry.attributes,
+                file_entry.last_modification_datetime,
+                ContentType::Temp,
+            ));
+            self.push_sync();
+            self.pull_sync();
+            self.change_working_directory_along_path(&working_directory_path)?;
+            result?;
+
+            return self.delete_file(&DeleteRequest::new(
+                file_entry.name.clone(),
+                file_entry.extension.clone(),
+                true,
+                false,
+                false,
+            ));
+        }
+
+        // reparent the file entry (and its subtree) without copying its data
+        self.move_file_entry(&file_entry, &trash_path)
+    }
+
+    fn restore_from_trash(&mut self, name: &str, extension: &str) -> Void {
+        self.ensure_writable()?;
+
+        if self.is_working_directory_in_trash() {
+            return Err(Box::try_from("Cannot restore an entry into the trash").unwrap());
+        }
+
+        let full_name = match extension.is_empty() {
+            true => name.to_string(),
+            false => format!("{}.{}", name, extension),
+        };
+
+        // get the file entry from the trash
+        let file_entry = match self
+            .list_trash()
+            .into_iter()
+            .find(|file_entry| file_entry.name_matches(name, extension))
+        {
+            Some(file_entry) => file_entry,
+            None => {
+                return Err(Box::try_from(format!("{} is not in the trash", full_name)).unwrap())
+            }
+        };
+
+        // check that the working directory has no entry with the same name
+        if self
+            .get_root_table_for_working_directory()
+            .iter()
+            .any(|existing_entry| existing_entry.name_matches(name, extension))
+        {
+            return Err(Box::try_from(format!("{} already exists", full_name)).unwrap());
+        }
+
+        // check that the working directory has room for the entry
+        self.check_directory_capacity()?;
+
+        // move the file entry from the trash back to the working directory
+        let working_directory_path = self.get_working_directory_full_path();
+        self.change_working_directory_along_path(&format!(
+            "/{}",
+            DiskManager::TRASH_DIRECTORY_NAME
+        ))?;
+        let result = self.move_file_entry(&file_entry, &working_directory_path);
+        self.change_working_directory_along_path(&working_directory_path)?;
+
+        result
+    }
+
+    fn list_trash(&self) -> RootTable {
+        self.root
+            .iter()
+            .find(|file_entry| file_entry.matches_directory(DiskManager::TRASH_DIRECTORY_NAME))
+            .and_then(|trash_entry| trash_entry.children_entries.clone())
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|file_entry| !file_entry.name.is_empty())
+            .filter(|file_entry| file_entry.name != "." && file_entry.name != "..")
+            .collect()
+    }
+
+    fn empty_trash(&mut self) -> Void {
+        self.ensure_writable()?;
+
+        if self.is_working_directory_in_trash() {
+            return Err(Box::try_from("Cannot empty the trash from inside it").unwrap());
+        }
+
+        if !self
+            .root
+            .iter()
+            .any(|file_entry| file_entry.matches_directory(DiskManager::TRASH_DIRECTORY_NAME))
+        {
+            return Ok(());
+        }
+
+        // delete the trash directory from the root, along with its content
+        let working_directory_path = self.get_working_directory_full_path();
+        self.change_working_directory_to_root()?;
+        let result = self.delete_file(&DeleteRequest::new(
+            DiskManager::TRASH_DIRECTORY_NAME.to_string(),
+            "".to_string(),
+            true,
+            false,
+            false,
+        ));
+        self.push_sync();
+        self.pull_sync();
+        self.change_working_directory_along_path(&working_directory_path)?;
+
+        result
+    }
+
+    fn remove_empty_directory(&mut self, request: &RemoveDirectoryRequest) -> Void {
+        self.ensure_writable()?;
+
+        // check if the directory exists in the root table of the working directory
+        if !self
+            .get_root_table_for_working_directory()
+            .iter()
+            .any(|file_entry| file_entry.matches_directory(&request.name))
+        {
+            return Err(
+                Box::try_from(format!("Directory {} does not exist", request.name)).unwrap(),
+            );
+        }
+
+        // change working directory to the folder and check that it holds only `.` and `..`
+        let cd_request = ChangeDirectoryRequest::new(request.name.to_owned());
+        self.change_working_directory(&cd_request)?;
+        let is_empty = self
+            .get_root_table_for_working_directory()
+            .iter()
+            .all(|file_entry| file_entry.name == "." || file_entry.name == "..");
+
+        // change working directory back
+        let cd_request = ChangeDirectoryRequest::new("..".to_owned());
+        self.pull_sync();
+        self.change_working_directory(&cd_request)?;
+        self.pull_sync();
+
+        if !is_empty {
+            return Err(Box::try_from(format!("Directory {} is not empty", request.name)).unwrap());
+        }
+
+        self.delete_file(&DeleteRequest::new(
+            request.name.to_owned(),
+            "".to_owned(),
+            true,
+            false,
+            false,
+        ))
+    }
+
+    fn read_file_bytes(&mut self, request: &CatRequest) -> Result<Vec<u8>, Box<dyn Error>> {
         // check if the file exists in the working directory
         if !self
             .get_root_table_for_working_directory()
             .iter()
-            .any(|file_entry| {
-                file_entry.name == request.file_name
-                    && file_entry.extension == request.file_extension
-                    && file_entry.is_file()
-            })
+            .any(|file_entry| file_entry.matches_file(&request.file_name, &request.file_extension))
         {
             return Err(Box::try_from(format!(
                 "File {}.{} does not exist",
@@ -390,22 +666,42 @@ impl IDiskManager for DiskManager {
             .get_root_table_for_working_directory()
             .iter()
             .find(|&file_entry| {
-                file_entry.name == request.file_name
-                    && file_entry.extension == request.file_extension
-                    && file_entry.is_file()
+                file_entry.matches_file(&request.file_name, &request.file_extension)
             })
             .cloned()
             .unwrap();
 
+        let content = self.read_entry_bytes(&file_entry);
+
+        // update the last access date of the file entry
+        self.get_root_table_for_working_directory()
+            .iter_mut()
+            .find(|file_entry| file_entry.matches_file(&request.file_name, &request.file_extension))
+            .unwrap()
+            .mark_as_accessed();
+
+        if !self.working_directory.is_root() {
+            self.sync_directory_root_table_to_storage(&self.working_directory.clone())?;
+        }
+
+        Ok(content)
+    }
+
+    fn read_entry_bytes(&self, file_entry: &FileEntry) -> Vec<u8> {
+        // the empty files have no allocation chain to read from
+        if !file_entry.has_clusters() {
+            return Vec::new();
+        }
+
         // iterate through the cluster chain and read the file content from the storage buffer
-        let mut content = String::new();
+        let mut content: Vec<u8> = Vec::new();
         let mut current_cluster = file_entry.first_cluster as usize;
 
         while self.fat[current_cluster] != FatValue::EndOfChain {
             // push the content of the current cluster to the content string
-            content.push_str(&String::from_utf8_lossy(
+            content.extend_from_slice(
                 &self.storage_buffer[current_cluster][..self.boot_sector.cluster_size as usize],
-            ));
+            );
 
             // get the next cluster index
             let next_cluster_index: u16 = self.fat[current_cluster].clone().into();
@@ -423,155 +719,521 @@ impl IDiskManager for DiskManager {
             remaining_content_size += self.boot_sector.cluster_size as usize;
         }
 
-        content.push_str(&String::from_utf8_lossy(
-            &self.storage_buffer[current_cluster][..remaining_content_size],
-        ));
+        content.extend_from_slice(&self.storage_buffer[current_cluster][..remaining_content_size]);
 
-        Ok(content)
+        content
     }
 
-    fn copy_file(&mut self, request: &CopyRequest) -> Void {
-        // check if the src file exists in the working directory root table
-        if !self
-            .get_root_table_for_working_directory()
-            .iter()
-            .any(|file_entry| {
-                file_entry.name == request.src_name && file_entry.extension == request.src_extension
-            })
-        {
-            return Err(Box::try_from(format!(
-                "File {}.{} does not exist",
-                request.src_name, request.src_extension
-            ))
-            .unwrap());
-        }
+    fn get_file_content(&mut self, request: &CatRequest) -> Result<String, Box<dyn Error>> {
+        let content = self.read_file_bytes(request)?;
 
-        // check if the dest file already exists in the working directory root table
-        if self
-            .get_root_table_for_working_directory()
-            .iter()
-            .any(|file_entry| {
-                file_entry.name == request.dest_name
-                    && file_entry.extension == request.dest_extension
-            })
-        {
-            return Err(Box::try_from(format!(
-                "File {}.{} already exists",
-                request.dest_name, request.dest_extension
-            ))
-            .unwrap());
-        }
+        Ok(String::from_utf8_lossy(&content).to_string())
+    }
 
-        // check if there are empty entries in root when working directory is root
-        if self.working_directory.is_root()
-            && !self
-                .root
-                .iter()
-                .any(|file_entry| file_entry.name.is_empty())
-        {
-            return Err(Box::try_from("No empty entries in root").unwrap());
-        }
+    fn write_file_content(&mut self, request: &EchoRequest) -> Void {
+        self.ensure_writable()?;
+
+        // the size of a file is stored on 32 bits
+        let size = match u32::try_from(request.content.len()) {
+            Ok(size) => size,
+            Err(_) => {
+                return Err(Box::try_from(format!(
+                    "File {}.{} can't hold {} B, a file holds at most {} B",
+                    request.name,
+                    request.extension,
+                    request.content.len(),
+                    u32::MAX
+                ))
+                .unwrap())
+            }
+        };
 
-        // get the src file entry
-        let src_file_entry = self
+        // check if the file already exists in the working directory
+        let file_entry = self
             .get_root_table_for_working_directory()
             .iter()
-            .find(|file_entry| {
-                file_entry.name == request.src_name && file_entry.extension == request.src_extension
-            })
-            .cloned()
-            .unwrap();
-
-        // check if there is enough space in fat
-        let required_clusters =
-            (src_file_entry.size as f32 / self.boot_sector.cluster_size as f32).ceil() as usize;
-
-        if self
-            .fat
-            .iter()
-            .filter(|&fat_value| *fat_value == FatValue::Free)
-            .count()
-            < required_clusters
-        {
-            return Err(Box::try_from("Not enough space in fat").unwrap());
+            .find(|file_entry| file_entry.matches_file(&request.name, &request.extension))
+            .cloned();
+
+        // the allocation chain of a linked file is rewritten in place, so that the other links see the new content
+        if let Some(file_entry) = &file_entry {
+            if self.get_link_count(file_entry) > 1 {
+                self.truncate_file(&TruncateRequest::new(
+                    request.name.clone(),
+                    request.extension.clone(),
+                    0,
+                ))?;
+                return self.append_to_file(request);
+            }
         }
 
-        match src_file_entry.is_file() {
-            true => {
-                // create the dest file entry
-                let dest_file_first_cluster =
-                    self.get_next_free_cluster_index_gt(0).unwrap() as u16;
-                let dest_file_entry = FileEntry::new(
-                    request.dest_name.to_owned(),
-                    request.dest_extension.to_owned(),
-                    src_file_entry.size,
-                    dest_file_first_cluster,
-                    src_file_entry.attributes,
-                    Utc::now(),
-                    src_file_entry.parent_entry.clone(),
-                    src_file_entry.children_entries.clone(),
-                );
-
-                // iterate through the cluster chain and copy the file content from the storage buffer
-                let mut current_src_cluster_index = src_file_entry.first_cluster as usize;
-                let mut current_dest_cluster_index = dest_file_entry.first_cluster as usize;
+        // write the content to the temp buffer file (before anything is freed, as writing it may fail)
+        self.write_to_temp_buffer(request.content.as_bytes())?;
+
+        // if the file exists, free its cluster chain and its entry while preserving its attributes
+        // otherwise, create a new read-write visible file
+        let attributes = match file_entry {
+            Some(file_entry) => {
+                if file_entry.is_read_only() {
+                    return Err(Box::try_from(format!(
+                        "File {}.{} is read only",
+                        request.name, request.extension
+                    ))
+                    .unwrap());
+                }
 
-                while self.fat[current_src_cluster_index] != FatValue::EndOfChain {
-                    // copy the content of the current cluster in src to the current cluster in dest
-                    self.storage_buffer[current_dest_cluster_index] =
-                        self.storage_buffer[current_src_cluster_index].clone();
+                // the new content must fit in the free clusters along with the ones of the old content
+                // and the recreated entry in the directory once the old one is freed,
+                // which is checked before anything is freed so that a failed overwrite keeps the file
+                let required_clusters =
+                    (size as f64 / self.boot_sector.cluster_size as f64).ceil() as usize;
+                let released_clusters = match file_entry.has_clusters() {
+                    true => self
+                        .get_chain_clusters(file_entry.first_cluster as usize)
+                        .len(),
+                    false => 0,
+                };
+                if self.get_free_clusters_count() + released_clusters < required_clusters {
+                    return Err(Box::try_from("No space in fat".to_string()).unwrap());
+                }
 
-                    // get the index of the next free cluster for dest
-                    let next_dest_cluster_index: u16 = self
-                        .get_next_free_cluster_index_gt(current_dest_cluster_index)
-                        .unwrap() as u16;
+                self.check_directory_capacity_releasing(1)?;
 
-                    // mark the current dest cluster as used and point it to the next dest cluster
-                    self.fat[current_dest_cluster_index] = FatValue::Data(next_dest_cluster_index);
-                    current_dest_cluster_index = next_dest_cluster_index as usize;
+                self.free_clusters(&file_entry);
+                self.free_file_entry(&file_entry);
 
-                    // advance to the next src cluster as well
-                    let next_src_cluster_index: u16 =
-                        self.fat[current_src_cluster_index].clone().into();
-                    current_src_cluster_index = next_src_cluster_index as usize;
+                if !self.working_directory.is_root() {
+                    self.sync_directory_root_table_to_storage(&self.working_directory.clone())?;
                 }
 
-                // copy the content of the last cluster in src to the last cluster in dest
-                // and mark the last dest cluster as end of chain
-                self.storage_buffer[current_dest_cluster_index] =
-                    self.storage_buffer[current_src_cluster_index].clone();
-                self.fat[current_dest_cluster_index] = FatValue::EndOfChain;
-
-                // append the dest file entry to the root table of the working directory
-                self.append_to_root_table_of_working_dir(dest_file_entry)?;
+                // the content is rewritten, so the archive bit is set
+                file_entry.attributes | FileEntryAttributesFlags::Archive as u8
             }
-            false => {
-                // create the directory entry
-                let make_directory_request = MakeDirectoryRequest::new(
-                    request.dest_name.clone(),
-                    src_file_entry.attributes,
-                    Utc::now(),
-                );
-                self.make_directory(&make_directory_request)?;
-                // update the disk
-                self.push_sync();
-
-                // iterate over the src directory's root table and recreate the dir tree in the dest directory
-                self.inflate_directory_tree_inline(&src_file_entry, request.dest_name.clone())?;
+            None => FileEntryAttributes::combine(&[
+                FileEntryAttributes::File,
+                FileEntryAttributes::ReadWrite,
+                FileEntryAttributes::Visible,
+                FileEntryAttributes::Archive,
+            ]),
+        };
+
+        // recreate the file entry filled with the content from the temp buffer file
+        let create_request = CreateRequest::new(
+            request.name.clone(),
+            request.extension.clone(),
+            size,
+            attributes,
+            Utc::now(),
+            ContentType::Temp,
+        );
+        self.create_file(&create_request)
+    }
+
+    fn append_to_file(&mut self, request: &EchoRequest) -> Void {
+        self.ensure_writable()?;
+
+        // check if the file exists in the working directory, otherwise create it
+        let file_entry = self
+            .get_root_table_for_working_directory()
+            .iter()
+            .find(|file_entry| file_entry.matches_file(&request.name, &request.extension))
+            .cloned();
+
+        let file_entry = match file_entry {
+            Some(file_entry) => file_entry,
+            None => return self.write_file_content(request),
+        };
+
+        if file_entry.is_read_only() {
+            return Err(Box::try_from(format!(
+                "File {}.{} is read only",
+                request.name, request.extension
+            ))
+            .unwrap());
+        }
+
+        // the size of a file is stored on 32 bits
+        let new_size = match u32::try_from(request.content.len())
+            .ok()
+            .and_then(|content_size| file_entry.size.checked_add(content_size))
+        {
+            Some(new_size) => new_size,
+            None => {
+                return Err(Box::try_from(format!(
+                    "File {}.{} can't grow by {} B, a file holds at most {} B",
+                    request.name,
+                    request.extension,
+                    request.content.len(),
+                    u32::MAX
+                ))
+                .unwrap())
+            }
+        };
+
+        // an empty file gets the head of its allocation chain before anything is appended to it
+        let file_entry = match file_entry.has_clusters() || request.content.is_empty() {
+            true => file_entry,
+            false => self.allocate_first_cluster(&file_entry)?,
+        };
+
+        let cluster_size = self.boot_sector.cluster_size as usize;
+        let mut content = request.content.as_bytes().to_vec();
+        let content_size = content.len();
+
+        // compute the number of bytes used from the last cluster and its remaining slack
+        // (a non-empty file whose size is an exact multiple of the cluster size has no slack)
+        let last_cluster_used_size =
+            match (file_entry.size, file_entry.size as usize % cluster_size) {
+                (0, _) => 0,
+                (_, 0) => cluster_size,
+                (_, used_size) => used_size,
+            };
+        let slack_size = cluster_size - last_cluster_used_size;
+
+        // check if there is enough space in fat for the content which doesn't fit in the slack
+        let required_clusters =
+            (content_size.saturating_sub(slack_size) as f64 / cluster_size as f64).ceil() as usize;
+        if self.get_free_clusters_count() < required_clusters {
+            return Err(Box::try_from("No space in fat".to_string()).unwrap());
+        }
+
+        // nothing is appended to an empty file which has no allocation chain
+        if file_entry.has_clusters() {
+            // get to the last cluster in the allocation chain of the file
+            let mut current_cluster_index = file_entry.first_cluster as usize;
+            while self.fat[current_cluster_index] != FatValue::EndOfChain {
+                let next_cluster_index: u16 = self.fat[current_cluster_index].clone().into();
+                current_cluster_index = next_cluster_index as usize;
+            }
+
+            // fill the slack of the last cluster
+            let slack_content: ByteArray = content
+                .drain(..std::cmp::min(slack_size, content_size))
+                .collect();
+            let mut cluster_data = self.storage_buffer[current_cluster_index].clone();
+            cluster_data[last_cluster_used_size..last_cluster_used_size + slack_content.len()]
+                .copy_from_slice(&slack_content);
+            self.set_cluster_data(current_cluster_index, cluster_data);
+
+            // extend the allocation chain with the remaining content
+            while !content.is_empty() {
+                let next_cluster_index = self
+                    .get_next_free_cluster_index_gt(current_cluster_index)
+                    .or_else(|| self.get_next_free_cluster_index_gt(0))
+                    .unwrap();
+
+                let mut cluster_data: ByteArray = content
+                    .drain(..std::cmp::min(cluster_size, content.len()))
+                    .collect();
+                cluster_data.resize(cluster_size, 0);
+                self.set_cluster_data(next_cluster_index, cluster_data);
+
+                // point the current last cluster to the new last cluster
+                self.set_fat_value(
+                    current_cluster_index,
+                    FatValue::Data(next_cluster_index as u16),
+                );
+                self.set_fat_value(next_cluster_index, FatValue::EndOfChain);
+                current_cluster_index = next_cluster_index;
+            }
+        }
+
+        // update the size and the last modification datetime of the file entry
+        let file_entry = self
+            .get_root_table_for_working_directory()
+            .iter_mut()
+            .find(|file_entry| file_entry.matches_file(&request.name, &request.extension))
+            .unwrap();
+        file_entry.size = new_size;
+        file_entry.mark_as_modified();
+        let file_entry = file_entry.clone();
+        self.sync_links(&file_entry);
+
+        // persist the file entry modifications into the storage
+        if !self.working_directory.is_root() {
+            self.sync_directory_root_table_to_storage(&self.working_directory.clone())?;
+        }
+
+        Ok(())
+    }
+
+    fn truncate_file(&mut self, request: &TruncateRequest) -> Void {
+        self.ensure_writable()?;
+
+        // check if the file exists in the working directory
+        let file_entry = self
+            .get_root_table_for_working_directory()
+            .iter()
+            .find(|file_entry| file_entry.matches_file(&request.name, &request.extension))
+            .cloned();
+
+        let file_entry = match file_entry {
+            Some(file_entry) => file_entry,
+            None => {
+                return Err(Box::try_from(format!(
+                    "File {}.{} does not exist",
+                    request.name, request.extension
+                ))
+                .unwrap())
+            }
+        };
+
+        if file_entry.is_read_only() {
+            return Err(Box::try_from(format!(
+                "File {}.{} is read only",
+                request.name, request.extension
+            ))
+            .unwrap());
+        }
+
+        // compute the number of clusters occupied by the file before and after the truncation
+        // (a file always keeps at least the head of its allocation chain, unless it is empty and has none)
+        let cluster_size = self.boot_sector.cluster_size as usize;
+        let clusters_for_size =
+            |size: usize| std::cmp::max(1, (size as f64 / cluster_size as f64).ceil() as usize);
+        let current_clusters = match file_entry.has_clusters() {
+            true => clusters_for_size(file_entry.size as usize),
+            false => 0,
+        };
+        let new_clusters = match file_entry.has_clusters() || request.size > 0 {
+            true => clusters_for_size(request.size as usize),
+            false => 0,
+        };
+
+        // check if there is enough space in fat before allocating anything
+        if new_clusters > current_clusters
+            && self.get_free_clusters_count() < new_clusters - current_clusters
+        {
+            return Err(Box::try_from("No space in fat".to_string()).unwrap());
+        }
+
+        // an empty file gets the head of its allocation chain before growing
+        let (file_entry, current_clusters) = match file_entry.has_clusters() || request.size == 0 {
+            true => (file_entry, current_clusters),
+            false => (self.allocate_first_cluster(&file_entry)?, 1),
+        };
+
+        // an empty file which has no allocation chain stays without one
+        if file_entry.has_clusters() {
+            // walk the allocation chain up to the last cluster which is kept
+            let mut current_cluster_index = file_entry.first_cluster as usize;
+            for _ in 1..std::cmp::min(current_clusters, new_clusters) {
+                let next_cluster_index: u16 = self.fat[current_cluster_index].clone().into();
+                current_cluster_index = next_cluster_index as usize;
+            }
+
+            // zero the bytes of the last kept cluster which are beyond the new size
+            let used_size = std::cmp::min(file_entry.size, request.size) as usize
+                - (std::cmp::min(current_clusters, new_clusters) - 1) * cluster_size;
+            let mut cluster_data = self.storage_buffer[current_cluster_index].clone();
+            cluster_data[used_size..].fill(0);
+            self.set_cluster_data(current_cluster_index, cluster_data);
+
+            if new_clusters < current_clusters {
+                // free the tail of the allocation chain and mark the new last cluster as end of chain
+                let next_cluster_index: u16 = self.fat[current_cluster_index].clone().into();
+                self.set_fat_value(current_cluster_index, FatValue::EndOfChain);
+
+                let mut cluster_index = next_cluster_index as usize;
+                while self.fat[cluster_index] != FatValue::EndOfChain {
+                    let next_cluster_index: u16 = self.fat[cluster_index].clone().into();
+                    self.set_fat_value(cluster_index, FatValue::Free);
+                    cluster_index = next_cluster_index as usize;
+                }
+                self.set_fat_value(cluster_index, FatValue::Free);
+            } else {
+                // extend the allocation chain with clusters filled with zeros
+                for _ in current_clusters..new_clusters {
+                    let next_cluster_index = self
+                        .get_next_free_cluster_index_gt(current_cluster_index)
+                        .or_else(|| self.get_next_free_cluster_index_gt(0))
+                        .unwrap();
+
+                    self.set_cluster_data(next_cluster_index, vec![0; cluster_size]);
+                    self.set_fat_value(
+                        current_cluster_index,
+                        FatValue::Data(next_cluster_index as u16),
+                    );
+                    self.set_fat_value(next_cluster_index, FatValue::EndOfChain);
+                    current_cluster_index = next_cluster_index;
+                }
+            }
+        }
+
+        // update the size and the last modification datetime of the file entry
+        let file_entry = self
+            .get_root_table_for_working_directory()
+            .iter_mut()
+            .find(|file_entry| file_entry.matches_file(&request.name, &request.extension))
+            .unwrap();
+        file_entry.size = request.size;
+        file_entry.mark_as_modified();
+        let file_entry = file_entry.clone();
+        self.sync_links(&file_entry);
+
+        // persist the file entry modifications into the storage
+        if !self.working_directory.is_root() {
+            self.sync_directory_root_table_to_storage(&self.working_directory.clone())?;
+        }
+
+        Ok(())
+    }
+
+    fn copy_file(&mut self, request: &CopyRequest) -> Void {
+        self.ensure_writable()?;
+
+        // check if the src file exists in the working directory root table
+        if !self
+            .get_root_table_for_working_directory()
+            .iter()
+            .any(|file_entry| file_entry.name_matches(&request.src_name, &request.src_extension))
+        {
+            return Err(Box::try_from(format!(
+                "File {}.{} does not exist",
+                request.src_name, request.src_extension
+            ))
+            .unwrap());
+        }
+
+        // get the src file entry
+        let src_file_entry = self
+            .get_root_table_for_working_directory()
+            .iter()
+            .find(|file_entry| file_entry.name_matches(&request.src_name, &request.src_extension))
+            .cloned()
+            .unwrap();
+
+        let src_directory = self.working_directory.clone();
+        let dest_directory = match &request.dest_directory {
+            Some(dest_directory) => dest_directory,
+            None => {
+                return self.copy_entry_into_working_directory(
+                    &src_file_entry,
+                    &src_directory,
+                    request,
+                )
             }
+        };
+
+        // copy the entry from within the dest directory, then go back to the src directory
+        // (the copy is persisted before, as changing the working directory reloads the disk)
+        self.change_working_directory_along_path(dest_directory)?;
+        // reload the disk to link the root table of the dest directory (a parent directory is not linked to it)
+        self.pull_sync();
+        let result =
+            self.copy_entry_into_working_directory(&src_file_entry, &src_directory, request);
+        if result.is_ok() {
+            self.push_sync();
+        }
+        self.change_working_directory_to(&src_directory)?;
+        // record the src directory as the working directory again, as reloading the disk along the way
+        // recorded the directories it went through (even if the copy failed)
+        self.push_sync();
+
+        result
+    }
+
+    fn link_file(&mut self, request: &LinkRequest) -> Void {
+        self.ensure_writable()?;
+
+        // check if the linked file exists in the working directory root table
+        if !self
+            .get_root_table_for_working_directory()
+            .iter()
+            .any(|file_entry| file_entry.name_matches(&request.src_name, &request.src_extension))
+        {
+            return Err(Box::try_from(format!(
+                "File {}.{} does not exist",
+                request.src_name, request.src_extension
+            ))
+            .unwrap());
+        }
+
+        // get the linked file entry
+        let src_file_entry = self
+            .get_root_table_for_working_directory()
+            .iter()
+            .find(|file_entry| file_entry.name_matches(&request.src_name, &request.src_extension))
+            .cloned()
+            .unwrap();
+
+        if !src_file_entry.is_file() {
+            return Err(
+                Box::try_from(format!("Cannot link directory {}", request.src_name)).unwrap(),
+            );
+        }
+
+        // check if the link already exists in the working directory root table
+        if self
+            .get_root_table_for_working_directory()
+            .iter()
+            .any(|file_entry| file_entry.name_matches(&request.link_name, &request.link_extension))
+        {
+            return Err(Box::try_from(format!(
+                "File {}.{} already exists",
+                request.link_name, request.link_extension
+            ))
+            .unwrap());
+        }
+
+        // check if there is room for the entry in the working directory
+        self.check_directory_capacity()?;
+
+        // the link only needs clusters for its long name, if any, as it shares the data of the linked file
+        if self.get_free_clusters_count() < self.get_long_name_clusters_count(&request.link_name) {
+            return Err(Box::try_from("Not enough space in fat").unwrap());
+        }
+
+        // the link must share an allocation chain, so an empty file gets the head of one
+        let src_file_entry = match src_file_entry.has_clusters() {
+            true => src_file_entry,
+            false => self.allocate_first_cluster(&src_file_entry)?,
+        };
+
+        let link_file_entry = FileEntry::builder(&request.link_name)
+            .extension(&request.link_extension)
+            .size(src_file_entry.size)
+            .first_cluster(src_file_entry.first_cluster)
+            .attributes(src_file_entry.attributes)
+            .last_modification_datetime(src_file_entry.last_modification_datetime)
+            .parent(&self.working_directory)
+            .build()?;
+        self.append_to_root_table_of_working_dir(link_file_entry)?;
+
+        *self
+            .link_counts
+            .entry(src_file_entry.first_cluster)
+            .or_insert(1) += 1;
+
+        Ok(())
+    }
+
+    fn set_volume_label(&mut self, request: &LabelRequest) -> Void {
+        self.ensure_writable()?;
+
+        let label = BootSector::normalize_label(request.new_label.as_deref().unwrap_or_default())?;
+        if !self.boot_sector.can_hold_label() {
+            return Err(Box::try_from(format!(
+                "The boot sector has no room for a volume label with {}-byte clusters (format the disk with 32-byte clusters)",
+                self.boot_sector.cluster_size
+            ))
+            .unwrap());
         }
 
+        // the boot sector is written along with the rest of the disk on the next push
+        self.boot_sector.label = label;
+
         Ok(())
     }
 
     fn set_attributes(&mut self, request: &SetAttributesRequest) -> Void {
+        self.ensure_writable()?;
+
         // check if the file exists
         if !self
             .get_root_table_for_working_directory()
             .iter()
-            .any(|file_entry| {
-                file_entry.name == request.name && file_entry.extension == request.extension
-            })
+            .any(|file_entry| file_entry.name_matches(&request.name, &request.extension))
         {
             return Err(Box::try_from(format!(
                 "File {}.{} does not exist",
@@ -585,9 +1247,7 @@ impl IDiskManager for DiskManager {
             let file_entry_index = self
                 .root
                 .iter()
-                .position(|file_entry| {
-                    file_entry.name == request.name && file_entry.extension == request.extension
-                })
+                .position(|file_entry| file_entry.name_matches(&request.name, &request.extension))
                 .unwrap_or_default();
 
             // set the attributes
@@ -598,9 +1258,7 @@ impl IDiskManager for DiskManager {
             let file_entry = self
                 .get_root_table_for_working_directory()
                 .iter_mut()
-                .find(|file_entry| {
-                    file_entry.name == request.name && file_entry.extension == request.extension
-                })
+                .find(|file_entry| file_entry.name_matches(&request.name, &request.extension))
                 .unwrap();
 
             // set the attributes
@@ -608,128 +1266,239 @@ impl IDiskManager for DiskManager {
             file_entry.last_modification_datetime = Utc::now();
 
             // persist the file entry modifications into the storage
-            self.sync_directory_root_table_to_storage(&self.working_directory.clone());
+            self.sync_directory_root_table_to_storage(&self.working_directory.clone())?;
         }
 
         Ok(())
     }
 
     fn format_disk(&mut self, request: &FormatRequest) -> Void {
+        // a dry run only previews the geometry of the formatted disk, so it works on a read-only disk as well
+        if !request.dry_run {
+            self.ensure_writable()?;
+        }
+
         // create a new in memory disk representation associated with the new fat type
-        let mut boot_sector = self.get_boot_sector().clone();
-        let disk_size = boot_sector.cluster_size as u32 * boot_sector.cluster_count as u32;
+        // (fmt preserves the original disk size unless the new fat type cannot address all of it)
+        let boot_sector = self.get_boot_sector().with_format(
+            request.fat_type,
+            request
+                .cluster_size
+                .unwrap_or(self.get_boot_sector().cluster_size),
+        );
+        if boot_sector.get_disk_size() < self.get_boot_sector().get_disk_size() {
+            log::warn!(
+                "FAT{} cannot address the whole disk, shrinking it from {} B to {} B",
+                request.fat_type,
+                self.get_boot_sector().get_disk_size(),
+                boot_sector.get_disk_size()
+            );
+        }
 
-        boot_sector.cluster_size = request.fat_type;
-        // the cluster count is the old disk size divided by the new cluster size
-        // as fmt should preserve the original disk size
-        boot_sector.cluster_count = (disk_size / (boot_sector.cluster_size as u32)) as u16;
+        // the disk is left untouched by a dry run
+        if request.dry_run {
+            return Ok(());
+        }
 
-        let mut new_disk_manager = DiskManager::new(CONFIG_ARC.clone(), boot_sector);
+        let mut new_disk_manager = DiskManager::new(self.config.clone(), boot_sector);
 
         // push sync the new disk representation to the storage
-        new_disk_manager.push_sync();
+        new_disk_manager.push_sync_all();
+
+        // the unsaved pushes of the previous disk must never be written over the new one
+        self.last_unsaved_push = None;
+        self.dirty_cluster_indices.clear();
 
         Ok(())
     }
 
     fn defragment_disk(&mut self) -> Void {
-        // create a new temporary disk representation
-        let mut new_disk_manager =
-            DiskManager::new(CONFIG_ARC.clone(), self.get_boot_sector().clone());
+        self.ensure_writable()?;
+
+        // moving clusters around is only safe if every allocated cluster belongs to exactly one chain
+        let consistency_errors = self.check_disk();
+        if !consistency_errors.is_empty() {
+            return Err(Box::try_from(format!(
+                "Unable to defragment a disk with {} consistency error(s), run chkdsk for details",
+                consistency_errors.len()
+            ))
+            .unwrap());
+        }
+
+        // change the working directory to be the root
+        self.change_working_directory_to_root()?;
+
+        // move the chains to the front of the data region in root order, then fix the cluster references
+        // held by the file entries and rewrite the directory root tables in their new places
+        let chain_heads = self.collect_chain_heads();
+        let relocations = self.compact_chains(&chain_heads);
+        self.relocate_file_entries(&relocations);
+        self.rewrite_directory_root_tables();
+        self.working_directory.children_entries = Some(self.root.clone());
+        self.link_counts = self.collect_link_counts();
+
+        Ok(())
+    }
+
+    fn resize_disk(&mut self, request: &ResizeRequest) -> Void {
+        self.ensure_writable()?;
+
+        if request.cluster_count == self.boot_sector.cluster_count {
+            return Err(Box::try_from(format!(
+                "The disk already has {} clusters",
+                request.cluster_count
+            ))
+            .unwrap());
+        }
+
+        // moving clusters to the resized disk is only safe if every allocated cluster belongs to exactly one chain
+        let consistency_errors = self.check_disk();
+        if !consistency_errors.is_empty() {
+            return Err(Box::try_from(format!(
+                "Unable to resize a disk with {} consistency error(s), run chkdsk for details",
+                consistency_errors.len()
+            ))
+            .unwrap());
+        }
 
         // change the working directory to be the root
         self.change_working_directory_to_root()?;
 
-        // iterate over the root file entries
-        for file_entry in self.root.clone().iter() {
-            // skip empty file entries
-            if file_entry.name.is_empty() {
+        // the FAT table (and so the reserved region) grows or shrinks along with the cluster count
+        let boot_sector = BootSector {
+            cluster_count: request.cluster_count,
+            working_directory_cluster: 0,
+            ..self.boot_sector.clone()
+        };
+        boot_sector.validate().map_err(|err| {
+            format!(
+                "Unable to resize the disk to {} clusters: {}",
+                request.cluster_count, err
+            )
+        })?;
+
+        let mut resized_disk_manager = DiskManager::new(self.config.clone(), boot_sector);
+        self.move_chains_to_disk(&mut resized_disk_manager)?;
+
+        // replace the current disk with the resized one and reload it from the rewritten storage file
+        resized_disk_manager.clusters_read = self.clusters_read;
+        resized_disk_manager.clusters_written = self.clusters_written;
+        resized_disk_manager.push_sync_all();
+        *self = resized_disk_manager;
+        self.pull_sync();
+
+        Ok(())
+    }
+
+    fn walk(&self) -> Vec<(String, FileEntry)> {
+        let mut entries: Vec<(String, FileEntry)> = Vec::new();
+        // the entries are pushed in reverse order, so that they are popped in root order
+        let mut pending_entries: Vec<(&FileEntry, String)> = self
+            .root
+            .iter()
+            .rev()
+            .map(|file_entry| (file_entry, String::new()))
+            .collect();
+
+        while let Some((file_entry, parent_path)) = pending_entries.pop() {
+            // skip the empty entries and the special dir entries (which stand for actual directories)
+            if file_entry.name.is_empty() || file_entry.name == "." || file_entry.name == ".." {
                 continue;
             }
 
-            // create a new file entry in the new disk representation
-            match file_entry.is_file() {
-                true => {
-                    // get file content
-                    let cat_request =
-                        CatRequest::new(file_entry.name.clone(), file_entry.extension.clone());
-                    let file_content = self.get_file_content(&cat_request)?;
-
-                    // write the file content to the temp buffer file
-                    DiskManager::write_to_temp_buffer(file_content.as_str())?;
-
-                    // create the file entry
-                    let create_request = CreateRequest::new(
-                        file_entry.name.clone(),
-                        file_entry.extension.clone(),
-                        file_content.len() as u32,
-                        file_entry.attributes,
-                        file_entry.last_modification_datetime,
-                        ContentType::Temp,
-                    );
-                    new_disk_manager.create_file(&create_request)?;
-                }
-                false => {
-                    // create the directory entry
-                    let make_directory_request = MakeDirectoryRequest::new(
-                        file_entry.name.clone(),
-                        file_entry.attributes,
-                        file_entry.last_modification_datetime,
-                    );
-                    new_disk_manager.make_directory(&make_directory_request)?;
+            let entry_path = format!("{}/{}", parent_path, file_entry.get_full_name());
 
-                    // iterate over the directory's root table and recreate the dir tree in the new disk representation
-                    self.inflate_directory_tree(&mut new_disk_manager, file_entry)?;
-                }
+            if let Some(children_entries) = file_entry.children_entries.as_ref() {
+                pending_entries.extend(
+                    children_entries
+                        .iter()
+                        .rev()
+                        .map(|child_entry| (child_entry, entry_path.clone())),
+                );
             }
+
+            entries.push((entry_path, file_entry.clone()));
         }
 
-        // push sync
-        new_disk_manager.push_sync();
+        entries
+    }
 
-        Ok(())
+    fn fragmentation_report(&mut self) -> FragReport {
+        // update the in-memory disk representation
+        self.pull_sync();
+
+        self.build_fragmentation_report()
+    }
+
+    fn mark_bad_cluster(&mut self, request: &BadBlocksRequest) -> Void {
+        self.ensure_writable()?;
+
+        let cluster_index = request.cluster_index;
+
+        match self.fat.get(cluster_index) {
+            None => Err(Box::try_from(format!(
+                "Cluster {} is out of bounds (the disk has {} clusters)",
+                cluster_index,
+                self.fat.len()
+            ))
+            .unwrap()),
+            Some(FatValue::Reserved) => Err(Box::try_from(format!(
+                "Cluster {} is reserved for the boot sector, the FAT or the root table",
+                cluster_index
+            ))
+            .unwrap()),
+            Some(FatValue::Data(_)) | Some(FatValue::EndOfChain) => {
+                Err(Box::try_from(format!("Cluster {} is in use", cluster_index)).unwrap())
+            }
+            Some(FatValue::Bad) => Err(Box::try_from(format!(
+                "Cluster {} is already marked as bad",
+                cluster_index
+            ))
+            .unwrap()),
+            // the bad cluster is left out of the free cluster indices cache, so it is never allocated again
+            Some(FatValue::Free) => {
+                self.set_fat_value(cluster_index, FatValue::Bad);
+                Ok(())
+            }
+        }
     }
 
     fn make_directory(&mut self, request: &MakeDirectoryRequest) -> Void {
-        // check if the directory name already exists
-        if self
+        self.ensure_writable()?;
+
+        // check if the directory name already exists (a file without extension takes the name as well,
+        // as `del`, `rename` and `stat` couldn't tell them apart)
+        if let Some(file_entry) = self
             .get_root_table_for_working_directory()
             .iter()
-            .any(|file_entry| file_entry.name == request.name && !file_entry.is_file())
+            .find(|file_entry| file_entry.name_matches(&request.name, ""))
         {
-            return Err(
-                Box::try_from(format!("Directory {} already exists", request.name)).unwrap(),
-            );
+            return Err(Box::try_from(match file_entry.is_file() {
+                true => format!("File {} already exists", request.name),
+                false => format!("Directory {} already exists", request.name),
+            })
+            .unwrap());
         }
 
-        // check if there is enough space in fat
-        let free_clusters = self
-            .fat
-            .iter()
-            .filter(|&fat_value| *fat_value == FatValue::Free)
-            .count();
+        // check if there is room for the entry in the working directory
+        self.check_directory_capacity()?;
 
-        if free_clusters == 0 {
+        // check if there is enough space in fat (for both the directory table and the long name, if any)
+        if self.get_free_clusters_count() <= self.get_long_name_clusters_count(&request.name) {
             return Err(Box::try_from("Not enough space in FAT").unwrap());
         }
 
         // create the directory file entry and attach the two special dir entries: `.` and `..`
-        let first_cluster_index = self
-            .fat
-            .iter()
-            .position(|fat_value| *fat_value == FatValue::Free)
-            .unwrap() as u16;
+        let first_cluster_index = *self.free_cluster_indices.first().unwrap() as u16;
 
-        let mut dir_file_entry = FileEntry::new(
-            request.name.clone(),
-            "".to_string(),
-            (self.boot_sector.root_entry_cell_size * 2) as u32,
-            first_cluster_index,
-            request.attributes,
-            request.last_modification_datetime,
-            Some(Box::new(self.working_directory.clone())),
-            Some(Vec::new()),
-        );
+        let mut dir_file_entry = FileEntry::builder(&request.name)
+            .size((self.boot_sector.root_entry_cell_size * 2) as u32)
+            .first_cluster(first_cluster_index)
+            .attributes(request.attributes)
+            .last_modification_datetime(request.last_modification_datetime)
+            .parent(&self.working_directory)
+            .children(Vec::new())
+            .build()?;
 
         // create the self special dir entry
         let mut dot_dir_entry = dir_file_entry.clone();
@@ -744,6 +1513,7 @@ impl IDiskManager for DiskManager {
         let mut double_dot_dir_entry = self.working_directory.clone();
         double_dot_dir_entry.name = "..".to_string();
         double_dot_dir_entry.extension = "".to_string();
+        double_dot_dir_entry.long_name_cluster = 0;
         double_dot_dir_entry.attributes = FileEntryAttributes::combine(&[
             FileEntryAttributes::Directory,
             FileEntryAttributes::ReadOnly,
@@ -764,14 +1534,17 @@ impl IDiskManager for DiskManager {
     }
 
     fn change_working_directory(&mut self, request: &ChangeDirectoryRequest) -> Void {
+        // cd along a multi-segment path
+        if request.directory_name != "/" && request.directory_name.contains('/') {
+            return self.change_working_directory_along_path(&request.directory_name);
+        }
+
         // check if the directory exists
         if request.directory_name != "/"
             && !self
                 .get_root_table_for_working_directory()
                 .iter()
-                .any(|file_entry| {
-                    file_entry.name == request.directory_name && !file_entry.is_file()
-                })
+                .any(|file_entry| file_entry.matches_directory(&request.directory_name))
         {
             return Err(Box::try_from(format!(
                 "Directory {} does not exist",
@@ -793,7 +1566,16 @@ impl IDiskManager for DiskManager {
 
         // cd to parent
         if request.directory_name == ".." {
-            let parent = self.working_directory.parent_entry.clone().unwrap();
+            let parent = match self.working_directory.parent_entry.clone() {
+                Some(parent) => parent,
+                None => {
+                    return Err(Box::try_from(format!(
+                        "The parent chain of directory {} is broken, it has no parent",
+                        self.working_directory.name
+                    ))
+                    .unwrap())
+                }
+            };
             self.working_directory = *parent;
             return Ok(());
         }
@@ -802,29 +1584,246 @@ impl IDiskManager for DiskManager {
         self.working_directory = self
             .get_root_table_for_working_directory()
             .iter()
-            .find(|file_entry| file_entry.name == request.directory_name && !file_entry.is_file())
+            .find(|file_entry| file_entry.matches_directory(&request.directory_name))
             .unwrap()
             .clone();
 
         Ok(())
     }
 
-    fn get_working_directory_full_path(&self) -> String {
-        // construct the whole path from the root to the working directory
-        let mut dirs: Vec<&str> = Vec::new();
-        dirs.push(&self.working_directory.name);
+    fn get_file_entry(&mut self, request: &StatRequest) -> Result<FileEntry, Box<dyn Error>> {
+        self.get_root_table_for_working_directory()
+            .iter()
+            .find(|file_entry| {
+                file_entry.name_matches(&request.name, &request.extension)
+                    && file_entry.name != "."
+                    && file_entry.name != ".."
+            })
+            .cloned()
+            .ok_or_else(|| {
+                let error_message = match request.extension.is_empty() {
+                    true => format!("Directory {} does not exist", request.name),
+                    false => format!("File {}.{} does not exist", request.name, request.extension),
+                };
 
-        let mut current_dir = &self.working_directory;
-        // iterate over the parent entries until we reach the root
-        // and push the directory names to the dirs vector
-        while let Some(parent_dir) = current_dir.parent_entry.as_ref() {
-            current_dir = parent_dir;
-            dirs.push(&current_dir.name);
-        }
+                Box::try_from(error_message).unwrap()
+            })
+    }
 
-        // if current_dir is root, then we need to add an empty string to the dirs vector
-        // and remove the last element (which is the root dir)
-        // in order to get rid of the trailing slash
+    fn check_disk(&mut self) -> Vec<ConsistencyError> {
+        let mut consistency_errors: Vec<ConsistencyError> = Vec::new();
+        // the owner entry path of every cluster reachable from the directory tree
+        let mut cluster_owners: HashMap<usize, String> = HashMap::new();
+        // the first clusters of the files walked so far, as hard links share the allocation chain of a file
+        let mut file_chain_heads: HashSet<u16> = HashSet::new();
+
+        // iterate over the whole directory tree starting from the root table
+        // (the special dir entries share their chains with their actual directories, so they are not walked)
+        for (entry_path, file_entry) in self.walk() {
+            // walk the allocation chain of the file entry and the chain of its long name, if any
+            // (the empty files have no allocation chain)
+            let mut chains = vec![];
+            if !file_entry.is_file()
+                || file_entry.has_clusters() && file_chain_heads.insert(file_entry.first_cluster)
+            {
+                chains.push((file_entry.first_cluster as usize, entry_path.clone()));
+            }
+            if file_entry.long_name_cluster != 0 {
+                chains.push((
+                    file_entry.long_name_cluster as usize,
+                    format!("{} (long name)", entry_path),
+                ));
+            }
+
+            for (first_cluster_index, chain_owner_path) in chains {
+                let mut visited_clusters: HashSet<usize> = HashSet::new();
+                let mut cluster_index = first_cluster_index;
+
+                if self.fat.get(cluster_index) == Some(&FatValue::Free) {
+                    consistency_errors.push(ConsistencyError::FreeFirstCluster {
+                        entry_path: chain_owner_path.clone(),
+                        cluster_index,
+                    });
+                } else {
+                    loop {
+                        let fat_value = match self.fat.get(cluster_index) {
+                            Some(FatValue::Data(_)) | Some(FatValue::EndOfChain) => {
+                                self.fat[cluster_index].clone()
+                            }
+                            _ => {
+                                consistency_errors.push(ConsistencyError::BrokenChain {
+                                    entry_path: chain_owner_path.clone(),
+                                    cluster_index,
+                                });
+                                break;
+                            }
+                        };
+
+                        if !visited_clusters.insert(cluster_index) {
+                            consistency_errors.push(ConsistencyError::LoopingChain {
+                                entry_path: chain_owner_path.clone(),
+                                cluster_index,
+                            });
+                            break;
+                        }
+
+                        match cluster_owners.get(&cluster_index) {
+                            Some(other_entry_path) => {
+                                consistency_errors.push(ConsistencyError::CrossLinkedChain {
+                                    entry_path: chain_owner_path.clone(),
+                                    other_entry_path: other_entry_path.clone(),
+                                    cluster_index,
+                                });
+                            }
+                            None => {
+                                cluster_owners.insert(cluster_index, chain_owner_path.clone());
+                            }
+                        }
+
+                        match fat_value {
+                            FatValue::Data(next_cluster_index) => {
+                                cluster_index = next_cluster_index as usize
+                            }
+                            _ => break,
+                        }
+                    }
+                }
+            }
+        }
+
+        // collect the allocated clusters which are not reachable from any file entry
+        let lost_clusters: BTreeSet<usize> = self
+            .fat
+            .iter()
+            .enumerate()
+            .filter(|(cluster_index, fat_value)| {
+                matches!(fat_value, FatValue::Data(_) | FatValue::EndOfChain)
+                    && !cluster_owners.contains_key(cluster_index)
+            })
+            .map(|(cluster_index, _)| cluster_index)
+            .collect();
+
+        // group the lost clusters into chains starting from the clusters no other lost cluster points to
+        let pointed_clusters: HashSet<usize> = lost_clusters
+            .iter()
+            .filter_map(|&cluster_index| match self.fat[cluster_index] {
+                FatValue::Data(next_cluster_index) => Some(next_cluster_index as usize),
+                _ => None,
+            })
+            .collect();
+        let mut reported_clusters: HashSet<usize> = HashSet::new();
+
+        for &first_cluster_index in lost_clusters
+            .iter()
+            .filter(|cluster_index| !pointed_clusters.contains(cluster_index))
+            .chain(lost_clusters.iter())
+        {
+            let mut cluster_count = 0;
+            let mut cluster_index = first_cluster_index;
+
+            while lost_clusters.contains(&cluster_index) && reported_clusters.insert(cluster_index)
+            {
+                cluster_count += 1;
+                match self.fat[cluster_index] {
+                    FatValue::Data(next_cluster_index) => {
+                        cluster_index = next_cluster_index as usize
+                    }
+                    _ => break,
+                }
+            }
+
+            if cluster_count > 0 {
+                consistency_errors.push(ConsistencyError::LostChain {
+                    first_cluster_index,
+                    cluster_count,
+                });
+            }
+        }
+
+        consistency_errors
+    }
+
+    fn read_cluster(&self, cluster_index: usize) -> Result<Vec<u8>, Box<dyn Error>> {
+        if cluster_index >= self.boot_sector.cluster_count as usize {
+            return Err(Box::try_from(format!(
+                "Cluster {} is out of bounds (the disk has {} clusters)",
+                cluster_index, self.boot_sector.cluster_count
+            ))
+            .unwrap());
+        }
+
+        Ok(self.storage_buffer[cluster_index].clone())
+    }
+
+    fn write_cluster(&mut self, request: &WriteClusterRequest) -> Void {
+        self.ensure_writable()?;
+
+        let cluster_index = request.cluster_index;
+        let cluster_size = self.boot_sector.cluster_size as usize;
+
+        if cluster_index >= self.boot_sector.cluster_count as usize {
+            return Err(Box::try_from(format!(
+                "Cluster {} is out of bounds (the disk has {} clusters)",
+                cluster_index, self.boot_sector.cluster_count
+            ))
+            .unwrap());
+        }
+
+        if cluster_index < self.boot_sector.get_reserved_clusters_count() as usize {
+            return Err(Box::try_from(format!(
+                "Cluster {} is reserved for the boot sector, the FAT or the root table",
+                cluster_index
+            ))
+            .unwrap());
+        }
+
+        if request.data.len() > cluster_size {
+            return Err(Box::try_from(format!(
+                "{} B don't fit in a cluster of {} B",
+                request.data.len(),
+                cluster_size
+            ))
+            .unwrap());
+        }
+
+        // the FAT is deliberately left as is, even if the cluster is free or holds a directory table
+        let mut cluster_data = request.data.clone();
+        cluster_data.resize(cluster_size, 0);
+        self.set_cluster_data(cluster_index, cluster_data);
+
+        Ok(())
+    }
+
+    fn get_fat_slice(&self, start: usize, count: usize) -> Vec<(usize, FatValue)> {
+        self.fat
+            .iter()
+            .cloned()
+            .enumerate()
+            .skip(start)
+            .take(count)
+            .collect()
+    }
+
+    fn cluster_chain(&self, first_cluster: usize) -> Vec<usize> {
+        self.get_chain_clusters(first_cluster)
+    }
+
+    fn get_working_directory_full_path(&self) -> String {
+        // construct the whole path from the root to the working directory
+        let mut dirs: Vec<&str> = Vec::new();
+        dirs.push(&self.working_directory.name);
+
+        let mut current_dir = &self.working_directory;
+        // iterate over the parent entries until we reach the root
+        // and push the directory names to the dirs vector
+        while let Some(parent_dir) = current_dir.parent_entry.as_ref() {
+            current_dir = parent_dir;
+            dirs.push(&current_dir.name);
+        }
+
+        // if current_dir is root, then we need to add an empty string to the dirs vector
+        // and remove the last element (which is the root dir)
+        // in order to get rid of the trailing slash
         if !self.working_directory.is_root() {
             dirs.pop();
             dirs.push("");
@@ -835,6 +1834,27 @@ impl IDiskManager for DiskManager {
         dirs.join("/")
     }
 
+    fn root_capacity(&self) -> (usize, usize) {
+        let used_entries_count = self
+            .root
+            .iter()
+            .filter(|file_entry| !file_entry.name.is_empty())
+            .count();
+
+        (
+            used_entries_count,
+            self.boot_sector.root_entry_count as usize,
+        )
+    }
+
+    fn get_previous_directory(&self) -> Option<String> {
+        self.previous_directory.clone()
+    }
+
+    fn set_previous_directory(&mut self, path: String) {
+        self.previous_directory = Some(path);
+    }
+
     fn get_boot_sector(&self) -> &BootSector {
         &self.boot_sector
     }
@@ -844,16 +1864,1622 @@ impl IDiskManager for DiskManager {
         self.pull_sync();
 
         // count the free clusters and multiply them by the cluster size to get the free space
-        let free_clusters = self
+        let free_clusters = self.get_free_clusters_count();
+
+        (free_clusters * self.boot_sector.cluster_size as usize) as u64
+    }
+
+    fn get_bad_space(&self) -> u64 {
+        let bad_clusters = self
             .fat
             .iter()
-            .filter(|&fat_value| *fat_value == FatValue::Free)
+            .filter(|fat_value| **fat_value == FatValue::Bad)
             .count();
 
-        (free_clusters * self.boot_sector.cluster_size as usize) as u64
+        (bad_clusters * self.boot_sector.cluster_size as usize) as u64
     }
 
     fn get_total_space(&self) -> u64 {
         (self.fat.len() * self.boot_sector.cluster_size as usize) as u64
     }
+
+    fn get_cluster_io_counts(&self) -> (u64, u64) {
+        (self.clusters_read, self.clusters_written)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::frag_report::FileFragmentation;
+    use crate::infrastructure::test_disk::TestDisk;
+    use chrono::TimeZone;
+
+    /// Builds the request of `cp [-f] <src_name>.<src_extension> <dest_name>.<dest_extension>`.
+    fn copy_request(
+        src_name: &str,
+        src_extension: &str,
+        dest_name: &str,
+        dest_extension: &str,
+        force: bool,
+    ) -> CopyRequest {
+        CopyRequest::new(
+            src_name.to_string(),
+            src_extension.to_string(),
+            dest_name.to_string(),
+            dest_extension.to_string(),
+            None,
+            force,
+        )
+    }
+
+    /// Returns the names of the entries of the working directory, the free ones, `.` and `..` aside.
+    fn list_names(disk: &mut TestDisk) -> Vec<String> {
+        disk.pull_sync();
+        disk.get_root_table_for_working_directory()
+            .iter()
+            .filter(|file_entry| !file_entry.name.is_empty())
+            .filter(|file_entry| file_entry.name != "." && file_entry.name != "..")
+            .map(|file_entry| file_entry.get_full_name())
+            .collect()
+    }
+
+    #[test]
+    fn copy_onto_an_existing_file_requires_force() {
+        let mut disk = TestDisk::new();
+        disk.write("a", "txt", "source").unwrap();
+        disk.write("b", "txt", "destination").unwrap();
+
+        let result = disk.run(|disk_manager| {
+            disk_manager.copy_file(&copy_request("a", "txt", "b", "txt", false))
+        });
+
+        assert_eq!(result.unwrap_err().to_string(), "File b.txt already exists");
+        assert_eq!(disk.read("b", "txt"), b"destination");
+    }
+
+    #[test]
+    fn copy_with_force_overwrites_a_file() {
+        let mut disk = TestDisk::new();
+        disk.write("a", "txt", "source").unwrap();
+        disk.create("b", "txt", 500).unwrap();
+        let free_clusters_count = disk.get_free_clusters_count();
+
+        disk.run(|disk_manager| {
+            disk_manager.copy_file(&copy_request("a", "txt", "b", "txt", true))
+        })
+        .unwrap();
+
+        assert_eq!(disk.read("b", "txt"), b"source");
+        assert_eq!(disk.read("a", "txt"), b"source");
+        assert_eq!(list_names(&mut disk), vec!["a.txt", "b.txt"]);
+        // the chain of the overwritten file has been freed, the copy taking a single cluster
+        assert_eq!(disk.get_free_clusters_count(), free_clusters_count + 32 - 1);
+    }
+
+    #[test]
+    fn copy_with_force_overwrites_a_directory() {
+        let mut disk = TestDisk::new();
+        disk.mkdir("src").unwrap();
+        disk.cd("src").unwrap();
+        disk.write("new", "txt", "copied").unwrap();
+        disk.cd("..").unwrap();
+        disk.mkdir("dest").unwrap();
+        disk.cd("dest").unwrap();
+        disk.write("old", "txt", "overwritten").unwrap();
+        disk.cd("..").unwrap();
+
+        disk.run(|disk_manager| disk_manager.copy_file(&copy_request("src", "", "dest", "", true)))
+            .unwrap();
+
+        disk.cd("dest").unwrap();
+        assert_eq!(list_names(&mut disk), vec!["new.txt"]);
+        assert_eq!(disk.read("new", "txt"), b"copied");
+    }
+
+    #[test]
+    fn copy_with_force_out_of_space_leaves_the_destination_deleted() {
+        let mut disk = TestDisk::new();
+        disk.write("b", "txt", "destination").unwrap();
+        // fill almost the rest of the disk with the src file, so that freeing the dest file is not enough for the copy
+        let cluster_size = disk.boot_sector.cluster_size as u32;
+        let size = (disk.get_free_clusters_count() as u32 - 1) * cluster_size;
+        disk.create("a", "txt", size).unwrap();
+        let free_clusters_count = disk.get_free_clusters_count();
+
+        let result = disk.run(|disk_manager| {
+            disk_manager.copy_file(&copy_request("a", "txt", "b", "txt", true))
+        });
+
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Not enough space in fat, a.txt has not been copied (the destination, if any, has been deleted)"
+        );
+        assert_eq!(list_names(&mut disk), vec!["a.txt"]);
+        assert_eq!(disk.get_free_clusters_count(), free_clusters_count + 1);
+    }
+
+    #[test]
+    fn copy_with_force_onto_a_destination_below_every_other_free_cluster() {
+        let mut disk = TestDisk::new();
+        let cluster_size = disk.boot_sector.cluster_size as u32;
+        disk.create("b", "txt", 3 * cluster_size).unwrap();
+        disk.create("a", "txt", 4 * cluster_size).unwrap();
+        // fill the rest of the disk but a single cluster, past the chains of both files
+        let size = (disk.get_free_clusters_count() as u32 - 1) * cluster_size;
+        disk.create("fill", "bin", size).unwrap();
+        let last_free_cluster = *disk.free_cluster_indices.first().unwrap();
+        let chain_of_b = chain_clusters(&mut disk, "b", "txt");
+        assert!(chain_of_b
+            .iter()
+            .all(|&cluster_index| cluster_index < last_free_cluster));
+
+        disk.run(|disk_manager| {
+            disk_manager.copy_file(&copy_request("a", "txt", "b", "txt", true))
+        })
+        .unwrap();
+
+        // the copy fills the hole left by the overwritten file, then jumps to the last free cluster
+        assert_eq!(disk.read("b", "txt"), disk.read("a", "txt"));
+        assert_eq!(
+            chain_clusters(&mut disk, "b", "txt"),
+            [chain_of_b, vec![last_free_cluster]].concat()
+        );
+        assert_eq!(disk.get_free_clusters_count(), 0);
+    }
+
+    /// Builds the tree `/docs/reports` and `/other`, leaving `/other` as the working directory.
+    fn build_directory_tree(disk: &mut TestDisk) {
+        disk.mkdir("docs").unwrap();
+        disk.cd("docs").unwrap();
+        disk.mkdir("reports").unwrap();
+        disk.cd("..").unwrap();
+        disk.mkdir("other").unwrap();
+        disk.cd("other").unwrap();
+    }
+
+    #[test]
+    fn cd_follows_an_absolute_path() {
+        let mut disk = TestDisk::new();
+        build_directory_tree(&mut disk);
+
+        disk.cd("/docs/reports").unwrap();
+
+        assert_eq!(disk.get_working_directory_full_path(), "/docs/reports");
+    }
+
+    #[test]
+    fn cd_follows_a_relative_path() {
+        let mut disk = TestDisk::new();
+        build_directory_tree(&mut disk);
+
+        disk.cd("../docs/reports").unwrap();
+        assert_eq!(disk.get_working_directory_full_path(), "/docs/reports");
+
+        disk.cd("../../other/.").unwrap();
+        assert_eq!(disk.get_working_directory_full_path(), "/other");
+    }
+
+    #[test]
+    fn cd_along_a_path_with_a_missing_segment_stays_in_place() {
+        let mut disk = TestDisk::new();
+        build_directory_tree(&mut disk);
+
+        let result = disk.cd("/docs/missing/reports");
+
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Cannot change directory to /docs/missing/reports: Directory missing does not exist"
+        );
+        disk.pull_sync();
+        assert_eq!(disk.get_working_directory_full_path(), "/other");
+    }
+
+    /// Returns the size of a file of the working directory.
+    fn file_size(disk: &mut TestDisk, name: &str, extension: &str) -> u32 {
+        disk.pull_sync();
+        disk.get_root_table_for_working_directory()
+            .iter()
+            .find(|file_entry| file_entry.name == name && file_entry.extension == extension)
+            .unwrap()
+            .size
+    }
+
+    #[test]
+    fn echo_creates_a_missing_file() {
+        let mut disk = TestDisk::new();
+
+        disk.write("a", "txt", "hello world").unwrap();
+
+        assert_eq!(disk.read("a", "txt"), b"hello world");
+        assert_eq!(file_size(&mut disk, "a", "txt"), 11);
+    }
+
+    #[test]
+    fn echo_overwriting_with_a_shorter_content_frees_the_old_clusters() {
+        let mut disk = TestDisk::new();
+        disk.create("a", "txt", 100).unwrap();
+        let free_clusters_count = disk.get_free_clusters_count();
+
+        disk.write("a", "txt", "short").unwrap();
+
+        assert_eq!(disk.read("a", "txt"), b"short");
+        assert_eq!(file_size(&mut disk, "a", "txt"), 5);
+        // 100 bytes take 7 clusters of 16 bytes, while 5 bytes take a single one
+        assert_eq!(disk.get_free_clusters_count(), free_clusters_count + 6);
+    }
+
+    #[test]
+    fn echo_overwriting_past_the_free_space_keeps_the_old_content() {
+        let mut disk = TestDisk::new();
+        disk.mkdir("docs").unwrap();
+        disk.cd("docs").unwrap();
+        disk.write("a", "txt", "old content").unwrap();
+        // fill the disk but a single cluster (the table of the directory taking 2 more clusters for the new entry,
+        // plus the free cluster a subdirectory table always looks for past its end)
+        let free_clusters_count = disk.get_free_clusters_count() as u32;
+        disk.create("fill", "bin", (free_clusters_count - 3) * 16)
+            .unwrap();
+        assert_eq!(disk.get_free_clusters_count(), 1);
+
+        // 40 bytes take 3 clusters, while only 2 are free once the single cluster of the old content is reused
+        assert!(disk
+            .write("a", "txt", "a much longer content, spanning 3 clusters")
+            .is_err());
+
+        assert_eq!(disk.read("a", "txt"), b"old content");
+        assert_eq!(file_size(&mut disk, "a", "txt"), 11);
+        assert_eq!(disk.reopen().get_free_clusters_count(), 1);
+    }
+
+    #[test]
+    fn append_fills_the_slack_of_the_last_cluster() {
+        let mut disk = TestDisk::new();
+        disk.write("a", "txt", "0123456789").unwrap();
+        let free_clusters_count = disk.get_free_clusters_count();
+
+        disk.append("a", "txt", "ABCDEFGHIJ").unwrap();
+
+        assert_eq!(disk.read("a", "txt"), b"0123456789ABCDEFGHIJ");
+        assert_eq!(file_size(&mut disk, "a", "txt"), 20);
+        // 6 bytes fit in the slack of the last cluster, the remaining 4 ones need a new cluster
+        assert_eq!(disk.get_free_clusters_count(), free_clusters_count - 1);
+    }
+
+    #[test]
+    fn append_to_a_file_without_slack_extends_its_chain() {
+        let mut disk = TestDisk::new();
+        disk.write("a", "txt", "0123456789ABCDEF").unwrap();
+        let free_clusters_count = disk.get_free_clusters_count();
+
+        disk.append("a", "txt", "tail").unwrap();
+
+        assert_eq!(disk.read("a", "txt"), b"0123456789ABCDEFtail");
+        assert_eq!(file_size(&mut disk, "a", "txt"), 20);
+        assert_eq!(disk.get_free_clusters_count(), free_clusters_count - 1);
+    }
+
+    #[test]
+    fn append_within_the_slack_takes_no_new_cluster() {
+        let mut disk = TestDisk::new();
+        disk.write("a", "txt", "head").unwrap();
+        let free_clusters_count = disk.get_free_clusters_count();
+
+        disk.append("a", "txt", "tail").unwrap();
+
+        assert_eq!(disk.read("a", "txt"), b"headtail");
+        assert_eq!(file_size(&mut disk, "a", "txt"), 8);
+        assert_eq!(disk.get_free_clusters_count(), free_clusters_count);
+    }
+
+    /// Returns the cluster chain of a file of the working directory.
+    fn chain_clusters(disk: &mut TestDisk, name: &str, extension: &str) -> Vec<usize> {
+        disk.pull_sync();
+        let first_cluster = disk
+            .get_root_table_for_working_directory()
+            .iter()
+            .find(|file_entry| file_entry.name == name && file_entry.extension == extension)
+            .unwrap()
+            .first_cluster as usize;
+        disk.get_chain_clusters(first_cluster)
+    }
+
+    /// Truncates a file of the working directory to the given size.
+    fn truncate(disk: &mut TestDisk, name: &str, extension: &str, size: u32) -> Void {
+        disk.run(|disk_manager| {
+            disk_manager.truncate_file(&TruncateRequest::new(
+                name.to_string(),
+                extension.to_string(),
+                size,
+            ))
+        })
+    }
+
+    #[test]
+    fn truncate_shrinking_a_file_frees_the_tail_of_its_chain() {
+        let mut disk = TestDisk::new();
+        disk.create("a", "txt", 48).unwrap();
+        let chain = chain_clusters(&mut disk, "a", "txt");
+        assert_eq!(chain.len(), 3);
+
+        truncate(&mut disk, "a", "txt", 16).unwrap();
+
+        assert_eq!(disk.fat[chain[0]], FatValue::EndOfChain);
+        assert_eq!(disk.fat[chain[1]], FatValue::Free);
+        assert_eq!(disk.fat[chain[2]], FatValue::Free);
+        assert_eq!(file_size(&mut disk, "a", "txt"), 16);
+        assert_eq!(disk.read("a", "txt"), b"ABCDEFGHIJKLMNOP");
+    }
+
+    #[test]
+    fn truncate_growing_a_file_pads_it_with_zeros() {
+        let mut disk = TestDisk::new();
+        disk.write("a", "txt", "abc").unwrap();
+
+        truncate(&mut disk, "a", "txt", 20).unwrap();
+
+        let mut content = b"abc".to_vec();
+        content.resize(20, 0);
+        assert_eq!(disk.read("a", "txt"), content);
+        let chain = chain_clusters(&mut disk, "a", "txt");
+        assert_eq!(chain.len(), 2);
+    }
+
+    #[test]
+    fn truncate_growing_past_the_free_space_leaves_the_chain_untouched() {
+        let mut disk = TestDisk::new();
+        disk.create("a", "txt", 40).unwrap();
+        let chain = chain_clusters(&mut disk, "a", "txt");
+        let free_clusters_count = disk.get_free_clusters_count();
+        let size = (free_clusters_count as u32 + chain.len() as u32 + 1) * 16;
+
+        let result = truncate(&mut disk, "a", "txt", size);
+
+        assert_eq!(result.unwrap_err().to_string(), "No space in fat");
+        assert_eq!(chain_clusters(&mut disk, "a", "txt"), chain);
+        assert_eq!(disk.get_free_clusters_count(), free_clusters_count);
+        assert_eq!(file_size(&mut disk, "a", "txt"), 40);
+    }
+
+    #[test]
+    fn truncate_rejects_a_read_only_file() {
+        let mut disk = TestDisk::new();
+        disk.write("a", "txt", "locked").unwrap();
+        set_attributes(&mut disk, "a", "txt", &["-w"]).unwrap();
+
+        let result = truncate(&mut disk, "a", "txt", 0);
+
+        assert_eq!(result.unwrap_err().to_string(), "File a.txt is read only");
+        assert_eq!(disk.read("a", "txt"), b"locked");
+    }
+
+    #[test]
+    fn check_disk_reports_nothing_on_a_consistent_disk() {
+        let mut disk = TestDisk::new();
+        disk.create("a", "txt", 100).unwrap();
+        disk.mkdir("dir").unwrap();
+        disk.cd("dir").unwrap();
+        disk.create("b", "txt", 40).unwrap();
+
+        disk.pull_sync();
+        assert_eq!(disk.check_disk(), vec![]);
+    }
+
+    #[test]
+    fn check_disk_reports_a_looping_chain() {
+        let mut disk = TestDisk::new();
+        disk.create("a", "txt", 48).unwrap();
+        let chain = chain_clusters(&mut disk, "a", "txt");
+
+        disk.fat[chain[2]] = FatValue::Data(chain[0] as u16);
+
+        assert_eq!(
+            disk.check_disk(),
+            vec![ConsistencyError::LoopingChain {
+                entry_path: "/a.txt".to_string(),
+                cluster_index: chain[0],
+            }]
+        );
+    }
+
+    #[test]
+    fn check_disk_reports_a_lost_chain() {
+        let mut disk = TestDisk::new();
+        disk.create("a", "txt", 16).unwrap();
+        let free_cluster = *disk.free_cluster_indices.first().unwrap();
+
+        disk.fat[free_cluster] = FatValue::Data(free_cluster as u16 + 1);
+        disk.fat[free_cluster + 1] = FatValue::EndOfChain;
+
+        assert_eq!(
+            disk.check_disk(),
+            vec![ConsistencyError::LostChain {
+                first_cluster_index: free_cluster,
+                cluster_count: 2,
+            }]
+        );
+    }
+
+    #[test]
+    fn check_disk_reports_a_free_first_cluster() {
+        let mut disk = TestDisk::new();
+        disk.create("a", "txt", 16).unwrap();
+        let chain = chain_clusters(&mut disk, "a", "txt");
+
+        disk.fat[chain[0]] = FatValue::Free;
+
+        assert_eq!(
+            disk.check_disk(),
+            vec![ConsistencyError::FreeFirstCluster {
+                entry_path: "/a.txt".to_string(),
+                cluster_index: chain[0],
+            }]
+        );
+    }
+
+    #[test]
+    fn check_disk_reports_a_cross_linked_chain() {
+        let mut disk = TestDisk::new();
+        disk.create("a", "txt", 32).unwrap();
+        disk.create("b", "txt", 32).unwrap();
+        let chain_of_a = chain_clusters(&mut disk, "a", "txt");
+        let chain_of_b = chain_clusters(&mut disk, "b", "txt");
+
+        // the head of a points to the tail of b, leaving the tail of a unreachable
+        disk.fat[chain_of_a[0]] = FatValue::Data(chain_of_b[1] as u16);
+
+        let consistency_errors = disk.check_disk();
+        // the file met first by the walk of the directory tree owns the shared cluster
+        assert!(consistency_errors.iter().any(|consistency_error| {
+            let cross_link =
+                |entry_path: &str, other_entry_path: &str| ConsistencyError::CrossLinkedChain {
+                    entry_path: entry_path.to_string(),
+                    other_entry_path: other_entry_path.to_string(),
+                    cluster_index: chain_of_b[1],
+                };
+            *consistency_error == cross_link("/a.txt", "/b.txt")
+                || *consistency_error == cross_link("/b.txt", "/a.txt")
+        }));
+        assert!(consistency_errors.contains(&ConsistencyError::LostChain {
+            first_cluster_index: chain_of_a[1],
+            cluster_count: 1,
+        }));
+    }
+
+    /// Returns the path from the root to a file entry of the working directory.
+    fn entry_path(disk: &mut TestDisk, name: &str, extension: &str) -> Vec<String> {
+        disk.pull_sync();
+        let file_entry = disk
+            .get_file_entry(&StatRequest::new(name.to_string(), extension.to_string()))
+            .unwrap();
+        assert!(file_entry.children_entries.is_none());
+
+        DiskManager::get_path_from_root_to_entry(&file_entry)
+    }
+
+    #[test]
+    fn a_copied_file_belongs_to_the_directory_it_is_copied_into() {
+        let mut disk = TestDisk::new();
+        disk.mkdir("dest").unwrap();
+        disk.mkdir("src").unwrap();
+        disk.cd("src").unwrap();
+        disk.write("a", "txt", "copied").unwrap();
+
+        disk.run(|disk_manager| {
+            disk_manager.copy_file(&copy_request("a", "txt", "b", "txt", false))
+        })
+        .unwrap();
+        disk.run(|disk_manager| {
+            disk_manager.copy_file(&CopyRequest::new(
+                "a".to_string(),
+                "txt".to_string(),
+                "c".to_string(),
+                "txt".to_string(),
+                Some("../dest".to_string()),
+                false,
+            ))
+        })
+        .unwrap();
+
+        assert_eq!(entry_path(&mut disk, "b", "txt"), vec!["/", "src", "b"]);
+        disk.cd("../dest").unwrap();
+        assert_eq!(entry_path(&mut disk, "c", "txt"), vec!["/", "dest", "c"]);
+        assert_eq!(disk.read("c", "txt"), b"copied");
+    }
+
+    #[test]
+    fn names_differing_only_in_case_are_duplicates() {
+        let mut disk = TestDisk::new();
+        disk.create("FILE", "TXT", 10).unwrap();
+        disk.mkdir("Docs").unwrap();
+        disk.create("other", "txt", 10).unwrap();
+
+        assert_eq!(
+            disk.create("file", "txt", 10).unwrap_err().to_string(),
+            "File file.txt already exists"
+        );
+        assert!(disk.mkdir("DOCS").is_err());
+        assert!(disk
+            .run(|disk_manager| disk_manager
+                .copy_file(&copy_request("FILE", "TXT", "File", "Txt", false)))
+            .is_err());
+        assert!(disk
+            .run(|disk_manager| {
+                disk_manager.rename_file(&RenameRequest::new(
+                    "other".to_string(),
+                    "txt".to_string(),
+                    "file".to_string(),
+                    "txt".to_string(),
+                ))
+            })
+            .is_err());
+        assert_eq!(list_names(&mut disk), vec!["FILE.TXT", "Docs", "other.txt"]);
+    }
+
+    /// Renames an entry of the working directory.
+    fn rename(
+        disk: &mut TestDisk,
+        old_name: &str,
+        old_extension: &str,
+        new_name: &str,
+        new_extension: &str,
+    ) -> Void {
+        disk.run(|disk_manager| {
+            disk_manager.rename_file(&RenameRequest::new(
+                old_name.to_string(),
+                old_extension.to_string(),
+                new_name.to_string(),
+                new_extension.to_string(),
+            ))
+        })
+    }
+
+    /// Returns the paths of all the entries of the disk, as reloaded from its storage file by the next session.
+    fn reopened_paths(disk: &TestDisk) -> Vec<String> {
+        disk.reopen()
+            .walk()
+            .into_iter()
+            .map(|(path, _)| path)
+            .collect()
+    }
+
+    #[test]
+    fn rename_in_the_root_is_persisted() {
+        let mut disk = TestDisk::new();
+        disk.mkdir("old").unwrap();
+        disk.write("a", "txt", "content").unwrap();
+
+        rename(&mut disk, "old", "", "new", "").unwrap();
+
+        assert_eq!(reopened_paths(&disk), vec!["/new", "/a.txt"]);
+    }
+
+    #[test]
+    fn renaming_a_root_file_survives_a_reload_from_the_storage_file() {
+        let mut disk = TestDisk::new();
+        disk.create("old", "txt", 10).unwrap();
+
+        rename(&mut disk, "old", "txt", "new", "txt").unwrap();
+
+        // the listing reloads the disk from its storage file first
+        assert_eq!(list_names(&mut disk), vec!["new.txt"]);
+        assert_eq!(reopened_paths(&disk), vec!["/new.txt"]);
+    }
+
+    #[test]
+    fn rename_in_a_subdirectory_is_persisted() {
+        let mut disk = TestDisk::new();
+        disk.mkdir("docs").unwrap();
+        disk.cd("docs").unwrap();
+        disk.write("old", "txt", "content").unwrap();
+
+        rename(&mut disk, "old", "txt", "new", "md").unwrap();
+
+        assert_eq!(reopened_paths(&disk), vec!["/docs", "/docs/new.md"]);
+        assert_eq!(disk.read("new", "md"), b"content");
+    }
+
+    #[test]
+    fn lookups_ignore_the_case_while_the_stored_name_keeps_it() {
+        let mut disk = TestDisk::new();
+        disk.write("FILE", "TXT", "found").unwrap();
+
+        assert_eq!(disk.read("file", "txt"), b"found");
+        assert_eq!(list_names(&mut disk), vec!["FILE.TXT"]);
+        assert_eq!(disk.reopen().walk()[0].0, "/FILE.TXT");
+    }
+
+    /// Checks whether the archive bit of a file entry of the working directory is set.
+    fn is_archived(disk: &mut TestDisk, name: &str, extension: &str) -> bool {
+        disk.pull_sync();
+        let file_entry = disk
+            .get_file_entry(&StatRequest::new(name.to_string(), extension.to_string()))
+            .unwrap();
+
+        file_entry.attributes & FileEntryAttributesFlags::Archive as u8 != 0
+    }
+
+    #[test]
+    fn the_archive_bit_is_set_by_writes_and_cleared_by_setattr() {
+        let mut disk = TestDisk::new();
+        disk.create("a", "txt", 10).unwrap();
+        assert!(is_archived(&mut disk, "a", "txt"));
+
+        set_attributes(&mut disk, "a", "txt", &["-a"]).unwrap();
+        assert!(!is_archived(&mut disk, "a", "txt"));
+
+        disk.write("a", "txt", "modified").unwrap();
+        assert!(is_archived(&mut disk, "a", "txt"));
+
+        set_attributes(&mut disk, "a", "txt", &["-a"]).unwrap();
+        disk.append("a", "txt", " again").unwrap();
+        assert!(is_archived(&mut disk, "a", "txt"));
+
+        set_attributes(&mut disk, "a", "txt", &["-a"]).unwrap();
+        truncate(&mut disk, "a", "txt", 3).unwrap();
+        assert!(is_archived(&mut disk, "a", "txt"));
+
+        set_attributes(&mut disk, "a", "txt", &["-a"]).unwrap();
+        disk.run(|disk_manager| {
+            disk_manager.rename_file(&RenameRequest::new(
+                "a".to_string(),
+                "txt".to_string(),
+                "b".to_string(),
+                "txt".to_string(),
+            ))
+        })
+        .unwrap();
+        assert!(is_archived(&mut disk, "b", "txt"));
+    }
+
+    #[test]
+    fn reading_a_file_leaves_its_archive_bit_alone() {
+        let mut disk = TestDisk::new();
+        disk.write("a", "txt", "content").unwrap();
+        set_attributes(&mut disk, "a", "txt", &["-a"]).unwrap();
+
+        disk.read("a", "txt");
+        disk.push_sync();
+
+        assert!(!is_archived(&mut disk, "a", "txt"));
+    }
+
+    #[test]
+    fn reading_a_file_only_bumps_its_last_access_date() {
+        let mut disk = TestDisk::new();
+        let created_at = Utc.with_ymd_and_hms(2000, 1, 1, 12, 0, 0).unwrap();
+        disk.run(|disk_manager| {
+            disk_manager.create_file(&CreateRequest::new(
+                "a".to_string(),
+                "txt".to_string(),
+                10,
+                FileEntryAttributes::File | FileEntryAttributes::ReadWrite,
+                created_at,
+                ContentType::Num,
+            ))
+        })
+        .unwrap();
+
+        disk.run(|disk_manager| {
+            disk_manager
+                .read_file_bytes(&CatRequest::new("a".to_string(), "txt".to_string()))
+                .map(|_| ())
+        })
+        .unwrap();
+
+        let file_entry = disk
+            .reopen()
+            .get_file_entry(&StatRequest::new("a".to_string(), "txt".to_string()))
+            .unwrap();
+        assert_eq!(file_entry.creation_datetime, created_at);
+        assert_eq!(file_entry.last_modification_datetime, created_at);
+        assert_eq!(file_entry.last_access_date, Utc::now().date_naive());
+    }
+
+    #[test]
+    fn a_long_name_is_listed_in_full_after_a_reload() {
+        let mut disk = TestDisk::new();
+        let free_clusters_count = disk.get_free_clusters_count();
+
+        disk.write("quarterly_report_q3x", "txt", "long").unwrap();
+
+        // the 12 characters past the 8 stored in the entry spill into a cluster of their own
+        assert_eq!(disk.get_free_clusters_count(), free_clusters_count - 2);
+        assert_eq!(list_names(&mut disk), vec!["quarterly_report_q3x.txt"]);
+        assert_eq!(disk.reopen().walk()[0].0, "/quarterly_report_q3x.txt");
+        assert_eq!(disk.read("QUARTERLY_REPORT_Q3X", "txt"), b"long");
+
+        disk.delete("quarterly_report_q3x", "txt").unwrap();
+        assert_eq!(disk.get_free_clusters_count(), free_clusters_count);
+    }
+
+    #[test]
+    fn long_names_sharing_their_first_8_characters_are_distinct() {
+        let mut disk = TestDisk::new();
+
+        disk.write("document_first", "txt", "first").unwrap();
+        disk.write("document_second", "txt", "second").unwrap();
+
+        assert_eq!(disk.read("document_first", "txt"), b"first");
+        assert_eq!(disk.read("document_second", "txt"), b"second");
+        assert_eq!(
+            list_names(&mut disk),
+            vec!["document_first.txt", "document_second.txt"]
+        );
+    }
+
+    /// Lists the working directory with the given filters and sort, returning the full names of the entries.
+    fn ls(disk: &mut TestDisk, filters: Vec<FilterType>, sort: Option<SortType>) -> Vec<String> {
+        disk.pull_sync();
+        disk.list_files(&ListRequest::new(filters, sort))
+            .unwrap()
+            .iter()
+            .map(|file_entry| file_entry.get_full_name())
+            .collect()
+    }
+
+    #[test]
+    fn ls_sorts_by_extension_then_by_name() {
+        let mut disk = TestDisk::new();
+        disk.create("b", "txt", 1).unwrap();
+        disk.mkdir("zdir").unwrap();
+        disk.create("c", "md", 1).unwrap();
+        disk.create("a", "txt", 1).unwrap();
+        disk.mkdir("adir").unwrap();
+
+        assert_eq!(
+            ls(&mut disk, vec![], Some(SortType::ExtAsc)),
+            vec!["adir", "zdir", "c.md", "a.txt", "b.txt"]
+        );
+        assert_eq!(
+            ls(&mut disk, vec![], Some(SortType::ExtDesc)),
+            vec!["a.txt", "b.txt", "c.md", "adir", "zdir"]
+        );
+    }
+
+    #[test]
+    fn ls_filters_by_glob_pattern() {
+        let mut disk = TestDisk::new();
+        disk.create("readme", "md", 1).unwrap();
+        disk.create("notes", "txt", 1).unwrap();
+        disk.create("todo", "txt", 1).unwrap();
+
+        let glob = |pattern: &str| vec![FilterType::Glob(pattern.to_string())];
+        assert!(ls(&mut disk, glob("*.log"), None).is_empty());
+        assert_eq!(ls(&mut disk, glob("read*.md"), None), vec!["readme.md"]);
+        assert_eq!(
+            ls(&mut disk, glob("*.TXT"), Some(SortType::NameAsc)),
+            vec!["notes.txt", "todo.txt"]
+        );
+    }
+
+    #[test]
+    fn used_and_free_space_add_up_to_the_total_space() {
+        let mut disk = TestDisk::new();
+        disk.create("a", "txt", 100).unwrap();
+        disk.create("b", "txt", 16).unwrap();
+        disk.create("c", "txt", 0).unwrap();
+
+        let free_space = disk.get_free_space();
+        let total_space = disk.get_total_space();
+
+        // the reserved clusters and the 7 + 1 clusters of the files are used
+        let cluster_size = disk.boot_sector.cluster_size as u64;
+        let used_clusters = disk.boot_sector.get_reserved_clusters_count() as u64 + 8;
+        assert_eq!(used_clusters * cluster_size + free_space, total_space);
+        assert_eq!(
+            total_space,
+            disk.boot_sector.cluster_count as u64 * cluster_size
+        );
+    }
+
+    #[test]
+    fn the_fat_slice_shows_the_chain_of_a_file() {
+        let mut disk = TestDisk::new();
+        disk.create("a", "txt", 40).unwrap();
+        let chain = chain_clusters(&mut disk, "a", "txt");
+
+        let fat_slice = disk.get_fat_slice(chain[0], 4);
+
+        assert_eq!(
+            fat_slice,
+            vec![
+                (chain[0], FatValue::Data(chain[1] as u16)),
+                (chain[1], FatValue::Data(chain[2] as u16)),
+                (chain[2], FatValue::EndOfChain),
+                (chain[2] + 1, FatValue::Free),
+            ]
+        );
+        assert_eq!(fat_slice[0].1.to_string(), format!("->{}", chain[0] + 1));
+    }
+
+    #[test]
+    fn the_fat_slice_stops_at_the_end_of_the_fat() {
+        let disk = TestDisk::new();
+        let cluster_count = disk.fat.len();
+
+        assert_eq!(disk.get_fat_slice(cluster_count - 2, 10).len(), 2);
+        assert!(disk.get_fat_slice(cluster_count, 10).is_empty());
+    }
+
+    /// Creates a file of the working directory holding the given bytes (through the temp buffer file).
+    fn create_binary(disk: &mut TestDisk, name: &str, extension: &str, content: &[u8]) -> Void {
+        let temp_file_path = disk.config.lock().unwrap().temp_file_path.clone();
+        std::fs::write(temp_file_path, content)?;
+
+        disk.run(|disk_manager| {
+            disk_manager.create_file(&CreateRequest::new(
+                name.to_string(),
+                extension.to_string(),
+                content.len() as u32,
+                FileEntryAttributes::combine(&[
+                    FileEntryAttributes::File,
+                    FileEntryAttributes::ReadWrite,
+                    FileEntryAttributes::Visible,
+                ]),
+                Utc::now(),
+                ContentType::Temp,
+            ))
+        })
+    }
+
+    /// Bytes which are not valid UTF-8, spanning a few clusters.
+    const BINARY_CONTENT: [u8; 40] = [
+        0xFF, 0xFE, 0x00, 0x80, 0xC3, 0x28, 0xA0, 0xA1, 0xE2, 0x28, 0xA1, 0xF0, 0x28, 0x8C, 0xBC,
+        0x00, 0x01, 0x02, 0x7F, 0x80, 0x81, 0xFD, 0xFE, 0xFF, 0x00, 0xC0, 0xC1, 0xF5, 0xF8, 0xFC,
+        0x0A, 0x0D, 0x1B, 0x9F, 0xBF, 0xEF, 0xBB, 0xBF, 0xED, 0xA0,
+    ];
+
+    #[test]
+    fn non_utf8_bytes_are_read_back_identically() {
+        let mut disk = TestDisk::new();
+        create_binary(&mut disk, "data", "bin", &BINARY_CONTENT).unwrap();
+
+        assert_eq!(disk.read("data", "bin"), BINARY_CONTENT);
+        disk.pull_sync();
+        let content = disk
+            .get_file_content(&CatRequest::new("data".to_string(), "bin".to_string()))
+            .unwrap();
+        assert!(content.contains(char::REPLACEMENT_CHARACTER));
+    }
+
+    #[test]
+    fn copy_and_defragment_keep_non_utf8_bytes_intact() {
+        let mut disk = TestDisk::new();
+        disk.write("gap", "txt", "fills a few clusters of the disk")
+            .unwrap();
+        create_binary(&mut disk, "data", "bin", &BINARY_CONTENT).unwrap();
+        disk.run(|disk_manager| {
+            disk_manager.copy_file(&copy_request("data", "bin", "copy", "bin", false))
+        })
+        .unwrap();
+        disk.delete("gap", "txt").unwrap();
+
+        disk.run(|disk_manager| disk_manager.defragment_disk())
+            .unwrap();
+
+        assert_eq!(disk.read("data", "bin"), BINARY_CONTENT);
+        assert_eq!(disk.read("copy", "bin"), BINARY_CONTENT);
+    }
+
+    #[test]
+    fn copying_a_directory_into_its_own_subtree_is_rejected() {
+        let mut disk = TestDisk::new();
+        disk.mkdir("dir").unwrap();
+        disk.cd("dir").unwrap();
+        disk.mkdir("sub").unwrap();
+        disk.write("file", "txt", "content").unwrap();
+        disk.cd("/").unwrap();
+        let storage_file_path = disk.config.lock().unwrap().storage_file_path.clone();
+        let storage_file_content = std::fs::read(&storage_file_path).unwrap();
+        let expected_error =
+            "Invalid path: cannot copy directory dir into itself or one of its subdirectories";
+
+        let into_itself = disk
+            .run(|disk_manager| disk_manager.copy_file(&copy_request("dir", "", "dir", "", true)));
+        let into_its_subdirectory = disk.run(|disk_manager| {
+            disk_manager.copy_file(&CopyRequest::new(
+                "dir".to_string(),
+                String::new(),
+                "dir".to_string(),
+                String::new(),
+                Some("/dir/sub".to_string()),
+                false,
+            ))
+        });
+
+        assert_eq!(into_itself.unwrap_err().to_string(), expected_error);
+        assert_eq!(
+            into_its_subdirectory.unwrap_err().to_string(),
+            expected_error
+        );
+        assert_eq!(
+            std::fs::read(&storage_file_path).unwrap(),
+            storage_file_content
+        );
+        let paths: Vec<String> = disk
+            .reopen()
+            .walk()
+            .into_iter()
+            .map(|(path, _)| path)
+            .collect();
+        assert_eq!(paths, vec!["/dir", "/dir/sub", "/dir/file.txt"]);
+    }
+
+    /// Returns the path and the content of every file of the tree, in root order.
+    fn read_tree(disk: &DiskManager) -> Vec<(String, Vec<u8>)> {
+        disk.walk()
+            .into_iter()
+            .map(|(path, file_entry)| match file_entry.is_file() {
+                true => (path, disk.read_entry_bytes(&file_entry)),
+                false => (path, Vec::new()),
+            })
+            .collect()
+    }
+
+    /// Builds a tree whose files `a.txt` and `docs/c.txt` are fragmented around the other ones.
+    fn build_fragmented_tree(disk: &mut TestDisk) {
+        disk.create("a", "txt", 40).unwrap();
+        disk.create("b", "txt", 64).unwrap();
+        disk.mkdir("docs").unwrap();
+        disk.cd("docs").unwrap();
+        disk.create("c", "txt", 20).unwrap();
+        disk.write("d", "txt", "the last file of the tree").unwrap();
+        disk.cd("/").unwrap();
+        disk.delete("b", "txt").unwrap();
+        disk.append(
+            "a",
+            "txt",
+            &"spills over the deleted b file and beyond ".repeat(3),
+        )
+        .unwrap();
+        disk.cd("docs").unwrap();
+        disk.append("c", "txt", &"and c takes what a left ".repeat(2))
+            .unwrap();
+        disk.cd("/").unwrap();
+        disk.pull_sync();
+    }
+
+    #[test]
+    fn defragment_makes_every_chain_contiguous_and_keeps_the_tree() {
+        let mut disk = TestDisk::new();
+        build_fragmented_tree(&mut disk);
+        let tree = read_tree(&disk);
+        assert!(
+            disk.build_fragmentation_report()
+                .get_fragmented_transitions_count()
+                > 0
+        );
+        let temp_file_path = disk.config.lock().unwrap().temp_file_path.clone();
+        std::fs::write(&temp_file_path, "left alone").unwrap();
+
+        disk.run(|disk_manager| disk_manager.defragment_disk())
+            .unwrap();
+
+        let mut disk_manager = disk.reopen();
+        assert_eq!(read_tree(&disk_manager), tree);
+        let fragmentation_report = disk_manager.build_fragmentation_report();
+        assert_eq!(fragmentation_report.get_fragmented_transitions_count(), 0);
+        assert!(fragmentation_report
+            .files
+            .iter()
+            .all(|file| !file.is_fragmented()));
+        // the allocated clusters fill the front of the data region
+        let first_free_cluster = *disk_manager.free_cluster_indices.first().unwrap();
+        assert!(disk_manager.fat[first_free_cluster..]
+            .iter()
+            .all(|fat_value| *fat_value == FatValue::Free));
+        assert!(disk_manager.check_disk().is_empty());
+        assert_eq!(std::fs::read(&temp_file_path).unwrap(), b"left alone");
+    }
+
+    #[test]
+    fn the_fragmentation_report_counts_the_jumps_of_each_chain() {
+        let mut disk = TestDisk::new();
+        // a takes 2 clusters, b the next one, then a grows by 2 clusters after b
+        disk.create("a", "txt", 32).unwrap();
+        disk.create("b", "txt", 16).unwrap();
+        disk.append("a", "txt", &"x".repeat(32)).unwrap();
+
+        let fragmentation_report = disk.fragmentation_report();
+
+        assert_eq!(
+            fragmentation_report.files,
+            vec![
+                FileFragmentation {
+                    entry_path: "/a.txt".to_string(),
+                    clusters_count: 4,
+                    fragmented_transitions: 1,
+                },
+                FileFragmentation {
+                    entry_path: "/b.txt".to_string(),
+                    clusters_count: 1,
+                    fragmented_transitions: 0,
+                },
+            ]
+        );
+        assert_eq!(fragmentation_report.get_transitions_count(), 3);
+        assert!((fragmentation_report.get_fragmentation_percentage() - 100.0 / 3.0).abs() < 1e-9);
+    }
+
+    /// Applies `setattr <flags>` to a file entry of the working directory.
+    fn set_attributes(disk: &mut TestDisk, name: &str, extension: &str, flags: &[&str]) -> Void {
+        disk.run(|disk_manager| {
+            disk_manager.set_attributes(&SetAttributesRequest::new(
+                name.to_string(),
+                extension.to_string(),
+                flags.iter().map(|flag| flag.parse().unwrap()).collect(),
+            ))
+        })
+    }
+
+    #[test]
+    fn walk_yields_every_entry_depth_first_with_its_path() {
+        let mut disk = TestDisk::new();
+        disk.write("a", "txt", "a").unwrap();
+        disk.mkdir("docs").unwrap();
+        disk.mkdir("empty").unwrap();
+        disk.write("z", "txt", "z").unwrap();
+        disk.cd("docs").unwrap();
+        disk.write("b", "txt", "b").unwrap();
+        disk.mkdir("sub").unwrap();
+        disk.cd("sub").unwrap();
+        disk.write("c", "md", "c").unwrap();
+        disk.cd("/").unwrap();
+        set_attributes(&mut disk, "z", "txt", &["+h"]).unwrap();
+
+        let walked: Vec<(String, bool)> = disk
+            .reopen()
+            .walk()
+            .into_iter()
+            .map(|(path, file_entry)| (path, file_entry.is_file()))
+            .collect();
+
+        assert_eq!(
+            walked,
+            vec![
+                ("/a.txt".to_string(), true),
+                ("/docs".to_string(), false),
+                ("/docs/b.txt".to_string(), true),
+                ("/docs/sub".to_string(), false),
+                ("/docs/sub/c.md".to_string(), true),
+                ("/empty".to_string(), false),
+                ("/z.txt".to_string(), true),
+            ]
+        );
+    }
+
+    fn mark_bad(disk: &mut TestDisk, cluster_index: usize) -> Void {
+        disk.run(|disk_manager| {
+            disk_manager.mark_bad_cluster(&BadBlocksRequest::new(cluster_index))
+        })
+    }
+
+    #[test]
+    fn a_bad_cluster_is_never_allocated() {
+        let mut disk = TestDisk::new();
+        disk.create("a", "txt", 16).unwrap();
+        let bad_cluster = *disk.free_cluster_indices.iter().nth(1).unwrap();
+        let free_space = disk.get_free_space();
+
+        mark_bad(&mut disk, bad_cluster).unwrap();
+        disk.create("b", "txt", 64).unwrap();
+        disk.append("a", "txt", &"x".repeat(64)).unwrap();
+
+        let mut disk_manager = disk.reopen();
+        assert_eq!(disk_manager.fat[bad_cluster], FatValue::Bad);
+        assert!(!disk_manager.free_cluster_indices.contains(&bad_cluster));
+        for (_, file_entry) in disk_manager.walk() {
+            assert!(!disk_manager
+                .get_chain_clusters(file_entry.first_cluster as usize)
+                .contains(&bad_cluster));
+        }
+        // the bad cluster is neither free nor used by the 4 + 4 new clusters of the files
+        assert_eq!(disk_manager.get_bad_space(), 16);
+        assert_eq!(disk_manager.get_free_space(), free_space - 16 - 8 * 16);
+    }
+
+    #[test]
+    fn only_a_free_cluster_can_be_marked_as_bad() {
+        let mut disk = TestDisk::new();
+        disk.create("a", "txt", 16).unwrap();
+        let used_cluster = chain_clusters(&mut disk, "a", "txt")[0];
+        let free_cluster = *disk.free_cluster_indices.first().unwrap();
+        let cluster_count = disk.fat.len();
+        mark_bad(&mut disk, free_cluster).unwrap();
+
+        assert_eq!(
+            mark_bad(&mut disk, 1).unwrap_err().to_string(),
+            "Cluster 1 is reserved for the boot sector, the FAT or the root table"
+        );
+        assert_eq!(
+            mark_bad(&mut disk, used_cluster).unwrap_err().to_string(),
+            format!("Cluster {} is in use", used_cluster)
+        );
+        assert_eq!(
+            mark_bad(&mut disk, free_cluster).unwrap_err().to_string(),
+            format!("Cluster {} is already marked as bad", free_cluster)
+        );
+        assert_eq!(
+            mark_bad(&mut disk, cluster_count).unwrap_err().to_string(),
+            format!(
+                "Cluster {} is out of bounds (the disk has {} clusters)",
+                cluster_count, cluster_count
+            )
+        );
+    }
+
+    #[test]
+    fn the_clusters_below_the_sentinel_range_are_allocated_and_read_back() {
+        let mut disk = TestDisk::with_config(|config| {
+            config.disk.fat_type = Some(12);
+            config.disk.cluster_count = Some(0x0FF7);
+        });
+        // a write needs a spare free cluster, which is the last one of the disk
+        let size = (disk.get_free_clusters_count() as u32 - 1) * 16;
+
+        disk.create("full", "txt", size).unwrap();
+
+        let clusters = chain_clusters(&mut disk, "full", "txt");
+        assert_eq!(clusters[clusters.len() - 2..], [0x0FF4, 0x0FF5]);
+        assert_eq!(
+            disk.free_cluster_indices
+                .iter()
+                .copied()
+                .collect::<Vec<_>>(),
+            vec![0x0FF6]
+        );
+        let mut disk_manager = disk.reopen();
+        assert_eq!(disk_manager.fat.len(), 0x0FF7);
+        assert_eq!(disk_manager.fat[0x0FF4], FatValue::Data(0x0FF5));
+        assert_eq!(disk_manager.fat[0x0FF5], FatValue::EndOfChain);
+        assert!(disk_manager.check_disk().is_empty());
+        assert_eq!(disk.read("full", "txt").len(), size as usize);
+    }
+
+    fn link(disk: &mut TestDisk, src_name: &str, link_name: &str) -> Void {
+        disk.run(|disk_manager| {
+            disk_manager.link_file(&LinkRequest::new(
+                src_name.to_string(),
+                "txt".to_string(),
+                link_name.to_string(),
+                "txt".to_string(),
+            ))
+        })
+    }
+
+    #[test]
+    fn the_clusters_of_a_linked_file_are_freed_with_its_last_link() {
+        let mut disk = TestDisk::new();
+        let free_cluster_indices = disk.free_cluster_indices.clone();
+        disk.create("a", "txt", 40).unwrap();
+        let clusters = chain_clusters(&mut disk, "a", "txt");
+        link(&mut disk, "a", "b").unwrap();
+        assert_eq!(chain_clusters(&mut disk, "b", "txt"), clusters);
+
+        disk.delete("a", "txt").unwrap();
+
+        let mut disk_manager = disk.reopen();
+        assert!(clusters
+            .iter()
+            .all(|cluster_index| !disk_manager.free_cluster_indices.contains(cluster_index)));
+        assert!(disk_manager.check_disk().is_empty());
+        assert_eq!(
+            disk.read("b", "txt"),
+            "ABCDEFGHIJKLMNOPQRSTUVWXYZABCDEFGHIJKLMN".as_bytes()
+        );
+
+        disk.delete("b", "txt").unwrap();
+
+        assert!(list_names(&mut disk).is_empty());
+        assert_eq!(disk.reopen().free_cluster_indices, free_cluster_indices);
+    }
+
+    #[test]
+    fn a_write_through_a_link_shows_through_the_other_one() {
+        let mut disk = TestDisk::new();
+        disk.write("a", "txt", "shared").unwrap();
+        link(&mut disk, "a", "b").unwrap();
+
+        disk.append("a", "txt", " content, appended through a")
+            .unwrap();
+        assert_eq!(disk.read("b", "txt"), b"shared content, appended through a");
+
+        disk.write("b", "txt", "overwritten through b").unwrap();
+        assert_eq!(disk.read("a", "txt"), b"overwritten through b");
+        assert_eq!(
+            chain_clusters(&mut disk, "a", "txt"),
+            chain_clusters(&mut disk, "b", "txt")
+        );
+        assert!(disk.reopen().check_disk().is_empty());
+    }
+
+    fn resize(disk: &mut TestDisk, cluster_count: u16) -> Void {
+        disk.run(|disk_manager| disk_manager.resize_disk(&ResizeRequest::new(cluster_count)))
+    }
+
+    /// Builds a small tree whose file `docs/c.txt` is fragmented around `b.txt`.
+    fn build_resized_tree(disk: &mut TestDisk) {
+        disk.create("a", "txt", 40).unwrap();
+        disk.mkdir("docs").unwrap();
+        disk.cd("docs").unwrap();
+        disk.write("c", "txt", "the first part").unwrap();
+        disk.cd("/").unwrap();
+        disk.create("b", "txt", 100).unwrap();
+        disk.cd("docs").unwrap();
+        disk.append("c", "txt", " and the second part, after b")
+            .unwrap();
+        disk.cd("/").unwrap();
+        disk.pull_sync();
+    }
+
+    #[test]
+    fn growing_the_disk_keeps_the_files_and_adds_free_clusters() {
+        let mut disk = TestDisk::new();
+        build_resized_tree(&mut disk);
+        let tree = read_tree(&disk);
+        let free_clusters_count = disk.get_free_clusters_count();
+
+        resize(&mut disk, 9000).unwrap();
+
+        let mut disk_manager = disk.reopen();
+        assert_eq!(disk_manager.boot_sector.cluster_count, 9000);
+        assert_eq!(disk_manager.fat.len(), 9000);
+        assert_eq!(read_tree(&disk_manager), tree);
+        // the FAT of the 808 new clusters takes 101 clusters of its own
+        assert_eq!(
+            disk_manager.get_free_clusters_count(),
+            free_clusters_count + 808 - 101
+        );
+        assert!(disk_manager.check_disk().is_empty());
+    }
+
+    #[test]
+    fn shrinking_the_disk_keeps_the_files() {
+        let mut disk = TestDisk::new();
+        build_resized_tree(&mut disk);
+        let tree = read_tree(&disk);
+
+        resize(&mut disk, 1000).unwrap();
+
+        let mut disk_manager = disk.reopen();
+        assert_eq!(disk_manager.boot_sector.cluster_count, 1000);
+        assert_eq!(read_tree(&disk_manager), tree);
+        assert!(disk_manager.check_disk().is_empty());
+        // the resized disk is used as any other one
+        disk.create("d", "txt", 16).unwrap();
+        assert_eq!(disk.read("d", "txt"), b"ABCDEFGHIJKLMNOP");
+    }
+
+    #[test]
+    fn shrinking_the_disk_below_its_used_clusters_is_refused() {
+        let mut disk = TestDisk::new();
+        build_resized_tree(&mut disk);
+        disk.create("big", "bin", 800 * 16).unwrap();
+        let storage_file_path = disk.config.lock().unwrap().storage_file_path.clone();
+        let storage_file_content = std::fs::read(&storage_file_path).unwrap();
+
+        let result = resize(&mut disk, 1000);
+
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .ends_with("don't fit in the 746 free cluster(s) of the resized disk"));
+        assert_eq!(
+            std::fs::read(&storage_file_path).unwrap(),
+            storage_file_content
+        );
+        assert_eq!(disk.reopen().boot_sector.cluster_count, 8192);
+    }
+
+    /// Runs `undo` against the disk as the shell does, returning whether there was a command to undo.
+    fn undo(disk: &mut TestDisk) -> Result<bool, Box<dyn Error>> {
+        disk.pull_sync();
+        let undone = disk.restore_snapshot()?;
+        disk.push_sync();
+        disk.snapshot();
+
+        Ok(undone)
+    }
+
+    #[test]
+    fn undo_of_a_create_removes_the_file_and_restores_the_free_space() {
+        let mut disk = TestDisk::new();
+        disk.write("kept", "txt", "written before").unwrap();
+        disk.snapshot();
+        let free_cluster_indices = disk.free_cluster_indices.clone();
+        let storage_file_path = disk.config.lock().unwrap().storage_file_path.clone();
+        let storage_file_content = std::fs::read(&storage_file_path).unwrap();
+        disk.create("a", "txt", 100).unwrap();
+        disk.snapshot();
+
+        assert!(undo(&mut disk).unwrap());
+
+        assert_eq!(list_names(&mut disk), vec!["kept.txt"]);
+        assert_eq!(disk.free_cluster_indices, free_cluster_indices);
+        assert_eq!(disk.read("kept", "txt"), b"written before");
+        assert_eq!(
+            std::fs::read(&storage_file_path).unwrap(),
+            storage_file_content
+        );
+        assert_eq!(disk.reopen().free_cluster_indices, free_cluster_indices);
+    }
+
+    #[test]
+    fn undo_without_a_prior_command_does_nothing() {
+        let mut disk = TestDisk::new();
+        let storage_file_path = disk.config.lock().unwrap().storage_file_path.clone();
+        let storage_file_content = std::fs::read(&storage_file_path).unwrap();
+        let free_cluster_indices = disk.free_cluster_indices.clone();
+
+        assert!(!undo(&mut disk).unwrap());
+
+        assert!(list_names(&mut disk).is_empty());
+        assert_eq!(disk.free_cluster_indices, free_cluster_indices);
+        assert_eq!(
+            std::fs::read(&storage_file_path).unwrap(),
+            storage_file_content
+        );
+    }
+
+    #[test]
+    fn redo_after_undo_returns_to_the_state_after_the_command() {
+        let mut disk = TestDisk::new();
+        disk.write("a", "txt", "first").unwrap();
+        disk.snapshot();
+        disk.write("a", "txt", "second, spanning a few clusters")
+            .unwrap();
+        disk.snapshot();
+        let storage_file_path = disk.config.lock().unwrap().storage_file_path.clone();
+        let storage_file_content = std::fs::read(&storage_file_path).unwrap();
+        let free_cluster_indices = disk.free_cluster_indices.clone();
+        undo(&mut disk).unwrap();
+        assert_eq!(disk.read("a", "txt"), b"first");
+
+        disk.pull_sync();
+        assert!(disk.reapply_snapshot().unwrap());
+        disk.push_sync();
+        disk.snapshot();
+
+        assert_eq!(disk.read("a", "txt"), b"second, spanning a few clusters");
+        assert_eq!(disk.free_cluster_indices, free_cluster_indices);
+        assert_eq!(
+            std::fs::read(&storage_file_path).unwrap(),
+            storage_file_content
+        );
+        // the reapplied command can be undone again, while there is nothing left to redo
+        assert!(!disk.reapply_snapshot().unwrap());
+        assert!(undo(&mut disk).unwrap());
+        assert_eq!(disk.read("a", "txt"), b"first");
+    }
+
+    #[test]
+    fn the_entry_past_the_quota_of_the_root_is_rejected() {
+        let mut disk = TestDisk::with_config(|config| config.max_directory_entries = 3);
+        disk.create("a", "txt", 10).unwrap();
+        disk.write("b", "txt", "b").unwrap();
+        disk.mkdir("c").unwrap();
+        let expected_error =
+            "The directory / has reached its quota (3/3 entries used, see max_directory_entries in the config)";
+
+        assert_eq!(
+            disk.create("d", "txt", 10).unwrap_err().to_string(),
+            expected_error
+        );
+        assert_eq!(disk.mkdir("d").unwrap_err().to_string(), expected_error);
+        assert_eq!(list_names(&mut disk), vec!["a.txt", "b.txt", "c"]);
+        // a freed entry makes room for a new one
+        disk.delete("a", "txt").unwrap();
+        disk.create("d", "txt", 10).unwrap();
+    }
+
+    #[test]
+    fn the_entry_past_the_quota_of_a_subdirectory_is_rejected() {
+        let mut disk = TestDisk::with_config(|config| config.max_directory_entries = 2);
+        disk.mkdir("dir").unwrap();
+        disk.cd("dir").unwrap();
+        disk.create("a", "txt", 10).unwrap();
+        disk.mkdir("sub").unwrap();
+
+        assert_eq!(
+            disk.write("c", "txt", "c").unwrap_err().to_string(),
+            "The directory /dir has reached its quota (2/2 entries used, see max_directory_entries in the config)"
+        );
+        assert_eq!(list_names(&mut disk), vec!["a.txt", "sub"]);
+        // overwriting an entry of a full directory only replaces it
+        disk.write("a", "txt", "overwritten").unwrap();
+        assert_eq!(disk.read("a", "txt"), b"overwritten");
+        // the quota applies to each directory on its own
+        disk.cd("sub").unwrap();
+        disk.create("a", "txt", 10).unwrap();
+        disk.create("b", "txt", 10).unwrap();
+    }
+
+    fn trash(disk: &mut TestDisk, name: &str, extension: &str) -> Void {
+        disk.run(|disk_manager| {
+            disk_manager.move_to_trash(&DeleteRequest::new(
+                name.to_string(),
+                extension.to_string(),
+                true,
+                false,
+                false,
+            ))
+        })
+    }
+
+    fn restore(disk: &mut TestDisk, name: &str, extension: &str) -> Void {
+        disk.run(|disk_manager| disk_manager.restore_from_trash(name, extension))
+    }
+
+    /// Returns the full names of the entries of the trash.
+    fn trash_names(disk: &mut TestDisk) -> Vec<String> {
+        disk.pull_sync();
+        disk.list_trash()
+            .iter()
+            .map(|file_entry| file_entry.get_full_name())
+            .collect()
+    }
+
+    #[test]
+    fn a_trashed_file_is_restored_with_its_content() {
+        let mut disk = TestDisk::new();
+        disk.mkdir("docs").unwrap();
+        disk.cd("docs").unwrap();
+        disk.write("a", "txt", "kept while in the trash").unwrap();
+        let clusters = chain_clusters(&mut disk, "a", "txt");
+
+        trash(&mut disk, "a", "txt").unwrap();
+
+        assert!(list_names(&mut disk).is_empty());
+        assert_eq!(trash_names(&mut disk), vec!["a.txt"]);
+        // the entry has been reparented, its clusters are still allocated
+        assert!(clusters
+            .iter()
+            .all(|cluster_index| !disk.free_cluster_indices.contains(cluster_index)));
+
+        restore(&mut disk, "a", "txt").unwrap();
+
+        assert_eq!(list_names(&mut disk), vec!["a.txt"]);
+        assert!(trash_names(&mut disk).is_empty());
+        assert_eq!(chain_clusters(&mut disk, "a", "txt"), clusters);
+        assert_eq!(disk.read("a", "txt"), b"kept while in the trash");
+    }
+
+    #[test]
+    fn a_trashed_directory_is_restored_with_its_subtree() {
+        let mut disk = TestDisk::new();
+        disk.mkdir("dir").unwrap();
+        disk.cd("dir").unwrap();
+        disk.mkdir("sub").unwrap();
+        disk.write("file", "txt", "nested").unwrap();
+        disk.cd("/").unwrap();
+
+        trash(&mut disk, "dir", "").unwrap();
+        assert_eq!(list_names(&mut disk), vec![".trash"]);
+        restore(&mut disk, "dir", "").unwrap();
+
+        let paths: Vec<String> = disk
+            .reopen()
+            .walk()
+            .into_iter()
+            .map(|(path, _)| path)
+            .collect();
+        assert_eq!(paths, vec!["/dir", "/dir/sub", "/dir/file.txt", "/.trash"]);
+        disk.cd("dir").unwrap();
+        assert_eq!(disk.read("file", "txt"), b"nested");
+    }
+
+    #[test]
+    fn restoring_onto_an_existing_name_is_rejected() {
+        let mut disk = TestDisk::new();
+        disk.write("a", "txt", "trashed").unwrap();
+        trash(&mut disk, "a", "txt").unwrap();
+        disk.write("a", "txt", "written since").unwrap();
+
+        assert_eq!(
+            restore(&mut disk, "a", "txt").unwrap_err().to_string(),
+            "a.txt already exists"
+        );
+        assert_eq!(disk.read("a", "txt"), b"written since");
+        assert_eq!(trash_names(&mut disk), vec!["a.txt"]);
+
+        // once the name is free, the trashed file comes back
+        disk.delete("a", "txt").unwrap();
+        restore(&mut disk, "a", "txt").unwrap();
+        assert_eq!(disk.read("a", "txt"), b"trashed");
+    }
+
+    #[test]
+    fn emptying_the_trash_frees_its_clusters() {
+        let mut disk = TestDisk::new();
+        let free_cluster_indices = disk.free_cluster_indices.clone();
+        disk.create("a", "txt", 100).unwrap();
+        trash(&mut disk, "a", "txt").unwrap();
+
+        disk.run(|disk_manager| disk_manager.empty_trash()).unwrap();
+
+        assert!(list_names(&mut disk).is_empty());
+        assert_eq!(disk.reopen().free_cluster_indices, free_cluster_indices);
+        assert_eq!(
+            restore(&mut disk, "a", "txt").unwrap_err().to_string(),
+            "a.txt is not in the trash"
+        );
+    }
+
+    fn write_cluster(disk: &mut TestDisk, cluster_index: usize, data: &[u8]) -> Void {
+        disk.run(|disk_manager| {
+            disk_manager.write_cluster(&WriteClusterRequest::new(cluster_index, data.to_vec()))
+        })
+    }
+
+    #[test]
+    fn the_clusters_of_a_created_file_are_read_back_raw() {
+        let mut disk = TestDisk::new();
+        disk.create("a", "txt", 20).unwrap();
+        let clusters = chain_clusters(&mut disk, "a", "txt");
+
+        assert_eq!(clusters.len(), 2);
+        assert_eq!(disk.read_cluster(clusters[0]).unwrap(), b"ABCDEFGHIJKLMNOP");
+        assert_eq!(&disk.read_cluster(clusters[1]).unwrap()[..4], b"QRST");
+    }
+
+    #[test]
+    fn a_written_cluster_is_read_back_while_the_fat_is_left_alone() {
+        let mut disk = TestDisk::new();
+        disk.create("a", "txt", 20).unwrap();
+        let clusters = chain_clusters(&mut disk, "a", "txt");
+
+        write_cluster(&mut disk, clusters[0], b"xyz").unwrap();
+
+        let mut expected_cluster = b"xyz".to_vec();
+        expected_cluster.resize(16, 0);
+        assert_eq!(
+            disk.reopen().read_cluster(clusters[0]).unwrap(),
+            expected_cluster
+        );
+        assert_eq!(chain_clusters(&mut disk, "a", "txt"), clusters);
+        assert_eq!(&disk.read("a", "txt")[..3], b"xyz");
+    }
+
+    #[test]
+    fn reserved_and_out_of_range_clusters_are_rejected() {
+        let mut disk = TestDisk::new();
+        let cluster_count = disk.boot_sector.cluster_count as usize;
+        let reserved_clusters_count = disk.boot_sector.get_reserved_clusters_count() as usize;
+        let first_data_cluster = *disk.free_cluster_indices.first().unwrap();
+        let out_of_bounds_error = format!(
+            "Cluster {} is out of bounds (the disk has {} clusters)",
+            cluster_count, cluster_count
+        );
+
+        assert_eq!(
+            disk.read_cluster(cluster_count).unwrap_err().to_string(),
+            out_of_bounds_error
+        );
+        assert_eq!(
+            write_cluster(&mut disk, cluster_count, b"x")
+                .unwrap_err()
+                .to_string(),
+            out_of_bounds_error
+        );
+        for cluster_index in [0, reserved_clusters_count - 1] {
+            assert_eq!(
+                write_cluster(&mut disk, cluster_index, b"x")
+                    .unwrap_err()
+                    .to_string(),
+                format!(
+                    "Cluster {} is reserved for the boot sector, the FAT or the root table",
+                    cluster_index
+                )
+            );
+        }
+        assert_eq!(
+            write_cluster(&mut disk, first_data_cluster, &[0; 17])
+                .unwrap_err()
+                .to_string(),
+            "17 B don't fit in a cluster of 16 B"
+        );
+        // the reserved clusters can still be inspected
+        assert!(disk.read_cluster(0).is_ok());
+    }
 }
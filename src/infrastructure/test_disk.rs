@@ -0,0 +1,193 @@
+use crate::application::commands::cd::ChangeDirectoryRequest;
+use crate::application::commands::create::CreateRequest;
+use crate::application::commands::del::DeleteRequest;
+use crate::application::commands::echo::EchoRequest;
+use crate::application::commands::mkdir::MakeDirectoryRequest;
+use crate::application::queries::cat::CatRequest;
+use crate::application::Void;
+use crate::core::config::Config;
+use crate::core::content_type::ContentType;
+use crate::core::Arm;
+use crate::domain::file_entry::FileEntryAttributes;
+use crate::domain::i_disk_manager::IDiskManager;
+use crate::infrastructure::disk_manager::DiskManager;
+use chrono::Utc;
+use std::ops::{Deref, DerefMut};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// The number of test disks created so far by the process, which makes the name of their folders unique
+static TEST_DISKS_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+/// TestDisk is a disk manager opened on a storage file of its own, in a temp folder removed along with it,
+/// so that the tests can run in parallel.
+///
+/// The operations it provides run like the commands of the shell do: the disk is reloaded from its storage file first
+/// and, if the operation succeeds, its modifications are persisted to the storage file.
+pub(crate) struct TestDisk {
+    pub(crate) disk_manager: DiskManager,
+    pub(crate) config: Arm<Config>,
+    dir_path: PathBuf,
+}
+
+impl TestDisk {
+    /// Opens a new disk with the default configuration.
+    pub(crate) fn new() -> Self {
+        Self::with_config(|_| {})
+    }
+
+    /// Opens a new disk with the default configuration updated by the given closure
+    /// (the paths of the files of the disk being already set to the temp folder).
+    pub(crate) fn with_config(configure: impl FnOnce(&mut Config)) -> Self {
+        let dir_path = std::env::temp_dir().join(format!(
+            "rodos-test-{}-{}",
+            std::process::id(),
+            TEST_DISKS_COUNT.fetch_add(1, Ordering::SeqCst)
+        ));
+        std::fs::create_dir_all(&dir_path).expect("Unable to create the test disk folder");
+        let file_path = |file_name: &str| dir_path.join(file_name).to_string_lossy().into_owned();
+
+        let mut config = Config {
+            disk_dir_path: dir_path.to_string_lossy().into_owned(),
+            storage_file_path: file_path("storage.bin"),
+            stdin_file_path: file_path("stdin.in"),
+            temp_file_path: file_path("temp"),
+            history_file_path: file_path("history.txt"),
+            audit_log_file_path: String::new(),
+            confirm_destructive: false,
+            ..Config::default()
+        };
+        configure(&mut config);
+        std::fs::write(&config.stdin_file_path, "").expect("Unable to create the stdin file");
+        std::fs::write(&config.temp_file_path, "").expect("Unable to create the temp file");
+
+        let config = Arc::new(Mutex::new(config));
+        let disk_manager = DiskManager::open(config.clone()).expect("Unable to open the test disk");
+
+        Self {
+            disk_manager,
+            config,
+            dir_path,
+        }
+    }
+
+    /// Opens the storage file of the disk in a new disk manager, as the next session would.
+    pub(crate) fn reopen(&self) -> DiskManager {
+        DiskManager::open(self.config.clone()).expect("Unable to reopen the test disk")
+    }
+
+    /// Runs an operation against the disk reloaded from its storage file, persisting it if it succeeds.
+    pub(crate) fn run(&mut self, operation: impl FnOnce(&mut DiskManager) -> Void) -> Void {
+        self.disk_manager.pull_sync();
+        operation(&mut self.disk_manager)?;
+        self.disk_manager.push_sync();
+
+        Ok(())
+    }
+
+    /// Creates a file of the given size filled with A-Z in the working directory.
+    pub(crate) fn create(&mut self, name: &str, extension: &str, size: u32) -> Void {
+        self.run(|disk_manager| {
+            disk_manager.create_file(&CreateRequest::new(
+                name.to_string(),
+                extension.to_string(),
+                size,
+                FileEntryAttributes::combine(&[
+                    FileEntryAttributes::File,
+                    FileEntryAttributes::ReadWrite,
+                    FileEntryAttributes::Visible,
+                ]),
+                Utc::now(),
+                ContentType::Alpha,
+            ))
+        })
+    }
+
+    /// Writes the given content into a file of the working directory, creating it if it doesn't exist.
+    pub(crate) fn write(&mut self, name: &str, extension: &str, content: &str) -> Void {
+        self.run(|disk_manager| {
+            disk_manager.write_file_content(&EchoRequest::new(
+                name.to_string(),
+                extension.to_string(),
+                content.to_string(),
+                false,
+            ))
+        })
+    }
+
+    /// Appends the given content to a file of the working directory, creating it if it doesn't exist.
+    pub(crate) fn append(&mut self, name: &str, extension: &str, content: &str) -> Void {
+        self.run(|disk_manager| {
+            disk_manager.append_to_file(&EchoRequest::new(
+                name.to_string(),
+                extension.to_string(),
+                content.to_string(),
+                true,
+            ))
+        })
+    }
+
+    /// Returns the content of a file of the working directory.
+    pub(crate) fn read(&mut self, name: &str, extension: &str) -> Vec<u8> {
+        self.disk_manager.pull_sync();
+        self.disk_manager
+            .read_file_bytes(&CatRequest::new(name.to_string(), extension.to_string()))
+            .expect("Unable to read the file")
+    }
+
+    /// Deletes a file or a directory (along with its whole content) of the working directory.
+    pub(crate) fn delete(&mut self, name: &str, extension: &str) -> Void {
+        self.run(|disk_manager| {
+            disk_manager.delete_file(&DeleteRequest::new(
+                name.to_string(),
+                extension.to_string(),
+                true,
+                false,
+                false,
+            ))
+        })
+    }
+
+    /// Creates a directory in the working directory.
+    pub(crate) fn mkdir(&mut self, name: &str) -> Void {
+        self.run(|disk_manager| {
+            disk_manager.make_directory(&MakeDirectoryRequest::new(
+                name.to_string(),
+                FileEntryAttributes::combine(&[
+                    FileEntryAttributes::Directory,
+                    FileEntryAttributes::ReadWrite,
+                    FileEntryAttributes::Visible,
+                ]),
+                Utc::now(),
+            ))
+        })
+    }
+
+    /// Changes the working directory along a (relative or absolute) path.
+    pub(crate) fn cd(&mut self, path: &str) -> Void {
+        self.run(|disk_manager| {
+            disk_manager.change_working_directory(&ChangeDirectoryRequest::new(path.to_string()))
+        })
+    }
+}
+
+impl Deref for TestDisk {
+    type Target = DiskManager;
+
+    fn deref(&self) -> &Self::Target {
+        &self.disk_manager
+    }
+}
+
+impl DerefMut for TestDisk {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.disk_manager
+    }
+}
+
+impl Drop for TestDisk {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.dir_path);
+    }
+}
@@ -0,0 +1,682 @@
+use crate::application::commands::badblocks::BadBlocksHandler;
+use crate::application::commands::cd::ChangeDirectoryHandler;
+use crate::application::commands::cp::CopyHandler;
+use crate::application::commands::create::CreateHandler;
+use crate::application::commands::defrag::DefragmentHandler;
+use crate::application::commands::del::DeleteHandler;
+use crate::application::commands::echo::EchoHandler;
+use crate::application::commands::fmt::FormatHandler;
+use crate::application::commands::import::ImportHandler;
+use crate::application::commands::label::LabelHandler;
+use crate::application::commands::ln::LinkHandler;
+use crate::application::commands::mkdir::MakeDirectoryHandler;
+use crate::application::commands::mount::MountHandler;
+use crate::application::commands::redo::RedoHandler;
+use crate::application::commands::rename::RenameHandler;
+use crate::application::commands::resize::ResizeHandler;
+use crate::application::commands::rmdir::RemoveDirectoryHandler;
+use crate::application::commands::setattr::SetAttributesHandler;
+use crate::application::commands::trash::TrashHandler;
+use crate::application::commands::truncate::TruncateHandler;
+use crate::application::commands::umount::UnmountHandler;
+use crate::application::commands::undo::UndoHandler;
+use crate::application::commands::writecluster::WriteClusterHandler;
+use crate::application::queries::bootinfo::BootInfoHandler;
+use crate::application::queries::cat::CatHandler;
+use crate::application::queries::chkdsk::CheckDiskHandler;
+use crate::application::queries::cksum::CksumHandler;
+use crate::application::queries::clear::ClearHandler;
+use crate::application::queries::df::DfHandler;
+use crate::application::queries::diff::DiffHandler;
+use crate::application::queries::dumpfat::DumpFatHandler;
+use crate::application::queries::export::ExportHandler;
+use crate::application::queries::fraginfo::FragInfoHandler;
+use crate::application::queries::grep::GrepHandler;
+use crate::application::queries::head::HeadHandler;
+use crate::application::queries::help::HelpHandler;
+use crate::application::queries::hexdump::HexdumpHandler;
+use crate::application::queries::ls::ListHandler;
+use crate::application::queries::neofetch::NeofetchHandler;
+use crate::application::queries::pwd::PwdHandler;
+use crate::application::queries::readcluster::ReadClusterHandler;
+use crate::application::queries::stat::StatHandler;
+use crate::application::queries::tail::TailHandler;
+use crate::application::queries::version::VersionHandler;
+use crate::application::queries::wc::WcHandler;
+use crate::core::audit_log::{AuditLog, AuditRecord};
+use crate::core::autosave;
+use crate::core::cli_parser::CliParser;
+use crate::core::completion::Completer;
+use crate::core::config::Config;
+use crate::core::confirmation_prompt::StdinConfirmationPrompt;
+use crate::core::line_editor::LineEditor;
+use crate::core::mount_table::MountTable;
+use crate::core::Arm;
+use crate::domain::i_disk_manager::IDiskManager;
+use crate::infrastructure::disk_manager::DiskManager;
+use color_print::{cprint, cprintln};
+use lazy_static::lazy_static;
+use log4rs::append::file::FileAppender;
+use log4rs::config::{Appender, Logger, Root};
+use log4rs::encode::pattern::PatternEncoder;
+use mediator::{DefaultMediator, Mediator};
+use std::io::Write;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+mod application;
+mod core;
+mod domain;
+mod filesystem;
+mod infrastructure;
+
+pub use crate::filesystem::{EntryInfo, Filesystem};
+
+/// The environment variable overriding the path of the config file of the shell
+const CONFIG_FILE_PATH_VAR: &str = "RODOS_CONFIG";
+
+/// The path of the config file of the shell, relative to the working directory of the process
+const DEFAULT_CONFIG_FILE_PATH: &str = "config/config.toml";
+
+/// The path of the log4rs config file, relative to the working directory of the process
+const LOG_CONFIG_FILE_PATH: &str = "config/log4rs.yaml";
+
+/// The path of the log file used when the log4rs config file is missing or invalid
+const DEFAULT_LOG_FILE_PATH: &str = "logs/rodos.logs";
+
+// default wiring of the shell (the disk manager and the mediator can be built from any config instead)
+lazy_static! {
+    /// Config is a singleton that holds the configuration for the entire application
+    pub(crate) static ref CONFIG: Config = {
+        let config_file_path = std::env::var(CONFIG_FILE_PATH_VAR)
+            .unwrap_or_else(|_| DEFAULT_CONFIG_FILE_PATH.to_string());
+
+        Config::load(&config_file_path).unwrap_or_else(|err| {
+            error!("Error: {}", err);
+            log::error!("{}", err);
+            std::process::exit(1);
+        })
+    };
+    pub(crate) static ref CONFIG_ARC: Arm<Config> = Arc::new(Mutex::new(CONFIG.clone()));
+    /// Disk manager singleton wrapped in an Arc<Mutex<>>, shared by the command handlers and the autosave thread
+    pub(crate) static ref DISK_ARC: Arm<dyn IDiskManager> = {
+        let disk_manager = DiskManager::open(CONFIG_ARC.clone()).unwrap_or_else(|err| {
+            error!("Error: {}", err);
+            log::error!("{}", err);
+            std::process::exit(1);
+        });
+
+        Arc::new(Mutex::new(disk_manager))
+    };
+    /// The disks mounted next to the main one, keyed by their labels
+    pub(crate) static ref MOUNT_TABLE: Arm<MountTable> = Arc::new(Mutex::new(MountTable::new(
+        CONFIG.storage_file_path.clone(),
+        open_mounted_disk
+    )));
+    /// Audit log every executed command is appended to
+    pub(crate) static ref AUDIT_LOG: AuditLog = AuditLog::new(CONFIG.audit_log_file_path.clone());
+    /// The mediator is responsible for redirecting commands to the appropriate handlers
+    pub(crate) static ref MEDIATOR: DefaultMediator =
+        build_mediator(CONFIG_ARC.clone(), DISK_ARC.clone(), MOUNT_TABLE.clone());
+}
+
+/// Returns the disk managers of the mounted disks and of the main one.
+fn get_disk_managers() -> Vec<Arm<dyn IDiskManager>> {
+    let mut disk_managers = MOUNT_TABLE.lock().unwrap().disk_managers();
+    disk_managers.push(DISK_ARC.clone());
+    disk_managers
+}
+
+/// Opens the disk stored in the given storage file with the config of the shell, to be mounted next to the main disk.
+fn open_mounted_disk(image_path: &str, read_only: bool) -> Result<Arm<dyn IDiskManager>, String> {
+    let config = Config {
+        storage_file_path: image_path.to_string(),
+        read_only,
+        ..CONFIG.clone()
+    };
+    let disk_manager = DiskManager::open(Arc::new(Mutex::new(config)))?;
+
+    Ok(Arc::new(Mutex::new(disk_manager)))
+}
+
+/// Builds the mediator redirecting the commands to handlers working with the given config, disk manager
+/// and table of mounted disks.
+pub(crate) fn build_mediator(
+    config: Arm<Config>,
+    disk_manager: Arm<dyn IDiskManager>,
+    mount_table: Arm<MountTable>,
+) -> DefaultMediator {
+    DefaultMediator::builder()
+        .add_handler(HelpHandler::new(config.clone()))
+        .add_handler(NeofetchHandler::new(config.clone(), disk_manager.clone()))
+        .add_handler(VersionHandler::new(config.clone()))
+        .add_handler(ClearHandler::new())
+        .add_handler(CreateHandler::new(config.clone(), disk_manager.clone()))
+        .add_handler(ListHandler::new(disk_manager.clone()))
+        .add_handler(RenameHandler::new(disk_manager.clone()))
+        .add_handler(DeleteHandler::new(
+            config.clone(),
+            disk_manager.clone(),
+            Box::new(StdinConfirmationPrompt),
+        ))
+        .add_handler(CatHandler::new(disk_manager.clone()))
+        .add_handler(CopyHandler::new(
+            config.clone(),
+            disk_manager.clone(),
+            mount_table.clone(),
+        ))
+        .add_handler(LinkHandler::new(disk_manager.clone()))
+        .add_handler(FormatHandler::new(
+            config.clone(),
+            disk_manager.clone(),
+            Box::new(StdinConfirmationPrompt),
+        ))
+        .add_handler(DefragmentHandler::new(disk_manager.clone()))
+        .add_handler(ResizeHandler::new(disk_manager.clone()))
+        .add_handler(UndoHandler::new(disk_manager.clone()))
+        .add_handler(RedoHandler::new(disk_manager.clone()))
+        .add_handler(LabelHandler::new(disk_manager.clone()))
+        .add_handler(TrashHandler::new(disk_manager.clone()))
+        .add_handler(ReadClusterHandler::new(disk_manager.clone()))
+        .add_handler(WriteClusterHandler::new(disk_manager.clone()))
+        .add_handler(BadBlocksHandler::new(disk_manager.clone()))
+        .add_handler(SetAttributesHandler::new(disk_manager.clone()))
+        .add_handler(MakeDirectoryHandler::new(disk_manager.clone()))
+        .add_handler(RemoveDirectoryHandler::new(disk_manager.clone()))
+        .add_handler(ChangeDirectoryHandler::new(disk_manager.clone()))
+        .add_handler(PwdHandler::new(disk_manager.clone()))
+        .add_handler(EchoHandler::new(disk_manager.clone()))
+        .add_handler(TruncateHandler::new(disk_manager.clone()))
+        .add_handler(CheckDiskHandler::new(disk_manager.clone()))
+        .add_handler(FragInfoHandler::new(disk_manager.clone()))
+        .add_handler(StatHandler::new(disk_manager.clone()))
+        .add_handler(DfHandler::new(disk_manager.clone()))
+        .add_handler(BootInfoHandler::new(disk_manager.clone()))
+        .add_handler(DumpFatHandler::new(disk_manager.clone()))
+        .add_handler(ExportHandler::new(disk_manager.clone()))
+        .add_handler(ImportHandler::new(config.clone(), disk_manager.clone()))
+        .add_handler(HexdumpHandler::new(disk_manager.clone()))
+        .add_handler(CksumHandler::new(disk_manager.clone()))
+        .add_handler(DiffHandler::new(disk_manager.clone()))
+        .add_handler(GrepHandler::new(disk_manager.clone()))
+        .add_handler(HeadHandler::new(disk_manager.clone()))
+        .add_handler(TailHandler::new(disk_manager.clone()))
+        .add_handler(WcHandler::new(disk_manager.clone()))
+        .add_handler(MountHandler::new(mount_table.clone()))
+        .add_handler(UnmountHandler::new(mount_table.clone()))
+        .build()
+}
+
+/// Run RoDOS as configured by `config/config.toml` (or by the config file given by the `RODOS_CONFIG` variable): a script (`--script <script_path>`), a one-shot command given as
+/// process arguments or, by default, the interactive REPL.
+pub fn run() {
+    init_logger();
+    let mut mediator = MEDIATOR.clone();
+
+    log::info!("RoDOS is booting up...");
+
+    // with the autosave, the modifications are written once the disks are idle (and in any case before shutting down)
+    if CONFIG.autosave_delay_ms > 0 {
+        autosave::spawn(
+            Duration::from_millis(CONFIG.autosave_delay_ms),
+            get_disk_managers,
+        );
+    }
+
+    let args: Vec<String> = std::env::args().skip(1).collect();
+
+    // if a script is given, run its commands one by one and exit (batch mode)
+    if args.first().map(String::as_str) == Some("--script") {
+        let script_path = match args.get(1) {
+            Some(script_path) => script_path,
+            None => {
+                info!("Usage: rodos --script <script_path> [--continue-on-error]");
+                std::process::exit(1);
+            }
+        };
+        let continue_on_error = args.iter().skip(2).any(|arg| arg == "--continue-on-error");
+
+        let succeeded = run_script(&mut mediator, script_path, continue_on_error);
+        autosave::flush_all(get_disk_managers);
+        std::process::exit(if succeeded { 0 } else { 1 });
+    }
+
+    // if a command is given as process arguments, run it once and exit (non-interactive mode)
+    // (`--version` is accepted as an alias of the `version` command)
+    if !args.is_empty() {
+        let input = match args.first().map(String::as_str) {
+            Some("--version") => "version".to_string(),
+            _ => args.join(" "),
+        };
+        log::info!("Running one-shot command: {}", input);
+
+        let succeeded = dispatch_command(&mut mediator, input);
+        autosave::flush_all(get_disk_managers);
+        std::process::exit(if succeeded { 0 } else { 1 });
+    }
+
+    let completer = Completer::new(CONFIG.commands.keys().cloned().collect(), DISK_ARC.clone());
+    let mut line_editor = LineEditor::new(
+        CONFIG.history_file_path.clone(),
+        CONFIG.history_size,
+        completer,
+        || {
+            prompt!();
+        },
+    );
+
+    loop {
+        prompt!();
+
+        // read input from stdin (with line editing and history recall when it is a terminal)
+        let input = match line_editor.read_line() {
+            Ok(Some(input)) => {
+                log::info!("Read {} bytes from stdin", input.len());
+                input
+            }
+            // the input is exhausted (e.g. Ctrl-D or the end of a piped input), so shut down as on `exit`
+            Ok(None) => "exit".to_string(),
+            Err(err) => {
+                warn!("Unable to read input, please try again!");
+
+                log::warn!("Unable to read input, please try again! Error: {}", err);
+                continue;
+            }
+        };
+
+        dispatch_command(&mut mediator, input);
+    }
+}
+
+/// Run the commands of the given script file line by line, skipping empty lines and `#` comments,
+/// and stop at the first failing command unless `continue_on_error` is set
+/// (returns whether all the commands were handled successfully)
+fn run_script(mediator: &mut DefaultMediator, script_path: &str, continue_on_error: bool) -> bool {
+    let script = match std::fs::read_to_string(script_path) {
+        Ok(script) => script,
+        Err(err) => {
+            error!("Error: Unable to read script {}: {}", script_path, err);
+            log::error!("Unable to read script {}: {}", script_path, err);
+            return false;
+        }
+    };
+
+    log::info!("Running script: {}", script_path);
+    let mut succeeded = true;
+    for line in script.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        // echo the command as if it was typed in the REPL
+        prompt!();
+        println!("{}", line);
+
+        if !dispatch_command(mediator, line.to_string()) {
+            succeeded = false;
+
+            if !continue_on_error {
+                let err = format!("Script {} stopped at command `{}`", script_path, line);
+                error!("Error: {}", err);
+                log::error!("{}", err);
+                break;
+            }
+        }
+    }
+
+    succeeded
+}
+
+/// Parse the given input and redirect it to the appropriate handler, working with the mounted disk addressed
+/// by a `label:` prefix of its arguments if any (otherwise with the main disk),
+/// returning whether the command was handled successfully
+fn dispatch_command(mediator: &mut DefaultMediator, input: String) -> bool {
+    // time wraps another command, which is dispatched like any other one
+    if input.split_whitespace().next() == Some("time") {
+        return match CliParser::parse_time(&input) {
+            Ok(timed_input) => dispatch_timed_command(mediator, timed_input),
+            Err(err) => {
+                warn!("Warning: {}", err);
+                log::warn!("parser_level: {}", err);
+                false
+            }
+        };
+    }
+
+    // cp resolves the disks of its source and of its destination itself, as it can copy across disks
+    let route = match input.split_whitespace().next() {
+        Some("cp") => Ok(None),
+        _ => MOUNT_TABLE.lock().unwrap().route(&input),
+    };
+
+    // the audit log records the working directory as it was before the command, which its relative paths refer to
+    let (succeeded, path) = match route {
+        Ok(Some((disk_manager, routed_input))) => {
+            log::info!("Routing command to a mounted disk: {}", routed_input);
+
+            let path = disk_manager
+                .lock()
+                .unwrap()
+                .get_working_directory_full_path();
+            let mut mediator =
+                build_mediator(CONFIG_ARC.clone(), disk_manager, MOUNT_TABLE.clone());
+            (dispatch_to_handler(&mut mediator, routed_input), path)
+        }
+        Ok(None) => {
+            let path = DISK_ARC.lock().unwrap().get_working_directory_full_path();
+            (dispatch_to_handler(mediator, input.clone()), path)
+        }
+        Err(err) => {
+            error!("Error: {}", err);
+            log::error!("dispatch_level: {}", err);
+            (false, String::new())
+        }
+    };
+
+    if let Err(err) = AUDIT_LOG.append(&AuditRecord::new(&input, succeeded, path)) {
+        log::warn!("Unable to append the command to the audit log: {}", err);
+    }
+
+    // keep what the command has modified on every disk, so that it can be undone
+    for disk_manager in get_disk_managers() {
+        disk_manager.lock().unwrap().snapshot();
+    }
+
+    succeeded
+}
+
+/// Dispatch the given input and print how long it took along with the number of clusters read from and written to
+/// the storage files of the disks (the main one and the mounted ones, including the ones mounted by the command),
+/// returning whether the command was handled successfully
+fn dispatch_timed_command(mediator: &mut DefaultMediator, input: String) -> bool {
+    let get_cluster_io_counts =
+        |disk_manager: &Arm<dyn IDiskManager>| disk_manager.lock().unwrap().get_cluster_io_counts();
+
+    let disk_managers_before = get_disk_managers();
+    let cluster_io_counts_before: Vec<(u64, u64)> = disk_managers_before
+        .iter()
+        .map(get_cluster_io_counts)
+        .collect();
+
+    let start = Instant::now();
+    let succeeded = dispatch_command(mediator, input.clone());
+    let elapsed = start.elapsed();
+
+    // the disks unmounted by the command are still counted, as their disk managers are kept alive until now
+    let mut disk_managers = disk_managers_before;
+    for disk_manager in get_disk_managers() {
+        if !disk_managers
+            .iter()
+            .any(|other_disk_manager| Arc::ptr_eq(other_disk_manager, &disk_manager))
+        {
+            disk_managers.push(disk_manager);
+        }
+    }
+    let (clusters_read, clusters_written) = disk_managers
+        .iter()
+        .enumerate()
+        .map(|(index, disk_manager)| {
+            let (read, written) = get_cluster_io_counts(disk_manager);
+            let (read_before, written_before) = cluster_io_counts_before
+                .get(index)
+                .copied()
+                .unwrap_or((0, 0));
+            (read - read_before, written - written_before)
+        })
+        .fold((0, 0), |(total_read, total_written), (read, written)| {
+            (total_read + read, total_written + written)
+        });
+
+    cprintln!(
+        "<c!>Time</>: <w!>{:.3} ms</> (clusters read: <w!>{}</>, clusters written: <w!>{}</>)",
+        elapsed.as_secs_f64() * 1000.0,
+        clusters_read,
+        clusters_written
+    );
+    log::info!(
+        "Command `{}` took {:?} (clusters read: {}, clusters written: {})",
+        input,
+        elapsed,
+        clusters_read,
+        clusters_written
+    );
+
+    succeeded
+}
+
+/// Parse the given input and redirect it to the appropriate handler of the given mediator,
+/// returning whether the command was handled successfully
+fn dispatch_to_handler(mediator: &mut DefaultMediator, input: String) -> bool {
+    // get the first word of the input - this is the command
+    let command = input.split_whitespace().next();
+
+    if command.is_none() {
+        warn!("Please enter a command!");
+        return false;
+    }
+
+    // match the command to the appropriate handler
+    match command.unwrap() {
+        "neofetch" => handle!(mediator, parse_neofetch, input.as_str()),
+        "version" => handle!(mediator, parse_version, input.as_str()),
+        "clear" => handle!(mediator, parse_clear, input.as_str()),
+        "create" => handle!(
+            mediator,
+            parse_create,
+            input.as_str(),
+            "File created successfully!"
+        ),
+        "ls" => handle!(mediator, parse_ls, input.as_str()),
+        "rename" => handle!(
+            mediator,
+            parse_rename,
+            input.as_str(),
+            "File renamed successfully!"
+        ),
+        // a dry run only lists the file entries which would be deleted
+        "del"
+            if input
+                .split_whitespace()
+                .any(|word| word == "-n" || word == "--dry-run") =>
+        {
+            handle!(mediator, parse_del, input.as_str())
+        }
+        "del" => handle!(
+            mediator,
+            parse_del,
+            input.as_str(),
+            "File deleted successfully!"
+        ),
+        "cat" => handle!(mediator, parse_cat, input.as_str()),
+        "hexdump" => handle!(mediator, parse_hexdump, input.as_str()),
+        "cksum" => handle!(mediator, parse_cksum, input.as_str()),
+        "grep" => handle!(mediator, parse_grep, input.as_str()),
+        "head" => handle!(mediator, parse_head, input.as_str()),
+        "tail" => handle!(mediator, parse_tail, input.as_str()),
+        "wc" => handle!(mediator, parse_wc, input.as_str()),
+        "diff" => handle!(mediator, parse_diff, input.as_str()),
+        "cp" => handle!(
+            mediator,
+            parse_cp,
+            input.as_str(),
+            "File copied successfully!"
+        ),
+        "ln" => handle!(
+            mediator,
+            parse_ln,
+            input.as_str(),
+            "Link created successfully!"
+        ),
+        "setattr" => handle!(
+            mediator,
+            parse_setattr,
+            input.as_str(),
+            "File attributes set successfully!"
+        ),
+        // a dry run doesn't modify the disk, so there is nothing to reboot for
+        "fmt" if input.split_whitespace().any(|word| word == "--dry-run") => {
+            handle!(mediator, parse_fmt, input.as_str())
+        }
+        "fmt" => handle!(
+            mediator,
+            parse_fmt,
+            input.as_str(),
+            "Disk formatted successfully",
+            reboot_system,
+            "The system requires a reboot in order to properly persist the modifications!\nRoDOS is shutting down..."
+        ),
+        "defrag" => handle!(
+            mediator,
+            parse_defrag,
+            input.as_str(),
+            "Disk defragmented successfully"
+        ),
+        "undo" => handle!(mediator, parse_undo, input.as_str()),
+        "redo" => handle!(mediator, parse_redo, input.as_str()),
+        "label" => handle!(mediator, parse_label, input.as_str()),
+        "trash" => handle!(mediator, parse_trash, input.as_str()),
+        "resize" => handle!(
+            mediator,
+            parse_resize,
+            input.as_str(),
+            "Disk resized successfully"
+        ),
+        "badblocks" => handle!(
+            mediator,
+            parse_badblocks,
+            input.as_str(),
+            "Cluster marked as bad successfully!"
+        ),
+        "mkdir" => handle!(
+            mediator,
+            parse_mkdir,
+            input.as_str(),
+            "Directory created successfully!"
+        ),
+        "cd" => handle!(mediator, parse_cd, input.as_str()),
+        "pwd" => handle!(mediator, parse_pwd, input.as_str()),
+        "rmdir" => handle!(
+            mediator,
+            parse_rmdir,
+            input.as_str(),
+            "Directory deleted successfully!"
+        ),
+        "echo" => handle!(
+            mediator,
+            parse_echo,
+            input.as_str(),
+            "File written successfully!"
+        ),
+        "truncate" => handle!(
+            mediator,
+            parse_truncate,
+            input.as_str(),
+            "File truncated successfully!"
+        ),
+        "stat" => handle!(mediator, parse_stat, input.as_str()),
+        "chkdsk" => handle!(mediator, parse_chkdsk, input.as_str()),
+        "fraginfo" => handle!(mediator, parse_fraginfo, input.as_str()),
+        "df" => handle!(mediator, parse_df, input.as_str()),
+        "bootinfo" => handle!(mediator, parse_bootinfo, input.as_str()),
+        "dumpfat" => handle!(mediator, parse_dumpfat, input.as_str()),
+        "readcluster" => handle!(mediator, parse_readcluster, input.as_str()),
+        "writecluster" => handle!(
+            mediator,
+            parse_writecluster,
+            input.as_str(),
+            "Cluster written successfully!"
+        ),
+        "import" => handle!(
+            mediator,
+            parse_import,
+            input.as_str(),
+            "Imported successfully!"
+        ),
+        "export" => handle!(
+            mediator,
+            parse_export,
+            input.as_str(),
+            "Exported successfully!"
+        ),
+        "mount" => handle!(
+            mediator,
+            parse_mount,
+            input.as_str(),
+            "Disk mounted successfully!"
+        ),
+        "umount" => handle!(
+            mediator,
+            parse_umount,
+            input.as_str(),
+            "Disk unmounted successfully!"
+        ),
+        "help" => handle!(mediator, parse_help, input.as_str()),
+        "exit" => handle!(
+            parse_exit,
+            input.as_str(),
+            reboot_system,
+            "RoDOS is shutting down..."
+        ),
+        _ => {
+            warn!("Warning: Command not found!");
+            false
+        }
+    }
+}
+
+/// Initializes the logger from the log4rs config file, falling back to logging into the default log file
+/// if it is missing or invalid, and to no logging at all if even the default log file can't be created
+/// (the logs never prevent the shell from starting).
+fn init_logger() {
+    match log4rs::init_file(LOG_CONFIG_FILE_PATH, Default::default()) {
+        Ok(_) => {}
+        Err(_) => {
+            let file_appender = match FileAppender::builder()
+                .encoder(Box::new(PatternEncoder::new(
+                    "{d(%Y-%m-%d %H:%M:%S%.3f)} {h({l})} {M} - {m}{n}",
+                )))
+                .build(DEFAULT_LOG_FILE_PATH)
+            {
+                Ok(file_appender) => file_appender,
+                Err(err) => {
+                    warn!(
+                        "Warning: Unable to create log file {} ({}), logging is disabled",
+                        DEFAULT_LOG_FILE_PATH, err
+                    );
+                    return;
+                }
+            };
+
+            let log_config = log4rs::config::Config::builder()
+                .appender(Appender::builder().build("file_appender", Box::new(file_appender)))
+                .logger(
+                    Logger::builder()
+                        .appender("file_appender")
+                        .additive(false)
+                        .build("rodos", log::LevelFilter::Info),
+                )
+                .build(
+                    Root::builder()
+                        .appender("file_appender")
+                        .build(log::LevelFilter::Info),
+                )
+                .expect("Invalid default log config");
+
+            if let Err(err) = log4rs::init_config(log_config) {
+                warn!(
+                    "Warning: Unable to initialize the logger ({}), logging is disabled",
+                    err
+                );
+            }
+        }
+    }
+}
+
+fn reboot_system(bye_message: &str) {
+    warn!("Warning: {}", bye_message);
+    autosave::flush_all(get_disk_managers);
+
+    log::info!("RoDOS is shutting down...");
+    std::process::exit(0);
+}
@@ -0,0 +1,70 @@
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// The number of test shells created so far by the process, which makes the name of their folders unique
+static TEST_SHELLS_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+/// TestShell runs the rodos binary against a disk of its own, in a temp folder removed along with it:
+/// its config is the default one with the files of the disk moved to the temp folder
+/// and the confirmation of destructive commands disabled.
+pub struct TestShell {
+    dir_path: PathBuf,
+    config_file_path: PathBuf,
+}
+
+impl TestShell {
+    pub fn new() -> Self {
+        let dir_path = std::env::temp_dir().join(format!(
+            "rodos-cli-test-{}-{}",
+            std::process::id(),
+            TEST_SHELLS_COUNT.fetch_add(1, Ordering::SeqCst)
+        ));
+        std::fs::create_dir_all(&dir_path).expect("Unable to create the test shell folder");
+
+        let config = std::fs::read_to_string("config/config.toml")
+            .expect("Unable to read the default config")
+            .replace("\"disk", &format!("\"{}", dir_path.display()))
+            .replace("confirm_destructive = true", "confirm_destructive = false");
+        let config_file_path = dir_path.join("config.toml");
+        std::fs::write(&config_file_path, config).expect("Unable to write the test config");
+
+        Self {
+            dir_path,
+            config_file_path,
+        }
+    }
+
+    /// Returns the path of a file of the temp folder of the shell.
+    pub fn path(&self, file_name: &str) -> PathBuf {
+        self.dir_path.join(file_name)
+    }
+
+    /// Runs the binary with the given arguments.
+    pub fn run(&self, args: &[&str]) -> Output {
+        Command::new(env!("CARGO_BIN_EXE_rodos"))
+            .args(args)
+            .env("RODOS_CONFIG", &self.config_file_path)
+            .output()
+            .expect("Unable to run rodos")
+    }
+
+    /// Runs the binary with the given arguments, checking that it succeeds, and returns its standard output.
+    pub fn run_ok(&self, args: &[&str]) -> String {
+        let output = self.run(args);
+        assert!(
+            output.status.success(),
+            "rodos {:?} failed: {}",
+            args,
+            String::from_utf8_lossy(&output.stdout)
+        );
+
+        String::from_utf8_lossy(&output.stdout).into_owned()
+    }
+}
+
+impl Drop for TestShell {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(Path::new(&self.dir_path));
+    }
+}
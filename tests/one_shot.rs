@@ -0,0 +1,108 @@
+mod common;
+
+use common::TestShell;
+
+#[test]
+fn a_one_shot_command_runs_and_exits() {
+    let shell = TestShell::new();
+
+    shell.run_ok(&["create", "a.txt", "10", "-alpha"]);
+    let output = shell.run_ok(&["cat", "a.txt"]);
+
+    assert!(output.contains("ABCDEFGHIJ"), "{}", output);
+    assert!(shell.path("storage.bin").exists());
+}
+
+#[test]
+fn a_one_shot_command_given_as_a_single_argument_runs() {
+    let shell = TestShell::new();
+
+    shell.run_ok(&["echo \"hello\" > a.txt"]);
+    let output = shell.run_ok(&["cat a.txt"]);
+
+    assert!(output.contains("hello"), "{}", output);
+}
+
+#[test]
+fn a_failing_one_shot_command_exits_with_an_error_code() {
+    let shell = TestShell::new();
+
+    let output = shell.run(&["cat", "missing.txt"]);
+
+    assert_eq!(output.status.code(), Some(1));
+}
+
+#[test]
+fn version_prints_the_configured_version_on_one_line() {
+    let shell = TestShell::new();
+
+    let output = shell.run_ok(&["version"]);
+
+    assert!(
+        output.lines().any(|line| line
+            == format!(
+                "RoDOS 0.1.0 (rodos {}) by Sami Barbut-Dica",
+                env!("CARGO_PKG_VERSION")
+            )),
+        "{}",
+        output
+    );
+}
+
+/// Removes the ANSI color codes from the given output.
+fn strip_colors(output: &str) -> String {
+    let mut stripped = String::with_capacity(output.len());
+    let mut in_escape = false;
+    for character in output.chars() {
+        match character {
+            '\u{1b}' => in_escape = true,
+            'm' if in_escape => in_escape = false,
+            _ if in_escape => {}
+            character => stripped.push(character),
+        }
+    }
+
+    stripped
+}
+
+/// Returns whether the given names are in alphabetical order.
+fn is_sorted(names: &[String]) -> bool {
+    names.windows(2).all(|pair| pair[0] < pair[1])
+}
+
+#[test]
+fn help_all_shows_the_usage_of_every_command_in_alphabetical_order() {
+    let shell = TestShell::new();
+
+    let output = strip_colors(&shell.run_ok(&["help", "--all"]));
+
+    // every command is shown in a block of its own: its name followed by a colon, its description and its usage
+    let names: Vec<String> = output
+        .split("\n\n")
+        .filter_map(|block| block.lines().next()?.strip_suffix(':'))
+        .map(str::to_string)
+        .collect();
+    assert!(names.len() > 10, "{}", output);
+    assert!(is_sorted(&names), "{:?}", names);
+    assert!(
+        output.contains("create <file_name>.<file_extension> <file_size> -<file_content_type>"),
+        "{}",
+        output
+    );
+}
+
+#[test]
+fn help_lists_the_commands_in_alphabetical_order() {
+    let shell = TestShell::new();
+
+    let output = strip_colors(&shell.run_ok(&["help"]));
+
+    let names: Vec<String> = output
+        .lines()
+        .filter_map(|line| line.split_once(" - "))
+        .map(|(name, _)| name.to_string())
+        .collect();
+    assert!(names.len() > 10, "{}", output);
+    assert!(is_sorted(&names), "{:?}", names);
+    assert!(names.contains(&"create".to_string()), "{:?}", names);
+}
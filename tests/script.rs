@@ -0,0 +1,129 @@
+mod common;
+
+use common::TestShell;
+use rodos::Filesystem;
+
+/// Returns the full names of the visible entries of the working directory of the file system.
+fn list_names(filesystem: &mut Filesystem) -> Vec<String> {
+    filesystem
+        .list()
+        .unwrap()
+        .into_iter()
+        .filter(|entry| entry.name != "." && entry.name != "..")
+        .map(|entry| match entry.is_file {
+            true => format!("{}.{}", entry.name, entry.extension),
+            false => entry.name,
+        })
+        .collect()
+}
+
+/// Writes the given script into the temp folder of the shell and runs it with the given extra arguments.
+fn run_script(shell: &TestShell, script: &str, extra_args: &[&str]) -> std::process::Output {
+    let script_path = shell.path("script.rodos");
+    std::fs::write(&script_path, script).unwrap();
+
+    let mut args = vec!["--script", script_path.to_str().unwrap()];
+    args.extend_from_slice(extra_args);
+    shell.run(&args)
+}
+
+#[test]
+fn a_script_builds_the_disk_command_by_command() {
+    let shell = TestShell::new();
+    let script = "\
+# a commented out line
+create readme.txt 40 -alpha
+
+mkdir docs
+cd docs
+create notes.txt 20 -num
+cd /
+ls -R
+";
+
+    let output = run_script(&shell, script, &[]);
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("mkdir docs"), "{}", stdout);
+    assert!(!stdout.contains("a commented out line"), "{}", stdout);
+    let readme = shell.run_ok(&["cat", "readme.txt"]);
+    assert!(
+        readme.contains("ABCDEFGHIJKLMNOPQRSTUVWXYZABCDEFGHIJKLMN"),
+        "{}",
+        readme
+    );
+
+    let mut filesystem = Filesystem::open(shell.path("storage.bin").to_str().unwrap()).unwrap();
+    assert_eq!(list_names(&mut filesystem), vec!["readme.txt", "docs"]);
+    filesystem.change_directory("docs").unwrap();
+    assert_eq!(list_names(&mut filesystem), vec!["notes.txt"]);
+    assert_eq!(
+        filesystem.read_file("notes", "txt").unwrap(),
+        b"01234567890123456789"
+    );
+}
+
+#[test]
+fn a_script_stops_at_the_first_failing_command() {
+    let shell = TestShell::new();
+
+    let output = run_script(&shell, "mkdir before\ncd missing\nmkdir after\n", &[]);
+
+    assert_eq!(output.status.code(), Some(1));
+    let mut filesystem = Filesystem::open(shell.path("storage.bin").to_str().unwrap()).unwrap();
+    assert_eq!(list_names(&mut filesystem), vec!["before"]);
+}
+
+#[test]
+fn a_script_continues_after_a_failing_command_if_asked() {
+    let shell = TestShell::new();
+
+    let output = run_script(
+        &shell,
+        "mkdir before\ncd missing\nmkdir after\n",
+        &["--continue-on-error"],
+    );
+
+    assert_eq!(output.status.code(), Some(1));
+    let mut filesystem = Filesystem::open(shell.path("storage.bin").to_str().unwrap()).unwrap();
+    assert_eq!(list_names(&mut filesystem), vec!["before", "after"]);
+}
+
+#[test]
+fn every_command_of_a_script_is_appended_to_the_audit_log() {
+    let shell = TestShell::new();
+
+    run_script(
+        &shell,
+        "mkdir docs\ncd docs\ncreate a.txt 5 -alpha\ncd missing\n",
+        &["--continue-on-error"],
+    );
+
+    let audit_log = std::fs::read_to_string(shell.path("audit.log")).unwrap();
+    let records: Vec<(String, bool, String)> = audit_log
+        .lines()
+        .map(|line| {
+            let record: serde_json::Value = serde_json::from_str(line).unwrap();
+            assert!(record["timestamp"].is_string(), "{}", line);
+            (
+                record["command"].as_str().unwrap().to_string(),
+                record["succeeded"].as_bool().unwrap(),
+                record["path"].as_str().unwrap().to_string(),
+            )
+        })
+        .collect();
+    let expected_records = [
+        ("mkdir docs", true, "/"),
+        ("cd docs", true, "/"),
+        ("create a.txt 5 -alpha", true, "/docs"),
+        ("cd missing", false, "/docs"),
+    ];
+    assert_eq!(
+        records,
+        expected_records
+            .iter()
+            .map(|(command, succeeded, path)| (command.to_string(), *succeeded, path.to_string()))
+            .collect::<Vec<_>>()
+    );
+}